@@ -8,8 +8,9 @@
 //! Sphere ↔ Wall, Sphere ↔ Floor, Player ↔ Sphere, Sphere ↔ Orb collisions
 
 use metatopia_engine::prelude::*;
+use metatopia_engine::input::{InputEvent, InputManager};
 use winit::{
-    event::{Event, WindowEvent as WinitWindowEvent, ElementState, DeviceEvent},
+    event::{Event, WindowEvent as WinitWindowEvent, ElementState},
     keyboard::{KeyCode, PhysicalKey},
     event_loop::{ControlFlow, EventLoop},
     window::WindowBuilder as WinitWindowBuilder,
@@ -583,28 +584,27 @@ impl NonEuclideanDemo {
 
     fn check_portal_transitions(&mut self) {
         let forward = self.get_forward_vector();
-        if let Ok(manifold) = self.manifold.read() {
-            if let Some((_id, intersection, new_chart)) =
+        if let Ok(manifold) = self.manifold.read()
+            && let Some((_id, intersection, new_chart)) =
                 manifold.ray_portal_intersection(self.camera_position, forward, self.current_chart)
-            {
-                self.camera_position = intersection;
-                self.current_chart = new_chart;
-                drop(manifold);
-                if let Ok(mut m) = self.manifold.write() { m.set_active_chart(new_chart); }
-
-                // Update cached space type
-                self.space_type = self.resolve_space_type();
-                self.sound_events.push(SoundEvent::Portal);
-                let phys = SpacePhysics::for_type(self.space_type);
-                println!("🌀 Portal → Chart {:?} | Physics: {} | G={:.0} bounce={:.0}%",
-                    new_chart, phys.label, phys.gravity, phys.restitution * 100.0);
-
-                // Reset objects with new physics
-                self.sphere.position = Vector3::new(0.0, 3.0, 0.0); // drop from height
-                self.sphere.velocity = Vector3::new(0.0, 0.0, 0.0);
-                self.orbs = default_orb_positions();
-                self.collected = 0;
-            }
+        {
+            self.camera_position = intersection;
+            self.current_chart = new_chart;
+            drop(manifold);
+            if let Ok(mut m) = self.manifold.write() { m.set_active_chart(new_chart); }
+
+            // Update cached space type
+            self.space_type = self.resolve_space_type();
+            self.sound_events.push(SoundEvent::Portal);
+            let phys = SpacePhysics::for_type(self.space_type);
+            println!("🌀 Portal → Chart {:?} | Physics: {} | G={:.0} bounce={:.0}%",
+                new_chart, phys.label, phys.gravity, phys.restitution * 100.0);
+
+            // Reset objects with new physics
+            self.sphere.position = Vector3::new(0.0, 3.0, 0.0); // drop from height
+            self.sphere.velocity = Vector3::new(0.0, 0.0, 0.0);
+            self.orbs = default_orb_positions();
+            self.collected = 0;
         }
     }
 
@@ -782,6 +782,7 @@ async fn run() {
     surface.configure(&device, &config);
 
     let mut demo = NonEuclideanDemo::new();
+    let mut input = InputManager::new();
     let mut audio = GameAudio::new();
     if audio.is_some() { println!("🔊 Audio system initialized"); }
     else { println!("⚠️  No audio device found – continuing without sound"); }
@@ -852,12 +853,10 @@ async fn run() {
         match event {
             Event::WindowEvent { ref event, window_id } if window_id == window.id() => match event {
                 WinitWindowEvent::CloseRequested => target.exit(),
-                WinitWindowEvent::Resized(s) => {
-                    if s.width > 0 && s.height > 0 {
-                        config.width = s.width; config.height = s.height;
-                        surface.configure(&device, &config);
-                        depth_view = create_depth_texture(&device, config.width, config.height);
-                    }
+                WinitWindowEvent::Resized(s) if s.width > 0 && s.height > 0 => {
+                    config.width = s.width; config.height = s.height;
+                    surface.configure(&device, &config);
+                    depth_view = create_depth_texture(&device, config.width, config.height);
                 }
                 WinitWindowEvent::KeyboardInput { event, .. } => {
                     if let PhysicalKey::Code(kc) = event.physical_key {
@@ -872,6 +871,10 @@ async fn run() {
                     last_time = now;
                     let elapsed = start_time.elapsed().as_secs_f32();
 
+                    let look = input.raw_mouse_delta();
+                    demo.handle_mouse_motion(look.x as f64, look.y as f64);
+                    input.poll_events();
+                    input.tick(now);
                     demo.update(dt, elapsed);
 
                     // Process audio events
@@ -928,7 +931,7 @@ async fn run() {
                     queue.submit(std::iter::once(enc.finish()));
                     output.present();
 
-                    if frame_count % 60 == 0 {
+                    if frame_count.is_multiple_of(60) {
                         let fps = frame_count as f32 / elapsed;
                         let phys = SpacePhysics::for_type(demo.space_type);
                         let dir = match ((demo.camera_rotation.0.to_degrees() + 360.0) % 360.0) as i32 {
@@ -945,7 +948,9 @@ async fn run() {
                 }
                 _ => {}
             },
-            Event::DeviceEvent { event: DeviceEvent::MouseMotion { delta }, .. } => { demo.handle_mouse_motion(delta.0, delta.1); }
+            Event::DeviceEvent { ref event, .. } => {
+                if let Some(event) = InputEvent::from_device_event(event) { input.process_event(event); }
+            }
             Event::AboutToWait => { window.request_redraw(); }
             _ => {}
         }
@@ -146,13 +146,13 @@ async fn run() {
                 WinitWindowEvent::CloseRequested => {
                     target.exit();
                 }
-                WinitWindowEvent::Resized(physical_size) => {
-                    if physical_size.width > 0 && physical_size.height > 0 {
-                        config.width = physical_size.width;
-                        config.height = physical_size.height;
-                        surface.configure(&device, &config);
-                        window.request_redraw();
-                    }
+                WinitWindowEvent::Resized(physical_size)
+                    if physical_size.width > 0 && physical_size.height > 0 =>
+                {
+                    config.width = physical_size.width;
+                    config.height = physical_size.height;
+                    surface.configure(&device, &config);
+                    window.request_redraw();
                 }
                 WinitWindowEvent::RedrawRequested => {
                     frame_count += 1;
@@ -198,7 +198,7 @@ async fn run() {
                     output.present();
                     
                     // Print FPS every 60 frames
-                    if frame_count % 60 == 0 {
+                    if frame_count.is_multiple_of(60) {
                         let fps = frame_count as f32 / elapsed;
                         println!("FPS: {:.1} | Frame: {} | Time: {:.1}s", fps, frame_count, elapsed);
                         
@@ -350,11 +350,9 @@ async fn run() {
         match event {
             Event::WindowEvent { ref event, window_id } if window_id == window.id() => match event {
                 WinitWindowEvent::CloseRequested => target.exit(),
-                WinitWindowEvent::Resized(s) => {
-                    if s.width > 0 && s.height > 0 {
-                        config.width = s.width; config.height = s.height;
-                        surface.configure(&device, &config);
-                    }
+                WinitWindowEvent::Resized(s) if s.width > 0 && s.height > 0 => {
+                    config.width = s.width; config.height = s.height;
+                    surface.configure(&device, &config);
                 }
                 WinitWindowEvent::KeyboardInput { event, .. } => {
                     if event.state != ElementState::Pressed { return; }
@@ -365,8 +363,10 @@ async fn run() {
                                 if let Some(ref a) = audio { a.play_power_change(explorer.power); }
                             } else {
                                 println!("{}", msg);
-                                if msg.contains("palette") {
-                                    if let Some(ref a) = audio { a.play_palette_shift(); }
+                                if msg.contains("palette")
+                                    && let Some(ref a) = audio
+                                {
+                                    a.play_palette_shift();
                                 }
                             }
                         }
@@ -421,7 +421,7 @@ async fn run() {
                     queue.submit(std::iter::once(enc.finish()));
                     output.present();
 
-                    if frame_count % 60 == 0 {
+                    if frame_count.is_multiple_of(60) {
                         let fps = frame_count as f32 / elapsed;
                         let pal_name = match explorer.palette {
                             0 => "🔥Fire", 1 => "🌊Ocean", 2 => "🌌Nebula",
@@ -474,7 +474,7 @@ impl PestControlGame {
     }
 
     fn update_scene_uniform(&mut self, time: f32) {
-        let loc = ((self.level - 1) % 6) as u32;
+        let loc = (self.level - 1) % 6;
 
         // ── Location-specific lighting ────────────────────────────
         let sd = Vector3::new(0.2_f32, 0.9, 0.3).normalize();
@@ -692,12 +692,10 @@ async fn run() {
         match event {
             Event::WindowEvent { ref event, window_id } if window_id == window.id() => match event {
                 WinitWindowEvent::CloseRequested => target.exit(),
-                WinitWindowEvent::Resized(s) => {
-                    if s.width > 0 && s.height > 0 {
-                        config.width = s.width; config.height = s.height;
-                        surface.configure(&device, &config);
-                        depth_view = create_depth_texture(&device, config.width, config.height);
-                    }
+                WinitWindowEvent::Resized(s) if s.width > 0 && s.height > 0 => {
+                    config.width = s.width; config.height = s.height;
+                    surface.configure(&device, &config);
+                    depth_view = create_depth_texture(&device, config.width, config.height);
                 }
                 WinitWindowEvent::KeyboardInput { event: key_event, .. } => {
                     if let PhysicalKey::Code(kc) = key_event.physical_key {
@@ -705,11 +703,9 @@ async fn run() {
                         else { game.handle_keyboard(kc, key_event.state == ElementState::Pressed); }
                     }
                 }
-                WinitWindowEvent::MouseInput { state: ElementState::Pressed, button: MouseButton::Left, .. } => {
-                    if !game_over {
-                        let elapsed = start_time.elapsed().as_secs_f32();
-                        game.try_fire(elapsed);
-                    }
+                WinitWindowEvent::MouseInput { state: ElementState::Pressed, button: MouseButton::Left, .. } if !game_over => {
+                    let elapsed = start_time.elapsed().as_secs_f32();
+                    game.try_fire(elapsed);
                 }
                 WinitWindowEvent::RedrawRequested => {
                     frame_count += 1;
@@ -771,7 +767,7 @@ async fn run() {
                     output.present();
 
                     // HUD
-                    if frame_count % 60 == 0 && !game_over {
+                    if frame_count.is_multiple_of(60) && !game_over {
                         let alive = game.pests.iter().filter(|p| p.ai_state != PestAI::Dead).count();
                         let t = &game.tools[game.current_tool];
                         let fps = frame_count as f32 / elapsed;
@@ -66,7 +66,7 @@ fn main() {
     let euclidean_path = Geodesic::compute(
         Point3::new(0.0, 0.0, 0.0),
         Point3::new(5.0, 5.0, 0.0),
-        &euclidean_metric,
+        euclidean_metric,
         5
     );
     println!("Euclidean geodesic (straight line):");
@@ -80,7 +80,7 @@ fn main() {
     let hyperbolic_path = Geodesic::compute(
         Point3::new(0.0, 0.0, 0.0),
         Point3::new(0.5, 0.0, 0.0),
-        &hyperbolic_metric,
+        hyperbolic_metric,
         5
     );
     println!("Hyperbolic geodesic (curved path in Poincaré disk):");
@@ -94,7 +94,7 @@ fn main() {
     let spherical_path = Geodesic::compute(
         Point3::new(1.0, 0.0, 0.0),
         Point3::new(0.0, 1.0, 0.0),
-        &spherical_metric,
+        spherical_metric,
         5
     );
     println!("Spherical geodesic (great circle on sphere):");
@@ -555,52 +555,50 @@ async fn run() {
         match event {
             Event::WindowEvent { ref event, window_id } if window_id == window.id() => match event {
                 WinitWindowEvent::CloseRequested => target.exit(),
-                WinitWindowEvent::Resized(s) => {
-                    if s.width > 0 && s.height > 0 {
-                        config.width = s.width; config.height = s.height;
-                        surface.configure(&device, &config);
-                    }
+                WinitWindowEvent::Resized(s) if s.width > 0 && s.height > 0 => {
+                    config.width = s.width; config.height = s.height;
+                    surface.configure(&device, &config);
                 }
                 WinitWindowEvent::KeyboardInput { event, .. } => {
-                    if let PhysicalKey::Code(kc) = event.physical_key {
-                        if event.state == ElementState::Pressed {
-                            if kc == KeyCode::Escape { target.exit(); return; }
-
-                            // Space switching
-                            let new_space = match kc {
-                                KeyCode::Digit1 => Some(TheaterSpace::HyperbolicLobby),
-                                KeyCode::Digit2 => Some(TheaterSpace::SphericalDome),
-                                KeyCode::Digit3 => Some(TheaterSpace::EscherTheater),
-                                KeyCode::Digit4 => Some(TheaterSpace::PersonalPocket),
-                                KeyCode::Digit5 => Some(TheaterSpace::SocialHub),
-                                _ => None,
-                            };
-                            if let Some(sp) = new_space {
-                                if sp != theater.space {
-                                    theater.setup_space(sp);
-                                    if let Some(ref a) = audio { a.play_transition(); }
-                                    println!("\n{} Entering {} | {} screens",
-                                        sp.icon(), sp.name(), theater.screens.len());
-                                }
-                            }
-
-                            // Tab = cycle selection
-                            if kc == KeyCode::Tab {
-                                if theater.cycle_selected() {
-                                    if let Some(ref a) = audio { a.play_select(); }
-                                    let m = &MOVIES[theater.screens[theater.selected].movie_idx];
-                                    let status = if theater.screens[theater.selected].playing { "▶" } else { "⏸" };
-                                    println!("  → [{}] {} {} ({}) ★{:.1}", status,
-                                        m.title, m.genre, m.year, m.rating);
-                                }
-                            }
-
-                            theater.handle_keyboard(kc, true);
+                    if let PhysicalKey::Code(kc) = event.physical_key
+                        && event.state == ElementState::Pressed
+                    {
+                        if kc == KeyCode::Escape { target.exit(); return; }
+
+                        // Space switching
+                        let new_space = match kc {
+                            KeyCode::Digit1 => Some(TheaterSpace::HyperbolicLobby),
+                            KeyCode::Digit2 => Some(TheaterSpace::SphericalDome),
+                            KeyCode::Digit3 => Some(TheaterSpace::EscherTheater),
+                            KeyCode::Digit4 => Some(TheaterSpace::PersonalPocket),
+                            KeyCode::Digit5 => Some(TheaterSpace::SocialHub),
+                            _ => None,
+                        };
+                        if let Some(sp) = new_space
+                            && sp != theater.space
+                        {
+                            theater.setup_space(sp);
+                            if let Some(ref a) = audio { a.play_transition(); }
+                            println!("\n{} Entering {} | {} screens",
+                                sp.icon(), sp.name(), theater.screens.len());
                         }
+
+                        // Tab = cycle selection
+                        if kc == KeyCode::Tab && theater.cycle_selected() {
+                            if let Some(ref a) = audio { a.play_select(); }
+                            let m = &MOVIES[theater.screens[theater.selected].movie_idx];
+                            let status = if theater.screens[theater.selected].playing { "▶" } else { "⏸" };
+                            println!("  → [{}] {} {} ({}) ★{:.1}", status,
+                                m.title, m.genre, m.year, m.rating);
+                        }
+
+                        theater.handle_keyboard(kc, true);
                     }
                 }
                 WinitWindowEvent::MouseInput { state: ElementState::Pressed, button: MouseButton::Left, .. } => {
-                    if theater.toggle_playing() {
+                    // Not a match guard, since toggling changes state
+                    let toggled = theater.toggle_playing();
+                    if toggled {
                         let playing = theater.screens[theater.selected].playing;
                         let m = &MOVIES[theater.screens[theater.selected].movie_idx];
                         if playing {
@@ -647,7 +645,7 @@ async fn run() {
                     queue.submit(std::iter::once(enc.finish()));
                     output.present();
 
-                    if frame_count % 120 == 0 {
+                    if frame_count.is_multiple_of(120) {
                         let fps = frame_count as f32 / elapsed;
                         let playing_count = theater.screens.iter().filter(|s| s.playing).count();
                         println!("{} {} | 📺{}+{}▶ | 🎬{} | 💡{:.1} | {:.0}fps",
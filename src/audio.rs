@@ -1,7 +1,8 @@
 //! Audio engine wrapping rodio for easy sound playback.
 //!
 //! Provides fire-and-forget sound effects, looping background music,
-//! volume control, and sound preloading.
+//! volume control, sound preloading, and spatial sources whose volume
+//! falls off with distance according to the geometry they are in.
 //!
 //! # Example
 //! ```no_run
@@ -16,6 +17,67 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::io::BufReader;
+use crate::manifold::{GeometryType, Manifold, ManifoldPosition};
+
+/// Playback settings for a sound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioParams {
+    /// Volume before falloff (0.0 = silent, 1.0 = normal).
+    pub volume: f32,
+    /// Repeat the sound until stopped.
+    pub looping: bool,
+    /// Distance within which a spatial source plays at full volume.
+    pub reference_distance: f32,
+}
+
+impl Default for AudioParams {
+    fn default() -> Self {
+        Self { volume: 1.0, looping: false, reference_distance: 1.0 }
+    }
+}
+
+impl AudioParams {
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    pub fn with_reference_distance(mut self, distance: f32) -> Self {
+        self.reference_distance = distance;
+        self
+    }
+}
+
+/// Volume multiplier for a source `distance` away from the listener.
+///
+/// Within `reference_distance` the source is at full volume. Beyond it:
+/// - Euclidean, spherical and custom charts use inverse-square falloff,
+///   `(ref / d)²`. Refocusing past a sphere's equator is not modelled.
+/// - Hyperbolic charts fall off exponentially, `exp(-2 (d - ref) / ref)`,
+///   because the area of a hyperbolic sphere grows like `sinh²(d)`.
+pub fn spatial_falloff(geometry: GeometryType, distance: f32, reference_distance: f32) -> f32 {
+    let reference = reference_distance.max(f32::EPSILON);
+    let distance = distance.max(reference);
+    match geometry {
+        GeometryType::Hyperbolic => (-2.0 * (distance - reference) / reference).exp(),
+        _ => (reference / distance).powi(2),
+    }
+}
+
+/// Identifier of a spatial source started with [`AudioEngine::play_spatial`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SourceId(pub u32);
+
+struct SpatialSource {
+    sink: rodio::Sink,
+    position: ManifoldPosition,
+    params: AudioParams,
+}
 
 /// Handle to a playing sound effect (for stop/volume control).
 pub struct SfxHandle {
@@ -41,6 +103,9 @@ pub struct AudioEngine {
     sfx_volume: f32,
     preloaded: HashMap<String, PathBuf>,
     initialized: bool,
+    listener: Option<ManifoldPosition>,
+    sources: HashMap<SourceId, SpatialSource>,
+    next_source_id: u32,
 }
 
 impl Default for AudioEngine {
@@ -60,6 +125,9 @@ impl AudioEngine {
                     sfx_volume: 1.0,
                     preloaded: HashMap::new(),
                     initialized: true,
+                    listener: None,
+                    sources: HashMap::new(),
+                    next_source_id: 0,
                 }
             }
             Err(e) => {
@@ -72,6 +140,9 @@ impl AudioEngine {
                     sfx_volume: 1.0,
                     preloaded: HashMap::new(),
                     initialized: false,
+                    listener: None,
+                    sources: HashMap::new(),
+                    next_source_id: 0,
                 }
             }
         }
@@ -102,14 +173,98 @@ impl AudioEngine {
         self.play_sfx_path(Path::new(path))
     }
 
+    /// Play a preloaded sound by name with the given settings.
+    pub fn play_with(&self, name: &str, params: AudioParams) -> Option<SfxHandle> {
+        let path = self.preloaded.get(name)?;
+        let sink = self.start_sink(path, params.looping)?;
+        sink.set_volume(self.sfx_volume * params.volume);
+        Some(SfxHandle { sink })
+    }
+
     fn play_sfx_path(&self, path: &Path) -> Option<SfxHandle> {
+        let sink = self.start_sink(path, false)?;
+        sink.set_volume(self.sfx_volume);
+        Some(SfxHandle { sink })
+    }
+
+    fn start_sink(&self, path: &Path, looping: bool) -> Option<rodio::Sink> {
         let handle = self.stream_handle.as_ref()?;
         let file = std::fs::File::open(path).ok()?;
         let source = rodio::Decoder::new(BufReader::new(file)).ok()?;
         let sink = rodio::Sink::try_new(handle).ok()?;
-        sink.set_volume(self.sfx_volume);
-        sink.append(source);
-        Some(SfxHandle { sink })
+        if looping {
+            sink.append(rodio::source::Source::repeat_infinite(source));
+        } else {
+            sink.append(source);
+        }
+        Some(sink)
+    }
+
+    /// Play a preloaded sound from a position in the manifold.
+    ///
+    /// The source starts silent; its volume is set by the next [`update_spatial`](Self::update_spatial).
+    pub fn play_spatial(&mut self, name: &str, position: ManifoldPosition, params: AudioParams) -> Option<SourceId> {
+        let path = self.preloaded.get(name)?;
+        let sink = self.start_sink(path, params.looping)?;
+        sink.set_volume(0.0);
+
+        let id = SourceId(self.next_source_id);
+        self.next_source_id += 1;
+        self.sources.insert(id, SpatialSource { sink, position, params });
+        Some(id)
+    }
+
+    /// Set where spatial sources are heard from.
+    pub fn set_listener(&mut self, position: ManifoldPosition) {
+        self.listener = Some(position);
+    }
+
+    /// Move a spatial source. Returns false if the source has finished or never existed.
+    pub fn set_source_position(&mut self, id: SourceId, position: ManifoldPosition) -> bool {
+        self.sources.get_mut(&id).map(|source| source.position = position).is_some()
+    }
+
+    /// Set a spatial source's volume before falloff.
+    pub fn set_source_volume(&mut self, id: SourceId, volume: f32) -> bool {
+        self.sources.get_mut(&id).map(|source| source.params.volume = volume).is_some()
+    }
+
+    /// Stop a spatial source.
+    pub fn stop_source(&mut self, id: SourceId) {
+        if let Some(source) = self.sources.remove(&id) {
+            source.sink.stop();
+        }
+    }
+
+    /// Number of spatial sources still playing.
+    pub fn source_count(&self) -> usize { self.sources.len() }
+
+    /// Recompute spatial source volumes from the listener's position. Call once per frame.
+    ///
+    /// Distances use the metric of the listener's chart. Sources in other charts
+    /// are muted, since the sound would have to travel through portals. Finished
+    /// sources are dropped.
+    pub fn update_spatial(&mut self, manifold: &Manifold) {
+        self.sources.retain(|_, source| !source.sink.empty());
+        let Some(listener) = self.listener else {
+            return;
+        };
+        let geometry = manifold
+            .chart(listener.chart_id)
+            .map(|chart| chart.geometry())
+            .unwrap_or(GeometryType::Euclidean);
+
+        for source in self.sources.values() {
+            let gain = if source.position.chart_id == listener.chart_id {
+                manifold
+                    .distance_cached(listener.chart_id, listener.local.to_point(), source.position.local.to_point())
+                    .map(|distance| spatial_falloff(geometry, distance, source.params.reference_distance))
+                    .unwrap_or(0.0)
+            } else {
+                0.0
+            };
+            source.sink.set_volume(self.sfx_volume * source.params.volume * gain);
+        }
     }
 
     /// Play looping background music. Stops any currently playing music.
@@ -184,5 +339,8 @@ impl AudioEngine {
     /// Stop all audio.
     pub fn stop_all(&mut self) {
         self.stop_music();
+        for (_, source) in self.sources.drain() {
+            source.sink.stop();
+        }
     }
 }
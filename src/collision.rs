@@ -93,9 +93,9 @@ impl AABB {
 
     /// Expand this AABB to include a point.
     pub fn expand_to(&mut self, p: [f32; 3]) {
-        for i in 0..3 {
-            if p[i] < self.min[i] { self.min[i] = p[i]; }
-            if p[i] > self.max[i] { self.max[i] = p[i]; }
+        for (i, &v) in p.iter().enumerate() {
+            if v < self.min[i] { self.min[i] = v; }
+            if v > self.max[i] { self.max[i] = v; }
         }
     }
 
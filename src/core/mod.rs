@@ -2,8 +2,10 @@
 //!
 //! Provides the main Engine struct, configuration, and game state trait.
 
+use std::time::Instant;
 use crate::ecs::World;
-use crate::time::Time;
+use crate::time::{FixedTimestep, FrameLimiter, Time};
+use crate::window::{Window, WindowBuilder, WindowEvent};
 
 /// Configuration for the engine
 #[derive(Debug, Clone)]
@@ -16,10 +18,14 @@ pub struct EngineConfig {
     pub height: u32,
     /// Enable vertical sync
     pub vsync: bool,
-    /// Target frames per second (None = unlimited)
+    /// Target frames per second (None = unlimited), enforced when vsync is off or headless
     pub target_fps: Option<u32>,
     /// Whether the window is resizable
     pub resizable: bool,
+    /// Run without a window, surface or renderer (servers, CI)
+    pub headless: bool,
+    /// Rate of `on_fixed_update` calls in Hz
+    pub fixed_update_rate: f32,
 }
 
 impl Default for EngineConfig {
@@ -31,10 +37,86 @@ impl Default for EngineConfig {
             vsync: true,
             target_fps: None,
             resizable: true,
+            headless: false,
+            fixed_update_rate: 60.0,
         }
     }
 }
 
+impl EngineConfig {
+    /// Start building a configuration from the defaults
+    pub fn builder() -> EngineConfigBuilder {
+        EngineConfigBuilder::default()
+    }
+
+    /// Check for settings the engine can't run with
+    ///
+    /// Rejects a zero width or height, a `target_fps` of zero and a fixed
+    /// update rate that isn't a positive number.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.width == 0 || self.height == 0 {
+            return Err(format!("Window size must be non-zero, got {}x{}", self.width, self.height));
+        }
+        if self.target_fps == Some(0) {
+            return Err("target_fps must be non-zero; use None for unlimited".to_string());
+        }
+        if !(self.fixed_update_rate > 0.0 && self.fixed_update_rate.is_finite()) {
+            return Err(format!("fixed_update_rate must be positive, got {}", self.fixed_update_rate));
+        }
+        Ok(())
+    }
+}
+
+/// Fluent builder for [`EngineConfig`], see [`EngineConfig::builder`]
+#[derive(Debug, Clone, Default)]
+pub struct EngineConfigBuilder {
+    config: EngineConfig,
+}
+
+impl EngineConfigBuilder {
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.config.title = title.into();
+        self
+    }
+
+    pub fn with_dimensions(mut self, width: u32, height: u32) -> Self {
+        self.config.width = width;
+        self.config.height = height;
+        self
+    }
+
+    pub fn with_vsync(mut self, vsync: bool) -> Self {
+        self.config.vsync = vsync;
+        self
+    }
+
+    pub fn with_target_fps(mut self, target_fps: Option<u32>) -> Self {
+        self.config.target_fps = target_fps;
+        self
+    }
+
+    pub fn with_resizable(mut self, resizable: bool) -> Self {
+        self.config.resizable = resizable;
+        self
+    }
+
+    pub fn with_headless(mut self, headless: bool) -> Self {
+        self.config.headless = headless;
+        self
+    }
+
+    pub fn with_fixed_update_rate(mut self, rate: f32) -> Self {
+        self.config.fixed_update_rate = rate;
+        self
+    }
+
+    /// Finish the configuration, checking it with [`EngineConfig::validate`]
+    pub fn build(self) -> Result<EngineConfig, String> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
 /// Trait for implementing game states
 pub trait GameState {
     /// Called once when the game state is initialised
@@ -43,7 +125,16 @@ pub trait GameState {
     /// Called every frame with the delta time in seconds
     fn on_update(&mut self, engine: &mut Engine, dt: f32);
 
-    /// Called every frame for rendering
+    /// Called at the fixed update rate, before `on_update`, with the fixed step in seconds
+    fn on_fixed_update(&mut self, _engine: &mut Engine, _dt: f32) {}
+
+    /// Called for each window event passed to [`Engine::handle_window_event`]
+    ///
+    /// Events arriving during a frame are dispatched before that frame's
+    /// `on_fixed_update` and `on_update`, so a `Resized` is already visible to them.
+    fn on_window_event(&mut self, _engine: &mut Engine, _event: &WindowEvent) {}
+
+    /// Called every frame for rendering (never in headless mode)
     fn on_render(&mut self, _engine: &mut Engine, _renderer: &mut crate::graphics::Renderer) {}
 
     /// Called when the game state is being cleaned up
@@ -58,18 +149,151 @@ pub struct Engine {
     pub world: World,
     /// Time management
     pub time: Time,
+    /// Window the game renders to, headless unless given to `with_window`
+    window: Window,
     /// Whether the engine is currently running
     running: bool,
+    /// Whether `on_cleanup` has already run
+    cleaned_up: bool,
+    /// Accumulator driving `on_fixed_update`
+    fixed_timestep: FixedTimestep,
+    /// Paces frames to `target_fps`
+    frame_limiter: FrameLimiter,
 }
 
 impl Engine {
     /// Create a new engine instance with the given configuration
+    ///
+    /// The engine gets a headless [`Window`] built from the configuration.
+    /// Windowed games use [`with_window`](Self::with_window) instead.
     pub fn new(config: EngineConfig) -> Self {
+        let window = WindowBuilder::from_config(&config).build_headless();
+        Self::with_window(config, window)
+    }
+
+    /// Create an engine around the window the game renders to
+    ///
+    /// Build the window on the event loop that drives the game, e.g. with
+    /// [`EventLoopWrapper::build_window`](crate::window::EventLoopWrapper::build_window),
+    /// and create the `Renderer` from [`window`](Self::window) so the engine's
+    /// window is the one on screen.
+    pub fn with_window(config: EngineConfig, window: Window) -> Self {
+        let fixed_timestep = FixedTimestep::new(config.fixed_update_rate);
+        let frame_limiter = FrameLimiter::new(config.target_fps);
         Self {
             config,
             world: World::new(),
             time: Time::new(),
+            window,
             running: true,
+            cleaned_up: false,
+            fixed_timestep,
+            frame_limiter,
+        }
+    }
+
+    /// Create an engine that never opens a window or touches the GPU
+    pub fn headless(config: EngineConfig) -> Self {
+        Self::new(EngineConfig { headless: true, ..config })
+    }
+
+    /// Check if the engine runs without a window
+    pub fn is_headless(&self) -> bool {
+        self.config.headless
+    }
+
+    /// The window the game renders to
+    pub fn window(&self) -> &Window {
+        &self.window
+    }
+
+    /// Mutable access to the window, to set its title or toggle fullscreen
+    pub fn window_mut(&mut self) -> &mut Window {
+        &mut self.window
+    }
+
+    /// Advance one frame by `dt` seconds without rendering
+    ///
+    /// Runs any due `on_fixed_update` steps, then `on_update`. Lets servers and
+    /// tests drive the game from their own clock. `dt` is clamped by `Time`'s max delta.
+    ///
+    /// Once [`quit`](Self::quit) has been called the frame in progress finishes,
+    /// and later ticks do nothing.
+    pub fn tick<G: GameState>(&mut self, game: &mut G, dt: f32) {
+        if !self.running {
+            return;
+        }
+
+        self.time.update(dt);
+        let dt = self.time.delta_time();
+
+        let fixed_dt = self.fixed_timestep.fixed_dt();
+        for _ in 0..self.fixed_timestep.update(dt) {
+            game.on_fixed_update(self, fixed_dt);
+        }
+
+        game.on_update(self, dt);
+    }
+
+    /// Forward a window event to the game, then apply the engine's own handling
+    ///
+    /// Call for every event before the frame's [`tick`](Self::tick). `Resized`
+    /// updates the configured width and height and `CloseRequested` quits once
+    /// the game has seen it.
+    pub fn handle_window_event<G: GameState>(&mut self, game: &mut G, event: &WindowEvent) {
+        game.on_window_event(self, event);
+
+        match *event {
+            WindowEvent::Resized(width, height) => {
+                self.config.width = width;
+                self.config.height = height;
+            }
+            WindowEvent::CloseRequested => self.quit(),
+            _ => {}
+        }
+    }
+
+    /// Wait out the rest of the frame to hold `target_fps`
+    ///
+    /// Call once per frame after presenting. Does nothing without a
+    /// `target_fps`, or when vsync is on in a windowed engine, since
+    /// presentation already paces frames then. See [`FrameLimiter`].
+    pub fn limit_frame_rate(&mut self) {
+        let limited = self.config.headless || !self.config.vsync;
+        self.frame_limiter.set_target_fps(self.config.target_fps.filter(|_| limited));
+        self.frame_limiter.wait();
+    }
+
+    /// Run a game from a wall-clock loop until it calls [`quit`](Self::quit)
+    ///
+    /// Nothing is rendered and `on_render` is never called. Frames are paced to
+    /// `target_fps` when set, otherwise they run back to back.
+    pub fn run_headless<G: GameState>(&mut self, game: &mut G) {
+        game.on_init(self);
+
+        let mut last_frame = Instant::now();
+        while self.running {
+            let frame_start = Instant::now();
+            let dt = frame_start.duration_since(last_frame).as_secs_f32();
+            last_frame = frame_start;
+
+            self.tick(game, dt);
+            self.limit_frame_rate();
+        }
+
+        self.shutdown(game);
+    }
+
+    /// Stop the engine and run the game's `on_cleanup`
+    ///
+    /// `on_cleanup` runs exactly once however often this is called. A windowed
+    /// loop should call this after its last frame and drop its `Renderer`
+    /// afterwards, so cleanup can still release GPU resources it owns.
+    pub fn shutdown<G: GameState>(&mut self, game: &mut G) {
+        self.running = false;
+        if !self.cleaned_up {
+            self.cleaned_up = true;
+            game.on_cleanup(self);
         }
     }
 
@@ -78,7 +302,17 @@ impl Engine {
         self.running
     }
 
+    /// Check if [`quit`](Self::quit) has been requested
+    ///
+    /// Systems can use this to stop spawning new work while the last frame finishes.
+    pub fn is_quitting(&self) -> bool {
+        !self.running
+    }
+
     /// Signal the engine to quit
+    ///
+    /// The current frame runs to completion, then no further updates happen.
+    /// The loop calls [`shutdown`](Self::shutdown) on its way out.
     pub fn quit(&mut self) {
         self.running = false;
     }
@@ -0,0 +1,138 @@
+//! Entities that turn to face the camera
+
+use cgmath::{InnerSpace, Matrix3, Quaternion, Vector3};
+use crate::manifold::{Geodesic, Manifold, ManifoldOrientation, ManifoldPosition};
+use super::{with_manifold, Component, System, Transform, World};
+
+/// Points of the geodesic used to find the direction to the camera in curved charts
+const DIRECTION_STEPS: usize = 8;
+
+/// How a billboard may turn to face the camera
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BillboardAxis {
+    /// Turn freely so the front points straight at the camera
+    #[default]
+    Full,
+    /// Only turn about the Y axis, staying upright
+    Y,
+}
+
+/// Keeps an entity's local +Z axis pointing at the camera, see [`BillboardSystem`]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Billboard {
+    pub axis: BillboardAxis,
+}
+
+impl Billboard {
+    pub fn new(axis: BillboardAxis) -> Self {
+        Self { axis }
+    }
+    
+    /// Rotation turning +Z towards `direction` with +Y kept as close to up as the axis allows
+    ///
+    /// `None` if there's no way to face `direction`: it is zero, or straight up
+    /// or down for a [`BillboardAxis::Y`] billboard.
+    pub fn rotation_towards(&self, direction: Vector3<f32>) -> Option<Quaternion<f32>> {
+        let up = Vector3::unit_y();
+        let forward = match self.axis {
+            BillboardAxis::Full => direction,
+            BillboardAxis::Y => direction - up * direction.dot(up),
+        };
+        if forward.magnitude2() <= f32::EPSILON {
+            return None;
+        }
+        let forward = forward.normalize();
+        
+        // Looking straight up or down, any horizontal right axis will do
+        let right = up.cross(forward);
+        let right = if right.magnitude2() > f32::EPSILON { right.normalize() } else { Vector3::unit_x() };
+        let up = forward.cross(right);
+        
+        Some(Quaternion::from(Matrix3::from_cols(right, up, forward)).normalize())
+    }
+}
+
+impl Component for Billboard {
+    fn as_any(&self) -> &dyn std::any::Any { self }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+}
+
+/// Where billboards should face and LOD distances are measured from, stored as a world resource
+#[derive(Debug, Clone, Copy)]
+pub struct CameraPosition(pub ManifoldPosition);
+
+impl CameraPosition {
+    /// The `CameraPosition` resource, or failing that the position of a
+    /// [`Camera`](crate::graphics::Camera) resource
+    pub fn find(world: &World) -> Option<ManifoldPosition> {
+        world.get_resource::<CameraPosition>()
+            .map(|camera| camera.0)
+            .or_else(|| world.get_resource::<crate::graphics::Camera>().map(|camera| camera.position))
+    }
+}
+
+/// Turns every [`Billboard`] entity's `Transform` towards the camera
+///
+/// The camera is the [`CameraPosition`] resource, or failing that a
+/// [`Camera`](crate::graphics::Camera) resource. Within a chart the direction
+/// is the start of the geodesic to the camera, so billboards in curved charts
+/// face along the line of sight rather than the straight coordinate line; the
+/// chart's metric comes from the world's manifold (see [`with_manifold`]), and
+/// without one charts are treated as flat. Billboards in a different chart
+/// from the camera face its position in world space.
+///
+/// Add it to [`Stage::PostUpdate`](super::Stage::PostUpdate), after movement.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BillboardSystem;
+
+impl BillboardSystem {
+    /// Direction from `from` towards `camera`
+    fn direction(from: ManifoldPosition, camera: ManifoldPosition, manifold: Option<&Manifold>) -> Vector3<f32> {
+        let (start, end) = (from.local.to_point(), camera.local.to_point());
+        let Some(manifold) = manifold else {
+            return end - start;
+        };
+        
+        if from.chart_id != camera.chart_id {
+            return from.to_world(manifold)
+                .zip(camera.to_world(manifold))
+                .map_or(end - start, |(start, end)| end - start);
+        }
+        
+        manifold.chart(from.chart_id)
+            .and_then(|chart| Geodesic::compute(start, end, chart.metric(), DIRECTION_STEPS).tangents.first().copied())
+            .unwrap_or(end - start)
+    }
+}
+
+impl System for BillboardSystem {
+    fn update(&self, world: &mut World, _dt: f32) {
+        let Some(camera) = CameraPosition::find(world) else {
+            return;
+        };
+        
+        let rotations_with = |manifold: Option<&Manifold>| -> Vec<_> {
+            world.query2::<Billboard, Transform>()
+                .into_iter()
+                .filter_map(|entity| {
+                    let billboard = world.get_component::<Billboard>(entity)?;
+                    let transform = world.get_component::<Transform>(entity)?;
+                    let direction = Self::direction(transform.position, camera, manifold);
+                    billboard.rotation_towards(direction).map(|rotation| (entity, rotation))
+                })
+                .collect()
+        };
+        let rotations = with_manifold(world, |manifold| rotations_with(Some(manifold)))
+            .unwrap_or_else(|| rotations_with(None));
+        
+        for (entity, rotation) in rotations {
+            if let Some(transform) = world.get_component_mut::<Transform>(entity) {
+                transform.orientation = ManifoldOrientation::new(rotation);
+            }
+        }
+    }
+    
+    fn clone_box(&self) -> Box<dyn System> {
+        Box::new(*self)
+    }
+}
@@ -0,0 +1,65 @@
+//! Level-of-detail mesh selection by camera distance
+
+use cgmath::MetricSpace;
+use crate::manifold::{Manifold, ManifoldPosition};
+use super::{with_manifold, CameraPosition, Renderable, System, Transform, World};
+
+/// Picks each [`Renderable`]'s `lod_level` from its distance to the camera
+///
+/// The camera is found as for billboards, see [`CameraPosition::find`].
+/// Distances use the chart's metric, and the cheapest portal route for entities
+/// in another chart than the camera, with the metric coming from the world's
+/// manifold (see [`with_manifold`]). Without one, distances are Euclidean in
+/// local coordinates. Entities the camera can't reach get their lowest detail.
+///
+/// Add it to [`Stage::PostUpdate`](super::Stage::PostUpdate), after movement.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LodSystem;
+
+impl LodSystem {
+    /// Distance from the camera to `position`
+    fn distance(camera: ManifoldPosition, position: ManifoldPosition, manifold: Option<&Manifold>) -> f32 {
+        match manifold {
+            Some(manifold) => manifold.geodesic_distance(camera, position).unwrap_or(f32::INFINITY),
+            None if camera.chart_id == position.chart_id => {
+                camera.local.to_point().distance(position.local.to_point())
+            }
+            None => f32::INFINITY,
+        }
+    }
+}
+
+impl System for LodSystem {
+    fn update(&self, world: &mut World, _dt: f32) {
+        let Some(camera) = CameraPosition::find(world) else {
+            return;
+        };
+        
+        let levels_with = |manifold: Option<&Manifold>| -> Vec<_> {
+            world.query2::<Renderable, Transform>()
+                .into_iter()
+                .filter_map(|entity| {
+                    let renderable = world.get_component::<Renderable>(entity)?;
+                    if renderable.lod_meshes.is_empty() {
+                        return None;
+                    }
+                    let transform = world.get_component::<Transform>(entity)?;
+                    let distance = Self::distance(camera, transform.position, manifold);
+                    Some((entity, renderable.lod_for_distance(distance)))
+                })
+                .collect()
+        };
+        let levels = with_manifold(world, |manifold| levels_with(Some(manifold)))
+            .unwrap_or_else(|| levels_with(None));
+        
+        for (entity, level) in levels {
+            if let Some(renderable) = world.get_component_mut::<Renderable>(entity) {
+                renderable.lod_level = level;
+            }
+        }
+    }
+    
+    fn clone_box(&self) -> Box<dyn System> {
+        Box::new(*self)
+    }
+}
@@ -1,10 +1,20 @@
 //! Entity Component System for the non-Euclidean engine
 
 use std::any::{Any, TypeId};
-use std::collections::HashMap;
-use cgmath::{Point3, Quaternion, InnerSpace};
+use std::collections::{HashMap, HashSet};
+use cgmath::{Point3, Quaternion, InnerSpace, Vector3};
 use crate::manifold::{ManifoldPosition, ManifoldOrientation, ChartId};
 
+pub mod spatial;
+pub mod billboard;
+pub mod lod;
+pub mod portal_sync;
+
+pub use spatial::{SpatialGrid, SpatialIndexSystem};
+pub use billboard::{Billboard, BillboardAxis, BillboardSystem, CameraPosition};
+pub use lod::LodSystem;
+pub use portal_sync::PortalSyncSystem;
+
 /// Entity identifier
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Entity(pub u32);
@@ -56,6 +66,48 @@ pub struct Renderable {
     pub mesh_id: String,
     pub shader_id: String,
     pub visible: bool,
+    /// Alpha-blended; drawn after opaque geometry, back to front (see `RenderQueue`)
+    pub transparent: bool,
+    /// Lower-detail meshes as `(distance, mesh id)`, each used from its distance on
+    pub lod_meshes: Vec<(f32, String)>,
+    /// Index into `lod_meshes` picked by [`LodSystem`], `None` for `mesh_id`
+    pub lod_level: Option<usize>,
+}
+
+impl Renderable {
+    /// A visible, opaque renderable without LODs
+    pub fn new(mesh_id: &str, shader_id: &str) -> Self {
+        Self {
+            mesh_id: mesh_id.to_string(),
+            shader_id: shader_id.to_string(),
+            visible: true,
+            transparent: false,
+            lod_meshes: Vec::new(),
+            lod_level: None,
+        }
+    }
+    
+    /// Add a mesh to draw from `distance` away onwards
+    pub fn with_lod(mut self, distance: f32, mesh_id: &str) -> Self {
+        self.lod_meshes.push((distance, mesh_id.to_string()));
+        self
+    }
+    
+    /// LOD level for a camera `distance` away: the farthest threshold not beyond it
+    pub fn lod_for_distance(&self, distance: f32) -> Option<usize> {
+        self.lod_meshes.iter()
+            .enumerate()
+            .filter(|(_, (threshold, _))| *threshold <= distance)
+            .max_by(|a, b| a.1.0.total_cmp(&b.1.0))
+            .map(|(index, _)| index)
+    }
+    
+    /// Mesh to draw at `lod_level`, falling back to `mesh_id`
+    pub fn current_mesh_id(&self) -> &str {
+        self.lod_level
+            .and_then(|level| self.lod_meshes.get(level))
+            .map_or(&self.mesh_id, |(_, mesh_id)| mesh_id)
+    }
 }
 
 impl Component for Renderable {
@@ -63,7 +115,7 @@ impl Component for Renderable {
     fn as_any_mut(&mut self) -> &mut dyn Any { self }
 }
 
-/// Portal entity marker
+/// Portal entity marker, kept in sync with the manifold by [`PortalSyncSystem`]
 #[derive(Debug, Clone)]
 pub struct PortalEntity {
     pub portal_id: crate::manifold::PortalId,
@@ -77,7 +129,7 @@ impl Component for PortalEntity {
 
 /// Component storage
 struct ComponentStorage {
-    components: HashMap<TypeId, HashMap<Entity, Box<dyn Component>>>,
+    components: HashMap<TypeId, ComponentMap>,
 }
 
 impl ComponentStorage {
@@ -91,7 +143,7 @@ impl ComponentStorage {
         let type_id = TypeId::of::<T>();
         self.components
             .entry(type_id)
-            .or_insert_with(HashMap::new)
+            .or_default()
             .insert(entity, Box::new(component));
     }
     
@@ -127,23 +179,191 @@ impl ComponentStorage {
     }
 }
 
+/// Double-buffered queue of one event type
+struct EventQueue<E> {
+    /// Events sent during the current frame
+    pending: Vec<E>,
+    /// Events sent during the previous frame, readable now
+    readable: Vec<E>,
+}
+
+trait AnyEventQueue: Send + Sync {
+    fn swap_buffers(&mut self);
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<E: Send + Sync + 'static> AnyEventQueue for EventQueue<E> {
+    fn swap_buffers(&mut self) {
+        // Unread events from the previous frame are dropped
+        self.readable = std::mem::take(&mut self.pending);
+    }
+    
+    fn as_any_mut(&mut self) -> &mut dyn Any { self }
+}
+
+/// Storage for typed event queues
+struct EventStorage {
+    queues: HashMap<TypeId, Box<dyn AnyEventQueue>>,
+}
+
+impl EventStorage {
+    fn new() -> Self {
+        Self {
+            queues: HashMap::new(),
+        }
+    }
+    
+    fn queue_mut<E: Send + Sync + 'static>(&mut self) -> &mut EventQueue<E> {
+        self.queues
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| Box::new(EventQueue::<E> { pending: Vec::new(), readable: Vec::new() }))
+            .as_any_mut()
+            .downcast_mut::<EventQueue<E>>()
+            .expect("event queue stored under the wrong type")
+    }
+    
+    fn swap_buffers(&mut self) {
+        for queue in self.queues.values_mut() {
+            queue.swap_buffers();
+        }
+    }
+}
+
+/// Shared state stored in the world, at most one value per type
+///
+/// Unlike components, resources belong to no entity; systems reach them through `World`.
+pub struct Resources {
+    values: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl Default for Resources {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Resources {
+    pub fn new() -> Self {
+        Self {
+            values: HashMap::new(),
+        }
+    }
+    
+    /// Insert a resource, returning the previous value of the same type
+    pub fn insert<R: Send + Sync + 'static>(&mut self, resource: R) -> Option<R> {
+        self.values
+            .insert(TypeId::of::<R>(), Box::new(resource))
+            .and_then(|old| old.downcast::<R>().ok())
+            .map(|old| *old)
+    }
+    
+    pub fn get<R: Send + Sync + 'static>(&self) -> Option<&R> {
+        self.values.get(&TypeId::of::<R>())?.downcast_ref::<R>()
+    }
+    
+    pub fn get_mut<R: Send + Sync + 'static>(&mut self) -> Option<&mut R> {
+        self.values.get_mut(&TypeId::of::<R>())?.downcast_mut::<R>()
+    }
+    
+    pub fn remove<R: Send + Sync + 'static>(&mut self) -> Option<R> {
+        self.values
+            .remove(&TypeId::of::<R>())
+            .and_then(|old| old.downcast::<R>().ok())
+            .map(|old| *old)
+    }
+    
+    pub fn contains<R: Send + Sync + 'static>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<R>())
+    }
+}
+
+/// Every component of one type, by entity
+type ComponentMap = HashMap<Entity, Box<dyn Component>>;
+
+/// Clones a boxed component of one type registered for snapshots
+type ComponentCloner = fn(&dyn Component) -> Box<dyn Component>;
+
+fn clone_component<T: Component + Clone>(component: &dyn Component) -> Box<dyn Component> {
+    let component = component.as_any()
+        .downcast_ref::<T>()
+        .expect("component stored under the wrong type");
+    Box::new(component.clone())
+}
+
+/// Entities and snapshot-registered components saved by [`World::snapshot`]
+pub struct WorldSnapshot {
+    entities: Vec<Entity>,
+    next_entity_id: u32,
+    components: HashMap<TypeId, (ComponentCloner, ComponentMap)>,
+}
+
+impl WorldSnapshot {
+    /// Number of entities saved
+    pub fn entity_count(&self) -> usize {
+        self.entities.len()
+    }
+    
+    fn clone_components(&self) -> impl Iterator<Item = (TypeId, ComponentCloner, ComponentMap)> + '_ {
+        self.components.iter().map(|(type_id, (cloner, components))| {
+            let copies = components.iter()
+                .map(|(entity, component)| (*entity, cloner(component.as_ref())))
+                .collect();
+            (*type_id, *cloner, copies)
+        })
+    }
+}
+
+impl Clone for WorldSnapshot {
+    fn clone(&self) -> Self {
+        Self {
+            entities: self.entities.clone(),
+            next_entity_id: self.next_entity_id,
+            components: self.clone_components()
+                .map(|(type_id, cloner, components)| (type_id, (cloner, components)))
+                .collect(),
+        }
+    }
+}
+
 /// ECS World containing all entities and components
 pub struct World {
     entities: Vec<Entity>,
     next_entity_id: u32,
     components: ComponentStorage,
-    systems: Vec<Box<dyn System>>,
+    /// Systems sorted by stage, in insertion order within a stage
+    systems: Vec<(Stage, Box<dyn System>)>,
+    events: EventStorage,
+    resources: Resources,
+    /// Component types copied by `snapshot`
+    snapshot_types: HashMap<TypeId, ComponentCloner>,
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl World {
     /// Create a new empty world
+    ///
+    /// The engine's own components are registered for snapshots.
     pub fn new() -> Self {
-        Self {
+        let mut world = Self {
             entities: Vec::new(),
             next_entity_id: 0,
             components: ComponentStorage::new(),
             systems: Vec::new(),
-        }
+            events: EventStorage::new(),
+            resources: Resources::new(),
+            snapshot_types: HashMap::new(),
+        };
+        world.register_snapshot_component::<Transform>();
+        world.register_snapshot_component::<Velocity>();
+        world.register_snapshot_component::<Renderable>();
+        world.register_snapshot_component::<PortalEntity>();
+        world.register_snapshot_component::<Billboard>();
+        world
     }
     
     /// Create a new entity
@@ -154,6 +374,13 @@ impl World {
         entity
     }
     
+    /// Create an entity with every component of a bundle
+    pub fn spawn<B: Bundle>(&mut self, bundle: B) -> Entity {
+        let entity = self.create_entity();
+        bundle.insert(self, entity);
+        entity
+    }
+    
     /// Destroy an entity and all its components
     pub fn destroy_entity(&mut self, entity: Entity) {
         if let Some(pos) = self.entities.iter().position(|&e| e == entity) {
@@ -192,6 +419,20 @@ impl World {
         }
     }
     
+    /// Iterate over every component of type `T` with its entity
+    ///
+    /// Borrows the world instead of collecting entities like [`query`](Self::query),
+    /// so nothing is allocated. Order is unspecified.
+    pub fn iter<T: Component + 'static>(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.components.components
+            .get(&TypeId::of::<T>())
+            .into_iter()
+            .flat_map(|components| components.iter())
+            .filter_map(|(entity, component)| {
+                component.as_any().downcast_ref::<T>().map(|component| (*entity, component))
+            })
+    }
+    
     /// Query entities with two component types
     pub fn query2<T1: Component + 'static, T2: Component + 'static>(&self) -> Vec<Entity> {
         let entities1 = self.query::<T1>();
@@ -201,16 +442,92 @@ impl World {
             .collect()
     }
     
-    /// Add a system to the world
+    /// Query entities with three component types
+    pub fn query3<T1: Component + 'static, T2: Component + 'static, T3: Component + 'static>(&self) -> Vec<Entity> {
+        self.query2::<T1, T2>()
+            .into_iter()
+            .filter(|&e| self.get_component::<T3>(e).is_some())
+            .collect()
+    }
+    
+    /// Query entities with `T` that lack `Without`
+    pub fn query_without<T: Component + 'static, Without: Component + 'static>(&self) -> Vec<Entity> {
+        let excluded = self.components.components.get(&TypeId::of::<Without>());
+        self.query::<T>()
+            .into_iter()
+            .filter(|e| excluded.is_none_or(|components| !components.contains_key(e)))
+            .collect()
+    }
+    
+    /// Query entities with `T1` and `T2` that lack `Without`
+    pub fn query2_without<T1: Component + 'static, T2: Component + 'static, Without: Component + 'static>(&self) -> Vec<Entity> {
+        let excluded = self.components.components.get(&TypeId::of::<Without>());
+        self.query2::<T1, T2>()
+            .into_iter()
+            .filter(|e| excluded.is_none_or(|components| !components.contains_key(e)))
+            .collect()
+    }
+    
+    /// Add a system to the world's `Update` stage
     pub fn add_system(&mut self, system: Box<dyn System>) {
-        self.systems.push(system);
+        self.add_system_to_stage(Stage::Update, system);
+    }
+    
+    /// Add a system to a stage, after the systems already in it
+    pub fn add_system_to_stage(&mut self, stage: Stage, system: Box<dyn System>) {
+        let index = self.systems.partition_point(|(existing, _)| *existing <= stage);
+        self.systems.insert(index, (stage, system));
+    }
+    
+    /// Insert a shared resource, replacing any previous value of the same type
+    pub fn insert_resource<R: Send + Sync + 'static>(&mut self, resource: R) -> Option<R> {
+        self.resources.insert(resource)
+    }
+    
+    /// Get a shared resource
+    pub fn get_resource<R: Send + Sync + 'static>(&self) -> Option<&R> {
+        self.resources.get()
+    }
+    
+    /// Get a mutable shared resource
+    pub fn get_resource_mut<R: Send + Sync + 'static>(&mut self) -> Option<&mut R> {
+        self.resources.get_mut()
+    }
+    
+    /// Remove a shared resource
+    pub fn remove_resource<R: Send + Sync + 'static>(&mut self) -> Option<R> {
+        self.resources.remove()
+    }
+    
+    /// Get the resource container
+    pub fn resources(&self) -> &Resources {
+        &self.resources
+    }
+    
+    /// Send an event; it becomes readable on the next frame
+    pub fn send_event<E: Send + Sync + 'static>(&mut self, event: E) {
+        self.events.queue_mut::<E>().pending.push(event);
+    }
+    
+    /// Take all events of type `E` sent during the previous frame
+    pub fn drain_events<E: Send + Sync + 'static>(&mut self) -> Vec<E> {
+        std::mem::take(&mut self.events.queue_mut::<E>().readable)
+    }
+    
+    /// Make events sent this frame readable and drop unread ones from the frame before
+    ///
+    /// Called by `update` before systems run.
+    pub fn swap_event_buffers(&mut self) {
+        self.events.swap_buffers();
     }
     
     /// Update all systems
     pub fn update(&mut self, dt: f32) {
+        self.swap_event_buffers();
+        
         // Clone systems to avoid borrow issues
         let systems = self.systems.clone();
-        for system in systems.iter() {
+        for (_, system) in systems.iter() {
             system.update(self, dt);
         }
     }
@@ -219,6 +536,138 @@ impl World {
     pub fn entities(&self) -> &[Entity] {
         &self.entities
     }
+    
+    /// Number of live entities
+    pub fn entity_count(&self) -> usize {
+        self.entities.len()
+    }
+    
+    /// Number of entities with a component of type `T`
+    pub fn component_count<T: Component + 'static>(&self) -> usize {
+        self.components.components
+            .get(&TypeId::of::<T>())
+            .map_or(0, HashMap::len)
+    }
+    
+    /// Destroy every entity and component at once
+    ///
+    /// Systems, resources and events are kept. Entity ids keep counting up so
+    /// handles from before the clear never refer to new entities; see
+    /// [`reset_entity_ids`](Self::reset_entity_ids).
+    pub fn clear(&mut self) {
+        self.entities.clear();
+        self.components.components.clear();
+    }
+    
+    /// Like [`clear`](Self::clear), but also removes every system
+    pub fn clear_all(&mut self) {
+        self.clear();
+        self.systems.clear();
+    }
+    
+    /// Include components of type `T` in [`snapshot`](Self::snapshot)
+    pub fn register_snapshot_component<T: Component + Clone>(&mut self) {
+        self.snapshot_types.insert(TypeId::of::<T>(), clone_component::<T>);
+    }
+    
+    /// Whether components of type `T` are included in snapshots
+    pub fn is_snapshot_component<T: Component>(&self) -> bool {
+        self.snapshot_types.contains_key(&TypeId::of::<T>())
+    }
+    
+    /// Copy the entities and every registered component type
+    ///
+    /// Systems, resources and events aren't part of the snapshot.
+    pub fn snapshot(&self) -> WorldSnapshot {
+        let components = self.snapshot_types.iter()
+            .map(|(type_id, cloner)| {
+                let copies = self.components.components
+                    .get(type_id)
+                    .into_iter()
+                    .flat_map(|components| components.iter())
+                    .map(|(entity, component)| (*entity, cloner(component.as_ref())))
+                    .collect();
+                (*type_id, (*cloner, copies))
+            })
+            .collect();
+        
+        WorldSnapshot {
+            entities: self.entities.clone(),
+            next_entity_id: self.next_entity_id,
+            components,
+        }
+    }
+    
+    /// Put the entities and registered components back as they were in `snapshot`
+    ///
+    /// Component types that weren't registered when the snapshot was taken can't
+    /// be rolled back; they're only removed from entities the snapshot doesn't have.
+    pub fn restore(&mut self, snapshot: &WorldSnapshot) {
+        self.entities = snapshot.entities.clone();
+        self.next_entity_id = snapshot.next_entity_id;
+        
+        let alive: HashSet<Entity> = self.entities.iter().copied().collect();
+        for components in self.components.components.values_mut() {
+            components.retain(|entity, _| alive.contains(entity));
+        }
+        for (type_id, _, components) in snapshot.clone_components() {
+            self.components.components.insert(type_id, components);
+        }
+    }
+    
+    /// Restart entity ids from 0
+    ///
+    /// Only allowed on a world without entities, so ids can't collide. Returns
+    /// whether the ids were reset.
+    pub fn reset_entity_ids(&mut self) -> bool {
+        if !self.entities.is_empty() {
+            return false;
+        }
+        self.next_entity_id = 0;
+        true
+    }
+}
+
+/// A set of components inserted together by `World::spawn`
+///
+/// Implemented for tuples of up to eight components.
+pub trait Bundle {
+    fn insert(self, world: &mut World, entity: Entity);
+}
+
+macro_rules! impl_bundle {
+    ($($name:ident),+) => {
+        impl<$($name: Component + 'static),+> Bundle for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn insert(self, world: &mut World, entity: Entity) {
+                let ($($name,)+) = self;
+                $(world.add_component(entity, $name);)+
+            }
+        }
+    };
+}
+
+impl_bundle!(A);
+impl_bundle!(A, B);
+impl_bundle!(A, B, C);
+impl_bundle!(A, B, C, D);
+impl_bundle!(A, B, C, D, E);
+impl_bundle!(A, B, C, D, E, F);
+impl_bundle!(A, B, C, D, E, F, G);
+impl_bundle!(A, B, C, D, E, F, G, H);
+
+/// Point in `World::update` at which a system runs
+///
+/// Stages run in declaration order; systems within a stage run in the order
+/// they were added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    /// Before gameplay, e.g. applying input
+    PreUpdate,
+    /// Gameplay and movement integration (the default)
+    Update,
+    /// After movement, e.g. portal transitions and spatial indexing
+    PostUpdate,
 }
 
 /// System trait for ECS systems
@@ -233,17 +682,58 @@ impl Clone for Box<dyn System> {
     }
 }
 
-/// Transform system that handles manifold positions
+/// Run `f` on the world's manifold, returning `None` if it has none
+///
+/// Systems find the manifold in a `Manifold` world resource, or else in an
+/// `Arc<RwLock<Manifold>>` one shared with [`PortalTransitionSystem`]. The
+/// plain `Manifold` wins if both are present. A poisoned lock is still read.
+pub fn with_manifold<R>(world: &World, f: impl FnOnce(&crate::manifold::Manifold) -> R) -> Option<R> {
+    if let Some(manifold) = world.get_resource::<crate::manifold::Manifold>() {
+        return Some(f(manifold));
+    }
+    let shared = world.get_resource::<std::sync::Arc<std::sync::RwLock<crate::manifold::Manifold>>>()?;
+    Some(f(&shared.read().unwrap_or_else(|e| e.into_inner())))
+}
+
+/// Run `f` on the world's manifold mutably, found as for [`with_manifold`]
+pub fn with_manifold_mut<R>(world: &mut World, f: impl FnOnce(&mut crate::manifold::Manifold) -> R) -> Option<R> {
+    if let Some(manifold) = world.get_resource_mut::<crate::manifold::Manifold>() {
+        return Some(f(manifold));
+    }
+    let shared = world.get_resource::<std::sync::Arc<std::sync::RwLock<crate::manifold::Manifold>>>()?;
+    Some(f(&mut shared.write().unwrap_or_else(|e| e.into_inner())))
+}
+
+/// Transform system that keeps positions inside their chart
+///
+/// Each position is passed through its chart's `wrap_coordinates`, so entities
+/// leaving a periodic chart reappear on the other side and those leaving a
+/// spherical or Poincaré chart are pulled back in. The charts come from the
+/// world's manifold, see [`with_manifold`]; without one the system does nothing.
 pub struct TransformSystem;
 
 impl System for TransformSystem {
     fn update(&self, world: &mut World, _dt: f32) {
-        let entities = world.query::<Transform>();
-        for entity in entities {
-            // Update transform positions based on manifold rules
-            if let Some(_transform) = world.get_component_mut::<Transform>(entity) {
-                // Wrap coordinates if needed
-                // This would interact with the manifold system
+        let positions: Vec<(Entity, ManifoldPosition)> = world.query::<Transform>()
+            .into_iter()
+            .filter_map(|entity| Some((entity, world.get_component::<Transform>(entity)?.position)))
+            .collect();
+        
+        let wrapped: Option<Vec<_>> = with_manifold(world, |manifold| {
+            positions.iter()
+                .filter_map(|(entity, position)| {
+                    let wrapped = manifold.chart(position.chart_id)?.wrap_coordinates(position.local);
+                    (wrapped.to_point() != position.local.to_point()).then_some((*entity, wrapped))
+                })
+                .collect()
+        });
+        let Some(wrapped) = wrapped else {
+            return;
+        };
+        
+        for (entity, local) in wrapped {
+            if let Some(transform) = world.get_component_mut::<Transform>(entity) {
+                transform.position.local = local;
             }
         }
     }
@@ -254,18 +744,40 @@ impl System for TransformSystem {
 }
 
 /// Portal transition system
+///
+/// Entities that will reach a portal within the frame, i.e. whose speed times
+/// `dt` covers the distance to it, are moved to the matching point at its exit,
+/// pushed `exit_margin` further along their new direction of travel. Landing
+/// exactly on the exit would put them on the partner portal of a bidirectional
+/// pair, which would send them straight back the next frame; past the margin
+/// the partner is behind them and they can't re-enter it without turning round.
+///
+/// Add it to [`Stage::PostUpdate`] so it sees the frame's movement.
 pub struct PortalTransitionSystem {
     manifold: std::sync::Arc<std::sync::RwLock<crate::manifold::Manifold>>,
+    exit_margin: f32,
 }
 
 impl PortalTransitionSystem {
+    /// Distance entities are pushed past a portal exit unless configured otherwise
+    pub const DEFAULT_EXIT_MARGIN: f32 = 0.05;
+    
     pub fn new(manifold: std::sync::Arc<std::sync::RwLock<crate::manifold::Manifold>>) -> Self {
-        Self { manifold }
+        Self { manifold, exit_margin: Self::DEFAULT_EXIT_MARGIN }
+    }
+    
+    pub fn with_exit_margin(mut self, exit_margin: f32) -> Self {
+        self.exit_margin = exit_margin.max(0.0);
+        self
+    }
+    
+    pub fn exit_margin(&self) -> f32 {
+        self.exit_margin
     }
 }
 
 impl System for PortalTransitionSystem {
-    fn update(&self, world: &mut World, _dt: f32) {
+    fn update(&self, world: &mut World, dt: f32) {
         let entities = world.query2::<Transform, Velocity>();
         
         for entity in entities {
@@ -274,13 +786,18 @@ impl System for PortalTransitionSystem {
                 let transform = world.get_component::<Transform>(entity);
                 let velocity = world.get_component::<Velocity>(entity);
                 
-                if let (Some(transform), Some(velocity)) = (transform, velocity) {
+                if let (Some(transform), Some(velocity)) = (transform, velocity)
+                    // Stationary entities have no direction to cross a portal in
+                    && velocity.linear.magnitude2() > 0.0
+                {
                     if let Ok(manifold) = self.manifold.read() {
                         let position = transform.position.local.to_point();
                         let direction = velocity.linear.normalize();
+                        let reach = velocity.linear.magnitude() * dt;
                         
-                        if let Some((_portal_id, intersection, new_chart)) =
-                            manifold.ray_portal_intersection(position, direction, transform.position.chart_id) {
+                        if let Some((portal_id, intersection, new_chart)) =
+                            manifold.ray_portal_intersection(position, direction, transform.position.chart_id)
+                                .filter(|(_, intersection, _)| (intersection - position).magnitude() <= reach) {
                             
                             let path = manifold.compute_geodesic(
                                 position,
@@ -289,7 +806,7 @@ impl System for PortalTransitionSystem {
                                 10
                             );
                             
-                            Some((new_chart, intersection, path))
+                            Some((portal_id, new_chart, intersection, path))
                         } else {
                             None
                         }
@@ -302,23 +819,39 @@ impl System for PortalTransitionSystem {
             };
             
             // Apply the transition if needed
-            if let Some((new_chart, intersection, path)) = transition_data {
-                if let Some(transform_mut) = world.get_component_mut::<Transform>(entity) {
-                    transform_mut.position.chart_id = new_chart;
-                    transform_mut.position.local = crate::manifold::LocalCoordinate::from_point(intersection);
-                    
-                    // Update orientation with parallel transport
-                    if let Some(path) = path {
-                        if let Ok(manifold) = self.manifold.read() {
-                            transform_mut.orientation.transport_along(&path, &manifold, new_chart);
-                        }
-                    }
+            let Some((portal_id, new_chart, intersection, path)) = transition_data else {
+                continue;
+            };
+            
+            let Ok(manifold) = self.manifold.read() else {
+                continue;
+            };
+            let Some(portal) = manifold.portal(portal_id) else {
+                continue;
+            };
+            
+            // Velocities are re-expressed in the target chart's frame
+            let mut exit_direction = Vector3::new(0.0, 0.0, 0.0);
+            if let Some(velocity) = world.get_component_mut::<Velocity>(entity) {
+                velocity.linear = portal.transform_vector(velocity.linear);
+                velocity.angular = portal.transform_vector(velocity.angular);
+                exit_direction = velocity.linear.normalize();
+            }
+            let exit = portal.transform_point(intersection) + exit_direction * self.exit_margin;
+            
+            if let Some(transform_mut) = world.get_component_mut::<Transform>(entity) {
+                transform_mut.position.chart_id = new_chart;
+                transform_mut.position.local = crate::manifold::LocalCoordinate::from_point(exit);
+                
+                // Update orientation with parallel transport
+                if let Some(path) = path {
+                    transform_mut.orientation.transport_along(&path, &manifold, new_chart);
                 }
             }
         }
     }
     
     fn clone_box(&self) -> Box<dyn System> {
-        Box::new(PortalTransitionSystem::new(self.manifold.clone()))
+        Box::new(PortalTransitionSystem::new(self.manifold.clone()).with_exit_margin(self.exit_margin))
     }
 }
\ No newline at end of file
@@ -0,0 +1,80 @@
+//! Keeps manifold portals in step with their ECS entities
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use crate::manifold::PortalId;
+use super::{with_manifold_mut, Entity, PortalEntity, System, Transform, World};
+
+/// Moves and toggles manifold portals to match [`PortalEntity`] entities
+///
+/// Each entity with a `PortalEntity` and a [`Transform`] places the entrance of
+/// its portal at the transform's position and sets whether it is active, so
+/// portals can be moved and switched by editing entities. The transform must be
+/// in the portal's source chart. Entities naming a portal the manifold doesn't
+/// have, or sitting in another chart, are skipped with a warning, logged once
+/// per entity and portal. Clones of the system, like the ones [`World::update`]
+/// runs each frame, share that record.
+///
+/// Moving an entrance also moves the exit of its reverse portal, see
+/// [`Manifold::move_portal`](crate::manifold::Manifold::move_portal). The
+/// manifold is the world's, see [`with_manifold`](super::with_manifold);
+/// without one the system does nothing.
+///
+/// Add it to [`Stage::PostUpdate`](super::Stage::PostUpdate), before
+/// `PortalTransitionSystem` so crossings use the portals' new places.
+#[derive(Clone, Default)]
+pub struct PortalSyncSystem {
+    warned: Arc<Mutex<HashSet<(Entity, PortalId)>>>,
+}
+
+impl PortalSyncSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Number of entity and portal pairs warned about so far
+    pub fn warning_count(&self) -> usize {
+        self.warned.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+    
+    fn warn_once(&self, entity: Entity, portal_id: PortalId, message: &str) {
+        let mut warned = self.warned.lock().unwrap_or_else(|e| e.into_inner());
+        if warned.insert((entity, portal_id)) {
+            eprintln!("Portal entity {:?} ({:?}) {}", entity, portal_id, message);
+        }
+    }
+}
+
+impl System for PortalSyncSystem {
+    fn update(&self, world: &mut World, _dt: f32) {
+        let portals: Vec<(Entity, PortalEntity, Transform)> = world.query2::<PortalEntity, Transform>()
+            .into_iter()
+            .filter_map(|entity| Some((
+                entity,
+                world.get_component::<PortalEntity>(entity)?.clone(),
+                world.get_component::<Transform>(entity)?.clone(),
+            )))
+            .collect();
+        
+        with_manifold_mut(world, |manifold| {
+            for (entity, portal_entity, transform) in portals {
+                let portal_id = portal_entity.portal_id;
+                let Some(portal) = manifold.portal_mut(portal_id) else {
+                    self.warn_once(entity, portal_id, "has no matching portal in the manifold");
+                    continue;
+                };
+                if portal.source_chart() != transform.position.chart_id {
+                    self.warn_once(entity, portal_id, "is not in the portal's source chart");
+                    continue;
+                }
+                
+                portal.set_active(portal_entity.active);
+                manifold.move_portal(portal_id, transform.position.local.to_point());
+            }
+        });
+    }
+    
+    fn clone_box(&self) -> Box<dyn System> {
+        Box::new(self.clone())
+    }
+}
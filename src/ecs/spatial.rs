@@ -0,0 +1,216 @@
+//! Uniform grid for finding entities by region
+
+use std::collections::{HashMap, HashSet};
+use cgmath::{MetricSpace, Point3};
+use crate::manifold::ChartId;
+use crate::math::Ray;
+use super::{Entity, System, Transform, World};
+
+/// Ray queries stop after crossing this many cells
+const MAX_RAY_CELLS: usize = 4096;
+
+type CellKey = (ChartId, [i32; 3]);
+
+/// Entities bucketed into cubic cells, separately per chart
+///
+/// Distances between charts depend on the portals joining them, so every query
+/// is confined to a single chart.
+#[derive(Debug, Clone)]
+pub struct SpatialGrid {
+    cell_size: f32,
+    cells: HashMap<CellKey, Vec<(Entity, Point3<f32>)>>,
+    len: usize,
+}
+
+impl SpatialGrid {
+    /// Create an empty grid with cubic cells of `cell_size`
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(f32::EPSILON),
+            cells: HashMap::new(),
+            len: 0,
+        }
+    }
+    
+    /// Edge length of a cell
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+    
+    /// Number of inserted entities
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    
+    /// Remove every entity, keeping the cell allocations
+    pub fn clear(&mut self) {
+        for entries in self.cells.values_mut() {
+            entries.clear();
+        }
+        self.len = 0;
+    }
+    
+    /// Add an entity at a point in a chart
+    pub fn insert(&mut self, entity: Entity, chart: ChartId, point: Point3<f32>) {
+        self.cells
+            .entry((chart, self.cell_of(point)))
+            .or_default()
+            .push((entity, point));
+        self.len += 1;
+    }
+    
+    /// Entities within `radius` of `point` in the same chart
+    ///
+    /// Looks only in the cells the radius overlaps, or in every occupied cell
+    /// when those are fewer, so huge and infinite radii stay cheap.
+    pub fn query_radius(&self, chart: ChartId, point: Point3<f32>, radius: f32) -> Vec<Entity> {
+        let min = self.cell_of(point - cgmath::Vector3::new(radius, radius, radius));
+        let max = self.cell_of(point + cgmath::Vector3::new(radius, radius, radius));
+        let radius2 = radius * radius;
+        
+        let volume = (0..3)
+            .map(|axis| (i64::from(max[axis]) - i64::from(min[axis]) + 1).max(0) as u128)
+            .product::<u128>();
+        if volume > self.cells.len() as u128 {
+            return self.cells
+                .iter()
+                .filter(|((cell_chart, _), _)| *cell_chart == chart)
+                .flat_map(|(_, entries)| entities_within(entries, point, radius2))
+                .collect();
+        }
+        
+        let mut found = Vec::new();
+        for x in min[0]..=max[0] {
+            for y in min[1]..=max[1] {
+                for z in min[2]..=max[2] {
+                    if let Some(entries) = self.cells.get(&(chart, [x, y, z])) {
+                        found.extend(entities_within(entries, point, radius2));
+                    }
+                }
+            }
+        }
+        found
+    }
+    
+    /// Candidate entities near a ray, up to `max_distance` along it
+    ///
+    /// Walks the cells the ray crosses and their neighbours, so every entity within
+    /// `cell_size` of the ray segment is returned, along with some further away.
+    /// Callers do the exact hit test on the candidates. At most 4096 cells are
+    /// walked, so very long rays should be given a finite `max_distance`.
+    pub fn query_ray(&self, chart: ChartId, ray: &Ray, max_distance: f32) -> Vec<Entity> {
+        let mut visited = HashSet::new();
+        let mut found = Vec::new();
+        
+        for cell in self.cells_along(ray, max_distance) {
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        let key = (chart, [cell[0] + dx, cell[1] + dy, cell[2] + dz]);
+                        if !visited.insert(key) {
+                            continue;
+                        }
+                        if let Some(entries) = self.cells.get(&key) {
+                            found.extend(entries.iter().map(|(entity, _)| *entity));
+                        }
+                    }
+                }
+            }
+        }
+        found
+    }
+    
+    fn cell_of(&self, point: Point3<f32>) -> [i32; 3] {
+        [point.x, point.y, point.z].map(|c| (c / self.cell_size).floor() as i32)
+    }
+    
+    /// Cells crossed by a ray segment, in order (3D DDA)
+    fn cells_along(&self, ray: &Ray, max_distance: f32) -> Vec<[i32; 3]> {
+        let mut cell = self.cell_of(ray.origin);
+        let origin = [ray.origin.x, ray.origin.y, ray.origin.z];
+        let direction = [ray.direction.x, ray.direction.y, ray.direction.z];
+        
+        let mut step = [0i32; 3];
+        let mut t_max = [f32::INFINITY; 3];
+        let mut t_delta = [f32::INFINITY; 3];
+        for axis in 0..3 {
+            if direction[axis] > 0.0 {
+                step[axis] = 1;
+                let boundary = (cell[axis] + 1) as f32 * self.cell_size;
+                t_max[axis] = (boundary - origin[axis]) / direction[axis];
+                t_delta[axis] = self.cell_size / direction[axis];
+            } else if direction[axis] < 0.0 {
+                step[axis] = -1;
+                let boundary = cell[axis] as f32 * self.cell_size;
+                t_max[axis] = (boundary - origin[axis]) / direction[axis];
+                t_delta[axis] = -self.cell_size / direction[axis];
+            }
+        }
+        
+        let mut cells = vec![cell];
+        loop {
+            let axis = (0..3)
+                .min_by(|&a, &b| t_max[a].total_cmp(&t_max[b]))
+                .unwrap_or(0);
+            if t_max[axis].is_nan() || t_max[axis] > max_distance || cells.len() >= MAX_RAY_CELLS {
+                break;
+            }
+            cell[axis] += step[axis];
+            t_max[axis] += t_delta[axis];
+            cells.push(cell);
+        }
+        cells
+    }
+}
+
+/// Entities in a cell's entries lying within `sqrt(radius2)` of `point`
+fn entities_within(
+    entries: &[(Entity, Point3<f32>)],
+    point: Point3<f32>,
+    radius2: f32,
+) -> impl Iterator<Item = Entity> + '_ {
+    entries
+        .iter()
+        .filter(move |(_, p)| p.distance2(point) <= radius2)
+        .map(|(entity, _)| *entity)
+}
+
+/// Rebuilds the world's [`SpatialGrid`] resource from every `Transform` each update
+///
+/// The grid is created with this system's cell size on first run. Add it to
+/// [`Stage::PostUpdate`](super::Stage::PostUpdate) so the grid reflects the frame's movement.
+#[derive(Debug, Clone, Copy)]
+pub struct SpatialIndexSystem {
+    pub cell_size: f32,
+}
+
+impl SpatialIndexSystem {
+    pub fn new(cell_size: f32) -> Self {
+        Self { cell_size }
+    }
+}
+
+impl System for SpatialIndexSystem {
+    fn update(&self, world: &mut World, _dt: f32) {
+        let mut grid = world
+            .remove_resource::<SpatialGrid>()
+            .unwrap_or_else(|| SpatialGrid::new(self.cell_size));
+        grid.clear();
+        
+        for entity in world.query::<Transform>() {
+            if let Some(transform) = world.get_component::<Transform>(entity) {
+                grid.insert(entity, transform.position.chart_id, transform.position.local.to_point());
+            }
+        }
+        
+        world.insert_resource(grid);
+    }
+    
+    fn clone_box(&self) -> Box<dyn System> {
+        Box::new(*self)
+    }
+}
@@ -0,0 +1,150 @@
+//! Texture atlases: one texture holding many named sprites
+
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+use super::{Texture, Vertex};
+
+/// Pixel rectangle of a sprite inside an atlas, origin at the top-left
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AtlasRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Named regions of an atlas, independent of the GPU texture
+///
+/// Can be described in JSON:
+/// `{"width": 256, "height": 256, "regions": {"cockroach": {"x": 0, "y": 0, "width": 64, "height": 64}}}`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AtlasLayout {
+    pub width: u32,
+    pub height: u32,
+    #[serde(default)]
+    regions: HashMap<String, AtlasRegion>,
+}
+
+impl AtlasLayout {
+    /// Create an empty layout for a texture of the given size
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            regions: HashMap::new(),
+        }
+    }
+    
+    /// Split the texture into a uniform grid of `columns` × `rows` cells
+    ///
+    /// Cells are numbered row by row from the top-left and named by `names` in
+    /// that order; cells without a name are named by their index (`"0"`, `"1"`, ...).
+    pub fn from_grid(width: u32, height: u32, columns: u32, rows: u32, names: &[&str]) -> Self {
+        let mut layout = Self::new(width, height);
+        let (cell_width, cell_height) = (width / columns.max(1), height / rows.max(1));
+        
+        for index in 0..columns * rows {
+            let region = AtlasRegion {
+                x: (index % columns) * cell_width,
+                y: (index / columns) * cell_height,
+                width: cell_width,
+                height: cell_height,
+            };
+            let name = names.get(index as usize)
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| index.to_string());
+            layout.regions.insert(name, region);
+        }
+        
+        layout
+    }
+    
+    /// Parse a layout from a JSON descriptor
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(serde_json::from_str(json)?)
+    }
+    
+    /// Add or replace a named region
+    pub fn insert(&mut self, name: impl Into<String>, region: AtlasRegion) {
+        self.regions.insert(name.into(), region);
+    }
+    
+    /// Get a region by name
+    pub fn region(&self, name: &str) -> Option<&AtlasRegion> {
+        self.regions.get(name)
+    }
+    
+    /// Names of all regions
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.regions.keys().map(String::as_str)
+    }
+    
+    /// Normalized texture coordinates `[u_min, v_min, u_max, v_max]` of a region
+    pub fn uv_rect(&self, name: &str) -> Option<[f32; 4]> {
+        let region = self.regions.get(name)?;
+        let (w, h) = (self.width.max(1) as f32, self.height.max(1) as f32);
+        Some([
+            region.x as f32 / w,
+            region.y as f32 / h,
+            (region.x + region.width) as f32 / w,
+            (region.y + region.height) as f32 / h,
+        ])
+    }
+    
+    /// Quad vertices and indices textured with a region, laid out like `Mesh::create_quad`
+    pub fn quad(&self, name: &str, size: f32) -> Option<(Vec<Vertex>, Vec<u32>)> {
+        self.uv_rect(name).map(|uv| quad_with_uv(uv, size))
+    }
+}
+
+/// A single texture plus the named sprites packed into it
+pub struct TextureAtlas {
+    pub texture: Texture,
+    pub layout: AtlasLayout,
+}
+
+impl TextureAtlas {
+    /// Pair a texture with a layout
+    pub fn new(texture: Texture, layout: AtlasLayout) -> Self {
+        Self { texture, layout }
+    }
+    
+    /// Split a texture into a uniform grid (see [`AtlasLayout::from_grid`])
+    pub fn from_grid(texture: Texture, columns: u32, rows: u32, names: &[&str]) -> Self {
+        let (width, height) = texture.size;
+        let layout = AtlasLayout::from_grid(width, height, columns, rows, names);
+        Self { texture, layout }
+    }
+    
+    /// Pair a texture with a layout parsed from a JSON descriptor
+    pub fn from_json(texture: Texture, json: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self::new(texture, AtlasLayout::from_json(json)?))
+    }
+    
+    /// Normalized texture coordinates `[u_min, v_min, u_max, v_max]` of a sprite
+    pub fn uv_rect(&self, name: &str) -> Option<[f32; 4]> {
+        self.layout.uv_rect(name)
+    }
+    
+    /// Quad vertices and indices textured with a sprite
+    pub fn quad(&self, name: &str, size: f32) -> Option<(Vec<Vertex>, Vec<u32>)> {
+        self.layout.quad(name, size)
+    }
+}
+
+/// Centered quad in the XY plane mapping `uv` onto its face
+pub fn quad_with_uv(uv: [f32; 4], size: f32) -> (Vec<Vertex>, Vec<u32>) {
+    let half_size = size / 2.0;
+    let [u0, v0, u1, v1] = uv;
+    let normal = [0.0, 0.0, 1.0];
+    let white = [1.0, 1.0, 1.0, 1.0];
+    
+    let vertices = vec![
+        Vertex::new([-half_size, -half_size, 0.0], [u0, v1], normal, white),
+        Vertex::new([half_size, -half_size, 0.0], [u1, v1], normal, white),
+        Vertex::new([half_size, half_size, 0.0], [u1, v0], normal, white),
+        Vertex::new([-half_size, half_size, 0.0], [u0, v0], normal, white),
+    ];
+    
+    (vertices, vec![0, 1, 2, 0, 2, 3])
+}
@@ -1,7 +1,11 @@
 //! Camera system for non-Euclidean rendering
 
-use cgmath::{Point3, Vector3, Matrix4, Rad, perspective, InnerSpace};
+use cgmath::{Point3, Vector3, Vector4, Matrix4, Rad, perspective, InnerSpace, SquareMatrix, Quaternion, One};
 use crate::manifold::{ManifoldPosition, ChartId, GeometryType};
+use crate::math::{Frustum, Ray};
+
+/// RK4 steps used by `Camera::move_geodesic` in curved charts
+const GEODESIC_MOVE_STEPS: usize = 32;
 
 /// Camera for viewing non-Euclidean spaces
 pub struct Camera {
@@ -15,6 +19,14 @@ pub struct Camera {
     pub view_matrix: Matrix4<f32>,
     pub projection_matrix: Matrix4<f32>,
     pub geometry_type: GeometryType,
+    /// `fovy` is multiplied by this in hyperbolic charts, widening the view
+    pub hyperbolic_fov_scale: f32,
+    /// `fovy` is multiplied by this in spherical charts
+    pub spherical_fov_scale: f32,
+    /// Largest field of view hyperbolic charts are allowed to reach
+    pub max_fov: Rad<f32>,
+    /// Smallest field of view spherical charts are allowed to reach
+    pub min_fov: Rad<f32>,
 }
 
 impl Camera {
@@ -44,6 +56,46 @@ impl Camera {
             view_matrix,
             projection_matrix,
             geometry_type: GeometryType::Euclidean,
+            hyperbolic_fov_scale: Self::DEFAULT_HYPERBOLIC_FOV_SCALE,
+            spherical_fov_scale: Self::DEFAULT_SPHERICAL_FOV_SCALE,
+            max_fov: Rad(Self::DEFAULT_MAX_FOV_DEGREES.to_radians()),
+            min_fov: Rad(Self::DEFAULT_MIN_FOV_DEGREES.to_radians()),
+        }
+    }
+    
+    /// Starting `hyperbolic_fov_scale`
+    pub const DEFAULT_HYPERBOLIC_FOV_SCALE: f32 = 1.5;
+    /// Starting `spherical_fov_scale`
+    pub const DEFAULT_SPHERICAL_FOV_SCALE: f32 = 0.9;
+    /// Starting `max_fov`, in degrees
+    pub const DEFAULT_MAX_FOV_DEGREES: f32 = 170.0;
+    /// Starting `min_fov`, in degrees
+    pub const DEFAULT_MIN_FOV_DEGREES: f32 = 30.0;
+    
+    /// Set the `fovy` multipliers for hyperbolic and spherical charts
+    pub fn set_fov_scales(&mut self, hyperbolic: f32, spherical: f32) {
+        self.hyperbolic_fov_scale = hyperbolic;
+        self.spherical_fov_scale = spherical;
+        self.update_projection();
+    }
+    
+    /// Set the floor for spherical and the cap for hyperbolic fields of view
+    pub fn set_fov_limits(&mut self, min: Rad<f32>, max: Rad<f32>) {
+        self.min_fov = min;
+        self.max_fov = max;
+        self.update_projection();
+    }
+    
+    /// Vertical field of view the projection uses in the current geometry
+    ///
+    /// Curved charts scale `fovy` by their multiplier. Hyperbolic views are
+    /// capped at `max_fov` and spherical ones floored at `min_fov`; other charts
+    /// use `fovy` as is.
+    pub fn projection_fovy(&self) -> Rad<f32> {
+        match self.geometry_type {
+            GeometryType::Hyperbolic => Rad((self.fovy.0 * self.hyperbolic_fov_scale).min(self.max_fov.0)),
+            GeometryType::Spherical => Rad((self.fovy.0 * self.spherical_fov_scale).max(self.min_fov.0)),
+            _ => self.fovy,
         }
     }
     
@@ -81,21 +133,7 @@ impl Camera {
     
     /// Update projection matrix based on geometry
     fn update_projection(&mut self) {
-        match self.geometry_type {
-            GeometryType::Hyperbolic => {
-                // Wider FOV for hyperbolic space
-                let hyperbolic_fov = Rad((self.fovy.0 * 1.5).min(170.0_f32.to_radians()));
-                self.projection_matrix = perspective(hyperbolic_fov, self.aspect, self.znear, self.zfar);
-            }
-            GeometryType::Spherical => {
-                // Adjust for spherical distortion
-                let spherical_fov = Rad((self.fovy.0 * 0.9).max(30.0_f32.to_radians()));
-                self.projection_matrix = perspective(spherical_fov, self.aspect, self.znear, self.zfar);
-            }
-            _ => {
-                self.projection_matrix = perspective(self.fovy, self.aspect, self.znear, self.zfar);
-            }
-        }
+        self.projection_matrix = perspective(self.projection_fovy(), self.aspect, self.znear, self.zfar);
     }
     
     /// Compute hyperbolic view matrix for Poincaré disk
@@ -130,14 +168,12 @@ impl Camera {
             path_end,
             self.position.chart_id,
             5,
+        ) && let Some(transported) = manifold.parallel_transport(
+            self.up,
+            &path,
+            self.position.chart_id,
         ) {
-            if let Some(transported) = manifold.parallel_transport(
-                self.up,
-                &path,
-                self.position.chart_id,
-            ) {
-                return transported.normalize();
-            }
+            return transported.normalize();
         }
         
         self.up
@@ -149,6 +185,45 @@ impl Camera {
         self.position.local = crate::manifold::LocalCoordinate::from_point(new_pos);
     }
     
+    /// Move `distance` along the geodesic leaving the camera in `direction`
+    ///
+    /// `distance` is measured with the chart's metric and `direction` is a
+    /// tangent vector in local coordinates. The look direction is carried along,
+    /// rotated the way the geodesic's tangent turned. Spherical charts move along
+    /// the great circle through the camera, hyperbolic and custom charts
+    /// integrate the geodesic equation, and flat charts (or a missing chart)
+    /// move in a straight line like [`move_local`](Self::move_local).
+    pub fn move_geodesic(&mut self, direction: Vector3<f32>, distance: f32, manifold: &crate::manifold::Manifold) {
+        if direction.magnitude2() <= f32::EPSILON || distance == 0.0 {
+            return;
+        }
+        let direction = direction.normalize();
+        let start = self.position.local.to_point();
+        let look = self.target - start;
+        
+        let metric = manifold.chart(self.position.chart_id).map(|chart| chart.metric().clone());
+        let (end, end_direction) = match metric {
+            Some(metric) if metric.geometry == GeometryType::Spherical => {
+                spherical_step(start, direction, distance, metric.parameters.radius)
+            }
+            Some(metric) if metric.geometry != GeometryType::Euclidean => {
+                // Scale the start velocity so the path's metric length is `distance`
+                let speed = metric.tensor_at(start).norm(direction);
+                let length = if speed > f32::EPSILON { distance / speed } else { distance };
+                let path = crate::manifold::Geodesic::integrate(start, direction, &metric, length, GEODESIC_MOVE_STEPS);
+                match (path.points.last(), path.tangents.last()) {
+                    (Some(&end), Some(&tangent)) if tangent.magnitude2() > 0.0 => (end, tangent),
+                    _ => (start + direction * distance, direction),
+                }
+            }
+            _ => (start + direction * distance, direction),
+        };
+        
+        let turn = Quaternion::from_arc(direction, end_direction.normalize(), Some(self.up));
+        self.position.local = crate::manifold::LocalCoordinate::from_point(end);
+        self.target = end + turn * look;
+    }
+    
     /// Rotate camera
     pub fn rotate(&mut self, yaw: Rad<f32>, pitch: Rad<f32>) {
         let position = self.position.local.to_point();
@@ -171,6 +246,50 @@ impl Camera {
         self.position = ManifoldPosition::new(chart_id, position);
     }
     
+    /// Ease the camera towards a goal position and look-at point
+    ///
+    /// Position and look direction approach the goal exponentially: `smoothing` is
+    /// the rate per second, so a larger value settles faster, and the result is
+    /// independent of the frame rate. If the goal lies in another chart there is no
+    /// path to interpolate along, so the camera snaps to it. A camera looking at
+    /// its own position, or a goal look-at point on the goal position, keeps its
+    /// current look direction.
+    pub fn move_towards(
+        &mut self,
+        target_pos: ManifoldPosition,
+        target_look: Point3<f32>,
+        smoothing: f32,
+        dt: f32,
+    ) {
+        if target_pos.chart_id != self.position.chart_id {
+            self.position = target_pos;
+            self.target = target_look;
+            return;
+        }
+        
+        let t = 1.0 - (-smoothing.max(0.0) * dt.max(0.0)).exp();
+        let eye = self.position.local.to_point();
+        let goal_eye = target_pos.local.to_point();
+        let new_eye = eye + (goal_eye - eye) * t;
+        
+        // Ease the look direction and distance separately so the view turns rather than sliding
+        let look = self.target - eye;
+        let goal_look = target_look - goal_eye;
+        let min_length2 = f32::EPSILON * f32::EPSILON;
+        let look = if look.magnitude2() > min_length2 && goal_look.magnitude2() > min_length2 {
+            // Turning half way round goes about the up axis
+            let turn = Quaternion::from_arc(look.normalize(), goal_look.normalize(), Some(self.up));
+            let direction = Quaternion::one().slerp(turn, t) * look.normalize();
+            direction * (look.magnitude() + (goal_look.magnitude() - look.magnitude()) * t)
+        } else {
+            // No direction to turn from or to, so keep looking the same way
+            look
+        };
+        
+        self.position.local = crate::manifold::LocalCoordinate::from_point(new_eye);
+        self.target = new_eye + look;
+    }
+    
     /// Get view-projection matrix
     pub fn view_projection(&self) -> Matrix4<f32> {
         self.projection_matrix * self.view_matrix
@@ -182,16 +301,62 @@ impl Camera {
         (self.target - position).normalize()
     }
     
+    /// Ray through a point in normalized device coordinates (`-1..=1` on both axes)
+    ///
+    /// The ray starts on the near plane and is expressed in the camera's chart coordinates.
+    pub fn screen_ray(&self, ndc_x: f32, ndc_y: f32) -> Ray {
+        let Some(inverse) = self.view_projection().invert() else {
+            return self.gaze_fallback();
+        };
+        
+        let unproject = |z: f32| {
+            let p = inverse * Vector4::new(ndc_x, ndc_y, z, 1.0);
+            Point3::new(p.x / p.w, p.y / p.w, p.z / p.w)
+        };
+        let near = unproject(-1.0);
+        let far = unproject(1.0);
+        
+        Ray::new(near, far - near)
+    }
+    
+    /// Ray through the center of the view
+    pub fn gaze_ray(&self) -> Ray {
+        self.screen_ray(0.0, 0.0)
+    }
+    
+    fn gaze_fallback(&self) -> Ray {
+        Ray::new(self.position.local.to_point(), self.forward())
+    }
+    
+    /// Build the view frustum from the current view-projection
+    ///
+    /// Only meaningful for objects in the camera's own chart.
+    pub fn frustum(&self) -> Frustum {
+        Frustum::from_matrix(self.view_projection())
+    }
+    
     /// Get camera right direction
     pub fn right(&self) -> Vector3<f32> {
         self.forward().cross(self.up).normalize()
     }
     
     /// Resize camera viewport
+    ///
+    /// Zero sizes (a minimized window) keep the current aspect ratio.
     pub fn resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
         self.aspect = width as f32 / height as f32;
         self.update_projection();
     }
+    
+    /// Update the aspect ratio on a `Resized` window event; other events are ignored
+    pub fn handle_window_event(&mut self, event: &crate::window::WindowEvent) {
+        if let crate::window::WindowEvent::Resized(width, height) = *event {
+            self.resize(width, height);
+        }
+    }
 }
 
 /// First-person camera controller
@@ -201,6 +366,12 @@ pub struct FPSCameraController {
     pub sensitivity: f32,
 }
 
+impl Default for FPSCameraController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FPSCameraController {
     pub fn new() -> Self {
         Self {
@@ -253,4 +424,159 @@ impl FPSCameraController {
             camera.rotate(yaw, pitch);
         }
     }
-}
\ No newline at end of file
+}
+
+/// Desired camera pose that a camera is eased towards every frame
+///
+/// Set the goal with `set_pose` and call `update` once per frame. Goals in a
+/// different chart than the camera are snapped to (see `Camera::move_towards`).
+pub struct CameraRig {
+    pub desired_position: ManifoldPosition,
+    pub desired_target: Point3<f32>,
+    pub smoothing: f32,
+}
+
+impl CameraRig {
+    /// Create a rig holding the camera's current pose
+    pub fn new(camera: &Camera, smoothing: f32) -> Self {
+        Self {
+            desired_position: camera.position,
+            desired_target: camera.target,
+            smoothing,
+        }
+    }
+    
+    pub fn set_pose(&mut self, position: ManifoldPosition, target: Point3<f32>) {
+        self.desired_position = position;
+        self.desired_target = target;
+    }
+    
+    /// Advance the camera towards the desired pose
+    pub fn update(&self, camera: &mut Camera, dt: f32) {
+        camera.move_towards(self.desired_position, self.desired_target, self.smoothing, dt);
+    }
+    
+    /// Jump straight to the desired pose
+    pub fn snap(&self, camera: &mut Camera) {
+        camera.position = self.desired_position;
+        camera.target = self.desired_target;
+    }
+    
+    /// Whether the camera is within `tolerance` of the desired pose
+    pub fn is_settled(&self, camera: &Camera, tolerance: f32) -> bool {
+        camera.position.chart_id == self.desired_position.chart_id
+            && (camera.position.local.to_point() - self.desired_position.local.to_point()).magnitude() <= tolerance
+            && (camera.target - self.desired_target).magnitude() <= tolerance
+    }
+}
+
+/// Third-person controller orbiting the camera around a focus point
+///
+/// Dragging with `drag_button` held changes yaw and pitch; the mouse wheel zooms.
+/// At zero yaw and pitch the camera sits on the focus point's +Z side, and positive
+/// yaw swings it towards +X.
+pub struct OrbitCameraController {
+    pub focus: Point3<f32>,
+    pub distance: f32,
+    pub yaw: Rad<f32>,
+    pub pitch: Rad<f32>,
+    pub min_distance: f32,
+    pub max_distance: f32,
+    pub sensitivity: f32,
+    pub zoom_speed: f32,
+    pub drag_button: crate::input::MouseButton,
+}
+
+impl OrbitCameraController {
+    /// Pitch is kept this far short of straight up/down so the view never flips
+    pub const MAX_PITCH: Rad<f32> = Rad(89.0 * std::f32::consts::PI / 180.0);
+    
+    pub fn new(focus: Point3<f32>, distance: f32) -> Self {
+        Self {
+            focus,
+            distance,
+            yaw: Rad(0.0),
+            pitch: Rad(0.0),
+            min_distance: 0.5,
+            max_distance: 100.0,
+            sensitivity: 0.005,
+            zoom_speed: 0.5,
+            drag_button: crate::input::MouseButton::Left,
+        }
+    }
+    
+    pub fn with_distance_limits(mut self, min: f32, max: f32) -> Self {
+        self.min_distance = min;
+        self.max_distance = max.max(min);
+        self.distance = self.distance.clamp(self.min_distance, self.max_distance);
+        self
+    }
+    
+    /// Rotate around the focus point, clamping pitch
+    pub fn rotate(&mut self, yaw: Rad<f32>, pitch: Rad<f32>) {
+        self.yaw += yaw;
+        self.pitch = Rad((self.pitch + pitch).0.clamp(-Self::MAX_PITCH.0, Self::MAX_PITCH.0));
+    }
+    
+    /// Move towards (positive) or away from (negative) the focus point, clamping distance
+    pub fn zoom(&mut self, amount: f32) {
+        self.distance = (self.distance - amount * self.zoom_speed).clamp(self.min_distance, self.max_distance);
+    }
+    
+    /// Camera position on the orbit sphere
+    pub fn eye(&self) -> Point3<f32> {
+        let (sin_yaw, cos_yaw) = self.yaw.0.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.0.sin_cos();
+        self.focus + Vector3::new(cos_pitch * sin_yaw, sin_pitch, cos_pitch * cos_yaw) * self.distance
+    }
+    
+    /// Place the camera on the orbit sphere looking at the focus point
+    ///
+    /// The camera stays in its current chart.
+    pub fn apply(&self, camera: &mut Camera) {
+        camera.position.local = crate::manifold::LocalCoordinate::from_point(self.eye());
+        camera.target = self.focus;
+    }
+    
+    pub fn update(&mut self, camera: &mut Camera, input: &crate::input::InputManager) {
+        if input.is_mouse_button_pressed(self.drag_button) {
+            let mouse_delta = input.mouse_delta();
+            self.rotate(Rad(-mouse_delta.x * self.sensitivity), Rad(mouse_delta.y * self.sensitivity));
+        }
+        
+        let scroll = input.scroll_delta();
+        if scroll != 0.0 {
+            self.zoom(scroll);
+        }
+        
+        self.apply(camera);
+    }
+}
+
+/// Move `start` along the great circle heading in `direction`
+///
+/// Distances are measured like `Metric::distance`: the central angle times the
+/// sphere's `sphere_radius`. Returns the end point and the great circle's
+/// tangent there. Only the part of `direction` tangent to the sphere through
+/// `start` counts; a radial direction or a start at the origin moves in a
+/// straight line instead.
+fn spherical_step(
+    start: Point3<f32>,
+    direction: Vector3<f32>,
+    distance: f32,
+    sphere_radius: f32,
+) -> (Point3<f32>, Vector3<f32>) {
+    let radial = start - Point3::new(0.0, 0.0, 0.0);
+    let radius = radial.magnitude();
+    let normal = if radius > f32::EPSILON { radial / radius } else { direction };
+    let tangent = direction - normal * normal.dot(direction);
+    if radius <= f32::EPSILON || tangent.magnitude2() <= f32::EPSILON {
+        return (start + direction * distance, direction);
+    }
+    let tangent = tangent.normalize();
+    
+    let (sin, cos) = (distance / sphere_radius).sin_cos();
+    let end = normal * cos + tangent * sin;
+    let end_tangent = tangent * cos - normal * sin;
+    (Point3::new(0.0, 0.0, 0.0) + end * radius, end_tangent)
+}
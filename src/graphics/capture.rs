@@ -0,0 +1,85 @@
+//! Reading rendered frames back from the GPU
+
+use wgpu::{Device, Queue, TextureFormat};
+use image::RgbaImage;
+
+/// Copy a texture into an RGBA image
+///
+/// The texture must have been created with `COPY_SRC` usage and use one of the
+/// 8-bit RGBA or BGRA formats. Bytes are returned as stored: sRGB formats yield
+/// sRGB-encoded pixels ready to save as PNG, and BGRA is swizzled to RGBA.
+/// Rows are in texture order, so row 0 is the top of the image.
+pub fn capture_texture(
+    device: &Device,
+    queue: &Queue,
+    texture: &wgpu::Texture,
+) -> Result<RgbaImage, Box<dyn std::error::Error>> {
+    let swap_red_blue = match texture.format() {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => false,
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => true,
+        format => return Err(format!("Cannot capture texture format {:?}", format).into()),
+    };
+    if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+        return Err("Texture was not created with COPY_SRC usage".into());
+    }
+    
+    let (width, height) = (texture.width(), texture.height());
+    let unpadded_bytes_per_row = width * 4;
+    let padded_bytes_per_row = padded_bytes_per_row(unpadded_bytes_per_row);
+    
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Capture Buffer"),
+        size: padded_bytes_per_row as u64 * height as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Capture Encoder"),
+    });
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row),
+                rows_per_image: Some(height),
+            },
+        },
+        wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+    );
+    queue.submit(std::iter::once(encoder.finish()));
+    
+    let slice = buffer.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    device.poll(wgpu::Maintain::Wait);
+    receiver.recv()??;
+    
+    let mut pixels = Vec::with_capacity((unpadded_bytes_per_row * height) as usize);
+    for row in slice.get_mapped_range().chunks_exact(padded_bytes_per_row as usize) {
+        pixels.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+    }
+    buffer.unmap();
+    
+    if swap_red_blue {
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+    }
+    
+    RgbaImage::from_raw(width, height, pixels).ok_or_else(|| "Captured pixel data has the wrong size".into())
+}
+
+/// Round a row size up to the alignment wgpu requires for texture-to-buffer copies
+pub fn padded_bytes_per_row(unpadded: u32) -> u32 {
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    unpadded.div_ceil(align) * align
+}
@@ -0,0 +1,251 @@
+//! Immediate-mode debug lines for visualizing rays, bounds and geodesics
+
+use wgpu::{util::DeviceExt, CommandEncoder, Device, TextureView};
+use bytemuck::{Pod, Zeroable};
+use cgmath::{Matrix4, Point3, SquareMatrix, Vector4};
+use crate::manifold::geodesic::GeodesicPath;
+use crate::math::{BoundingBox, Ray};
+use super::{Color, color_attachment, shader::{FRAGMENT_ENTRY_POINT, VERTEX_ENTRY_POINT}};
+
+/// Endpoint of a debug line in world space
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Pod, Zeroable)]
+pub struct DebugVertex {
+    pub position: [f32; 3],
+    pub color: [f32; 4],
+}
+
+/// Debug line endpoint projected into clip space for the line pipeline
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+struct ClipVertex {
+    position: [f32; 4],
+    color: [f32; 4],
+}
+
+impl ClipVertex {
+    const ATTRIBUTES: [wgpu::VertexAttribute; 2] = wgpu::vertex_attr_array![
+        0 => Float32x4,
+        1 => Float32x4,
+    ];
+    
+    fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<ClipVertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+}
+
+/// Line list accumulated from debug draw calls, two vertices per segment
+#[derive(Debug, Clone, Default)]
+pub struct DebugLines {
+    vertices: Vec<DebugVertex>,
+}
+
+impl DebugLines {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Add a segment from `a` to `b`
+    pub fn line(&mut self, a: Point3<f32>, b: Point3<f32>, color: Color) {
+        let color = color.to_array();
+        self.vertices.push(DebugVertex { position: a.into(), color });
+        self.vertices.push(DebugVertex { position: b.into(), color });
+    }
+    
+    /// Add a segment from the ray's origin to `length` along it
+    pub fn ray(&mut self, ray: &Ray, length: f32, color: Color) {
+        self.line(ray.origin, ray.point_at(length), color);
+    }
+    
+    /// Add the 12 edges of a box
+    pub fn aabb(&mut self, bounds: &BoundingBox, color: Color) {
+        let (min, max) = (bounds.min, bounds.max);
+        let corner = |i: usize| Point3::new(
+            if i & 1 == 0 { min.x } else { max.x },
+            if i & 2 == 0 { min.y } else { max.y },
+            if i & 4 == 0 { min.z } else { max.z },
+        );
+        // Corners one bit apart share an edge
+        for i in 0..8 {
+            for bit in [1, 2, 4] {
+                if i & bit == 0 {
+                    self.line(corner(i), corner(i | bit), color);
+                }
+            }
+        }
+    }
+    
+    /// Add a segment between each consecutive pair of points on the path
+    pub fn path(&mut self, path: &GeodesicPath, color: Color) {
+        for pair in path.points.windows(2) {
+            self.line(pair[0], pair[1], color);
+        }
+    }
+    
+    /// Vertices queued so far
+    pub fn vertices(&self) -> &[DebugVertex] {
+        &self.vertices
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+    
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+    }
+}
+
+const DEBUG_SHADER: &str = r#"
+    struct VertexOutput {
+        @builtin(position) clip_position: vec4<f32>,
+        @location(0) color: vec4<f32>,
+    }
+    
+    @vertex
+    fn vs_main(@location(0) position: vec4<f32>, @location(1) color: vec4<f32>) -> VertexOutput {
+        var out: VertexOutput;
+        out.clip_position = position;
+        out.color = color;
+        return out;
+    }
+    
+    @fragment
+    fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+        return in.color;
+    }
+"#;
+
+/// Draws queued [`DebugLines`] unlit and on top of the scene
+///
+/// Lines are projected on the CPU, so there is nothing to bind besides the vertices.
+pub struct DebugRenderer {
+    lines: DebugLines,
+    view_projection: Matrix4<f32>,
+    shader: wgpu::ShaderModule,
+    pipeline: Option<((wgpu::TextureFormat, u32), wgpu::RenderPipeline)>,
+}
+
+impl DebugRenderer {
+    pub fn new(device: &Device) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Debug Line Shader"),
+            source: wgpu::ShaderSource::Wgsl(DEBUG_SHADER.into()),
+        });
+        
+        Self {
+            lines: DebugLines::new(),
+            view_projection: Matrix4::identity(),
+            shader,
+            pipeline: None,
+        }
+    }
+    
+    /// Lines waiting to be drawn
+    pub fn lines(&self) -> &DebugLines {
+        &self.lines
+    }
+    
+    pub fn lines_mut(&mut self) -> &mut DebugLines {
+        &mut self.lines
+    }
+    
+    /// Set the view-projection matrix the lines are drawn with
+    pub fn set_view_projection(&mut self, view_projection: Matrix4<f32>) {
+        self.view_projection = view_projection;
+    }
+    
+    /// Draw the queued lines over `view`, keeping its contents, then clear them
+    ///
+    /// With `msaa_view` the lines are drawn multisampled and resolved into `view`.
+    pub fn render(
+        &mut self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        view: &TextureView,
+        msaa_view: Option<&TextureView>,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) {
+        if self.lines.is_empty() {
+            return;
+        }
+        
+        let vertices: Vec<ClipVertex> = self.lines.vertices()
+            .iter()
+            .map(|vertex| {
+                let [x, y, z] = vertex.position;
+                ClipVertex {
+                    position: (self.view_projection * Vector4::new(x, y, z, 1.0)).into(),
+                    color: vertex.color,
+                }
+            })
+            .collect();
+        self.lines.clear();
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Debug Line Vertices"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        
+        let key = (format, sample_count);
+        if self.pipeline.as_ref().is_none_or(|(built_for, _)| *built_for != key) {
+            self.pipeline = Some((key, self.create_pipeline(device, format, sample_count)));
+        }
+        let Some((_, pipeline)) = &self.pipeline else {
+            return;
+        };
+        
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Debug Line Pass"),
+            color_attachments: &[Some(color_attachment(view, msaa_view, wgpu::LoadOp::Load))],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(pipeline);
+        pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        pass.draw(0..vertices.len() as u32, 0..1);
+    }
+    
+    fn create_pipeline(&self, device: &Device, format: wgpu::TextureFormat, sample_count: u32) -> wgpu::RenderPipeline {
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Debug Line Pipeline Layout"),
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+        
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Debug Line Pipeline"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &self.shader,
+                entry_point: VERTEX_ENTRY_POINT,
+                buffers: &[ClipVertex::desc()],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &self.shader,
+                entry_point: FRAGMENT_ENTRY_POINT,
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::LineList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+            multiview: None,
+        })
+    }
+}
@@ -0,0 +1,83 @@
+//! Distance fog and the uniform that carries it to the geometry shaders
+
+use bytemuck::{Pod, Zeroable};
+use cgmath::Point3;
+use crate::manifold::GeometryType;
+use super::Color;
+use super::shader::geometry_code;
+
+/// Fog that fades distant surfaces towards `color`
+///
+/// How fast depends on the chart's geometry: hyperbolic charts fade
+/// exponentially with metric distance, as the metric stretches distance
+/// towards the edge of the disk, while the other geometries fade linearly and
+/// are fully fogged at `1 / density`. A density of zero disables fog.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FogSettings {
+    pub color: Color,
+    pub density: f32,
+    pub geometry: GeometryType,
+}
+
+impl FogSettings {
+    pub fn new(color: Color, density: f32, geometry: GeometryType) -> Self {
+        Self { color, density: density.max(0.0), geometry }
+    }
+    
+    /// Fog that leaves every surface untouched
+    pub fn disabled() -> Self {
+        Self::new(Color::BLACK, 0.0, GeometryType::Euclidean)
+    }
+    
+    /// Fraction of a surface's own color left at `distance`, as the shader computes it
+    ///
+    /// 1 is unfogged and 0 is entirely fog colored.
+    pub fn factor(&self, distance: f32) -> f32 {
+        let distance = distance.max(0.0);
+        match self.geometry {
+            GeometryType::Hyperbolic => (-self.density * distance).exp(),
+            _ => (1.0 - self.density * distance).clamp(0.0, 1.0),
+        }
+    }
+    
+    /// Blend `color` towards the fog color for a surface `distance` away, keeping its alpha
+    pub fn apply(&self, color: Color, distance: f32) -> Color {
+        Color::lerp(self.color, color, self.factor(distance)).with_alpha(color.a)
+    }
+    
+    /// Pack the settings for a camera at `camera_position`
+    pub fn to_uniform(&self, camera_position: Point3<f32>) -> FogUniform {
+        FogUniform {
+            fog_color: [self.color.r, self.color.g, self.color.b, 1.0],
+            camera_position: [camera_position.x, camera_position.y, camera_position.z, 1.0],
+            fog_density: self.density,
+            geometry_type: geometry_code(self.geometry),
+            _padding: [0.0; 2],
+        }
+    }
+}
+
+impl Default for FogSettings {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+/// Fog uniform for the geometry shaders, bound at group 0 binding 5
+///
+/// Matches the WGSL `Fog` struct. `geometry_type` is a [`geometry_code`].
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Pod, Zeroable)]
+pub struct FogUniform {
+    pub fog_color: [f32; 4],
+    pub camera_position: [f32; 4],
+    pub fog_density: f32,
+    pub geometry_type: f32,
+    pub _padding: [f32; 2],
+}
+
+impl Default for FogUniform {
+    fn default() -> Self {
+        FogSettings::disabled().to_uniform(Point3::new(0.0, 0.0, 0.0))
+    }
+}
@@ -0,0 +1,130 @@
+//! Light sources and the uniform that carries them to the geometry shaders
+
+use bytemuck::{Pod, Zeroable};
+use cgmath::{InnerSpace, Point3, Vector3};
+use super::Color;
+
+/// Number of lights the geometry shaders can use at once
+///
+/// Matches the size of the WGSL `lights` array.
+pub const MAX_LIGHTS: usize = 4;
+
+/// Where a light shines from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LightKind {
+    /// Infinitely distant light travelling along `direction`
+    Directional { direction: Vector3<f32> },
+    /// Light radiating from `position`, fading out to nothing at `range`
+    Point { position: Point3<f32>, range: f32 },
+}
+
+/// A light source
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Light {
+    pub kind: LightKind,
+    pub color: Color,
+    pub intensity: f32,
+}
+
+impl Light {
+    /// Create a directional light travelling along `direction`
+    pub fn directional(direction: Vector3<f32>, color: Color, intensity: f32) -> Self {
+        Self {
+            kind: LightKind::Directional { direction: direction.normalize() },
+            color,
+            intensity,
+        }
+    }
+    
+    /// Create a point light reaching out to `range`
+    pub fn point(position: Point3<f32>, range: f32, color: Color, intensity: f32) -> Self {
+        Self {
+            kind: LightKind::Point { position, range },
+            color,
+            intensity,
+        }
+    }
+    
+    /// Fraction of the light's intensity left at `distance`, as the shader computes it
+    ///
+    /// Directional lights don't fade. Point lights fall off as `(1 - d/range)²`.
+    /// Distances are Euclidean in every chart for now.
+    pub fn attenuation(&self, distance: f32) -> f32 {
+        match self.kind {
+            LightKind::Directional { .. } => 1.0,
+            LightKind::Point { range, .. } => {
+                let falloff = (1.0 - distance / range).clamp(0.0, 1.0);
+                falloff * falloff
+            }
+        }
+    }
+    
+    pub fn to_raw(&self) -> LightRaw {
+        let position = match self.kind {
+            LightKind::Directional { direction } => [direction.x, direction.y, direction.z, 0.0],
+            LightKind::Point { position, .. } => [position.x, position.y, position.z, 1.0],
+        };
+        let range = match self.kind {
+            LightKind::Directional { .. } => 0.0,
+            LightKind::Point { range, .. } => range,
+        };
+        
+        LightRaw {
+            position,
+            color: [self.color.r, self.color.g, self.color.b, self.intensity],
+            params: [range, 0.0, 0.0, 0.0],
+        }
+    }
+}
+
+/// GPU layout of a `Light`, matching the WGSL `Light` struct
+///
+/// - `position`: xyz = direction (w = 0) or position (w = 1)
+/// - `color`: rgb = color, a = intensity
+/// - `params`: x = range
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Pod, Zeroable)]
+pub struct LightRaw {
+    pub position: [f32; 4],
+    pub color: [f32; 4],
+    pub params: [f32; 4],
+}
+
+/// Lights uniform for the geometry shaders, bound at group 0 binding 4
+///
+/// Matches the WGSL `Lights` struct. Only the first `count` entries are lit.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub struct LightUniform {
+    pub lights: [LightRaw; MAX_LIGHTS],
+    pub count: u32,
+    pub _padding: [u32; 3],
+}
+
+impl LightUniform {
+    /// Pack up to [`MAX_LIGHTS`] lights, dropping the rest with a warning
+    pub fn new(lights: &[Light]) -> Self {
+        if lights.len() > MAX_LIGHTS {
+            eprintln!("{} lights given but only {} are supported; ignoring the rest", lights.len(), MAX_LIGHTS);
+        }
+        
+        let mut uniform = Self::zeroed();
+        for (raw, light) in uniform.lights.iter_mut().zip(lights) {
+            *raw = light.to_raw();
+        }
+        uniform.count = lights.len().min(MAX_LIGHTS) as u32;
+        uniform
+    }
+    
+    /// Number of lights in use
+    pub fn count(&self) -> usize {
+        self.count as usize
+    }
+}
+
+impl Default for LightUniform {
+    /// A single white light shining down at an angle
+    fn default() -> Self {
+        Self::new(&[Light::directional(Vector3::new(-0.5, -1.0, -0.3), Color::WHITE, 1.0)])
+    }
+}
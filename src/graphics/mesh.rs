@@ -1,7 +1,9 @@
 //! Mesh and vertex data structures
 
-use wgpu::{Buffer, Device, BufferUsages, util::DeviceExt};
+use wgpu::{Buffer, Device, Queue, BufferUsages, RenderPass, util::DeviceExt};
 use bytemuck::{Pod, Zeroable};
+use cgmath::{InnerSpace, Matrix, Matrix3, Matrix4, Point3, SquareMatrix, Vector3};
+use crate::math::{BoundingBox, Transform};
 
 /// Vertex data structure
 #[repr(C)]
@@ -57,22 +59,106 @@ impl Vertex {
     }
 }
 
+/// Per-instance data for instanced drawing
+#[derive(Copy, Clone, Debug)]
+pub struct Instance {
+    pub model: Matrix4<f32>,
+    pub color: [f32; 4],
+}
+
+impl Instance {
+    pub fn new(model: Matrix4<f32>) -> Self {
+        Self {
+            model,
+            color: [1.0, 1.0, 1.0, 1.0],
+        }
+    }
+    
+    pub fn with_color(mut self, color: [f32; 4]) -> Self {
+        self.color = color;
+        self
+    }
+    
+    pub fn to_raw(&self) -> InstanceRaw {
+        InstanceRaw {
+            model: self.model.into(),
+            color: self.color,
+        }
+    }
+}
+
+/// GPU layout of an `Instance`
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct InstanceRaw {
+    pub model: [[f32; 4]; 4],
+    pub color: [f32; 4],
+}
+
+impl InstanceRaw {
+    /// Convert instances to their GPU layout
+    pub fn from_instances(instances: &[Instance]) -> Vec<InstanceRaw> {
+        instances.iter().map(Instance::to_raw).collect()
+    }
+    
+    /// Instance buffer layout; the model matrix columns use locations 5-8 and color 9
+    pub fn desc() -> wgpu::VertexBufferLayout<'static> {
+        const ATTRIBUTES: [wgpu::VertexAttribute; 5] = wgpu::vertex_attr_array![
+            5 => Float32x4,
+            6 => Float32x4,
+            7 => Float32x4,
+            8 => Float32x4,
+            9 => Float32x4,
+        ];
+        
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<InstanceRaw>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &ATTRIBUTES,
+        }
+    }
+}
+
 /// Mesh structure containing vertex and index data
 pub struct Mesh {
     pub vertex_buffer: Buffer,
     pub index_buffer: Buffer,
     pub num_indices: u32,
     pub vertices: Vec<Vertex>,
-    pub indices: Vec<u16>,
+    pub indices: Vec<u32>,
+    dynamic: bool,
+    bounds: BoundingBox,
+    instance_buffer: Option<Buffer>,
+    instance_capacity: usize,
+    num_instances: u32,
 }
 
 impl Mesh {
     /// Create a new mesh from vertices and indices
-    pub fn new(device: &Device, vertices: Vec<Vertex>, indices: Vec<u16>) -> Self {
+    pub fn new(device: &Device, vertices: Vec<Vertex>, indices: Vec<u32>) -> Self {
+        Self::with_vertex_usage(device, vertices, indices, false)
+    }
+    
+    /// Create a mesh whose vertices can be rewritten with `update_vertices`
+    pub fn new_dynamic(device: &Device, vertices: Vec<Vertex>, indices: Vec<u32>) -> Self {
+        Self::with_vertex_usage(device, vertices, indices, true)
+    }
+    
+    /// Upload a loaded mesh resource to the GPU
+    pub fn from_resource(device: &Device, resource: &crate::resources::MeshResource) -> Self {
+        Self::new(device, resource.vertices.clone(), resource.indices.clone())
+    }
+    
+    fn with_vertex_usage(device: &Device, vertices: Vec<Vertex>, indices: Vec<u32>, dynamic: bool) -> Self {
+        let usage = if dynamic {
+            BufferUsages::VERTEX | BufferUsages::COPY_DST
+        } else {
+            BufferUsages::VERTEX
+        };
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Vertex Buffer"),
+            label: Some(if dynamic { "Dynamic Vertex Buffer" } else { "Vertex Buffer" }),
             contents: bytemuck::cast_slice(&vertices),
-            usage: BufferUsages::VERTEX,
+            usage,
         });
         
         let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -82,6 +168,7 @@ impl Mesh {
         });
         
         let num_indices = indices.len() as u32;
+        let bounds = compute_bounds(&vertices);
         
         Self {
             vertex_buffer,
@@ -89,9 +176,138 @@ impl Mesh {
             num_indices,
             vertices,
             indices,
+            dynamic,
+            bounds,
+            instance_buffer: None,
+            instance_capacity: 0,
+            num_instances: 0,
         }
     }
     
+    /// Create a mesh from `u16` indices, widening them to `u32`
+    pub fn from_u16(device: &Device, vertices: Vec<Vertex>, indices: &[u16]) -> Self {
+        Self::new(device, vertices, indices.iter().map(|&i| u32::from(i)).collect())
+    }
+    
+    /// Combine several meshes into one static mesh, each placed by its transform
+    ///
+    /// Normals go through the inverse-transpose so non-uniform scales keep them
+    /// perpendicular, and mirrored transforms have their triangle winding flipped.
+    pub fn merge(device: &Device, meshes: &[(&Mesh, Transform)]) -> Self {
+        let vertex_count: usize = meshes.iter().map(|(mesh, _)| mesh.vertices.len()).sum();
+        let index_count: usize = meshes.iter().map(|(mesh, _)| mesh.indices.len()).sum();
+        assert!(u32::try_from(vertex_count).is_ok(), "merged mesh has too many vertices for u32 indices");
+        
+        let mut vertices = Vec::with_capacity(vertex_count);
+        let mut indices = Vec::with_capacity(index_count);
+        
+        for (mesh, transform) in meshes {
+            let linear = Matrix3::from_cols(
+                transform.matrix.x.truncate(),
+                transform.matrix.y.truncate(),
+                transform.matrix.z.truncate(),
+            );
+            let normal_matrix = linear.invert().map(|inverse| inverse.transpose()).unwrap_or(linear);
+            let offset = vertices.len() as u32;
+            
+            vertices.extend(mesh.vertices.iter().map(|vertex| {
+                let position = transform.transform_point(Point3::from(vertex.position));
+                let normal = normal_matrix * Vector3::from(vertex.normal);
+                let normal = if normal.magnitude2() > 0.0 { normal.normalize() } else { normal };
+                Vertex { position: position.into(), normal: normal.into(), ..*vertex }
+            }));
+            
+            if linear.determinant() < 0.0 {
+                for triangle in mesh.indices.chunks(3) {
+                    indices.extend(triangle.iter().rev().map(|&i| i + offset));
+                }
+            } else {
+                indices.extend(mesh.indices.iter().map(|&i| i + offset));
+            }
+        }
+        
+        Self::new(device, vertices, indices)
+    }
+    
+    /// Whether the mesh was created with `new_dynamic`
+    pub fn is_dynamic(&self) -> bool {
+        self.dynamic
+    }
+    
+    /// Overwrite the vertex data of a dynamic mesh in place
+    ///
+    /// The buffer is not reallocated, so `vertices` must have the same length as
+    /// the mesh's current vertices. The CPU-side copy is updated as well.
+    pub fn update_vertices(&mut self, queue: &Queue, vertices: &[Vertex]) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.dynamic {
+            return Err("Mesh was not created with Mesh::new_dynamic".into());
+        }
+        if vertices.len() != self.vertices.len() {
+            return Err(format!(
+                "Vertex count mismatch: mesh has {}, update has {}",
+                self.vertices.len(),
+                vertices.len()
+            ).into());
+        }
+        
+        queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(vertices));
+        self.vertices.copy_from_slice(vertices);
+        self.bounds = compute_bounds(vertices);
+        Ok(())
+    }
+    
+    /// Axis-aligned bounds of the vertex positions in model space
+    ///
+    /// Computed on creation and by `update_vertices`. Empty meshes have a zero-size box at the origin.
+    pub fn local_bounds(&self) -> BoundingBox {
+        self.bounds
+    }
+    
+    /// Upload per-instance data for `draw_instanced`
+    ///
+    /// The instance buffer is reused while it is large enough and otherwise grown
+    /// to the next power of two, so steady instance counts do not reallocate.
+    pub fn set_instances(&mut self, device: &Device, queue: &Queue, instances: &[Instance]) {
+        let raw = InstanceRaw::from_instances(instances);
+        
+        if self.instance_buffer.is_none() || raw.len() > self.instance_capacity {
+            self.instance_capacity = raw.len().next_power_of_two().max(1);
+            self.instance_buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Instance Buffer"),
+                size: (self.instance_capacity * std::mem::size_of::<InstanceRaw>()) as wgpu::BufferAddress,
+                usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }));
+        }
+        
+        if let Some(buffer) = &self.instance_buffer {
+            queue.write_buffer(buffer, 0, bytemuck::cast_slice(&raw));
+        }
+        self.num_instances = raw.len() as u32;
+    }
+    
+    /// Number of instances uploaded by the last `set_instances`
+    pub fn num_instances(&self) -> u32 {
+        self.num_instances
+    }
+    
+    /// Draw every uploaded instance with a single call
+    ///
+    /// The bound pipeline must use `[Vertex::desc(), InstanceRaw::desc()]` as vertex buffers.
+    pub fn draw_instanced<'a>(&'a self, pass: &mut RenderPass<'a>) {
+        let Some(instance_buffer) = &self.instance_buffer else {
+            return;
+        };
+        if self.num_instances == 0 {
+            return;
+        }
+        
+        pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        pass.set_vertex_buffer(1, instance_buffer.slice(..));
+        pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        pass.draw_indexed(0..self.num_indices, 0, 0..self.num_instances);
+    }
+    
     /// Create a quad mesh
     pub fn create_quad(device: &Device, size: f32) -> Self {
         let half_size = size / 2.0;
@@ -163,4 +379,10 @@ impl Mesh {
         
         Self::new(device, vertices, indices)
     }
-}
\ No newline at end of file
+}
+
+fn compute_bounds(vertices: &[Vertex]) -> BoundingBox {
+    let positions: Vec<Point3<f32>> = vertices.iter().map(|v| Point3::from(v.position)).collect();
+    BoundingBox::from_points(&positions)
+        .unwrap_or_else(|| BoundingBox::new(Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, 0.0)))
+}
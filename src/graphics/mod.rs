@@ -6,16 +6,99 @@ use wgpu::{
     CommandEncoder, TextureView, RenderPass,
 };
 use std::sync::Arc;
+use image::RgbaImage;
 
 pub mod mesh;
 pub mod shader;
 pub mod texture;
 pub mod camera;
+pub mod portal_view;
+pub mod atlas;
+pub mod capture;
+pub mod render_target;
+pub mod light;
+pub mod fog;
+pub mod text;
+pub mod debug;
+pub mod render_queue;
 
-pub use mesh::{Mesh, Vertex};
-pub use shader::{Shader, ShaderProgram};
+pub use mesh::{Mesh, Vertex, Instance, InstanceRaw};
+pub use shader::{Shader, ShaderProgram, NonEuclideanUniform, PortalUniform, geometry_code, metric_normal, polygon_mode_feature, resolve_polygon_mode};
 pub use texture::Texture;
 pub use camera::Camera;
+pub use portal_view::{PortalView, build_portal_views, portal_stencil_state};
+pub use atlas::{TextureAtlas, AtlasLayout, AtlasRegion};
+pub use capture::{capture_texture, padded_bytes_per_row};
+pub use render_target::RenderTarget;
+pub use light::{Light, LightKind, LightRaw, LightUniform, MAX_LIGHTS};
+pub use fog::{FogSettings, FogUniform};
+pub use text::{GlyphQuad, TextRenderer, layout_text, measure_text};
+pub use debug::{DebugLines, DebugRenderer, DebugVertex};
+pub use render_queue::{DrawItem, RenderQueue};
+
+use portal_view::PortalPipelines;
+
+/// Color the frame is cleared to before drawing unless changed with `Renderer::set_clear_color`
+pub const DEFAULT_CLEAR_COLOR: Color = Color { r: 0.1, g: 0.2, b: 0.3, a: 1.0 };
+
+/// Pick the MSAA sample count to use for `requested`
+///
+/// Rounds down to the nearest count (8, 4, 2) the format supports, falling back to 1.
+pub fn supported_sample_count(requested: u32, features: wgpu::TextureFormatFeatureFlags) -> u32 {
+    [8, 4, 2]
+        .into_iter()
+        .find(|&count| count <= requested && features.sample_count_supported(count))
+        .unwrap_or(1)
+}
+
+/// Pick the present mode to use for `requested` given the modes a surface supports
+///
+/// Unsupported modes fall back to `Fifo`, which every surface supports.
+pub fn resolve_present_mode(requested: PresentMode, supported: &[PresentMode]) -> PresentMode {
+    if supported.contains(&requested) {
+        requested
+    } else {
+        PresentMode::Fifo
+    }
+}
+
+/// Depth attachment for scene passes after `render_portals` sealed the portal surfaces
+fn scene_depth_attachment(
+    depth_stencil: Option<&Texture>,
+    portals_rendered: bool,
+) -> Option<wgpu::RenderPassDepthStencilAttachment<'_>> {
+    depth_stencil
+        .filter(|_| portals_rendered)
+        .map(|texture| wgpu::RenderPassDepthStencilAttachment {
+            view: &texture.view,
+            depth_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Load,
+                store: wgpu::StoreOp::Store,
+            }),
+            stencil_ops: None,
+        })
+}
+
+/// Color attachment that renders into the MSAA target, if any, and resolves to `view`
+fn color_attachment<'a>(
+    view: &'a TextureView,
+    msaa_view: Option<&'a TextureView>,
+    load: wgpu::LoadOp<wgpu::Color>,
+) -> wgpu::RenderPassColorAttachment<'a> {
+    let (target, resolve_target) = match msaa_view {
+        Some(msaa) => (msaa, Some(view)),
+        None => (view, None),
+    };
+    
+    wgpu::RenderPassColorAttachment {
+        view: target,
+        resolve_target,
+        ops: wgpu::Operations {
+            load,
+            store: wgpu::StoreOp::Store,
+        },
+    }
+}
 
 /// Render context passed to rendering functions
 pub struct RenderContext<'a> {
@@ -34,17 +117,48 @@ pub struct Renderer {
     size: (u32, u32),
     current_frame: Option<CurrentFrame>,
     shader: Shader,
+    sample_count: u32,
+    msaa_texture: Option<Texture>,
+    present_modes: Vec<PresentMode>,
+    portal_depth_stencil: Option<Texture>,
+    portal_pipelines: Option<PortalPipelines>,
+    polygon_mode: wgpu::PolygonMode,
+    clear_color: Color,
+    text: TextRenderer,
+    #[cfg(feature = "debug-draw")]
+    debug: DebugRenderer,
 }
 
 struct CurrentFrame {
-    output: wgpu::SurfaceTexture,
+    output: FrameOutput,
     view: TextureView,
+    /// Multisampled target resolved into `view`
+    msaa_view: Option<TextureView>,
     encoder: CommandEncoder,
+    portals_rendered: bool,
+}
+
+/// Where the current frame is drawn
+enum FrameOutput {
+    Surface(wgpu::SurfaceTexture),
+    Target(RenderTarget),
+}
+
+impl FrameOutput {
+    fn texture(&self) -> &wgpu::Texture {
+        match self {
+            FrameOutput::Surface(output) => &output.texture,
+            FrameOutput::Target(target) => &target.color().texture,
+        }
+    }
 }
 
 impl Renderer {
     /// Create a new renderer for the given window
-    pub async fn new(window: &crate::window::Window) -> Result<Self, Box<dyn std::error::Error>> {
+    ///
+    /// `sample_count` selects MSAA (1, 2, 4 or 8). Counts the adapter does not
+    /// support for the surface format fall back to a supported one with a warning.
+    pub async fn new(window: &crate::window::Window, sample_count: u32) -> Result<Self, Box<dyn std::error::Error>> {
         let size = window.dimensions();
         
         // Create wgpu instance
@@ -54,7 +168,7 @@ impl Renderer {
         });
         
         // Create surface
-        let surface = instance.create_surface(window.window_arc())?;
+        let surface = instance.create_surface(window.window_arc().ok_or("Can't render to a headless window")?)?;
         
         // Request adapter
         let adapter = instance.request_adapter(&wgpu::RequestAdapterOptions {
@@ -67,7 +181,8 @@ impl Renderer {
         let (device, queue) = adapter.request_device(
             &wgpu::DeviceDescriptor {
                 label: Some("Metatopia Renderer Device"),
-                required_features: wgpu::Features::empty(),
+                // Wireframe is optional; `set_wireframe` checks the device for it
+                required_features: adapter.features() & wgpu::Features::POLYGON_MODE_LINE,
                 required_limits: wgpu::Limits::default(),
             },
             None,
@@ -80,8 +195,11 @@ impl Renderer {
             .copied()
             .unwrap_or(surface_caps.formats[0]);
         
+        // Allow reading frames back for `capture_frame` where the surface supports it
+        let usage = TextureUsages::RENDER_ATTACHMENT | (surface_caps.usages & TextureUsages::COPY_SRC);
+        
         let config = SurfaceConfiguration {
-            usage: TextureUsages::RENDER_ATTACHMENT,
+            usage,
             format: surface_format,
             width: size.0,
             height: size.1,
@@ -93,8 +211,20 @@ impl Renderer {
         
         surface.configure(&device, &config);
         
+        let features = adapter.get_texture_format_features(config.format).flags;
+        let supported = supported_sample_count(sample_count, features);
+        if supported != sample_count {
+            eprintln!("MSAA x{} is not supported, using x{}", sample_count, supported);
+        }
+        let sample_count = supported;
+        let msaa_texture = (sample_count > 1)
+            .then(|| Texture::create_msaa_texture(&device, &config, sample_count, "MSAA Color"));
+        
         let device = Arc::new(device);
         let shader = Shader::new(device.clone());
+        let text = TextRenderer::new(&device, &queue);
+        #[cfg(feature = "debug-draw")]
+        let debug = DebugRenderer::new(&device);
         
         Ok(Self {
             surface,
@@ -104,6 +234,16 @@ impl Renderer {
             size,
             current_frame: None,
             shader,
+            sample_count,
+            msaa_texture,
+            present_modes: surface_caps.present_modes,
+            portal_depth_stencil: None,
+            portal_pipelines: None,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            clear_color: DEFAULT_CLEAR_COLOR,
+            text,
+            #[cfg(feature = "debug-draw")]
+            debug,
         })
     }
     
@@ -125,7 +265,26 @@ impl Renderer {
         };
         
         let view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let msaa_view = self.msaa_texture.as_ref()
+            .map(|msaa| msaa.texture.create_view(&wgpu::TextureViewDescriptor::default()));
         
+        self.start_frame(FrameOutput::Surface(output), view, msaa_view);
+    }
+    
+    /// Begin a new frame drawn into an offscreen target instead of the window
+    ///
+    /// Render passes, `clear` and `draw_mesh_instanced` all go to `target` until
+    /// `end_frame` submits the work; nothing is presented. Returns the target's
+    /// color view for sampling once the frame has ended. The target is
+    /// single-sampled, so pipelines must use a sample count of 1, and
+    /// `render_portals` is not supported on offscreen frames.
+    pub fn begin_frame_to<'t>(&mut self, target: &'t RenderTarget) -> &'t TextureView {
+        let view = target.color().texture.create_view(&wgpu::TextureViewDescriptor::default());
+        self.start_frame(FrameOutput::Target(target.clone()), view, None);
+        target.view()
+    }
+    
+    fn start_frame(&mut self, output: FrameOutput, view: TextureView, msaa_view: Option<TextureView>) {
         let encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("Render Encoder"),
         });
@@ -133,61 +292,336 @@ impl Renderer {
         self.current_frame = Some(CurrentFrame {
             output,
             view,
+            msaa_view,
             encoder,
+            portals_rendered: false,
         });
     }
     
-    /// End the current frame and present it
+    /// End the current frame, submit its work and present it if it targets the window
+    ///
+    /// Debug lines and then text queued with `draw_text` are drawn over
+    /// everything else first.
     pub fn end_frame(&mut self) {
-        if let Some(frame) = self.current_frame.take() {
+        if let Some(mut frame) = self.current_frame.take() {
+            let texture = frame.output.texture();
+            let (format, size) = (texture.format(), (texture.width(), texture.height()));
+            #[cfg(feature = "debug-draw")]
+            {
+                let sample_count = if frame.msaa_view.is_some() { self.sample_count } else { 1 };
+                self.debug.render(
+                    &self.device,
+                    &mut frame.encoder,
+                    &frame.view,
+                    frame.msaa_view.as_ref(),
+                    format,
+                    sample_count,
+                );
+            }
+            self.text.render(&self.device, &mut frame.encoder, &frame.view, format, size);
+            
             self.queue.submit(std::iter::once(frame.encoder.finish()));
-            frame.output.present();
+            if let FrameOutput::Surface(output) = frame.output {
+                output.present();
+            }
         }
     }
     
+    /// Queue text to draw over the frame in screen space
+    ///
+    /// `position` is the top-left of the first glyph in pixels from the top-left
+    /// of the frame and `size` the glyph height in pixels. Everything queued
+    /// during a frame is drawn in one batch by `end_frame`.
+    pub fn draw_text(&mut self, text: &str, position: (f32, f32), size: f32, color: Color) {
+        self.text.queue(text, position, size, color);
+    }
+    
+    /// Set the camera debug lines are drawn from
+    pub fn set_debug_camera(&mut self, camera: &Camera) {
+        #[cfg(feature = "debug-draw")]
+        self.debug.set_view_projection(camera.view_projection());
+        #[cfg(not(feature = "debug-draw"))]
+        let _ = camera;
+    }
+    
+    /// Queue a debug line from `a` to `b` in world space, drawn at the end of the frame
+    ///
+    /// Without the `debug-draw` feature (on by default) the renderer has no
+    /// debug pipeline and the `debug_*` calls compile to empty functions, so
+    /// they can be left in release builds.
+    pub fn debug_line(&mut self, a: cgmath::Point3<f32>, b: cgmath::Point3<f32>, color: Color) {
+        #[cfg(feature = "debug-draw")]
+        self.debug.lines_mut().line(a, b, color);
+        #[cfg(not(feature = "debug-draw"))]
+        let _ = (a, b, color);
+    }
+    
+    /// Queue a debug line `length` along `ray`
+    pub fn debug_ray(&mut self, ray: &crate::math::Ray, length: f32, color: Color) {
+        #[cfg(feature = "debug-draw")]
+        self.debug.lines_mut().ray(ray, length, color);
+        #[cfg(not(feature = "debug-draw"))]
+        let _ = (ray, length, color);
+    }
+    
+    /// Queue the edges of a bounding box
+    pub fn debug_aabb(&mut self, bounds: &crate::math::BoundingBox, color: Color) {
+        #[cfg(feature = "debug-draw")]
+        self.debug.lines_mut().aabb(bounds, color);
+        #[cfg(not(feature = "debug-draw"))]
+        let _ = (bounds, color);
+    }
+    
+    /// Queue the segments of a geodesic path
+    pub fn debug_path(&mut self, path: &crate::manifold::geodesic::GeodesicPath, color: Color) {
+        #[cfg(feature = "debug-draw")]
+        self.debug.lines_mut().path(path, color);
+        #[cfg(not(feature = "debug-draw"))]
+        let _ = (path, color);
+    }
+    
+    /// Read back the current frame as an RGBA image
+    ///
+    /// Call between `begin_frame` and `end_frame`. Commands recorded so far are
+    /// submitted first so the image contains everything drawn up to this point;
+    /// drawing can continue afterwards. Fails if no frame is in progress or the
+    /// surface does not allow copying from its textures.
+    pub fn capture_frame(&mut self) -> Result<RgbaImage, Box<dyn std::error::Error>> {
+        let frame = self.current_frame.as_mut().ok_or("No frame in progress")?;
+        
+        let encoder = std::mem::replace(
+            &mut frame.encoder,
+            self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Render Encoder"),
+            }),
+        );
+        self.queue.submit(std::iter::once(encoder.finish()));
+        
+        capture_texture(&self.device, &self.queue, frame.output.texture())
+    }
+    
     /// Get a render pass for the current frame
+    ///
+    /// After `render_portals` the pass keeps the portal views and attaches the
+    /// portal depth/stencil buffer, so pipelines must use `Texture::DEPTH_STENCIL_FORMAT`.
     pub fn begin_render_pass(&mut self) -> Option<RenderPass<'_>> {
+        let depth_stencil = self.portal_depth_stencil.as_ref();
+        let clear_color = self.clear_color.into();
         self.current_frame.as_mut().map(|frame| {
+            let load = if frame.portals_rendered {
+                wgpu::LoadOp::Load
+            } else {
+                wgpu::LoadOp::Clear(clear_color)
+            };
+            let depth_stencil_attachment = match &frame.output {
+                FrameOutput::Target(target) => target.depth_attachment(true),
+                FrameOutput::Surface(_) => scene_depth_attachment(depth_stencil, frame.portals_rendered),
+            };
+            
             frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &frame.view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.1,
-                            g: 0.2,
-                            b: 0.3,
-                            a: 1.0,
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
+                color_attachments: &[Some(color_attachment(&frame.view, frame.msaa_view.as_ref(), load))],
+                depth_stencil_attachment,
                 timestamp_writes: None,
                 occlusion_query_set: None,
             })
         })
     }
     
+    /// Render the destination views of every portal visible from `camera`
+    ///
+    /// Call before `begin_render_pass`. Each portal surface is masked into the
+    /// stencil buffer (nested portals only inside their parent's region) and
+    /// `draw_view` is invoked with a pass whose stencil reference is set to the
+    /// view's depth; its pipelines should use `portal_stencil_state()`. Finally
+    /// the top-level portal surfaces are written into depth so the main scene
+    /// drawn afterwards is occluded by them. Recursion is bounded by `max_recursion`.
+    pub fn render_portals<F>(
+        &mut self,
+        manifold: &crate::manifold::Manifold,
+        camera: &Camera,
+        max_recursion: u32,
+        mut draw_view: F,
+    ) -> Vec<PortalView>
+    where
+        F: FnMut(&mut RenderPass<'_>, &PortalView),
+    {
+        use wgpu::util::DeviceExt;
+        
+        let views = build_portal_views(manifold, camera, max_recursion);
+        match &self.current_frame {
+            None => return views,
+            Some(CurrentFrame { output: FrameOutput::Target(_), .. }) => {
+                eprintln!("Portals cannot be rendered into an offscreen target, skipping");
+                return views;
+            }
+            Some(_) => {}
+        }
+        
+        if self.portal_depth_stencil.is_none() {
+            self.portal_depth_stencil = Some(Texture::create_depth_stencil_texture(
+                &self.device,
+                &self.config,
+                self.sample_count,
+                "Portal Depth Stencil",
+            ));
+        }
+        if self.portal_pipelines.is_none() {
+            self.portal_pipelines = Some(PortalPipelines::new(&self.device, self.config.format, self.sample_count));
+        }
+        
+        let create_surface_buffer = |portal_id, view_projection| {
+            let vertices = manifold
+                .portal(portal_id)
+                .map(|portal| portal_view::clip_space_surface(portal, view_projection))
+                .unwrap_or_default();
+            let buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Portal Surface Buffer"),
+                contents: bytemuck::cast_slice(&vertices),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+            (buffer, vertices.len() as u32)
+        };
+        let mask_buffers: Vec<_> = views
+            .iter()
+            .map(|view| create_surface_buffer(view.portal_id, view.parent_view_projection))
+            .collect();
+        let seal_buffers: Vec<_> = views
+            .iter()
+            .filter(|view| view.parent.is_none())
+            .map(|view| create_surface_buffer(view.portal_id, view.parent_view_projection))
+            .collect();
+        
+        let (Some(frame), Some(depth_stencil), Some(pipelines)) = (
+            self.current_frame.as_mut(),
+            self.portal_depth_stencil.as_ref(),
+            self.portal_pipelines.as_ref(),
+        ) else {
+            return views;
+        };
+        
+        let depth_attachment = |depth: wgpu::LoadOp<f32>, stencil: wgpu::LoadOp<u32>| {
+            wgpu::RenderPassDepthStencilAttachment {
+                view: &depth_stencil.view,
+                depth_ops: Some(wgpu::Operations { load: depth, store: wgpu::StoreOp::Store }),
+                stencil_ops: Some(wgpu::Operations { load: stencil, store: wgpu::StoreOp::Store }),
+            }
+        };
+        let mut color_load = wgpu::LoadOp::Clear(self.clear_color.into());
+        
+        for (view, (buffer, count)) in views.iter().zip(&mask_buffers) {
+            // Top-level views start from a clean stencil so siblings don't interfere
+            let stencil_load = if view.parent.is_none() {
+                wgpu::LoadOp::Clear(0)
+            } else {
+                wgpu::LoadOp::Load
+            };
+            
+            {
+                let mut pass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Portal Mask Pass"),
+                    color_attachments: &[Some(color_attachment(&frame.view, frame.msaa_view.as_ref(), color_load))],
+                    depth_stencil_attachment: Some(depth_attachment(wgpu::LoadOp::Load, stencil_load)),
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                pass.set_pipeline(&pipelines.mask);
+                pass.set_stencil_reference(view.depth - 1);
+                pass.set_vertex_buffer(0, buffer.slice(..));
+                pass.draw(0..*count, 0..1);
+            }
+            color_load = wgpu::LoadOp::Load;
+            
+            let mut pass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Portal View Pass"),
+                color_attachments: &[Some(color_attachment(&frame.view, frame.msaa_view.as_ref(), wgpu::LoadOp::Load))],
+                depth_stencil_attachment: Some(depth_attachment(wgpu::LoadOp::Clear(1.0), wgpu::LoadOp::Load)),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_stencil_reference(view.depth);
+            draw_view(&mut pass, view);
+        }
+        
+        let mut pass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Portal Seal Pass"),
+            color_attachments: &[Some(color_attachment(&frame.view, frame.msaa_view.as_ref(), color_load))],
+            depth_stencil_attachment: Some(depth_attachment(wgpu::LoadOp::Clear(1.0), wgpu::LoadOp::Clear(0))),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&pipelines.seal);
+        for (buffer, count) in &seal_buffers {
+            pass.set_vertex_buffer(0, buffer.slice(..));
+            pass.draw(0..*count, 0..1);
+        }
+        drop(pass);
+        
+        frame.portals_rendered = true;
+        views
+    }
+    
+    /// Draw every instance of a mesh with one call in its own pass over the current frame
+    ///
+    /// Instance data is uploaded into the mesh's reusable instance buffer first.
+    /// `bind_groups` are bound in order starting at group 0; the pipeline must use
+    /// `[Vertex::desc(), InstanceRaw::desc()]` as vertex buffers.
+    pub fn draw_mesh_instanced(
+        &mut self,
+        pipeline: &wgpu::RenderPipeline,
+        bind_groups: &[&wgpu::BindGroup],
+        mesh: &mut Mesh,
+        instances: &[Instance],
+    ) {
+        mesh.set_instances(&self.device, &self.queue, instances);
+        
+        let Some(frame) = self.current_frame.as_mut() else {
+            return;
+        };
+        let depth_stencil_attachment = match &frame.output {
+            FrameOutput::Target(target) => target.depth_attachment(false),
+            FrameOutput::Surface(_) => scene_depth_attachment(self.portal_depth_stencil.as_ref(), frame.portals_rendered),
+        };
+        
+        let mut pass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Instanced Pass"),
+            color_attachments: &[Some(color_attachment(&frame.view, frame.msaa_view.as_ref(), wgpu::LoadOp::Load))],
+            depth_stencil_attachment,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(pipeline);
+        for (index, group) in bind_groups.iter().enumerate() {
+            pass.set_bind_group(index as u32, group, &[]);
+        }
+        mesh.draw_instanced(&mut pass);
+    }
+    
+    /// Set the color `begin_render_pass` and `render_portals` clear the frame to
+    pub fn set_clear_color(&mut self, color: Color) {
+        self.clear_color = color;
+    }
+    
+    /// Get the color frames are cleared to
+    pub fn clear_color(&self) -> Color {
+        self.clear_color
+    }
+    
     /// Clear the screen with a color
     pub fn clear(&mut self, r: f32, g: f32, b: f32, a: f32) {
         if let Some(frame) = &mut self.current_frame {
             frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Clear Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &frame.view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: r as f64,
-                            g: g as f64,
-                            b: b as f64,
-                            a: a as f64
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
+                color_attachments: &[Some(color_attachment(
+                    &frame.view,
+                    frame.msaa_view.as_ref(),
+                    wgpu::LoadOp::Clear(wgpu::Color {
+                        r: r as f64,
+                        g: g as f64,
+                        b: b as f64,
+                        a: a as f64
+                    }),
+                ))],
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
@@ -195,14 +629,112 @@ impl Renderer {
         }
     }
     
-    /// Resize the renderer
+    /// Resize the renderer (see [`on_resize`](Self::on_resize))
     pub fn resize(&mut self, width: u32, height: u32) {
-        if width > 0 && height > 0 {
-            self.size = (width, height);
-            self.config.width = width;
-            self.config.height = height;
+        self.on_resize(width, height);
+    }
+    
+    /// Reconfigure the surface and recreate every size-dependent GPU resource
+    ///
+    /// Rebuilds the MSAA target and the portal depth/stencil buffer at the new
+    /// size. Zero sizes (a minimized window) are ignored.
+    pub fn on_resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        
+        self.size = (width, height);
+        self.config.width = width;
+        self.config.height = height;
+        self.surface.configure(&self.device, &self.config);
+        
+        if self.sample_count > 1 {
+            self.msaa_texture = Some(Texture::create_msaa_texture(
+                &self.device,
+                &self.config,
+                self.sample_count,
+                "MSAA Color",
+            ));
+        }
+        if self.portal_depth_stencil.is_some() {
+            self.portal_depth_stencil = Some(Texture::create_depth_stencil_texture(
+                &self.device,
+                &self.config,
+                self.sample_count,
+                "Portal Depth Stencil",
+            ));
+        }
+    }
+    
+    /// Resize on a `Resized` window event; other events are ignored
+    pub fn handle_window_event(&mut self, event: &crate::window::WindowEvent) {
+        if let crate::window::WindowEvent::Resized(width, height) = *event {
+            self.on_resize(width, height);
+        }
+    }
+    
+    /// Switch the present mode, falling back to `Fifo` if the surface does not support it
+    ///
+    /// Returns the mode actually applied.
+    pub fn set_present_mode(&mut self, mode: PresentMode) -> PresentMode {
+        let applied = resolve_present_mode(mode, &self.present_modes);
+        if applied != mode {
+            eprintln!("Present mode {:?} is not supported, using {:?}", mode, applied);
+        }
+        
+        if applied != self.config.present_mode {
+            self.config.present_mode = applied;
             self.surface.configure(&self.device, &self.config);
         }
+        applied
+    }
+    
+    /// Enable or disable vsync
+    ///
+    /// Disabling prefers `Immediate`, then `Mailbox`, and keeps `Fifo` if neither is available.
+    pub fn set_vsync(&mut self, vsync: bool) -> PresentMode {
+        let mode = if vsync {
+            PresentMode::Fifo
+        } else {
+            [PresentMode::Immediate, PresentMode::Mailbox]
+                .into_iter()
+                .find(|mode| self.present_modes.contains(mode))
+                .unwrap_or(PresentMode::Fifo)
+        };
+        self.set_present_mode(mode)
+    }
+    
+    /// Get the current present mode
+    pub fn present_mode(&self) -> PresentMode {
+        self.config.present_mode
+    }
+    
+    /// Switch between filled and wireframe rendering
+    ///
+    /// Pipelines are not rebuilt; draw with `ShaderProgram::active_pipeline(renderer.polygon_mode())`
+    /// after building both a fill and a line pipeline. Without the `POLYGON_MODE_LINE`
+    /// device feature this logs a warning and stays filled. Returns whether wireframe is on.
+    pub fn set_wireframe(&mut self, wireframe: bool) -> bool {
+        self.polygon_mode = resolve_polygon_mode(wireframe, self.device.features());
+        if wireframe && self.polygon_mode == wgpu::PolygonMode::Fill {
+            eprintln!("Wireframe rendering is not supported by this device");
+        }
+        self.is_wireframe()
+    }
+    
+    /// Whether wireframe rendering is on
+    pub fn is_wireframe(&self) -> bool {
+        self.polygon_mode == wgpu::PolygonMode::Line
+    }
+    
+    /// Whether the device supports wireframe rendering
+    pub fn supports_wireframe(&self) -> bool {
+        self.device.features().contains(wgpu::Features::POLYGON_MODE_LINE)
+    }
+    
+    /// Get the polygon mode pipelines should currently be drawn with
+    pub fn polygon_mode(&self) -> wgpu::PolygonMode {
+        self.polygon_mode
     }
     
     /// Get the device
@@ -220,6 +752,11 @@ impl Renderer {
         self.size
     }
     
+    /// Get the MSAA sample count pipelines must be created with
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+    
     /// Get aspect ratio
     pub fn aspect_ratio(&self) -> f32 {
         self.size.0 as f32 / self.size.1 as f32
@@ -261,4 +798,54 @@ impl Color {
     pub fn to_array(&self) -> [f32; 4] {
         [self.r, self.g, self.b, self.a]
     }
+    
+    /// Blend from `a` to `b` component-wise in linear space, with `t` clamped to [0, 1]
+    pub fn lerp(a: Color, b: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        a * (1.0 - t) + b * t
+    }
+    
+    /// Blend this color towards `other` by `t`, see [`lerp`](Self::lerp)
+    pub fn mix(self, other: Color, t: f32) -> Color {
+        Self::lerp(self, other, t)
+    }
+    
+    /// Same color with alpha replaced, clamped to [0, 1]
+    pub fn with_alpha(self, a: f32) -> Color {
+        Self { a: a.clamp(0.0, 1.0), ..self }
+    }
+    
+    /// Color with rgb multiplied by alpha, for premultiplied-alpha blending
+    pub fn premultiplied(self) -> Color {
+        Self::new(self.r * self.a, self.g * self.a, self.b * self.a, self.a)
+    }
+}
+
+/// Scales every component; rgb may exceed 1 for HDR, alpha is clamped to [0, 1]
+impl std::ops::Mul<f32> for Color {
+    type Output = Color;
+    
+    fn mul(self, scale: f32) -> Color {
+        Color::new(self.r * scale, self.g * scale, self.b * scale, (self.a * scale).clamp(0.0, 1.0))
+    }
+}
+
+/// Adds component-wise; rgb may exceed 1 for HDR, alpha is clamped to [0, 1]
+impl std::ops::Add for Color {
+    type Output = Color;
+    
+    fn add(self, other: Color) -> Color {
+        Color::new(self.r + other.r, self.g + other.g, self.b + other.b, (self.a + other.a).clamp(0.0, 1.0))
+    }
+}
+
+impl From<Color> for wgpu::Color {
+    fn from(color: Color) -> Self {
+        wgpu::Color {
+            r: color.r as f64,
+            g: color.g as f64,
+            b: color.b as f64,
+            a: color.a as f64,
+        }
+    }
 }
\ No newline at end of file
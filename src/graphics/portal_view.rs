@@ -0,0 +1,284 @@
+//! Virtual cameras for rendering the view through portals
+
+use cgmath::{Point3, Vector3, Matrix4, InnerSpace};
+use crate::manifold::{Manifold, ChartId, Portal, PortalId};
+use super::camera::Camera;
+
+/// View of a destination chart seen through a portal
+///
+/// Views are produced depth-first: every view is followed by the views nested
+/// inside it, so `parent` always refers to an earlier index.
+#[derive(Debug, Clone)]
+pub struct PortalView {
+    pub portal_id: PortalId,
+    /// Index of the enclosing view, `None` for portals seen by the main camera
+    pub parent: Option<usize>,
+    /// Recursion depth, starting at 1; also the stencil value of the view's region
+    pub depth: u32,
+    /// Chart rendered through the portal
+    pub chart_id: ChartId,
+    /// Virtual eye position in the destination chart
+    pub eye: Point3<f32>,
+    /// Virtual forward direction in the destination chart
+    pub forward: Vector3<f32>,
+    pub view_matrix: Matrix4<f32>,
+    pub projection_matrix: Matrix4<f32>,
+    /// View-projection of the camera looking at the portal surface
+    pub parent_view_projection: Matrix4<f32>,
+}
+
+impl PortalView {
+    /// Get the virtual camera's view-projection matrix
+    pub fn view_projection(&self) -> Matrix4<f32> {
+        self.projection_matrix * self.view_matrix
+    }
+}
+
+/// Build the virtual cameras for every portal visible from `camera`
+///
+/// Portals are visible when active, in front of the eye and facing it.
+/// Recursion stops after `max_recursion` levels; zero disables portal views.
+pub fn build_portal_views(manifold: &Manifold, camera: &Camera, max_recursion: u32) -> Vec<PortalView> {
+    let mut views = Vec::new();
+    let eye = camera.position.local.to_point();
+    
+    collect_views(
+        manifold,
+        ViewOrigin {
+            chart_id: camera.position.chart_id,
+            eye,
+            forward: camera.forward(),
+            view_projection: camera.view_projection(),
+            parent: None,
+            depth: 1,
+        },
+        camera.projection_matrix,
+        max_recursion,
+        &mut views,
+    );
+    
+    views
+}
+
+/// Camera state a set of portals is viewed from
+struct ViewOrigin {
+    chart_id: ChartId,
+    eye: Point3<f32>,
+    forward: Vector3<f32>,
+    view_projection: Matrix4<f32>,
+    parent: Option<usize>,
+    depth: u32,
+}
+
+fn collect_views(
+    manifold: &Manifold,
+    origin: ViewOrigin,
+    projection: Matrix4<f32>,
+    max_recursion: u32,
+    views: &mut Vec<PortalView>,
+) {
+    if origin.depth > max_recursion {
+        return;
+    }
+    
+    for portal in manifold.portals_from_chart(origin.chart_id) {
+        if !portal.is_active() {
+            continue;
+        }
+        
+        let to_portal = portal.bounds().center - origin.eye;
+        if !portal.is_front_facing(to_portal) || to_portal.dot(origin.forward) <= 0.0 {
+            continue;
+        }
+        
+        let view_matrix = portal.get_view_matrix(origin.eye);
+        let view = PortalView {
+            portal_id: portal.id(),
+            parent: origin.parent,
+            depth: origin.depth,
+            chart_id: portal.target_chart(),
+            eye: portal.transform_point(origin.eye),
+            forward: portal.transform_vector(origin.forward).normalize(),
+            view_matrix,
+            projection_matrix: projection,
+            parent_view_projection: origin.view_projection,
+        };
+        
+        // Extension point: nested portals are viewed from the virtual camera
+        let next = ViewOrigin {
+            chart_id: view.chart_id,
+            eye: view.eye,
+            forward: view.forward,
+            view_projection: view.view_projection(),
+            parent: Some(views.len()),
+            depth: origin.depth + 1,
+        };
+        
+        views.push(view);
+        collect_views(manifold, next, projection, max_recursion, views);
+    }
+}
+
+/// Triangulate the portal surface as a fan from the first point of its outline
+///
+/// Every portal shape is convex, so the fan covers it exactly even when the
+/// shape doesn't surround the portal center.
+pub fn portal_surface(portal: &Portal) -> Vec<Point3<f32>> {
+    let outline = portal.bounds().outline();
+    
+    let mut triangles = Vec::with_capacity(outline.len().saturating_sub(2) * 3);
+    for pair in outline.windows(2).skip(1) {
+        triangles.push(outline[0]);
+        triangles.push(pair[0]);
+        triangles.push(pair[1]);
+    }
+    triangles
+}
+
+/// Depth/stencil state for pipelines drawing a destination chart inside a portal view
+///
+/// Fragments pass only where the stencil equals the reference set for the view.
+pub fn portal_stencil_state() -> wgpu::DepthStencilState {
+    let face = wgpu::StencilFaceState {
+        compare: wgpu::CompareFunction::Equal,
+        fail_op: wgpu::StencilOperation::Keep,
+        depth_fail_op: wgpu::StencilOperation::Keep,
+        pass_op: wgpu::StencilOperation::Keep,
+    };
+    
+    wgpu::DepthStencilState {
+        format: super::Texture::DEPTH_STENCIL_FORMAT,
+        depth_write_enabled: true,
+        depth_compare: wgpu::CompareFunction::Less,
+        stencil: wgpu::StencilState {
+            front: face,
+            back: face,
+            read_mask: 0xff,
+            write_mask: 0x00,
+        },
+        bias: wgpu::DepthBiasState::default(),
+    }
+}
+
+const PORTAL_MASK_SHADER: &str = r#"
+@vertex
+fn vs_main(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {
+    return position;
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.0, 0.0, 0.0, 0.0);
+}
+"#;
+
+/// Pipelines writing portal surfaces into the stencil and depth buffers
+pub(crate) struct PortalPipelines {
+    /// Increments the stencil inside a portal nested in the referenced region
+    pub mask: wgpu::RenderPipeline,
+    /// Writes top-level portal surfaces into depth so the main scene cannot overdraw them
+    pub seal: wgpu::RenderPipeline,
+}
+
+impl PortalPipelines {
+    pub fn new(device: &wgpu::Device, color_format: wgpu::TextureFormat, sample_count: u32) -> Self {
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Portal Mask Shader"),
+            source: wgpu::ShaderSource::Wgsl(PORTAL_MASK_SHADER.into()),
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Portal Mask Layout"),
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+        
+        let increment = wgpu::StencilFaceState {
+            compare: wgpu::CompareFunction::Equal,
+            fail_op: wgpu::StencilOperation::Keep,
+            depth_fail_op: wgpu::StencilOperation::Keep,
+            pass_op: wgpu::StencilOperation::IncrementClamp,
+        };
+        let targets = PipelineTargets { color_format, sample_count };
+        let mask = Self::create_pipeline(device, &module, &layout, &targets, "Portal Mask Pipeline", wgpu::DepthStencilState {
+            format: super::Texture::DEPTH_STENCIL_FORMAT,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::Always,
+            stencil: wgpu::StencilState {
+                front: increment,
+                back: increment,
+                read_mask: 0xff,
+                write_mask: 0xff,
+            },
+            bias: wgpu::DepthBiasState::default(),
+        });
+        let seal = Self::create_pipeline(device, &module, &layout, &targets, "Portal Seal Pipeline", wgpu::DepthStencilState {
+            format: super::Texture::DEPTH_STENCIL_FORMAT,
+            depth_write_enabled: true,
+            depth_compare: wgpu::CompareFunction::Less,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        });
+        
+        Self { mask, seal }
+    }
+    
+    fn create_pipeline(
+        device: &wgpu::Device,
+        module: &wgpu::ShaderModule,
+        layout: &wgpu::PipelineLayout,
+        targets: &PipelineTargets,
+        label: &str,
+        depth_stencil: wgpu::DepthStencilState,
+    ) -> wgpu::RenderPipeline {
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(label),
+            layout: Some(layout),
+            vertex: wgpu::VertexState {
+                module,
+                entry_point: super::shader::VERTEX_ENTRY_POINT,
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<[f32; 4]>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x4],
+                }],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module,
+                entry_point: super::shader::FRAGMENT_ENTRY_POINT,
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: targets.color_format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::empty(),
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: Some(depth_stencil),
+            multisample: wgpu::MultisampleState {
+                count: targets.sample_count,
+                ..Default::default()
+            },
+            multiview: None,
+        })
+    }
+}
+
+/// Attachment formats the portal pipelines render into
+struct PipelineTargets {
+    color_format: wgpu::TextureFormat,
+    sample_count: u32,
+}
+
+/// Project a portal surface into clip space for the mask pipelines
+pub(crate) fn clip_space_surface(portal: &Portal, view_projection: Matrix4<f32>) -> Vec<[f32; 4]> {
+    portal_surface(portal)
+        .into_iter()
+        .map(|p| {
+            let clip = view_projection * p.to_homogeneous();
+            [clip.x, clip.y, clip.z, clip.w]
+        })
+        .collect()
+}
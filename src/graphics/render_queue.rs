@@ -0,0 +1,105 @@
+//! Draw ordering for opaque and transparent renderables
+
+use cgmath::{MetricSpace, Point3};
+use crate::ecs::{Entity, Renderable, Transform, World};
+
+/// One renderable queued for drawing
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawItem {
+    pub entity: Entity,
+    pub mesh_id: String,
+    pub shader_id: String,
+    pub position: Point3<f32>,
+    /// Squared distance from the camera, set by [`RenderQueue::sort`]
+    pub distance2: f32,
+}
+
+/// Renderables split into opaque and transparent lists, in the order to draw them
+///
+/// Opaque items draw first, nearest first, so the depth test rejects as much
+/// hidden geometry as possible. Transparent items draw afterwards, farthest
+/// first, so each blends over what is behind it. Their pipelines should test
+/// against depth but not write it.
+#[derive(Debug, Clone, Default)]
+pub struct RenderQueue {
+    opaque: Vec<DrawItem>,
+    transparent: Vec<DrawItem>,
+}
+
+impl RenderQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Queue every visible entity with a `Renderable` and a transform, sorted for `eye`
+    ///
+    /// Distances are measured in local coordinates, whatever chart each entity is in.
+    pub fn from_world(world: &World, eye: Point3<f32>) -> Self {
+        let mut queue = Self::new();
+        for (entity, renderable) in world.iter::<Renderable>() {
+            if !renderable.visible {
+                continue;
+            }
+            if let Some(transform) = world.get_component::<Transform>(entity) {
+                queue.push(entity, renderable, transform.position.local.to_point());
+            }
+        }
+        queue.sort(eye);
+        queue
+    }
+    
+    /// Add a renderable at `position`; call [`sort`](Self::sort) before drawing
+    pub fn push(&mut self, entity: Entity, renderable: &Renderable, position: Point3<f32>) {
+        let item = DrawItem {
+            entity,
+            mesh_id: renderable.current_mesh_id().to_string(),
+            shader_id: renderable.shader_id.clone(),
+            position,
+            distance2: 0.0,
+        };
+        if renderable.transparent {
+            self.transparent.push(item);
+        } else {
+            self.opaque.push(item);
+        }
+    }
+    
+    /// Order opaque items front-to-back and transparent items back-to-front from `eye`
+    ///
+    /// Equally distant items keep the order they were pushed in.
+    pub fn sort(&mut self, eye: Point3<f32>) {
+        for item in self.opaque.iter_mut().chain(self.transparent.iter_mut()) {
+            item.distance2 = item.position.distance2(eye);
+        }
+        self.opaque.sort_by(|a, b| a.distance2.total_cmp(&b.distance2));
+        self.transparent.sort_by(|a, b| b.distance2.total_cmp(&a.distance2));
+    }
+    
+    /// Opaque items, nearest first
+    pub fn opaque(&self) -> &[DrawItem] {
+        &self.opaque
+    }
+    
+    /// Transparent items, farthest first
+    pub fn transparent(&self) -> &[DrawItem] {
+        &self.transparent
+    }
+    
+    /// Every item in the order to draw them: opaque, then transparent
+    pub fn draw_order(&self) -> impl Iterator<Item = &DrawItem> {
+        self.opaque.iter().chain(&self.transparent)
+    }
+    
+    pub fn len(&self) -> usize {
+        self.opaque.len() + self.transparent.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
+    pub fn clear(&mut self) {
+        self.opaque.clear();
+        self.transparent.clear();
+    }
+}
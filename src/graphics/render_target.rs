@@ -0,0 +1,154 @@
+//! Offscreen targets for rendering into textures instead of the swapchain
+
+use std::sync::Arc;
+use wgpu::{CommandEncoder, Device, Queue, RenderPass, TextureView};
+use image::RgbaImage;
+use super::{Color, Texture, capture_texture, color_attachment};
+
+/// Color texture, plus optional depth buffer, that frames can be rendered into
+///
+/// The color texture can be bound for sampling (minimaps, portal views,
+/// post-processing) and read back with [`capture`](Self::capture). Targets are
+/// single-sampled, so pipelines drawing into them must use a sample count of 1.
+/// Cloning is cheap and shares the same textures.
+#[derive(Clone)]
+pub struct RenderTarget {
+    color: Arc<Texture>,
+    depth: Option<Arc<Texture>>,
+}
+
+impl RenderTarget {
+    /// Format of the optional depth buffer
+    pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+    
+    /// Create a color target of the given size and format
+    pub fn new(device: &Device, width: u32, height: u32, format: wgpu::TextureFormat) -> Self {
+        let color = create_attachment(
+            device,
+            (width, height),
+            format,
+            wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
+            "Render Target Color",
+        );
+        
+        Self {
+            color: Arc::new(color),
+            depth: None,
+        }
+    }
+    
+    /// Add a depth buffer in [`DEPTH_FORMAT`](Self::DEPTH_FORMAT)
+    pub fn with_depth(mut self, device: &Device) -> Self {
+        let depth = create_attachment(
+            device,
+            self.color.size,
+            Self::DEPTH_FORMAT,
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            "Render Target Depth",
+        );
+        self.depth = Some(Arc::new(depth));
+        self
+    }
+    
+    /// Get the color texture
+    pub fn color(&self) -> &Texture {
+        &self.color
+    }
+    
+    /// Get the depth texture, if any
+    pub fn depth(&self) -> Option<&Texture> {
+        self.depth.as_deref()
+    }
+    
+    /// View of the color texture, for sampling or as an attachment
+    pub fn view(&self) -> &TextureView {
+        &self.color.view
+    }
+    
+    /// Get the size in pixels
+    pub fn size(&self) -> (u32, u32) {
+        self.color.size
+    }
+    
+    /// Get the color format
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.color.texture.format()
+    }
+    
+    /// Begin a pass drawing into this target
+    ///
+    /// With a `clear` color the color and depth are cleared first, otherwise
+    /// their previous contents are kept.
+    pub fn begin_pass<'a>(&'a self, encoder: &'a mut CommandEncoder, clear: Option<Color>) -> RenderPass<'a> {
+        let load = match clear {
+            Some(color) => wgpu::LoadOp::Clear(color.into()),
+            None => wgpu::LoadOp::Load,
+        };
+        
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Render Target Pass"),
+            color_attachments: &[Some(color_attachment(&self.color.view, None, load))],
+            depth_stencil_attachment: self.depth_attachment(clear.is_some()),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        })
+    }
+    
+    /// Read the color texture back as an RGBA image (see [`capture_texture`])
+    pub fn capture(&self, device: &Device, queue: &Queue) -> Result<RgbaImage, Box<dyn std::error::Error>> {
+        capture_texture(device, queue, &self.color.texture)
+    }
+    
+    /// Depth attachment that clears to the far plane or keeps the previous depth
+    pub(crate) fn depth_attachment(&self, clear: bool) -> Option<wgpu::RenderPassDepthStencilAttachment<'_>> {
+        self.depth.as_ref().map(|depth| wgpu::RenderPassDepthStencilAttachment {
+            view: &depth.view,
+            depth_ops: Some(wgpu::Operations {
+                load: if clear { wgpu::LoadOp::Clear(1.0) } else { wgpu::LoadOp::Load },
+                store: wgpu::StoreOp::Store,
+            }),
+            stencil_ops: None,
+        })
+    }
+}
+
+fn create_attachment(
+    device: &Device,
+    size: (u32, u32),
+    format: wgpu::TextureFormat,
+    usage: wgpu::TextureUsages,
+    label: &str,
+) -> Texture {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size: wgpu::Extent3d {
+            width: size.0.max(1),
+            height: size.1.max(1),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+        address_mode_u: wgpu::AddressMode::ClampToEdge,
+        address_mode_v: wgpu::AddressMode::ClampToEdge,
+        address_mode_w: wgpu::AddressMode::ClampToEdge,
+        mag_filter: wgpu::FilterMode::Linear,
+        min_filter: wgpu::FilterMode::Linear,
+        ..Default::default()
+    });
+    
+    Texture {
+        texture,
+        view,
+        sampler,
+        size: (size.0.max(1), size.1.max(1)),
+    }
+}
@@ -1,4 +1,8 @@
 //! Shader management for non-Euclidean rendering
+//!
+//! Pipelines are built against wgpu 0.19, where `entry_point` is a plain `&str`
+//! (later wgpu releases take `Option<&str>`). Every shader in the engine and its
+//! examples names its entry points [`VERTEX_ENTRY_POINT`] and [`FRAGMENT_ENTRY_POINT`].
 
 use wgpu::{
     Device, ShaderModule, PipelineLayout, RenderPipeline,
@@ -6,12 +10,23 @@ use wgpu::{
 };
 use std::collections::HashMap;
 use std::sync::Arc;
+use cgmath::{EuclideanSpace, InnerSpace, Matrix4, Point3, SquareMatrix, Vector3};
+use crate::manifold::{ChartId, Metric};
+use super::{FogUniform, LightUniform, Texture};
+
+/// Name of the vertex shader entry point
+pub const VERTEX_ENTRY_POINT: &str = "vs_main";
+
+/// Name of the fragment shader entry point
+pub const FRAGMENT_ENTRY_POINT: &str = "fs_main";
 
 /// Shader program for metric-aware rendering
 pub struct ShaderProgram {
     pub vertex_module: ShaderModule,
     pub fragment_module: ShaderModule,
     pub pipeline: Option<RenderPipeline>,
+    /// Pipeline built with a non-fill polygon mode, for wireframe debugging
+    pub wireframe_pipeline: Option<RenderPipeline>,
     pub geometry_type: GeometryType,
 }
 
@@ -45,29 +60,41 @@ impl ShaderProgram {
             vertex_module,
             fragment_module,
             pipeline: None,
+            wireframe_pipeline: None,
             geometry_type,
         }
     }
     
     /// Create a render pipeline with metric-aware transformations
+    ///
+    /// `PolygonMode::Fill` builds `pipeline`; the other modes build `wireframe_pipeline`.
+    /// Non-fill modes need a device feature (see [`polygon_mode_feature`]); if the
+    /// device lacks it a warning is logged and no pipeline is built.
     pub fn create_pipeline(
         &mut self,
         device: &Device,
         layout: &PipelineLayout,
         vertex_layout: VertexBufferLayout,
         format: wgpu::TextureFormat,
+        sample_count: u32,
+        polygon_mode: wgpu::PolygonMode,
     ) {
+        if !device.features().contains(polygon_mode_feature(polygon_mode)) {
+            eprintln!("Polygon mode {:?} is not supported by the device, skipping pipeline", polygon_mode);
+            return;
+        }
+        
         let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
             label: Some("Non-Euclidean Render Pipeline"),
             layout: Some(layout),
             vertex: wgpu::VertexState {
                 module: &self.vertex_module,
-                entry_point: "vs_main",
+                entry_point: VERTEX_ENTRY_POINT,
                 buffers: &[vertex_layout],
             },
             fragment: Some(wgpu::FragmentState {
                 module: &self.fragment_module,
-                entry_point: "fs_main",
+                entry_point: FRAGMENT_ENTRY_POINT,
                 targets: &[Some(wgpu::ColorTargetState {
                     format,
                     blend: Some(wgpu::BlendState::ALPHA_BLENDING),
@@ -79,7 +106,7 @@ impl ShaderProgram {
                 strip_index_format: None,
                 front_face: wgpu::FrontFace::Ccw,
                 cull_mode: None, // No culling for non-Euclidean spaces
-                polygon_mode: wgpu::PolygonMode::Fill,
+                polygon_mode,
                 unclipped_depth: false,
                 conservative: false,
             },
@@ -91,14 +118,147 @@ impl ShaderProgram {
                 bias: wgpu::DepthBiasState::default(),
             }),
             multisample: wgpu::MultisampleState {
-                count: 1,
+                count: sample_count,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
             multiview: None,
         });
         
-        self.pipeline = Some(pipeline);
+        match polygon_mode {
+            wgpu::PolygonMode::Fill => self.pipeline = Some(pipeline),
+            _ => self.wireframe_pipeline = Some(pipeline),
+        }
+    }
+    
+    /// Pipeline to draw with in the given polygon mode
+    ///
+    /// Falls back to the fill pipeline if no wireframe pipeline was built.
+    pub fn active_pipeline(&self, polygon_mode: wgpu::PolygonMode) -> Option<&RenderPipeline> {
+        match polygon_mode {
+            wgpu::PolygonMode::Fill => self.pipeline.as_ref(),
+            _ => self.wireframe_pipeline.as_ref().or(self.pipeline.as_ref()),
+        }
+    }
+}
+
+/// Device feature a polygon mode requires (empty for `Fill`)
+pub fn polygon_mode_feature(polygon_mode: wgpu::PolygonMode) -> wgpu::Features {
+    match polygon_mode {
+        wgpu::PolygonMode::Fill => wgpu::Features::empty(),
+        wgpu::PolygonMode::Line => wgpu::Features::POLYGON_MODE_LINE,
+        wgpu::PolygonMode::Point => wgpu::Features::POLYGON_MODE_POINT,
+    }
+}
+
+/// Per-draw uniforms shared by the geometry shaders
+///
+/// Matches the WGSL `Uniforms` struct in [`Shader::create_geometry_shaders`]:
+/// - `metric_params`: x=sphere radius, y=scale
+/// - `geometry`: x=geometry code (see [`geometry_code`]), y=curvature
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct NonEuclideanUniform {
+    pub view_proj: [[f32; 4]; 4],
+    pub model: [[f32; 4]; 4],
+    pub chart_id: f32,
+    pub _padding: [f32; 3],
+    pub metric_params: [f32; 4],
+    pub geometry: [f32; 4],
+}
+
+impl NonEuclideanUniform {
+    /// Fill the uniforms for drawing a model in a chart with the given metric
+    pub fn new(view_proj: Matrix4<f32>, model: Matrix4<f32>, chart_id: ChartId, metric: &Metric) -> Self {
+        Self {
+            view_proj: view_proj.into(),
+            model: model.into(),
+            chart_id: chart_id.0 as f32,
+            _padding: [0.0; 3],
+            metric_params: [metric.parameters.radius, metric.scale, 0.0, 0.0],
+            geometry: [geometry_code(metric.geometry), metric.parameters.curvature, 0.0, 0.0],
+        }
+    }
+}
+
+/// Portal state read by the geometry fragment shader
+///
+/// Matches the WGSL `PortalData` struct; `is_active` and `target_chart` are
+/// padded to 16 bytes because `transform` is 16-byte aligned.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PortalUniform {
+    pub is_active: f32,
+    pub target_chart: f32,
+    pub _padding: [f32; 2],
+    pub transform: [[f32; 4]; 4],
+}
+
+impl PortalUniform {
+    /// State for drawing outside any portal
+    pub fn inactive() -> Self {
+        Self {
+            is_active: 0.0,
+            target_chart: 0.0,
+            _padding: [0.0; 2],
+            transform: Matrix4::identity().into(),
+        }
+    }
+    
+    /// State for drawing a portal leading to `target_chart`
+    pub fn active(target_chart: ChartId, transform: Matrix4<f32>) -> Self {
+        Self {
+            is_active: 1.0,
+            target_chart: target_chart.0 as f32,
+            _padding: [0.0; 2],
+            transform: transform.into(),
+        }
+    }
+}
+
+/// Numeric code for a geometry as seen by the shaders
+///
+/// Euclidean is 0, spherical 1, hyperbolic 2 and custom 3.
+pub fn geometry_code(geometry: crate::manifold::GeometryType) -> f32 {
+    match geometry {
+        crate::manifold::GeometryType::Euclidean => 0.0,
+        crate::manifold::GeometryType::Spherical => 1.0,
+        crate::manifold::GeometryType::Hyperbolic => 2.0,
+        crate::manifold::GeometryType::Custom => 3.0,
+    }
+}
+
+/// Normal to light a surface with, corrected for the chart's metric
+///
+/// `position` and `normal` are in model space. Spherical charts use the radial
+/// direction, as the spherical shader projects every vertex onto the sphere.
+/// Other charts treat `normal` as a covector and raise its index with the
+/// inverse metric (`n^i = g^ij n_j`). The result has unit Euclidean length so it
+/// can be dotted with a light direction.
+pub fn metric_normal(metric: &Metric, position: Point3<f32>, normal: Vector3<f32>) -> Vector3<f32> {
+    let adjusted = match metric.geometry {
+        crate::manifold::GeometryType::Spherical => position.to_vec(),
+        _ => {
+            let g = metric.tensor_at(position).g;
+            g.invert().map_or(normal, |g_inv| g_inv * normal)
+        }
+    };
+    
+    if adjusted.magnitude2() > f32::EPSILON {
+        adjusted.normalize()
+    } else {
+        normal.normalize()
+    }
+}
+
+/// Pick the polygon mode for a wireframe toggle given the device's features
+///
+/// Wireframe needs `POLYGON_MODE_LINE`; without it rendering stays filled.
+pub fn resolve_polygon_mode(wireframe: bool, features: wgpu::Features) -> wgpu::PolygonMode {
+    if wireframe && features.contains(wgpu::Features::POLYGON_MODE_LINE) {
+        wgpu::PolygonMode::Line
+    } else {
+        wgpu::PolygonMode::Fill
     }
 }
 
@@ -135,7 +295,79 @@ impl Shader {
         self.programs.get_mut(name)
     }
     
+    /// Bind group layout matching the geometry shaders' group 0
+    ///
+    /// Binding 0 is a [`NonEuclideanUniform`], 1 a [`PortalUniform`], 2 the
+    /// diffuse texture, 3 its filtering sampler, 4 a [`LightUniform`] and 5 a
+    /// [`FogUniform`].
+    pub fn bind_group_layout(device: &Device) -> wgpu::BindGroupLayout {
+        let uniform = |binding, visibility, size: usize| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: wgpu::BufferSize::new(size as u64),
+            },
+            count: None,
+        };
+        
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Non-Euclidean Bind Group Layout"),
+            entries: &[
+                uniform(0, wgpu::ShaderStages::VERTEX, std::mem::size_of::<NonEuclideanUniform>()),
+                uniform(1, wgpu::ShaderStages::FRAGMENT, std::mem::size_of::<PortalUniform>()),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                uniform(4, wgpu::ShaderStages::FRAGMENT, std::mem::size_of::<LightUniform>()),
+                uniform(5, wgpu::ShaderStages::FRAGMENT, std::mem::size_of::<FogUniform>()),
+            ],
+        })
+    }
+    
+    /// Bind the uniform buffers, texture and sampler for the geometry shaders
+    ///
+    /// `layout` must come from [`bind_group_layout`](Self::bind_group_layout).
+    pub fn create_bind_group(
+        device: &Device,
+        layout: &wgpu::BindGroupLayout,
+        uniform_buffer: &wgpu::Buffer,
+        portal_buffer: &wgpu::Buffer,
+        texture: &Texture,
+        light_buffer: &wgpu::Buffer,
+        fog_buffer: &wgpu::Buffer,
+    ) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Non-Euclidean Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: uniform_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: portal_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 2, resource: wgpu::BindingResource::TextureView(&texture.view) },
+                wgpu::BindGroupEntry { binding: 3, resource: wgpu::BindingResource::Sampler(&texture.sampler) },
+                wgpu::BindGroupEntry { binding: 4, resource: light_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 5, resource: fog_buffer.as_entire_binding() },
+            ],
+        })
+    }
+    
     /// Create shaders for different geometries
+    ///
+    /// Pipelines for these programs use [`bind_group_layout`](Self::bind_group_layout) as group 0.
     pub fn create_geometry_shaders(&mut self) {
         // Euclidean shader (standard)
         let euclidean_vertex = r#"
@@ -160,6 +392,7 @@ impl Shader {
                 model: mat4x4<f32>,
                 chart_id: f32,
                 metric_params: vec4<f32>,
+                geometry: vec4<f32>, // x: geometry code, y: curvature
             }
 
             @group(0) @binding(0)
@@ -201,7 +434,8 @@ impl Shader {
                 view_proj: mat4x4<f32>,
                 model: mat4x4<f32>,
                 chart_id: f32,
-                metric_params: vec4<f32>, // x: curvature, y: scale
+                metric_params: vec4<f32>, // x: radius, y: scale
+                geometry: vec4<f32>, // x: geometry code, y: curvature
             }
 
             @group(0) @binding(0)
@@ -216,6 +450,12 @@ impl Shader {
                 return p;
             }
 
+            // Raise the normal's index with the inverse Poincaré metric, g = diag(λ², λ², 1)
+            fn hyperbolic_normal(p: vec2<f32>, n: vec3<f32>) -> vec3<f32> {
+                let lambda = 2.0 / max(1.0 - dot(p, p), 0.01);
+                return vec3<f32>(n.xy / (lambda * lambda), n.z);
+            }
+
             @vertex
             fn vs_main(input: VertexInput) -> VertexOutput {
                 var out: VertexOutput;
@@ -224,10 +464,12 @@ impl Shader {
                 let hyperbolic_xy = hyperbolic_transform(input.position.xy * uniforms.metric_params.y);
                 let hyperbolic_pos = vec3<f32>(hyperbolic_xy, input.position.z);
                 
-                out.hyperbolic_pos = hyperbolic_pos;
-                out.clip_position = uniforms.view_proj * uniforms.model * vec4<f32>(hyperbolic_pos, 1.0);
+                let world_pos = uniforms.model * vec4<f32>(hyperbolic_pos, 1.0);
+                out.hyperbolic_pos = world_pos.xyz;
+                out.clip_position = uniforms.view_proj * world_pos;
                 out.tex_coords = input.tex_coords;
-                out.normal = normalize((uniforms.model * vec4<f32>(input.normal, 0.0)).xyz);
+                let normal = hyperbolic_normal(hyperbolic_xy, input.normal);
+                out.normal = normalize((uniforms.model * vec4<f32>(normal, 0.0)).xyz);
                 out.color = input.color;
                 out.chart_id = uniforms.chart_id;
                 return out;
@@ -257,6 +499,7 @@ impl Shader {
                 model: mat4x4<f32>,
                 chart_id: f32,
                 metric_params: vec4<f32>, // x: radius, y: scale
+                geometry: vec4<f32>, // x: geometry code, y: curvature
             }
 
             @group(0) @binding(0)
@@ -275,10 +518,12 @@ impl Shader {
                 
                 let spherical_pos = spherical_transform(input.position * uniforms.metric_params.y);
                 
-                out.spherical_pos = spherical_pos;
-                out.clip_position = uniforms.view_proj * uniforms.model * vec4<f32>(spherical_pos, 1.0);
+                let world_pos = uniforms.model * vec4<f32>(spherical_pos, 1.0);
+                out.spherical_pos = world_pos.xyz;
+                out.clip_position = uniforms.view_proj * world_pos;
                 out.tex_coords = input.tex_coords;
-                out.normal = normalize(spherical_pos);
+                // Radial direction, rotated into world space like the position
+                out.normal = normalize((uniforms.model * vec4<f32>(spherical_pos, 0.0)).xyz);
                 out.color = input.color;
                 out.chart_id = uniforms.chart_id;
                 return out;
@@ -310,13 +555,74 @@ impl Shader {
             @group(0) @binding(3)
             var s_diffuse: sampler;
 
+            struct Light {
+                position: vec4<f32>, // xyz: direction (w = 0) or position (w = 1)
+                color: vec4<f32>, // rgb: color, a: intensity
+                params: vec4<f32>, // x: range
+            }
+
+            struct Lights {
+                lights: array<Light, 4>,
+                count: u32,
+            }
+
+            @group(0) @binding(4)
+            var<uniform> lights: Lights;
+
+            struct Fog {
+                fog_color: vec4<f32>,
+                camera_position: vec4<f32>,
+                fog_density: f32,
+                geometry_type: f32, // geometry code of the chart being drawn
+            }
+
+            @group(0) @binding(5)
+            var<uniform> fog: Fog;
+
+            // Distance from the camera, measured in the Poincaré ball for hyperbolic charts
+            fn fog_distance(world_pos: vec3<f32>) -> f32 {
+                let camera = fog.camera_position.xyz;
+                if (fog.geometry_type > 1.5 && fog.geometry_type < 2.5) {
+                    let u2 = min(dot(world_pos, world_pos), 0.9999);
+                    let v2 = min(dot(camera, camera), 0.9999);
+                    let d = world_pos - camera;
+                    return acosh(1.0 + 2.0 * dot(d, d) / ((1.0 - u2) * (1.0 - v2)));
+                }
+                return length(world_pos - camera);
+            }
+
+            // Fraction of the surface color left, matching FogSettings::factor
+            fn fog_factor(distance: f32) -> f32 {
+                if (fog.geometry_type > 1.5 && fog.geometry_type < 2.5) {
+                    return exp(-fog.fog_density * distance);
+                }
+                return clamp(1.0 - fog.fog_density * distance, 0.0, 1.0);
+            }
+
+            fn light_contribution(light: Light, world_pos: vec3<f32>, normal: vec3<f32>) -> vec3<f32> {
+                var to_light = -light.position.xyz;
+                var attenuation = 1.0;
+                if (light.position.w > 0.5) {
+                    to_light = light.position.xyz - world_pos;
+                    // TODO: use the geodesic distance in curved charts, this is Euclidean
+                    let falloff = clamp(1.0 - length(to_light) / light.params.x, 0.0, 1.0);
+                    attenuation = falloff * falloff;
+                }
+                let diffuse = max(dot(normal, normalize(to_light)), 0.0);
+                return light.color.rgb * light.color.a * diffuse * attenuation;
+            }
+
             @fragment
             fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                 var base_color = in.color;
                 
-                // Simple lighting
-                let light_dir = normalize(vec3<f32>(0.5, 1.0, 0.3));
-                let diffuse = max(dot(in.normal, light_dir), 0.2);
+                // Sum the active lights over a fixed ambient floor
+                let normal = normalize(in.normal);
+                var lighting = vec3<f32>(0.0);
+                for (var i = 0u; i < min(lights.count, 4u); i = i + 1u) {
+                    lighting = lighting + light_contribution(lights.lights[i], in.world_pos, normal);
+                }
+                let diffuse = max(lighting, vec3<f32>(0.2));
                 
                 // Portal edge visualization
                 if (portal.is_active > 0.5) {
@@ -324,7 +630,9 @@ impl Shader {
                     base_color = base_color + vec4<f32>(portal_glow, portal_glow, portal_glow * 0.5, 0.0);
                 }
                 
-                return vec4<f32>(base_color.rgb * diffuse, base_color.a);
+                let lit = base_color.rgb * diffuse;
+                let visibility = fog_factor(fog_distance(in.world_pos));
+                return vec4<f32>(mix(fog.fog_color.rgb, lit, visibility), base_color.a);
             }
         "#;
         
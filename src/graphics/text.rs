@@ -0,0 +1,420 @@
+//! Screen-space text drawn with a built-in 8×8 bitmap font
+
+use wgpu::{util::DeviceExt, CommandEncoder, Device, Queue, TextureView};
+use bytemuck::{Pod, Zeroable};
+use super::{Color, Texture, color_attachment, shader::{FRAGMENT_ENTRY_POINT, VERTEX_ENTRY_POINT}};
+
+/// Size of a glyph in the font, in pixels
+pub const GLYPH_PIXELS: u32 = 8;
+
+/// Glyphs per row of the font atlas
+const ATLAS_COLUMNS: u32 = 16;
+
+/// Rows of glyphs in the font atlas
+const ATLAS_ROWS: u32 = 6;
+
+/// Drawn in place of characters the font doesn't have
+const FALLBACK_GLYPH: char = '?';
+
+/// One character of laid-out text, in pixels from the top-left of the screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphQuad {
+    pub min: [f32; 2],
+    pub max: [f32; 2],
+    pub uv_min: [f32; 2],
+    pub uv_max: [f32; 2],
+    pub color: [f32; 4],
+}
+
+/// Lay out a string as one quad per visible character
+///
+/// Glyphs are `size` pixels square and monospaced, starting with the top-left
+/// of the first one at `position`. `\n` starts a new line `size` pixels lower,
+/// spaces only advance, and characters outside printable ASCII are drawn as `?`.
+pub fn layout_text(text: &str, position: (f32, f32), size: f32, color: Color) -> Vec<GlyphQuad> {
+    let mut quads = Vec::with_capacity(text.len());
+    let (mut x, mut y) = position;
+    
+    for c in text.chars() {
+        match c {
+            '\n' => {
+                x = position.0;
+                y += size;
+                continue;
+            }
+            ' ' => {}
+            _ => {
+                let (uv_min, uv_max) = glyph_uv(c);
+                quads.push(GlyphQuad {
+                    min: [x, y],
+                    max: [x + size, y + size],
+                    uv_min,
+                    uv_max,
+                    color: color.to_array(),
+                });
+            }
+        }
+        x += size;
+    }
+    
+    quads
+}
+
+/// Width and height in pixels of text laid out by [`layout_text`]
+pub fn measure_text(text: &str, size: f32) -> (f32, f32) {
+    let columns = text.split('\n').map(|line| line.chars().count()).max().unwrap_or(0);
+    let lines = text.split('\n').count();
+    (columns as f32 * size, lines as f32 * size)
+}
+
+/// Atlas cell of a character, falling back to `?`
+fn glyph_index(c: char) -> u32 {
+    let c = if (' '..='~').contains(&c) { c } else { FALLBACK_GLYPH };
+    c as u32 - ' ' as u32
+}
+
+/// Texture coordinates of a character's cell in the font atlas
+fn glyph_uv(c: char) -> ([f32; 2], [f32; 2]) {
+    let index = glyph_index(c);
+    let (column, row) = (index % ATLAS_COLUMNS, index / ATLAS_COLUMNS);
+    let (width, height) = ((ATLAS_COLUMNS * GLYPH_PIXELS) as f32, (ATLAS_ROWS * GLYPH_PIXELS) as f32);
+    let min = [(column * GLYPH_PIXELS) as f32 / width, (row * GLYPH_PIXELS) as f32 / height];
+    let max = [min[0] + GLYPH_PIXELS as f32 / width, min[1] + GLYPH_PIXELS as f32 / height];
+    (min, max)
+}
+
+/// Render the font into a white atlas whose alpha is the glyph coverage
+fn font_atlas() -> image::RgbaImage {
+    let mut atlas = image::RgbaImage::new(ATLAS_COLUMNS * GLYPH_PIXELS, ATLAS_ROWS * GLYPH_PIXELS);
+    for (index, glyph) in FONT.iter().enumerate() {
+        let (column, row) = (index as u32 % ATLAS_COLUMNS, index as u32 / ATLAS_COLUMNS);
+        for (y, bits) in glyph.iter().enumerate() {
+            for x in 0..GLYPH_PIXELS {
+                let alpha = if bits & (1 << x) != 0 { 255 } else { 0 };
+                atlas.put_pixel(
+                    column * GLYPH_PIXELS + x,
+                    row * GLYPH_PIXELS + y as u32,
+                    image::Rgba([255, 255, 255, alpha]),
+                );
+            }
+        }
+    }
+    atlas
+}
+
+/// Vertex of a glyph quad, already in clip space
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+struct TextVertex {
+    position: [f32; 2],
+    uv: [f32; 2],
+    color: [f32; 4],
+}
+
+impl TextVertex {
+    const ATTRIBUTES: [wgpu::VertexAttribute; 3] = wgpu::vertex_attr_array![
+        0 => Float32x2,
+        1 => Float32x2,
+        2 => Float32x4,
+    ];
+    
+    fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<TextVertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+}
+
+const TEXT_SHADER: &str = r#"
+    struct VertexOutput {
+        @builtin(position) clip_position: vec4<f32>,
+        @location(0) uv: vec2<f32>,
+        @location(1) color: vec4<f32>,
+    }
+    
+    @vertex
+    fn vs_main(@location(0) position: vec2<f32>, @location(1) uv: vec2<f32>, @location(2) color: vec4<f32>) -> VertexOutput {
+        var out: VertexOutput;
+        out.clip_position = vec4<f32>(position, 0.0, 1.0);
+        out.uv = uv;
+        out.color = color;
+        return out;
+    }
+    
+    @group(0) @binding(0)
+    var t_font: texture_2d<f32>;
+    @group(0) @binding(1)
+    var s_font: sampler;
+    
+    @fragment
+    fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+        let coverage = textureSample(t_font, s_font, in.uv).a;
+        return vec4<f32>(in.color.rgb, in.color.a * coverage);
+    }
+"#;
+
+/// Batches screen-space text and draws it in one pass
+///
+/// Queue text with [`queue`](Self::queue) during the frame, then
+/// [`render`](Self::render) draws all of it over the target and empties the batch.
+pub struct TextRenderer {
+    quads: Vec<GlyphQuad>,
+    _atlas: Texture,
+    bind_group_layout: wgpu::BindGroupLayout,
+    bind_group: wgpu::BindGroup,
+    shader: wgpu::ShaderModule,
+    pipeline: Option<(wgpu::TextureFormat, wgpu::RenderPipeline)>,
+}
+
+impl TextRenderer {
+    /// Upload the font atlas
+    pub fn new(device: &Device, queue: &Queue) -> Self {
+        let atlas = Texture::from_image(device, queue, &font_atlas(), Some("Font Atlas"))
+            .expect("font atlas is a valid image");
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Font Sampler"),
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+        
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Text Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Text Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&atlas.view) },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(&sampler) },
+            ],
+        });
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Text Shader"),
+            source: wgpu::ShaderSource::Wgsl(TEXT_SHADER.into()),
+        });
+        
+        Self {
+            quads: Vec::new(),
+            _atlas: atlas,
+            bind_group_layout,
+            bind_group,
+            shader,
+            pipeline: None,
+        }
+    }
+    
+    /// Add text to the batch (see [`layout_text`])
+    pub fn queue(&mut self, text: &str, position: (f32, f32), size: f32, color: Color) {
+        self.quads.extend(layout_text(text, position, size, color));
+    }
+    
+    /// Glyph quads waiting to be drawn
+    pub fn queued(&self) -> &[GlyphQuad] {
+        &self.quads
+    }
+    
+    /// Draw the batch over `view`, keeping its contents, then clear the batch
+    ///
+    /// `size` is the target's size in pixels and `format` its color format.
+    pub fn render(
+        &mut self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        view: &TextureView,
+        format: wgpu::TextureFormat,
+        size: (u32, u32),
+    ) {
+        if self.quads.is_empty() {
+            return;
+        }
+        
+        let (width, height) = (size.0.max(1) as f32, size.1.max(1) as f32);
+        let to_clip = |x: f32, y: f32| [x / width * 2.0 - 1.0, 1.0 - y / height * 2.0];
+        let mut vertices = Vec::with_capacity(self.quads.len() * 6);
+        for quad in self.quads.drain(..) {
+            let corner = |x: usize, y: usize| TextVertex {
+                position: to_clip([quad.min[0], quad.max[0]][x], [quad.min[1], quad.max[1]][y]),
+                uv: [[quad.uv_min[0], quad.uv_max[0]][x], [quad.uv_min[1], quad.uv_max[1]][y]],
+                color: quad.color,
+            };
+            vertices.extend([corner(0, 0), corner(0, 1), corner(1, 1), corner(0, 0), corner(1, 1), corner(1, 0)]);
+        }
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Text Vertices"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        
+        if self.pipeline.as_ref().is_none_or(|(built_for, _)| *built_for != format) {
+            self.pipeline = Some((format, self.create_pipeline(device, format)));
+        }
+        let Some((_, pipeline)) = &self.pipeline else {
+            return;
+        };
+        
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Text Pass"),
+            color_attachments: &[Some(color_attachment(view, None, wgpu::LoadOp::Load))],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(0, &self.bind_group, &[]);
+        pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        pass.draw(0..vertices.len() as u32, 0..1);
+    }
+    
+    fn create_pipeline(&self, device: &Device, format: wgpu::TextureFormat) -> wgpu::RenderPipeline {
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Text Pipeline Layout"),
+            bind_group_layouts: &[&self.bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Text Pipeline"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &self.shader,
+                entry_point: VERTEX_ENTRY_POINT,
+                buffers: &[TextVertex::desc()],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &self.shader,
+                entry_point: FRAGMENT_ENTRY_POINT,
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        })
+    }
+}
+
+/// Printable ASCII (`' '` to `'~'`) from font8x8 by Daniel Hepper (public domain)
+///
+/// One byte per row from the top, with the least significant bit the leftmost pixel.
+const FONT: [[u8; 8]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x18, 0x3C, 0x3C, 0x18, 0x18, 0x00, 0x18, 0x00], // '!'
+    [0x36, 0x36, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '"'
+    [0x36, 0x36, 0x7F, 0x36, 0x7F, 0x36, 0x36, 0x00], // '#'
+    [0x0C, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x0C, 0x00], // '$'
+    [0x00, 0x63, 0x33, 0x18, 0x0C, 0x66, 0x63, 0x00], // '%'
+    [0x1C, 0x36, 0x1C, 0x6E, 0x3B, 0x33, 0x6E, 0x00], // '&'
+    [0x06, 0x06, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00], // "'"
+    [0x18, 0x0C, 0x06, 0x06, 0x06, 0x0C, 0x18, 0x00], // '('
+    [0x06, 0x0C, 0x18, 0x18, 0x18, 0x0C, 0x06, 0x00], // ')'
+    [0x00, 0x66, 0x3C, 0xFF, 0x3C, 0x66, 0x00, 0x00], // '*'
+    [0x00, 0x0C, 0x0C, 0x3F, 0x0C, 0x0C, 0x00, 0x00], // '+'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x06], // ','
+    [0x00, 0x00, 0x00, 0x3F, 0x00, 0x00, 0x00, 0x00], // '-'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00], // '.'
+    [0x60, 0x30, 0x18, 0x0C, 0x06, 0x03, 0x01, 0x00], // '/'
+    [0x3E, 0x63, 0x73, 0x7B, 0x6F, 0x67, 0x3E, 0x00], // '0'
+    [0x0C, 0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x3F, 0x00], // '1'
+    [0x1E, 0x33, 0x30, 0x1C, 0x06, 0x33, 0x3F, 0x00], // '2'
+    [0x1E, 0x33, 0x30, 0x1C, 0x30, 0x33, 0x1E, 0x00], // '3'
+    [0x38, 0x3C, 0x36, 0x33, 0x7F, 0x30, 0x78, 0x00], // '4'
+    [0x3F, 0x03, 0x1F, 0x30, 0x30, 0x33, 0x1E, 0x00], // '5'
+    [0x1C, 0x06, 0x03, 0x1F, 0x33, 0x33, 0x1E, 0x00], // '6'
+    [0x3F, 0x33, 0x30, 0x18, 0x0C, 0x0C, 0x0C, 0x00], // '7'
+    [0x1E, 0x33, 0x33, 0x1E, 0x33, 0x33, 0x1E, 0x00], // '8'
+    [0x1E, 0x33, 0x33, 0x3E, 0x30, 0x18, 0x0E, 0x00], // '9'
+    [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x00], // ':'
+    [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x06], // ';'
+    [0x18, 0x0C, 0x06, 0x03, 0x06, 0x0C, 0x18, 0x00], // '<'
+    [0x00, 0x00, 0x3F, 0x00, 0x00, 0x3F, 0x00, 0x00], // '='
+    [0x06, 0x0C, 0x18, 0x30, 0x18, 0x0C, 0x06, 0x00], // '>'
+    [0x1E, 0x33, 0x30, 0x18, 0x0C, 0x00, 0x0C, 0x00], // '?'
+    [0x3E, 0x63, 0x7B, 0x7B, 0x7B, 0x03, 0x1E, 0x00], // '@'
+    [0x0C, 0x1E, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x00], // 'A'
+    [0x3F, 0x66, 0x66, 0x3E, 0x66, 0x66, 0x3F, 0x00], // 'B'
+    [0x3C, 0x66, 0x03, 0x03, 0x03, 0x66, 0x3C, 0x00], // 'C'
+    [0x1F, 0x36, 0x66, 0x66, 0x66, 0x36, 0x1F, 0x00], // 'D'
+    [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x46, 0x7F, 0x00], // 'E'
+    [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x06, 0x0F, 0x00], // 'F'
+    [0x3C, 0x66, 0x03, 0x03, 0x73, 0x66, 0x7C, 0x00], // 'G'
+    [0x33, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x33, 0x00], // 'H'
+    [0x1E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'I'
+    [0x78, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E, 0x00], // 'J'
+    [0x67, 0x66, 0x36, 0x1E, 0x36, 0x66, 0x67, 0x00], // 'K'
+    [0x0F, 0x06, 0x06, 0x06, 0x46, 0x66, 0x7F, 0x00], // 'L'
+    [0x63, 0x77, 0x7F, 0x7F, 0x6B, 0x63, 0x63, 0x00], // 'M'
+    [0x63, 0x67, 0x6F, 0x7B, 0x73, 0x63, 0x63, 0x00], // 'N'
+    [0x1C, 0x36, 0x63, 0x63, 0x63, 0x36, 0x1C, 0x00], // 'O'
+    [0x3F, 0x66, 0x66, 0x3E, 0x06, 0x06, 0x0F, 0x00], // 'P'
+    [0x1E, 0x33, 0x33, 0x33, 0x3B, 0x1E, 0x38, 0x00], // 'Q'
+    [0x3F, 0x66, 0x66, 0x3E, 0x36, 0x66, 0x67, 0x00], // 'R'
+    [0x1E, 0x33, 0x07, 0x0E, 0x38, 0x33, 0x1E, 0x00], // 'S'
+    [0x3F, 0x2D, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'T'
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x3F, 0x00], // 'U'
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00], // 'V'
+    [0x63, 0x63, 0x63, 0x6B, 0x7F, 0x77, 0x63, 0x00], // 'W'
+    [0x63, 0x63, 0x36, 0x1C, 0x1C, 0x36, 0x63, 0x00], // 'X'
+    [0x33, 0x33, 0x33, 0x1E, 0x0C, 0x0C, 0x1E, 0x00], // 'Y'
+    [0x7F, 0x63, 0x31, 0x18, 0x4C, 0x66, 0x7F, 0x00], // 'Z'
+    [0x1E, 0x06, 0x06, 0x06, 0x06, 0x06, 0x1E, 0x00], // '['
+    [0x03, 0x06, 0x0C, 0x18, 0x30, 0x60, 0x40, 0x00], // '\\'
+    [0x1E, 0x18, 0x18, 0x18, 0x18, 0x18, 0x1E, 0x00], // ']'
+    [0x08, 0x1C, 0x36, 0x63, 0x00, 0x00, 0x00, 0x00], // '^'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF], // '_'
+    [0x0C, 0x0C, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00], // '`'
+    [0x00, 0x00, 0x1E, 0x30, 0x3E, 0x33, 0x6E, 0x00], // 'a'
+    [0x07, 0x06, 0x06, 0x3E, 0x66, 0x66, 0x3B, 0x00], // 'b'
+    [0x00, 0x00, 0x1E, 0x33, 0x03, 0x33, 0x1E, 0x00], // 'c'
+    [0x38, 0x30, 0x30, 0x3e, 0x33, 0x33, 0x6E, 0x00], // 'd'
+    [0x00, 0x00, 0x1E, 0x33, 0x3f, 0x03, 0x1E, 0x00], // 'e'
+    [0x1C, 0x36, 0x06, 0x0f, 0x06, 0x06, 0x0F, 0x00], // 'f'
+    [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x1F], // 'g'
+    [0x07, 0x06, 0x36, 0x6E, 0x66, 0x66, 0x67, 0x00], // 'h'
+    [0x0C, 0x00, 0x0E, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'i'
+    [0x30, 0x00, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E], // 'j'
+    [0x07, 0x06, 0x66, 0x36, 0x1E, 0x36, 0x67, 0x00], // 'k'
+    [0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'l'
+    [0x00, 0x00, 0x33, 0x7F, 0x7F, 0x6B, 0x63, 0x00], // 'm'
+    [0x00, 0x00, 0x1F, 0x33, 0x33, 0x33, 0x33, 0x00], // 'n'
+    [0x00, 0x00, 0x1E, 0x33, 0x33, 0x33, 0x1E, 0x00], // 'o'
+    [0x00, 0x00, 0x3B, 0x66, 0x66, 0x3E, 0x06, 0x0F], // 'p'
+    [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x78], // 'q'
+    [0x00, 0x00, 0x3B, 0x6E, 0x66, 0x06, 0x0F, 0x00], // 'r'
+    [0x00, 0x00, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x00], // 's'
+    [0x08, 0x0C, 0x3E, 0x0C, 0x0C, 0x2C, 0x18, 0x00], // 't'
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x33, 0x6E, 0x00], // 'u'
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00], // 'v'
+    [0x00, 0x00, 0x63, 0x6B, 0x7F, 0x7F, 0x36, 0x00], // 'w'
+    [0x00, 0x00, 0x63, 0x36, 0x1C, 0x36, 0x63, 0x00], // 'x'
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x3E, 0x30, 0x1F], // 'y'
+    [0x00, 0x00, 0x3F, 0x19, 0x0C, 0x26, 0x3F, 0x00], // 'z'
+    [0x38, 0x0C, 0x0C, 0x07, 0x0C, 0x0C, 0x38, 0x00], // '{'
+    [0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00], // '|'
+    [0x07, 0x0C, 0x0C, 0x38, 0x0C, 0x0C, 0x07, 0x00], // '}'
+    [0x6E, 0x3B, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '~'
+];
@@ -12,6 +12,9 @@ pub struct Texture {
 }
 
 impl Texture {
+    /// Format used for combined depth/stencil attachments
+    pub const DEPTH_STENCIL_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth24PlusStencil8;
+    
     /// Create a new texture from image data
     pub fn from_bytes(
         device: &Device,
@@ -130,6 +133,70 @@ impl Texture {
         }
     }
     
+    /// Create a combined depth/stencil texture for portal masking
+    pub fn create_depth_stencil_texture(
+        device: &Device,
+        config: &wgpu::SurfaceConfiguration,
+        sample_count: u32,
+        label: &str,
+    ) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width: config.width,
+                height: config.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: Self::DEPTH_STENCIL_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor::default());
+        
+        Self {
+            texture,
+            view,
+            sampler,
+            size: (config.width, config.height),
+        }
+    }
+    
+    /// Create a multisampled color target to be resolved into the surface
+    pub fn create_msaa_texture(
+        device: &Device,
+        config: &wgpu::SurfaceConfiguration,
+        sample_count: u32,
+        label: &str,
+    ) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width: config.width,
+                height: config.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor::default());
+        
+        Self {
+            texture,
+            view,
+            sampler,
+            size: (config.width, config.height),
+        }
+    }
+    
     /// Create a solid color texture
     pub fn from_color(
         device: &Device,
@@ -1,7 +1,10 @@
 //! Input handling for the non-Euclidean engine
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::Path;
+use std::time::{Duration, Instant};
 use cgmath::{Point2, Vector2};
+use serde::{Serialize, Deserialize};
 
 /// Input event types
 #[derive(Debug, Clone)]
@@ -11,14 +14,46 @@ pub enum InputEvent {
     MouseButtonPressed(MouseButton),
     MouseButtonReleased(MouseButton),
     MouseMoved(f32, f32),
+    /// Unaccelerated mouse motion, reported even when the cursor is grabbed
+    MouseMotionRaw(f32, f32),
+    /// Vertical scroll in lines, positive away from the user
     MouseWheel(f32),
     GamepadButtonPressed(GamepadButton),
     GamepadButtonReleased(GamepadButton),
     GamepadAxisMoved(GamepadAxis, f32),
 }
 
+/// Pixels of touchpad scrolling counted as one line of wheel scrolling
+pub const PIXELS_PER_LINE: f32 = 20.0;
+
+impl From<winit::event::MouseScrollDelta> for InputEvent {
+    /// Convert a winit scroll to `MouseWheel` lines, scaling pixel deltas by [`PIXELS_PER_LINE`]
+    fn from(delta: winit::event::MouseScrollDelta) -> Self {
+        match delta {
+            winit::event::MouseScrollDelta::LineDelta(_, y) => InputEvent::MouseWheel(y),
+            winit::event::MouseScrollDelta::PixelDelta(position) => {
+                InputEvent::MouseWheel(position.y as f32 / PIXELS_PER_LINE)
+            }
+        }
+    }
+}
+
+impl InputEvent {
+    /// Convert a winit device event, if it is one the input manager tracks
+    ///
+    /// Feed `Event::DeviceEvent`s through this for mouse-look with a grabbed cursor.
+    pub fn from_device_event(event: &winit::event::DeviceEvent) -> Option<Self> {
+        match event {
+            winit::event::DeviceEvent::MouseMotion { delta } => {
+                Some(InputEvent::MouseMotionRaw(delta.0 as f32, delta.1 as f32))
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Keyboard key codes
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum KeyCode {
     A, B, C, D, E, F, G, H, I, J, K, L, M,
     N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
@@ -31,7 +66,7 @@ pub enum KeyCode {
 }
 
 /// Mouse buttons
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum MouseButton {
     Left,
     Right,
@@ -41,7 +76,7 @@ pub enum MouseButton {
 }
 
 /// Gamepad buttons
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum GamepadButton {
     A, B, X, Y,
     LeftBumper, RightBumper,
@@ -52,7 +87,7 @@ pub enum GamepadButton {
 }
 
 /// Gamepad axes
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum GamepadAxis {
     LeftStickX,
     LeftStickY,
@@ -68,9 +103,89 @@ pub struct InputManager {
     pressed_mouse_buttons: HashSet<MouseButton>,
     mouse_position: Point2<f32>,
     mouse_delta: Vector2<f32>,
+    scroll_delta: f32,
+    raw_mouse_delta: Vector2<f32>,
     gamepad_buttons: HashSet<GamepadButton>,
     gamepad_axes: HashMap<GamepadAxis, f32>,
     events: Vec<InputEvent>,
+    now: Instant,
+    /// Set once `tick` is called; until then `poll_events` advances the clock
+    manual_clock: bool,
+    key_press_times: HashMap<KeyCode, Instant>,
+    /// Times of the previous and latest press of each mouse button
+    mouse_press_times: HashMap<MouseButton, (Option<Instant>, Instant)>,
+    /// Mouse buttons pressed since the last tick
+    mouse_pressed_this_tick: HashSet<MouseButton>,
+    /// Recorded frames still to be played back, replacing live events
+    replay: Option<VecDeque<InputSnapshot>>,
+}
+
+/// Serializable copy of the input state at one frame
+///
+/// Press times are not included, so `key_held_for` and double clicks count
+/// from when the snapshot was applied.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputSnapshot {
+    pub keys: BTreeSet<KeyCode>,
+    pub mouse_buttons: BTreeSet<MouseButton>,
+    pub gamepad_buttons: BTreeSet<GamepadButton>,
+    pub gamepad_axes: BTreeMap<GamepadAxis, f32>,
+    pub mouse_position: [f32; 2],
+    pub mouse_delta: [f32; 2],
+    pub raw_mouse_delta: [f32; 2],
+    pub scroll_delta: f32,
+}
+
+/// Log of per-frame input snapshots for replay
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct InputRecorder {
+    frames: Vec<InputSnapshot>,
+}
+
+impl InputRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Append the current state of `input`; call once per frame after processing events
+    pub fn record(&mut self, input: &InputManager) {
+        self.frames.push(input.snapshot());
+    }
+    
+    pub fn frames(&self) -> &[InputSnapshot] {
+        &self.frames
+    }
+    
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+    
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+    
+    /// Load a recording from a RON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(ron::from_str(&std::fs::read_to_string(path)?)?)
+    }
+    
+    /// Save the recording to a RON file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path, ron::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+impl Default for InputManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl InputManager {
@@ -81,23 +196,69 @@ impl InputManager {
             pressed_mouse_buttons: HashSet::new(),
             mouse_position: Point2::new(0.0, 0.0),
             mouse_delta: Vector2::new(0.0, 0.0),
+            scroll_delta: 0.0,
+            raw_mouse_delta: Vector2::new(0.0, 0.0),
             gamepad_buttons: HashSet::new(),
             gamepad_axes: HashMap::new(),
             events: Vec::new(),
+            now: Instant::now(),
+            manual_clock: false,
+            key_press_times: HashMap::new(),
+            mouse_press_times: HashMap::new(),
+            mouse_pressed_this_tick: HashSet::new(),
+            replay: None,
         }
     }
     
-    /// Process an input event
+    /// Advance the input clock; call once per frame before processing events
+    ///
+    /// Events processed afterwards are timestamped with `now`. While replaying,
+    /// this applies the next recorded frame and ends the replay after the last.
+    ///
+    /// Optional: without it `poll_events` starts each frame at `Instant::now()`.
+    /// Once called, the clock only moves when `tick` is called, so keep calling it.
+    pub fn tick(&mut self, now: Instant) {
+        self.manual_clock = true;
+        self.begin_frame(now);
+    }
+    
+    fn begin_frame(&mut self, now: Instant) {
+        self.now = now;
+        self.mouse_pressed_this_tick.clear();
+        self.advance_replay();
+    }
+    
+    /// Apply the next recorded frame, ending the replay after the last
+    fn advance_replay(&mut self) {
+        if let Some(frames) = self.replay.as_mut() {
+            match frames.pop_front() {
+                Some(snapshot) => self.apply_snapshot(&snapshot),
+                None => self.replay = None,
+            }
+        }
+    }
+    
+    /// Process an input event; live events are ignored while replaying
     pub fn process_event(&mut self, event: InputEvent) {
+        if self.replay.is_some() {
+            return;
+        }
+        
         match event.clone() {
             InputEvent::KeyPressed(key) => {
                 self.pressed_keys.insert(key);
+                // Key repeat keeps the original press time
+                self.key_press_times.entry(key).or_insert(self.now);
             }
             InputEvent::KeyReleased(key) => {
                 self.pressed_keys.remove(&key);
+                self.key_press_times.remove(&key);
             }
             InputEvent::MouseButtonPressed(button) => {
                 self.pressed_mouse_buttons.insert(button);
+                let previous = self.mouse_press_times.get(&button).map(|&(_, last)| last);
+                self.mouse_press_times.insert(button, (previous, self.now));
+                self.mouse_pressed_this_tick.insert(button);
             }
             InputEvent::MouseButtonReleased(button) => {
                 self.pressed_mouse_buttons.remove(&button);
@@ -107,6 +268,12 @@ impl InputManager {
                 self.mouse_delta = new_pos - self.mouse_position;
                 self.mouse_position = new_pos;
             }
+            InputEvent::MouseMotionRaw(dx, dy) => {
+                self.raw_mouse_delta += Vector2::new(dx, dy);
+            }
+            InputEvent::MouseWheel(delta) => {
+                self.scroll_delta += delta;
+            }
             InputEvent::GamepadButtonPressed(button) => {
                 self.gamepad_buttons.insert(button);
             }
@@ -116,17 +283,29 @@ impl InputManager {
             InputEvent::GamepadAxisMoved(axis, value) => {
                 self.gamepad_axes.insert(axis, value);
             }
-            _ => {}
         }
         
         self.events.push(event);
     }
     
-    /// Poll and return all pending events
+    /// Events processed since the last `poll_events`, without consuming them
+    pub fn pending_events(&self) -> &[InputEvent] {
+        &self.events
+    }
+    
+    /// Poll and return all pending events, ending the frame
+    ///
+    /// Unless the clock is driven by [`tick`](Self::tick), this also starts the
+    /// next frame at `Instant::now()`.
     pub fn poll_events(&mut self) -> Vec<InputEvent> {
         let events = self.events.clone();
         self.events.clear();
         self.mouse_delta = Vector2::new(0.0, 0.0);
+        self.scroll_delta = 0.0;
+        self.raw_mouse_delta = Vector2::new(0.0, 0.0);
+        if !self.manual_clock {
+            self.begin_frame(Instant::now());
+        }
         events
     }
     
@@ -140,6 +319,25 @@ impl InputManager {
         self.pressed_mouse_buttons.contains(&button)
     }
     
+    /// Check if a button was pressed this tick within `window` of its previous press
+    pub fn mouse_double_click(&self, button: MouseButton, window: Duration) -> bool {
+        if !self.mouse_pressed_this_tick.contains(&button) {
+            return false;
+        }
+        
+        match self.mouse_press_times.get(&button) {
+            Some(&(Some(previous), last)) => last.duration_since(previous) <= window,
+            _ => false,
+        }
+    }
+    
+    /// How long a key has been continuously held, or `None` if it is up
+    pub fn key_held_for(&self, key: KeyCode) -> Option<Duration> {
+        self.key_press_times
+            .get(&key)
+            .map(|&pressed| self.now.saturating_duration_since(pressed))
+    }
+    
     /// Get current mouse position
     pub fn mouse_position(&self) -> Point2<f32> {
         self.mouse_position
@@ -150,6 +348,19 @@ impl InputManager {
         self.mouse_delta
     }
     
+    /// Raw mouse motion accumulated since the last `poll_events`
+    ///
+    /// Unlike `mouse_delta` this keeps counting when the cursor is grabbed and
+    /// can't move, so use it for mouse-look.
+    pub fn raw_mouse_delta(&self) -> Vector2<f32> {
+        self.raw_mouse_delta
+    }
+    
+    /// Lines scrolled since the last `poll_events`
+    pub fn scroll_delta(&self) -> f32 {
+        self.scroll_delta
+    }
+    
     /// Check if a gamepad button is pressed
     pub fn is_gamepad_button_pressed(&self, button: GamepadButton) -> bool {
         self.gamepad_buttons.contains(&button)
@@ -160,22 +371,103 @@ impl InputManager {
         self.gamepad_axes.get(&axis).copied().unwrap_or(0.0)
     }
     
+    /// Copy of the current input state
+    pub fn snapshot(&self) -> InputSnapshot {
+        InputSnapshot {
+            keys: self.pressed_keys.iter().copied().collect(),
+            mouse_buttons: self.pressed_mouse_buttons.iter().copied().collect(),
+            gamepad_buttons: self.gamepad_buttons.iter().copied().collect(),
+            gamepad_axes: self.gamepad_axes.iter().map(|(&axis, &value)| (axis, value)).collect(),
+            mouse_position: self.mouse_position.into(),
+            mouse_delta: self.mouse_delta.into(),
+            raw_mouse_delta: self.raw_mouse_delta.into(),
+            scroll_delta: self.scroll_delta,
+        }
+    }
+    
+    /// Overwrite the input state with a snapshot
+    ///
+    /// Keys and mouse buttons that become pressed are timestamped with the
+    /// current tick, as if their press events had just arrived.
+    pub fn apply_snapshot(&mut self, snapshot: &InputSnapshot) {
+        self.key_press_times.retain(|key, _| snapshot.keys.contains(key));
+        for &key in &snapshot.keys {
+            self.key_press_times.entry(key).or_insert(self.now);
+        }
+        
+        for &button in &snapshot.mouse_buttons {
+            if !self.pressed_mouse_buttons.contains(&button) {
+                let previous = self.mouse_press_times.get(&button).map(|&(_, last)| last);
+                self.mouse_press_times.insert(button, (previous, self.now));
+                self.mouse_pressed_this_tick.insert(button);
+            }
+        }
+        
+        self.pressed_keys = snapshot.keys.iter().copied().collect();
+        self.pressed_mouse_buttons = snapshot.mouse_buttons.iter().copied().collect();
+        self.gamepad_buttons = snapshot.gamepad_buttons.iter().copied().collect();
+        self.gamepad_axes = snapshot.gamepad_axes.iter().map(|(&axis, &value)| (axis, value)).collect();
+        self.mouse_position = snapshot.mouse_position.into();
+        self.mouse_delta = snapshot.mouse_delta.into();
+        self.raw_mouse_delta = snapshot.raw_mouse_delta.into();
+        self.scroll_delta = snapshot.scroll_delta;
+    }
+    
+    /// Play back recorded frames instead of live events
+    ///
+    /// Frames advance wherever the clock does: one per `tick`, or one per
+    /// `poll_events` when `tick` is never called.
+    pub fn start_replay(&mut self, frames: impl IntoIterator<Item = InputSnapshot>) {
+        self.replay = Some(frames.into_iter().collect());
+    }
+    
+    /// Stop a replay and return to live events, keeping the current state
+    pub fn stop_replay(&mut self) {
+        self.replay = None;
+    }
+    
+    pub fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+    
     /// Clear all input state
     pub fn clear(&mut self) {
         self.pressed_keys.clear();
         self.pressed_mouse_buttons.clear();
         self.gamepad_buttons.clear();
         self.events.clear();
+        self.key_press_times.clear();
+        self.mouse_press_times.clear();
+        self.mouse_pressed_this_tick.clear();
         self.mouse_delta = Vector2::new(0.0, 0.0);
+        self.scroll_delta = 0.0;
+        self.raw_mouse_delta = Vector2::new(0.0, 0.0);
     }
 }
 
 /// Input action mapping for gameplay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct InputAction {
+    /// Taken from the owning `InputMap` key when deserialized
+    #[serde(skip)]
     pub name: String,
     pub keys: Vec<KeyCode>,
     pub mouse_buttons: Vec<MouseButton>,
     pub gamepad_buttons: Vec<GamepadButton>,
+    pub axes: Vec<GamepadAxis>,
+    /// Key pairs contributing +1 / -1 to the axis value
+    pub key_axes: Vec<(KeyCode, KeyCode)>,
+    /// Multiplier applied to gamepad axis values
+    pub axis_scale: f32,
+    /// Gamepad axis magnitudes below this are treated as zero
+    pub deadzone: f32,
+}
+
+impl Default for InputAction {
+    fn default() -> Self {
+        Self::new("")
+    }
 }
 
 impl InputAction {
@@ -185,6 +477,10 @@ impl InputAction {
             keys: Vec::new(),
             mouse_buttons: Vec::new(),
             gamepad_buttons: Vec::new(),
+            axes: Vec::new(),
+            key_axes: Vec::new(),
+            axis_scale: 1.0,
+            deadzone: 0.15,
         }
     }
     
@@ -203,6 +499,53 @@ impl InputAction {
         self
     }
     
+    pub fn with_axis(mut self, axis: GamepadAxis) -> Self {
+        self.axes.push(axis);
+        self
+    }
+    
+    pub fn with_key_axis(mut self, positive: KeyCode, negative: KeyCode) -> Self {
+        self.key_axes.push((positive, negative));
+        self
+    }
+    
+    pub fn with_axis_scale(mut self, scale: f32) -> Self {
+        self.axis_scale = scale;
+        self
+    }
+    
+    pub fn with_deadzone(mut self, deadzone: f32) -> Self {
+        self.deadzone = deadzone;
+        self
+    }
+    
+    /// Combined analog value of the bound axes and key pairs, clamped to [-1, 1]
+    ///
+    /// Gamepad values inside the deadzone read as zero; the remaining range is
+    /// rescaled so output still starts at zero at the deadzone edge.
+    pub fn axis_value(&self, input: &InputManager) -> f32 {
+        let mut value = 0.0;
+        
+        for axis in &self.axes {
+            let raw = input.gamepad_axis(*axis);
+            if raw.abs() > self.deadzone {
+                let magnitude = (raw.abs() - self.deadzone) / (1.0 - self.deadzone).max(f32::EPSILON);
+                value += magnitude.min(1.0) * raw.signum() * self.axis_scale;
+            }
+        }
+        
+        for (positive, negative) in &self.key_axes {
+            if input.is_key_pressed(*positive) {
+                value += 1.0;
+            }
+            if input.is_key_pressed(*negative) {
+                value -= 1.0;
+            }
+        }
+        
+        value.clamp(-1.0, 1.0)
+    }
+    
     pub fn is_pressed(&self, input: &InputManager) -> bool {
         for key in &self.keys {
             if input.is_key_pressed(*key) {
@@ -224,4 +567,170 @@ impl InputAction {
         
         false
     }
-}
\ No newline at end of file
+}
+
+/// A single physical input that can be bound to an action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+    Axis(GamepadAxis),
+    /// Positive and negative keys of an analog axis
+    KeyAxis(KeyCode, KeyCode),
+}
+
+impl InputAction {
+    /// Add a binding if the action does not already have it
+    pub fn bind(&mut self, binding: Binding) {
+        fn push_unique<T: PartialEq>(list: &mut Vec<T>, value: T) {
+            if !list.contains(&value) {
+                list.push(value);
+            }
+        }
+        
+        match binding {
+            Binding::Key(key) => push_unique(&mut self.keys, key),
+            Binding::Mouse(button) => push_unique(&mut self.mouse_buttons, button),
+            Binding::Gamepad(button) => push_unique(&mut self.gamepad_buttons, button),
+            Binding::Axis(axis) => push_unique(&mut self.axes, axis),
+            Binding::KeyAxis(positive, negative) => push_unique(&mut self.key_axes, (positive, negative)),
+        }
+    }
+    
+    /// Remove a binding
+    pub fn unbind(&mut self, binding: Binding) {
+        match binding {
+            Binding::Key(key) => self.keys.retain(|k| *k != key),
+            Binding::Mouse(button) => self.mouse_buttons.retain(|b| *b != button),
+            Binding::Gamepad(button) => self.gamepad_buttons.retain(|b| *b != button),
+            Binding::Axis(axis) => self.axes.retain(|a| *a != axis),
+            Binding::KeyAxis(positive, negative) => self.key_axes.retain(|pair| *pair != (positive, negative)),
+        }
+    }
+    
+    /// All bindings of the action
+    pub fn bindings(&self) -> Vec<Binding> {
+        self.keys.iter().map(|&k| Binding::Key(k))
+            .chain(self.mouse_buttons.iter().map(|&b| Binding::Mouse(b)))
+            .chain(self.gamepad_buttons.iter().map(|&b| Binding::Gamepad(b)))
+            .chain(self.axes.iter().map(|&a| Binding::Axis(a)))
+            .chain(self.key_axes.iter().map(|&(p, n)| Binding::KeyAxis(p, n)))
+            .collect()
+    }
+}
+
+/// Named, rebindable set of input actions
+///
+/// Serializes to RON as a map from action name to its bindings, so a settings
+/// file can be merged over the defaults built in code.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct InputMap {
+    actions: BTreeMap<String, InputAction>,
+}
+
+impl InputMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Add or replace an action
+    pub fn with_action(mut self, action: InputAction) -> Self {
+        self.insert(action);
+        self
+    }
+    
+    /// Add or replace an action
+    pub fn insert(&mut self, action: InputAction) {
+        self.actions.insert(action.name.clone(), action);
+    }
+    
+    pub fn action(&self, name: &str) -> Option<&InputAction> {
+        self.actions.get(name)
+    }
+    
+    pub fn actions(&self) -> impl Iterator<Item = &InputAction> {
+        self.actions.values()
+    }
+    
+    /// Bind an input to an action, creating the action if needed
+    pub fn bind(&mut self, action: &str, binding: Binding) {
+        self.actions
+            .entry(action.to_string())
+            .or_insert_with(|| InputAction::new(action))
+            .bind(binding);
+    }
+    
+    /// Remove a binding from an action
+    pub fn unbind(&mut self, action: &str, binding: Binding) {
+        if let Some(action) = self.actions.get_mut(action) {
+            action.unbind(binding);
+        }
+    }
+    
+    /// Check if any input bound to the action is pressed; unknown actions are never pressed
+    pub fn pressed(&self, action: &str, input: &InputManager) -> bool {
+        self.actions.get(action).is_some_and(|a| a.is_pressed(input))
+    }
+    
+    /// Analog value of the action; unknown actions read as zero
+    pub fn axis(&self, action: &str, input: &InputManager) -> f32 {
+        self.actions.get(action).map_or(0.0, |a| a.axis_value(input))
+    }
+    
+    /// Replace actions with those defined in `overrides`, keeping the rest
+    pub fn merge(&mut self, overrides: InputMap) {
+        self.actions.extend(overrides.actions);
+    }
+    
+    /// Physical inputs bound to more than one action, with the actions using them
+    ///
+    /// Keys of a key axis count as individual key bindings.
+    pub fn conflicts(&self) -> Vec<(Binding, Vec<String>)> {
+        let mut users: Vec<(Binding, Vec<String>)> = Vec::new();
+        
+        for (name, action) in &self.actions {
+            for binding in action.bindings() {
+                let physical = match binding {
+                    Binding::KeyAxis(positive, negative) => vec![Binding::Key(positive), Binding::Key(negative)],
+                    other => vec![other],
+                };
+                for input in physical {
+                    match users.iter_mut().find(|(b, _)| *b == input) {
+                        Some((_, names)) if !names.contains(name) => names.push(name.clone()),
+                        Some(_) => {}
+                        None => users.push((input, vec![name.clone()])),
+                    }
+                }
+            }
+        }
+        
+        users.into_iter().filter(|(_, names)| names.len() > 1).collect()
+    }
+    
+    /// Parse a map from RON text
+    pub fn from_ron(text: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut map: InputMap = ron::from_str(text)?;
+        for (name, action) in map.actions.iter_mut() {
+            action.name = name.clone();
+        }
+        Ok(map)
+    }
+    
+    /// Serialize the map to RON text
+    pub fn to_ron(&self) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?)
+    }
+    
+    /// Load a map from a RON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::from_ron(&std::fs::read_to_string(path)?)
+    }
+    
+    /// Save the map to a RON file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path, self.to_ron()?)?;
+        Ok(())
+    }
+}
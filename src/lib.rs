@@ -18,43 +18,43 @@ pub mod audio;
 pub mod quickstart;
 
 // Re-export commonly used types
-pub use core::{Engine, EngineConfig, GameState};
-pub use ecs::{World, Entity, Component, Velocity, Renderable, Transform as EcsTransform, TransformSystem, PortalTransitionSystem};
-pub use graphics::{Renderer, RenderContext, Color, Mesh, Vertex, Camera, camera::FPSCameraController};
-pub use input::{InputManager, InputEvent, KeyCode, MouseButton, GamepadButton, GamepadAxis};
+pub use core::{Engine, EngineConfig, EngineConfigBuilder, GameState};
+pub use ecs::{World, WorldSnapshot, Entity, Component, Bundle, Stage, Velocity, Renderable, Transform as EcsTransform, TransformSystem, PortalTransitionSystem};
+pub use graphics::{Renderer, RenderContext, Color, Mesh, Vertex, Camera, camera::{FPSCameraController, OrbitCameraController, CameraRig}};
+pub use input::{InputManager, InputEvent, InputSnapshot, InputRecorder, KeyCode, MouseButton, GamepadButton, GamepadAxis};
 pub use math::{Vec2, Vec3, Mat4, Transform};
-pub use resources::{ResourceManager, AssetLoader};
-pub use time::{Time, Timer};
-pub use window::{Window, WindowBuilder, WindowEvent};
+pub use resources::{ResourceManager, ResourceError, AssetLoader};
+pub use time::{Time, Timer, Stopwatch};
+pub use window::{Window, WindowBuilder, WindowEvent, WindowIcon, CursorIcon};
 pub use scoring::{ScoreTracker, ScoreEvent, HudData};
 pub use collision::{AABB, SphereCollider, Ray, RayHit, Collider, CollisionWorld};
-pub use audio::AudioEngine;
+pub use audio::{AudioEngine, AudioParams};
 
 // Prelude module for easy imports
 pub mod prelude {
     pub use crate::core::{Engine, EngineConfig, GameState};
-    pub use crate::ecs::{World, Entity, Component, Velocity, Renderable,
+    pub use crate::ecs::{World, Entity, Component, Bundle, Stage, Velocity, Renderable,
                          Transform as EcsTransform, TransformSystem, PortalTransitionSystem};
     pub use crate::graphics::{Renderer, RenderContext, Color, Mesh, Vertex,
-                              Camera, camera::FPSCameraController};
-    pub use crate::input::{InputManager, InputEvent, KeyCode, MouseButton, GamepadButton, GamepadAxis};
+                              Camera, camera::{FPSCameraController, OrbitCameraController, CameraRig}};
+    pub use crate::input::{InputManager, InputEvent, InputSnapshot, InputRecorder, KeyCode, MouseButton, GamepadButton, GamepadAxis};
     pub use crate::math::{Vec2, Vec3, Mat4, Transform};
-    pub use crate::resources::{ResourceManager, AssetLoader};
-    pub use crate::time::{Time, Timer};
+    pub use crate::resources::{ResourceManager, ResourceError, AssetLoader};
+    pub use crate::time::{Time, Timer, Stopwatch};
     pub use crate::window::{Window, WindowBuilder, WindowEvent};
     pub use crate::scoring::{ScoreTracker, ScoreEvent, HudData};
     pub use crate::collision::{AABB, SphereCollider, Ray, RayHit, Collider, CollisionWorld};
-    pub use crate::audio::AudioEngine;
+    pub use crate::audio::{AudioEngine, AudioParams};
     pub use crate::manifold::{Manifold, Chart, ChartId, Portal, PortalId,
                               GeometryType, MetricTensor, Geodesic, ManifoldPosition};
     pub use cgmath::{Point3, Vector3, Quaternion};
 }
 pub use manifold::{
-    Manifold, ManifoldPosition, ManifoldOrientation,
+    Manifold, ManifoldPosition, ManifoldOrientation, ManifoldWarning, ManifoldRay, RaySegment,
     Chart, ChartId, LocalCoordinate,
     Portal, PortalId,
     Geodesic, GeodesicPath,
-    Metric, MetricTensor, GeometryType,
+    Metric, MetricTensor, GeometryType, CustomMetricRegistry,
 };
 pub use manifold::geodesic::GeodesicRay;
 
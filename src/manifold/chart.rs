@@ -1,10 +1,12 @@
 //! Local coordinate charts for manifold patches
 
-use cgmath::{Point3, Vector3, Matrix4, InnerSpace, EuclideanSpace, SquareMatrix};
-use super::{GeodesicPath, Metric, GeometryType};
+use cgmath::{Point2, Point3, Vector3, Matrix4, InnerSpace, EuclideanSpace, SquareMatrix};
+use serde::{Serialize, Deserialize};
+use super::{GeodesicPath, Metric, MetricTensor, GeometryType};
+use super::hyperboloid::{self, HyperbolicModel};
 
 /// Unique identifier for a chart
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ChartId(pub u32);
 
 /// Local coordinates within a chart
@@ -26,7 +28,7 @@ impl LocalCoordinate {
 }
 
 /// A chart representing a local coordinate patch in the manifold
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Chart {
     id: ChartId,
     geometry: GeometryType,
@@ -36,14 +38,14 @@ pub struct Chart {
 }
 
 /// Bounds of a chart in local coordinates
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChartBounds {
     pub min: Point3<f32>,
     pub max: Point3<f32>,
     pub wrap_mode: WrapMode,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum WrapMode {
     None,           // No wrapping
     Periodic,       // Wrap like a torus
@@ -102,21 +104,66 @@ impl Chart {
         &self.metric
     }
     
+    /// Replace the metric
+    pub fn set_metric(&mut self, metric: Metric) {
+        self.metric = metric;
+    }
+    
+    /// Center and radius of the sphere spherical charts are bounded by
+    ///
+    /// Centered in the bounds box, with a radius of half its x extent.
+    fn bounding_sphere(&self) -> (Point3<f32>, f32) {
+        let center = self.bounds.min.midpoint(self.bounds.max);
+        (center, (self.bounds.max.x - self.bounds.min.x) / 2.0)
+    }
+    
+    /// Whether local coordinates are on the hyperboloid rather than the Poincaré disk
+    fn uses_hyperboloid(&self) -> bool {
+        self.geometry == GeometryType::Hyperbolic
+            && self.metric.parameters.hyperbolic_model == HyperbolicModel::Hyperboloid
+    }
+    
+    /// Get the bounds and wrap mode
+    pub fn bounds(&self) -> &ChartBounds {
+        &self.bounds
+    }
+    
+    /// Replace the bounds and wrap mode
+    pub fn set_bounds(&mut self, bounds: ChartBounds) {
+        self.bounds = bounds;
+    }
+    
+    /// Get the affine transform placing the chart in the world
+    pub fn transform(&self) -> Matrix4<f32> {
+        self.transform
+    }
+    
+    /// Replace the affine transform placing the chart in the world
+    pub fn set_transform(&mut self, transform: Matrix4<f32>) {
+        self.transform = transform;
+    }
+    
     /// Convert local coordinates to world coordinates
+    ///
+    /// The geometry-specific step comes first (projecting onto the sphere,
+    /// keeping inside or projecting onto the Poincaré disk), then the chart's
+    /// transform places the result in the world, for every geometry.
     pub fn to_world(&self, local: LocalCoordinate) -> Point3<f32> {
         let point = local.to_point();
         
-        match self.geometry {
-            GeometryType::Euclidean => {
-                // Simple transformation
-                Point3::from_homogeneous(self.transform * point.to_homogeneous())
-            }
+        let projected = match self.geometry {
+            GeometryType::Euclidean | GeometryType::Custom => point,
             GeometryType::Spherical => {
                 // Project onto sphere
                 let normalized = Vector3::new(point.x, point.y, point.z).normalize();
                 let radius = 10.0; // Default sphere radius
                 Point3::from_vec(normalized * radius)
             }
+            GeometryType::Hyperbolic if self.uses_hyperboloid() => {
+                // Project onto the Poincaré disk for rendering
+                let disk = hyperboloid::hyperboloid_to_poincare(hyperboloid::lift(point.x, point.y));
+                Point3::new(disk.x, disk.y, point.z)
+            }
             GeometryType::Hyperbolic => {
                 // Poincaré disk model
                 let r = (point.x * point.x + point.y * point.y).sqrt();
@@ -127,13 +174,16 @@ impl Chart {
                     point
                 }
             }
-            GeometryType::Custom => {
-                Point3::from_homogeneous(self.transform * point.to_homogeneous())
-            }
-        }
+        };
+        
+        Point3::from_homogeneous(self.transform * projected.to_homogeneous())
     }
     
     /// Convert world coordinates to local coordinates
+    ///
+    /// Undoes `to_world` in reverse order: the inverse transform, then the
+    /// inverse of the geometry-specific step. Spherical charts return the
+    /// direction on the unit sphere, since projecting discards the distance.
     pub fn to_local(&self, world: Point3<f32>) -> LocalCoordinate {
         let inverse = self.transform.invert().unwrap_or(Matrix4::from_scale(1.0));
         let local = Point3::from_homogeneous(inverse * world.to_homogeneous());
@@ -142,16 +192,16 @@ impl Chart {
         let adjusted = match self.geometry {
             GeometryType::Spherical => {
                 // Inverse spherical projection
-                let radius = (world.x * world.x + world.y * world.y + world.z * world.z).sqrt();
+                let radius = local.to_vec().magnitude();
                 if radius > 0.0 {
-                    Point3::new(world.x / radius, world.y / radius, world.z / radius)
+                    local / radius
                 } else {
                     local
                 }
             }
-            GeometryType::Hyperbolic => {
-                // Inverse Poincaré disk
-                local // Simplified - proper inverse Klein model would go here
+            GeometryType::Hyperbolic if self.uses_hyperboloid() => {
+                let lifted = hyperboloid::poincare_to_hyperboloid(Point2::new(local.x, local.y));
+                Point3::new(lifted.x, lifted.y, local.z)
             }
             _ => local,
         };
@@ -170,9 +220,12 @@ impl Chart {
                 point.z >= self.bounds.min.z && point.z <= self.bounds.max.z
             }
             WrapMode::Spherical => {
-                let r = (point.x * point.x + point.y * point.y + point.z * point.z).sqrt();
-                r <= 1.0
+                let (center, radius) = self.bounding_sphere();
+                // Allow for rounding on points `wrap_coordinates` put on the sphere
+                (point - center).magnitude() <= radius * (1.0 + 1e-5)
             }
+            // Hyperboloid coordinates cover the whole plane
+            WrapMode::Hyperbolic if self.uses_hyperboloid() => true,
             WrapMode::Hyperbolic => {
                 let r = (point.x * point.x + point.y * point.y).sqrt();
                 r < 1.0
@@ -182,29 +235,42 @@ impl Chart {
     }
     
     /// Apply boundary wrapping to coordinates
+    ///
+    /// - `Periodic` wraps x and y around the bounds like a torus, however many
+    ///   periods away the point is.
+    /// - `Spherical` projects points outside the bounding sphere back onto it.
+    /// - `Hyperbolic` pulls points near the disk boundary back inside, and leaves
+    ///   hyperboloid coordinates alone.
     pub fn wrap_coordinates(&self, local: LocalCoordinate) -> LocalCoordinate {
         let point = local.to_point();
         
         match self.bounds.wrap_mode {
             WrapMode::Periodic => {
-                let width = self.bounds.max.x - self.bounds.min.x;
-                let height = self.bounds.max.y - self.bounds.min.y;
+                let wrap = |value: f32, min: f32, max: f32| {
+                    let period = max - min;
+                    if period > 0.0 && (value < min || value > max) {
+                        min + (value - min).rem_euclid(period)
+                    } else {
+                        value
+                    }
+                };
                 
-                let mut wrapped = point;
-                if wrapped.x < self.bounds.min.x {
-                    wrapped.x += width;
-                } else if wrapped.x > self.bounds.max.x {
-                    wrapped.x -= width;
-                }
-                
-                if wrapped.y < self.bounds.min.y {
-                    wrapped.y += height;
-                } else if wrapped.y > self.bounds.max.y {
-                    wrapped.y -= height;
+                LocalCoordinate::new(
+                    wrap(point.x, self.bounds.min.x, self.bounds.max.x),
+                    wrap(point.y, self.bounds.min.y, self.bounds.max.y),
+                    point.z,
+                )
+            }
+            WrapMode::Spherical => {
+                let (center, radius) = self.bounding_sphere();
+                let offset = point - center;
+                if offset.magnitude() > radius {
+                    LocalCoordinate::from_point(center + offset.normalize() * radius)
+                } else {
+                    local
                 }
-                
-                LocalCoordinate::from_point(wrapped)
             }
+            WrapMode::Hyperbolic if self.uses_hyperboloid() => local,
             WrapMode::Hyperbolic => {
                 // Keep within Poincaré disk
                 let r = (point.x * point.x + point.y * point.y).sqrt();
@@ -215,7 +281,7 @@ impl Chart {
                     local
                 }
             }
-            _ => local,
+            WrapMode::None => local,
         }
     }
     
@@ -233,4 +299,46 @@ impl Chart {
     pub fn distance(&self, a: LocalCoordinate, b: LocalCoordinate) -> f32 {
         self.metric.distance(a.to_point(), b.to_point())
     }
+    
+    /// Evaluate the metric on a regular grid over the box from `min` to `max`
+    ///
+    /// Each axis gets `resolution` evenly spaced samples including both ends,
+    /// or a single sample at its midpoint if it has no extent or `resolution`
+    /// is below 2, so a flat box samples a slice. The box is clipped to the
+    /// chart bounds and samples the chart doesn't contain are skipped. Samples
+    /// are ordered with x varying fastest.
+    pub fn sample_metric_grid(
+        &self,
+        min: Point3<f32>,
+        max: Point3<f32>,
+        resolution: usize,
+    ) -> Vec<(Point3<f32>, MetricTensor)> {
+        let axis = |lo: f32, hi: f32, bound_lo: f32, bound_hi: f32| -> Vec<f32> {
+            let (lo, hi) = (lo.max(bound_lo), hi.min(bound_hi));
+            if hi < lo {
+                Vec::new()
+            } else if hi == lo || resolution < 2 {
+                vec![(lo + hi) / 2.0]
+            } else {
+                (0..resolution).map(|i| lo + (hi - lo) * i as f32 / (resolution - 1) as f32).collect()
+            }
+        };
+        
+        let xs = axis(min.x, max.x, self.bounds.min.x, self.bounds.max.x);
+        let ys = axis(min.y, max.y, self.bounds.min.y, self.bounds.max.y);
+        let zs = axis(min.z, max.z, self.bounds.min.z, self.bounds.max.z);
+        
+        let mut samples = Vec::with_capacity(xs.len() * ys.len() * zs.len());
+        for &z in &zs {
+            for &y in &ys {
+                for &x in &xs {
+                    let point = Point3::new(x, y, z);
+                    if self.contains(LocalCoordinate::from_point(point)) {
+                        samples.push((point, self.metric.tensor_at(point)));
+                    }
+                }
+            }
+        }
+        samples
+    }
 }
\ No newline at end of file
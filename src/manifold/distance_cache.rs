@@ -0,0 +1,124 @@
+//! Memoization of metric distance queries
+
+use cgmath::Point3;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use super::ChartId;
+
+/// Points are snapped to this grid unless configured otherwise
+pub const DEFAULT_RESOLUTION: f32 = 1e-3;
+
+/// The cache is flushed once it grows past this many entries
+const MAX_ENTRIES: usize = 1 << 16;
+
+type CacheKey = (ChartId, [i32; 3], [i32; 3]);
+
+/// Cache of metric distances keyed on quantized point pairs
+///
+/// Points are snapped to a grid of `resolution`; any two queries whose endpoints
+/// fall in the same cells share one result, computed from the first query's exact
+/// points. The cache knows nothing about the charts it was filled from, so it is
+/// stale as soon as a chart's metric changes and must then be cleared. Callers
+/// tracking moving objects typically clear it once per tick. To bound memory it
+/// also empties itself completely when a miss finds it holding 65536 entries,
+/// so even unchanging queries are occasionally recomputed.
+pub struct DistanceCache {
+    resolution: f32,
+    state: Mutex<CacheState>,
+}
+
+#[derive(Default, Clone)]
+struct CacheState {
+    entries: HashMap<CacheKey, f32>,
+    hits: u64,
+    misses: u64,
+}
+
+/// Hit/miss counters for a [`DistanceCache`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DistanceCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+impl Default for DistanceCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_RESOLUTION)
+    }
+}
+
+impl Clone for DistanceCache {
+    fn clone(&self) -> Self {
+        Self {
+            resolution: self.resolution,
+            state: Mutex::new(self.state.lock().unwrap().clone()),
+        }
+    }
+}
+
+impl DistanceCache {
+    /// Create an empty cache with the given grid resolution
+    pub fn new(resolution: f32) -> Self {
+        Self {
+            resolution: resolution.max(f32::EPSILON),
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+    
+    /// Grid cell size used to quantize points
+    pub fn resolution(&self) -> f32 {
+        self.resolution
+    }
+    
+    /// Look up a distance, computing and storing it on a miss
+    ///
+    /// The pair is unordered, so `(a, b)` and `(b, a)` share an entry. The cache
+    /// isn't locked while `compute` runs, so it may query the cache itself; if
+    /// another thread fills the entry meanwhile, its value is kept and returned.
+    pub fn get_or_insert_with(
+        &self,
+        chart: ChartId,
+        a: Point3<f32>,
+        b: Point3<f32>,
+        compute: impl FnOnce() -> f32,
+    ) -> f32 {
+        let (qa, qb) = (self.quantize(a), self.quantize(b));
+        let key = if qa <= qb { (chart, qa, qb) } else { (chart, qb, qa) };
+        
+        {
+            let mut state = self.state.lock().unwrap();
+            if let Some(&distance) = state.entries.get(&key) {
+                state.hits += 1;
+                return distance;
+            }
+            state.misses += 1;
+        }
+        
+        let distance = compute();
+        let mut state = self.state.lock().unwrap();
+        if state.entries.len() >= MAX_ENTRIES {
+            state.entries.clear();
+        }
+        *state.entries.entry(key).or_insert(distance)
+    }
+    
+    /// Drop every cached distance, keeping the hit/miss counters
+    pub fn clear(&self) {
+        self.state.lock().unwrap().entries.clear();
+    }
+    
+    /// Current hit/miss counters and entry count
+    pub fn stats(&self) -> DistanceCacheStats {
+        let state = self.state.lock().unwrap();
+        DistanceCacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            entries: state.entries.len(),
+        }
+    }
+    
+    fn quantize(&self, point: Point3<f32>) -> [i32; 3] {
+        [point.x, point.y, point.z].map(|c| (c / self.resolution).round() as i32)
+    }
+}
@@ -1,7 +1,8 @@
 //! Geodesic computation for paths in curved spaces
 
-use cgmath::{Point3, Vector3, InnerSpace, EuclideanSpace};
+use cgmath::{Point2, Point3, Vector3, Matrix3, InnerSpace, EuclideanSpace, SquareMatrix};
 use super::{Metric, GeometryType};
+use super::hyperboloid::{self, HyperbolicModel};
 
 /// A geodesic path through curved space
 #[derive(Debug, Clone)]
@@ -32,40 +33,99 @@ impl GeodesicPath {
         self.tangents.push(tangent.normalize());
     }
     
-    /// Get interpolated position along the path
-    pub fn interpolate(&self, t: f32) -> Option<Point3<f32>> {
-        if self.points.is_empty() {
+    /// Locate parameter `t` by arc length: the segment end index and the fraction along it
+    fn locate(&self, t: f32) -> Option<(usize, f32)> {
+        if self.points.len() < 2 {
             return None;
         }
         
-        let t_clamped = t.max(0.0).min(1.0);
-        let target_length = t_clamped * self.arc_length;
+        let target_length = t.clamp(0.0, 1.0) * self.arc_length;
         
         let mut accumulated_length = 0.0;
         for i in 1..self.points.len() {
             let segment_length = (self.points[i] - self.points[i-1]).magnitude();
             if accumulated_length + segment_length >= target_length {
-                let local_t = (target_length - accumulated_length) / segment_length;
-                return Some(Point3::new(
-                    self.points[i-1].x + (self.points[i].x - self.points[i-1].x) * local_t,
-                    self.points[i-1].y + (self.points[i].y - self.points[i-1].y) * local_t,
-                    self.points[i-1].z + (self.points[i].z - self.points[i-1].z) * local_t,
-                ));
+                let local_t = if segment_length > 0.0 {
+                    (target_length - accumulated_length) / segment_length
+                } else {
+                    0.0
+                };
+                return Some((i, local_t));
             }
             accumulated_length += segment_length;
         }
         
-        self.points.last().copied()
+        Some((self.points.len() - 1, 1.0))
+    }
+    
+    /// Get interpolated position along the path
+    pub fn interpolate(&self, t: f32) -> Option<Point3<f32>> {
+        let Some((i, local_t)) = self.locate(t) else {
+            return self.points.first().copied();
+        };
+        
+        Some(Point3::new(
+            self.points[i-1].x + (self.points[i].x - self.points[i-1].x) * local_t,
+            self.points[i-1].y + (self.points[i].y - self.points[i-1].y) * local_t,
+            self.points[i-1].z + (self.points[i].z - self.points[i-1].z) * local_t,
+        ))
+    }
+    
+    /// The same path walked from end to start
+    pub fn reversed(&self) -> GeodesicPath {
+        GeodesicPath {
+            points: self.points.iter().rev().copied().collect(),
+            tangents: self.tangents.iter().rev().map(|t| -*t).collect(),
+            arc_length: self.arc_length,
+            geometry: self.geometry,
+        }
+    }
+    
+    /// Join `other` onto the end of this path
+    ///
+    /// Fails if the geometries differ or `other` does not start where this path ends.
+    pub fn concat(&self, other: &GeodesicPath) -> Result<GeodesicPath, String> {
+        if self.geometry != other.geometry {
+            return Err(format!(
+                "Cannot join {:?} and {:?} geodesics",
+                self.geometry, other.geometry
+            ));
+        }
+        
+        let (Some(end), Some(start)) = (self.points.last(), other.points.first()) else {
+            return Ok(if self.points.is_empty() { other.clone() } else { self.clone() });
+        };
+        
+        if (end - start).magnitude() > 1e-3 {
+            return Err(format!("Path ends at {:?} but next path starts at {:?}", end, start));
+        }
+        
+        let mut joined = GeodesicPath::new(self.geometry);
+        for (point, tangent) in self.points.iter().zip(&self.tangents) {
+            joined.add_point(*point, *tangent);
+        }
+        for (point, tangent) in other.points.iter().zip(&other.tangents).skip(1) {
+            joined.add_point(*point, *tangent);
+        }
+        
+        Ok(joined)
     }
     
     /// Get tangent at parameter t
+    ///
+    /// Located by arc length like `interpolate`, blending the bracketing tangents.
     pub fn tangent_at(&self, t: f32) -> Option<Vector3<f32>> {
-        if self.tangents.is_empty() {
-            return None;
-        }
+        let Some((i, local_t)) = self.locate(t) else {
+            return self.tangents.first().copied();
+        };
         
-        let index = ((t * (self.tangents.len() - 1) as f32) as usize).min(self.tangents.len() - 1);
-        Some(self.tangents[index])
+        let (a, b) = (self.tangents[i-1], self.tangents[i]);
+        let blended = a + (b - a) * local_t;
+        if blended.magnitude2() > 1e-12 {
+            Some(blended.normalize())
+        } else {
+            Some(if local_t < 0.5 { a } else { b })
+        }
     }
 }
 
@@ -74,12 +134,16 @@ pub struct Geodesic;
 
 impl Geodesic {
     /// Compute geodesic between two points
+    ///
+    /// The path has `steps + 1` points; `steps` below 1 is treated as 1, giving
+    /// just the two endpoints.
     pub fn compute(
         start: Point3<f32>,
         end: Point3<f32>,
         metric: &Metric,
         steps: usize,
     ) -> GeodesicPath {
+        let steps = steps.max(1);
         match metric.geometry {
             GeometryType::Euclidean => Self::euclidean_geodesic(start, end, steps),
             GeometryType::Spherical => Self::spherical_geodesic(start, end, metric, steps),
@@ -88,6 +152,65 @@ impl Geodesic {
         }
     }
     
+    /// Compute a geodesic sampled densely only where it bends
+    ///
+    /// Segments are halved at the geodesic midpoint until the midpoint lies within
+    /// `max_error` of the chord, so the polyline stays about `max_error` from the
+    /// true geodesic: few points along near-straight stretches, many near the
+    /// Poincaré boundary or around a sphere towards the antipode. Subdivision
+    /// stops after [`Self::MAX_ADAPTIVE_DEPTH`] halvings.
+    pub fn compute_adaptive(
+        start: Point3<f32>,
+        end: Point3<f32>,
+        metric: &Metric,
+        max_error: f32,
+    ) -> GeodesicPath {
+        let whole = Self::compute(start, end, metric, 2);
+        let mut samples = vec![(whole.points[0], whole.tangents[0])];
+        Self::subdivide(whole.points[0], end, metric, max_error.max(1e-6), Self::MAX_ADAPTIVE_DEPTH, &mut samples);
+        
+        let mut path = GeodesicPath::new(metric.geometry);
+        for (point, tangent) in samples {
+            path.add_point(point, tangent);
+        }
+        path
+    }
+    
+    /// Most times [`Self::compute_adaptive`] halves a segment
+    pub const MAX_ADAPTIVE_DEPTH: u32 = 12;
+    
+    /// Push the samples after `a` up to and including `b`
+    fn subdivide(
+        a: Point3<f32>,
+        b: Point3<f32>,
+        metric: &Metric,
+        max_error: f32,
+        depth: u32,
+        samples: &mut Vec<(Point3<f32>, Vector3<f32>)>,
+    ) {
+        let halves = Self::compute(a, b, metric, 2);
+        let (mid, end) = (halves.points[1], halves.points[2]);
+        
+        if depth == 0 || Self::chord_distance(mid, a, end) <= max_error {
+            samples.push((end, halves.tangents[2]));
+            return;
+        }
+        
+        Self::subdivide(a, mid, metric, max_error, depth - 1, samples);
+        Self::subdivide(mid, end, metric, max_error, depth - 1, samples);
+    }
+    
+    /// Euclidean distance from `point` to the segment `a`-`b`
+    fn chord_distance(point: Point3<f32>, a: Point3<f32>, b: Point3<f32>) -> f32 {
+        let chord = b - a;
+        let length2 = chord.magnitude2();
+        if length2 <= f32::EPSILON {
+            return (point - a).magnitude();
+        }
+        let t = ((point - a).dot(chord) / length2).clamp(0.0, 1.0);
+        (point - (a + chord * t)).magnitude()
+    }
+    
     /// Straight line in Euclidean space
     fn euclidean_geodesic(start: Point3<f32>, end: Point3<f32>, steps: usize) -> GeodesicPath {
         let mut path = GeodesicPath::new(GeometryType::Euclidean);
@@ -107,6 +230,9 @@ impl Geodesic {
     }
     
     /// Great circle on a sphere
+    ///
+    /// Coincident endpoints give a zero-length path; for antipodal endpoints every
+    /// great circle is shortest, so an arbitrary one is chosen.
     fn spherical_geodesic(
         start: Point3<f32>,
         end: Point3<f32>,
@@ -120,40 +246,23 @@ impl Geodesic {
         let start_norm = Vector3::new(start.x, start.y, start.z).normalize() * radius;
         let end_norm = Vector3::new(end.x, end.y, end.z).normalize() * radius;
         
-        // Compute rotation axis and angle
-        let axis = start_norm.cross(end_norm).normalize();
-        let angle = (start_norm.dot(end_norm) / (radius * radius)).acos();
+        let cos_angle = (start_norm.dot(end_norm) / (radius * radius)).clamp(-1.0, 1.0);
+        let angle = cos_angle.acos();
+        
+        // Unit direction in the great-circle plane, perpendicular to the start point
+        let perpendicular = end_norm - start_norm * cos_angle;
+        let direction = if perpendicular.magnitude() > 1e-4 * radius {
+            perpendicular.normalize()
+        } else {
+            Self::any_perpendicular(start_norm)
+        };
         
         for i in 0..=steps {
-            let t = i as f32 / steps as f32;
-            let _theta = angle * t;
-            
-            // Slerp on sphere
-            let a = (1.0 - t) * angle;
-            let b = t * angle;
+            let theta = angle * i as f32 / steps as f32;
+            let (sin, cos) = theta.sin_cos();
             
-            let point = if angle.abs() > 0.001 {
-                let sin_angle = angle.sin();
-                let p = (start_norm * a.sin() + end_norm * b.sin()) / sin_angle;
-                Point3::from_vec(p)
-            } else {
-                Point3::from_vec(start_norm + (end_norm - start_norm) * t)
-            };
-            
-            // Tangent is perpendicular to radius
-            let tangent = if i < steps {
-                let next_t = (i + 1) as f32 / steps as f32;
-                let _next_theta = angle * next_t;
-                let next = if angle.abs() > 0.001 {
-                    let sin_angle = angle.sin();
-                    (start_norm * (1.0 - next_t) * angle.sin() + end_norm * next_t * angle.sin()) / sin_angle
-                } else {
-                    start_norm + (end_norm - start_norm) * next_t
-                };
-                (next - point.to_vec()).normalize()
-            } else {
-                axis.cross(point.to_vec()).normalize()
-            };
+            let point = Point3::from_vec(start_norm * cos + direction * (radius * sin));
+            let tangent = direction * cos - start_norm.normalize() * sin;
             
             path.add_point(point, tangent);
         }
@@ -161,18 +270,30 @@ impl Geodesic {
         path
     }
     
+    /// Some unit vector perpendicular to `v`
+    fn any_perpendicular(v: Vector3<f32>) -> Vector3<f32> {
+        let reference = if v.x.abs() < 0.9 * v.magnitude() {
+            Vector3::new(1.0, 0.0, 0.0)
+        } else {
+            Vector3::new(0.0, 1.0, 0.0)
+        };
+        v.cross(reference).normalize()
+    }
+    
     /// Geodesic in hyperbolic space (Poincaré disk)
+    ///
+    /// The arc orthogonal to the boundary through both points, found by lifting
+    /// them onto the hyperboloid and projecting the geodesic between them back,
+    /// so samples are evenly spaced in hyperbolic distance.
     fn hyperbolic_geodesic(
         start: Point3<f32>,
         end: Point3<f32>,
-        _metric: &Metric,
+        metric: &Metric,
         steps: usize,
     ) -> GeodesicPath {
-        let mut path = GeodesicPath::new(GeometryType::Hyperbolic);
-        
-        // Project to Poincaré disk (z=0 plane)
-        let start_2d = Vector3::new(start.x, start.y, 0.0);
-        let end_2d = Vector3::new(end.x, end.y, 0.0);
+        if metric.parameters.hyperbolic_model == HyperbolicModel::Hyperboloid {
+            return Self::hyperboloid_geodesic(start, end, steps);
+        }
         
         let start_r = (start.x * start.x + start.y * start.y).sqrt();
         let end_r = (end.x * end.x + end.y * end.y).sqrt();
@@ -183,129 +304,155 @@ impl Geodesic {
             return Self::euclidean_geodesic(start, end, steps);
         }
         
-        // Geodesics in Poincaré disk are circular arcs
-        // perpendicular to the boundary circle
-        
-        // Special case: geodesic through origin is a straight line
-        if start_r < 0.01 || end_r < 0.01 {
-            for i in 0..=steps {
-                let t = i as f32 / steps as f32;
-                let point = Point3::new(
-                    start.x + (end.x - start.x) * t,
-                    start.y + (end.y - start.y) * t,
-                    0.0,
-                );
-                let tangent = (end_2d - start_2d).normalize();
-                path.add_point(point, tangent);
-            }
-        } else {
-            // General case: find the circle through both points
-            // perpendicular to unit circle
-            let midpoint = (start_2d + end_2d) / 2.0;
-            let direction = (end_2d - start_2d).normalize();
-            let perpendicular = Vector3::new(-direction.y, direction.x, 0.0);
-            
-            // Find center of the geodesic circle
-            let t_center = -midpoint.dot(perpendicular) / perpendicular.dot(perpendicular);
-            let center = midpoint + perpendicular * t_center;
-            
-            // Compute arc
-            let radius = (start_2d - center).magnitude();
-            let angle_start = (start.y - center.y).atan2(start.x - center.x);
-            let angle_end = (end.y - center.y).atan2(end.x - center.x);
-            
-            let mut angle_diff = angle_end - angle_start;
-            if angle_diff > std::f32::consts::PI {
-                angle_diff -= 2.0 * std::f32::consts::PI;
-            } else if angle_diff < -std::f32::consts::PI {
-                angle_diff += 2.0 * std::f32::consts::PI;
-            }
-            
-            for i in 0..=steps {
-                let t = i as f32 / steps as f32;
-                let angle = angle_start + angle_diff * t;
-                
-                let point = Point3::new(
-                    center.x + radius * angle.cos(),
-                    center.y + radius * angle.sin(),
-                    0.0,
-                );
-                
-                // Tangent to the arc
-                let tangent = Vector3::new(
-                    -radius * angle.sin(),
-                    radius * angle.cos(),
-                    0.0,
-                ).normalize();
-                
-                path.add_point(point, tangent);
-            }
+        let a = hyperboloid::poincare_to_hyperboloid(Point2::new(start.x, start.y));
+        let b = hyperboloid::poincare_to_hyperboloid(Point2::new(end.x, end.y));
+        Self::hyperboloid_arc(a, b, steps, |point| {
+            let disk = hyperboloid::hyperboloid_to_poincare(point);
+            (disk.x, disk.y)
+        })
+    }
+    
+    /// Geodesic in hyperboloid coordinates
+    ///
+    /// Follows the intersection of the hyperboloid with the plane through the
+    /// origin and both endpoints, then drops back to spatial coordinates.
+    fn hyperboloid_geodesic(start: Point3<f32>, end: Point3<f32>, steps: usize) -> GeodesicPath {
+        let (a, b) = (hyperboloid::lift(start.x, start.y), hyperboloid::lift(end.x, end.y));
+        Self::hyperboloid_arc(a, b, steps, |point| (point.x, point.y))
+    }
+    
+    /// Sample the hyperboloid geodesic from `a` to `b`, mapping each point into
+    /// chart coordinates with `project`
+    fn hyperboloid_arc(
+        a: Vector3<f32>,
+        b: Vector3<f32>,
+        steps: usize,
+        project: impl Fn(Vector3<f32>) -> (f32, f32),
+    ) -> GeodesicPath {
+        let mut path = GeodesicPath::new(GeometryType::Hyperbolic);
+        let points: Vec<Point3<f32>> = (0..=steps)
+            .map(|i| project(hyperboloid::geodesic_point(a, b, i as f32 / steps as f32)))
+            .map(|(x, y)| Point3::new(x, y, 0.0))
+            .collect();
+        for (i, point) in points.iter().enumerate() {
+            let direction = points[(i + 1).min(steps)] - points[i.saturating_sub(1)];
+            let tangent = if direction.magnitude2() > 0.0 { direction.normalize() } else { direction };
+            path.add_point(*point, tangent);
         }
         
         path
     }
     
-    /// Numerical geodesic solver using gradient descent
+    /// Numerical geodesic solver using the shooting method
+    ///
+    /// Integrates the geodesic equation from `start` with RK4 and refines the
+    /// initial velocity with Newton iterations until the path ends at `end`.
     fn numerical_geodesic(
         start: Point3<f32>,
         end: Point3<f32>,
         metric: &Metric,
         steps: usize,
     ) -> GeodesicPath {
+        const MAX_ITERATIONS: usize = 20;
+        const TOLERANCE: f32 = 1e-4;
+        const JACOBIAN_STEP: f32 = 1e-3;
+        
+        let mut velocity = end - start;
+        
+        for _ in 0..MAX_ITERATIONS {
+            let reached = Self::integrate_rk4(start, velocity, metric, steps)
+                .last()
+                .map(|&(point, _)| point)
+                .unwrap_or(start);
+            let miss = end - reached;
+            if miss.magnitude() < TOLERANCE {
+                break;
+            }
+            
+            // Finite-difference Jacobian of the endpoint w.r.t. the initial velocity
+            let mut columns = [Vector3::new(0.0, 0.0, 0.0); 3];
+            for (axis, column) in columns.iter_mut().enumerate() {
+                let mut dv = Vector3::new(0.0, 0.0, 0.0);
+                dv[axis] = JACOBIAN_STEP;
+                let perturbed = Self::integrate_rk4(start, velocity + dv, metric, steps)
+                    .last()
+                    .map(|&(point, _)| point)
+                    .unwrap_or(start);
+                *column = (perturbed - reached) / JACOBIAN_STEP;
+            }
+            
+            let jacobian = Matrix3::from_cols(columns[0], columns[1], columns[2]);
+            velocity += jacobian.invert().map(|inverse| inverse * miss).unwrap_or(miss);
+        }
+        
         let mut path = GeodesicPath::new(GeometryType::Custom);
+        for (point, tangent) in Self::integrate_rk4(start, velocity, metric, steps) {
+            path.add_point(point, tangent);
+        }
         
-        // Initialize with straight line
-        let mut points = Vec::new();
-        for i in 0..=steps {
-            let t = i as f32 / steps as f32;
-            points.push(Point3::new(
-                start.x + (end.x - start.x) * t,
-                start.y + (end.y - start.y) * t,
-                start.z + (end.z - start.z) * t,
-            ));
+        path
+    }
+    
+    /// Integrate a geodesic from an initial point and direction
+    ///
+    /// Solves `d²xⁱ/dt² = −Γⁱⱼₖ (dxʲ/dt)(dxᵏ/dt)` with RK4, starting with a
+    /// coordinate velocity of `direction` scaled to `length`, over `steps` steps.
+    /// A zero `direction` goes nowhere and gives just the start point.
+    pub fn integrate(
+        start: Point3<f32>,
+        direction: Vector3<f32>,
+        metric: &Metric,
+        length: f32,
+        steps: usize,
+    ) -> GeodesicPath {
+        let mut path = GeodesicPath::new(metric.geometry);
+        if direction.magnitude2() <= f32::EPSILON * f32::EPSILON {
+            path.add_point(start, direction);
+            return path;
         }
         
-        // Optimize path to minimize length
-        let iterations = 20;
-        let learning_rate = 0.1;
-        
-        for _ in 0..iterations {
-            // Keep endpoints fixed
-            for i in 1..points.len() - 1 {
-                let prev = points[i - 1];
-                let curr = points[i];
-                let next = points[i + 1];
-                
-                // Compute gradient of arc length
-                let metric_curr = metric.tensor_at(curr);
-                let to_prev = prev - curr;
-                let to_next = next - curr;
-                
-                let grad = Vector3::new(
-                    metric_curr.norm(to_prev) + metric_curr.norm(to_next),
-                    metric_curr.norm(to_prev) + metric_curr.norm(to_next),
-                    metric_curr.norm(to_prev) + metric_curr.norm(to_next),
-                );
-                
-                // Update point
-                points[i] = Point3::from_vec(curr.to_vec() - grad * learning_rate);
-            }
+        let velocity = direction.normalize() * length;
+        
+        for (point, tangent) in Self::integrate_rk4(start, velocity, metric, steps.max(1)) {
+            path.add_point(point, tangent);
         }
         
-        // Build final path
-        for i in 0..points.len() {
-            let tangent = if i < points.len() - 1 {
-                (points[i + 1] - points[i]).normalize()
-            } else if i > 0 {
-                (points[i] - points[i - 1]).normalize()
-            } else {
-                Vector3::new(1.0, 0.0, 0.0)
-            };
+        path
+    }
+    
+    /// RK4 integration of the geodesic equation over the parameter range [0, 1]
+    fn integrate_rk4(
+        start: Point3<f32>,
+        velocity: Vector3<f32>,
+        metric: &Metric,
+        steps: usize,
+    ) -> Vec<(Point3<f32>, Vector3<f32>)> {
+        let acceleration = |x: Point3<f32>, v: Vector3<f32>| {
+            metric.christoffel_symbols_at(x).geodesic_acceleration(x.to_vec(), v)
+        };
+        
+        let dt = 1.0 / steps as f32;
+        let mut x = start;
+        let mut v = velocity;
+        let mut states = Vec::with_capacity(steps + 1);
+        states.push((x, v));
+        
+        for _ in 0..steps {
+            let k1x = v;
+            let k1v = acceleration(x, v);
+            let k2x = v + k1v * (dt / 2.0);
+            let k2v = acceleration(x + k1x * (dt / 2.0), k2x);
+            let k3x = v + k2v * (dt / 2.0);
+            let k3v = acceleration(x + k2x * (dt / 2.0), k3x);
+            let k4x = v + k3v * dt;
+            let k4v = acceleration(x + k3x * dt, k4x);
             
-            path.add_point(points[i], tangent);
+            x += (k1x + k2x * 2.0 + k3x * 2.0 + k4x) * (dt / 6.0);
+            v += (k1v + k2v * 2.0 + k3v * 2.0 + k4v) * (dt / 6.0);
+            states.push((x, v));
         }
         
-        path
+        states
     }
 }
 
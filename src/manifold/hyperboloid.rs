@@ -0,0 +1,72 @@
+//! Hyperboloid (Minkowski) model of the hyperbolic plane
+//!
+//! Points of the hyperboloid are `Vector3`s `(x, y, w)` with `w² − x² − y² = 1`
+//! and `w > 0`, where `w` is the time-like coordinate. Distances stay accurate
+//! far from the origin, where the Poincaré disk squeezes everything against
+//! its boundary. Charts using this model store the spatial `(x, y)` as their
+//! local coordinates and keep `z` as a flat height, like the disk model.
+
+use cgmath::{InnerSpace, Point2, Vector3};
+use serde::{Serialize, Deserialize};
+
+/// Coordinates a hyperbolic chart uses for its local positions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HyperbolicModel {
+    /// Points inside the unit disk
+    #[default]
+    Poincare,
+    /// Spatial coordinates of points on the hyperboloid, unbounded
+    Hyperboloid,
+}
+
+/// Minkowski inner product `x₁x₂ + y₁y₂ − w₁w₂`
+pub fn minkowski_inner(a: Vector3<f32>, b: Vector3<f32>) -> f32 {
+    a.x * b.x + a.y * b.y - a.z * b.z
+}
+
+/// Point on the hyperboloid above the spatial coordinates `(x, y)`
+pub fn lift(x: f32, y: f32) -> Vector3<f32> {
+    Vector3::new(x, y, (1.0 + x * x + y * y).sqrt())
+}
+
+/// Map a point of the Poincaré disk onto the hyperboloid
+pub fn poincare_to_hyperboloid(point: Point2<f32>) -> Vector3<f32> {
+    let r2 = point.x * point.x + point.y * point.y;
+    let denominator = (1.0 - r2).max(f32::EPSILON);
+    Vector3::new(2.0 * point.x, 2.0 * point.y, 1.0 + r2) / denominator
+}
+
+/// Map a point of the hyperboloid into the Poincaré disk
+///
+/// This is the projection from `(0, 0, −1)` onto the plane `w = 0`.
+pub fn hyperboloid_to_poincare(point: Vector3<f32>) -> Point2<f32> {
+    Point2::new(point.x, point.y) / (1.0 + point.z)
+}
+
+/// Hyperbolic distance between two hyperboloid points, `acosh(−⟨a, b⟩)`
+pub fn hyperboloid_distance(a: Vector3<f32>, b: Vector3<f32>) -> f32 {
+    (-minkowski_inner(a, b)).max(1.0).acosh()
+}
+
+/// Hyperbolic distance between two points of the Poincaré disk
+pub fn poincare_distance(a: Point2<f32>, b: Point2<f32>) -> f32 {
+    let (a2, b2) = (a.x * a.x + a.y * a.y, b.x * b.x + b.y * b.y);
+    let delta2 = (a - b).magnitude2();
+    let denominator = ((1.0 - a2) * (1.0 - b2)).max(f32::EPSILON);
+    (1.0 + 2.0 * delta2 / denominator).acosh()
+}
+
+/// Point a fraction `t` of the way along the geodesic from `a` to `b`
+///
+/// Geodesics are where the hyperboloid meets planes through the origin, so
+/// the point is the combination of `a` and `b` in that plane at distance
+/// `t · d(a, b)` from `a`.
+pub fn geodesic_point(a: Vector3<f32>, b: Vector3<f32>, t: f32) -> Vector3<f32> {
+    let distance = hyperboloid_distance(a, b);
+    if distance < 1e-6 {
+        return a + (b - a) * t;
+    }
+    
+    let sinh = distance.sinh();
+    a * (((1.0 - t) * distance).sinh() / sinh) + b * ((t * distance).sinh() / sinh)
+}
@@ -1,10 +1,14 @@
 //! Metric tensor and geometry definitions for curved spaces
 
 use cgmath::{Point3, Vector3, Matrix3, Matrix4, InnerSpace, SquareMatrix};
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
 use super::GeodesicPath;
+use super::hyperboloid::{self, HyperbolicModel};
 
 /// Type of geometry for a space region
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GeometryType {
     Euclidean,      // Flat space (zero curvature)
     Spherical,      // Positive curvature
@@ -60,6 +64,23 @@ impl MetricTensor {
         }
     }
     
+    /// Create the hyperbolic metric in hyperboloid coordinates
+    ///
+    /// The Minkowski metric restricted to the hyperboloid above `(x, y)`:
+    /// `gᵢⱼ = δᵢⱼ − xᵢxⱼ / w²` in the plane, flat along z.
+    pub fn hyperboloid(x: f32, y: f32) -> Self {
+        let w2 = 1.0 + x * x + y * y;
+        
+        Self {
+            g: Matrix3::new(
+                1.0 - x * x / w2, -x * y / w2, 0.0,
+                -x * y / w2, 1.0 - y * y / w2, 0.0,
+                0.0, 0.0, 1.0,
+            ),
+            curvature: -1.0,
+        }
+    }
+    
     /// Compute the norm of a vector using this metric
     pub fn norm(&self, v: Vector3<f32>) -> f32 {
         let gv = self.g * v;
@@ -98,6 +119,49 @@ impl ChristoffelSymbols {
         Self { gamma }
     }
     
+    /// Compute Christoffel symbols of a metric field at a point.
+    ///
+    /// Metric derivatives are taken by central differences of `Metric::tensor_at`,
+    /// then Γⁱⱼₖ = ½ gⁱˡ (∂ⱼ gₗₖ + ∂ₖ gₗⱼ − ∂ₗ gⱼₖ).
+    pub fn from_metric_field(metric: &Metric, point: Point3<f32>) -> Self {
+        Self::from_metric_field_with_step(metric, point, 1e-3)
+    }
+    
+    /// Christoffel symbols with metric derivatives taken over a step of `h`
+    fn from_metric_field_with_step(metric: &Metric, point: Point3<f32>, h: f32) -> Self {
+        let g_inv = metric.tensor_at(point).g.invert()
+            .unwrap_or(Matrix3::from_diagonal(Vector3::new(1.0, 1.0, 1.0)));
+        
+        // dg[l][a][b] = ∂ₗ g_ab
+        let mut dg = [[[0.0; 3]; 3]; 3];
+        for (l, dg_l) in dg.iter_mut().enumerate() {
+            let mut offset = Vector3::new(0.0, 0.0, 0.0);
+            offset[l] = h;
+            let g_plus = metric.tensor_at(point + offset).g;
+            let g_minus = metric.tensor_at(point - offset).g;
+            for a in 0..3 {
+                for b in 0..3 {
+                    dg_l[a][b] = (g_plus[a][b] - g_minus[a][b]) / (2.0 * h);
+                }
+            }
+        }
+        
+        let mut gamma = [[[0.0; 3]; 3]; 3];
+        for (i, gamma_i) in gamma.iter_mut().enumerate() {
+            for j in 0..3 {
+                for k in 0..3 {
+                    let mut sum = 0.0;
+                    for l in 0..3 {
+                        sum += g_inv[i][l] * (dg[j][l][k] + dg[k][l][j] - dg[l][j][k]);
+                    }
+                    gamma_i[j][k] = 0.5 * sum;
+                }
+            }
+        }
+        
+        Self { gamma }
+    }
+    
     /// Apply Christoffel symbols to compute geodesic acceleration
     pub fn geodesic_acceleration(&self, _position: Vector3<f32>, velocity: Vector3<f32>) -> Vector3<f32> {
         let mut accel = Vector3::new(0.0, 0.0, 0.0);
@@ -114,20 +178,111 @@ impl ChristoffelSymbols {
     }
 }
 
+/// Riemann tensor components Rᵖ_σμν, indexed `[ρ][σ][μ][ν]`
+type RiemannComponents = [[[[f32; 3]; 3]; 3]; 3];
+
+/// Step for the nested finite differences behind numeric curvature
+///
+/// Larger than the Christoffel step because the second derivatives amplify
+/// f32 rounding by 1/h².
+const CURVATURE_STEP: f32 = 1e-2;
+
 /// Metric for a region of space
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "MetricData")]
 pub struct Metric {
     pub geometry: GeometryType,
     pub scale: f32,
     pub parameters: MetricParameters,
+    /// Registry entry for `custom_name`, bound by [`resolve_custom`](Self::resolve_custom)
+    #[serde(skip)]
+    resolved_custom: Option<CustomMetricFn>,
+}
+
+/// Serialized fields of a [`Metric`], resolved against the registry on load
+#[derive(Deserialize)]
+struct MetricData {
+    geometry: GeometryType,
+    scale: f32,
+    parameters: MetricParameters,
+}
+
+impl From<MetricData> for Metric {
+    fn from(data: MetricData) -> Self {
+        let mut metric = Self {
+            geometry: data.geometry,
+            scale: data.scale,
+            parameters: data.parameters,
+            resolved_custom: None,
+        };
+        metric.resolve_custom();
+        metric
+    }
 }
 
 /// Parameters defining the metric
-#[derive(Debug, Clone)]
+///
+/// `custom_fn` is not serialized. Custom metrics that should survive a save
+/// are referenced by `custom_name` instead and looked up in the
+/// [`CustomMetricRegistry`] when the metric is created or loaded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricParameters {
     pub curvature: f32,
     pub radius: f32,
+    #[serde(skip)]
     pub custom_fn: Option<fn(Point3<f32>) -> MetricTensor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_name: Option<String>,
+    /// Coordinates hyperbolic charts use, ignored by other geometries
+    #[serde(default)]
+    pub hyperbolic_model: HyperbolicModel,
+}
+
+/// Metric tensor field registered with the [`CustomMetricRegistry`]
+pub type CustomMetricFn = Arc<dyn Fn(Point3<f32>) -> MetricTensor + Send + Sync>;
+
+/// Process-wide table of custom metrics by name
+///
+/// Register implementations at startup, before evaluating or loading worlds
+/// whose custom charts name them. Charts store only the name, so a world
+/// saved in one run binds to whatever is registered under that name in the next.
+/// Metrics bind when created or loaded; ones made before their name was
+/// registered look it up on each evaluation until
+/// [`Manifold::resolve_custom_metrics`](super::Manifold::resolve_custom_metrics)
+/// binds them, which is also how to pick up a replaced entry.
+pub struct CustomMetricRegistry;
+
+impl CustomMetricRegistry {
+    fn metrics() -> &'static RwLock<HashMap<String, CustomMetricFn>> {
+        static METRICS: OnceLock<RwLock<HashMap<String, CustomMetricFn>>> = OnceLock::new();
+        METRICS.get_or_init(Default::default)
+    }
+    
+    /// Register a metric under `name`, replacing any previous one
+    pub fn register<F>(name: impl Into<String>, metric: F)
+    where
+        F: Fn(Point3<f32>) -> MetricTensor + Send + Sync + 'static,
+    {
+        let mut metrics = Self::metrics().write().unwrap_or_else(|e| e.into_inner());
+        metrics.insert(name.into(), Arc::new(metric));
+    }
+    
+    /// Remove a metric, returning whether it was registered
+    pub fn unregister(name: &str) -> bool {
+        let mut metrics = Self::metrics().write().unwrap_or_else(|e| e.into_inner());
+        metrics.remove(name).is_some()
+    }
+    
+    /// Look up a metric by name
+    pub fn get(name: &str) -> Option<CustomMetricFn> {
+        let metrics = Self::metrics().read().unwrap_or_else(|e| e.into_inner());
+        metrics.get(name).cloned()
+    }
+    
+    pub fn is_registered(name: &str) -> bool {
+        let metrics = Self::metrics().read().unwrap_or_else(|e| e.into_inner());
+        metrics.contains_key(name)
+    }
 }
 
 impl Metric {
@@ -138,21 +293,29 @@ impl Metric {
                 curvature: 0.0,
                 radius: 1.0,
                 custom_fn: None,
+                custom_name: None,
+                hyperbolic_model: HyperbolicModel::Poincare,
             },
             GeometryType::Spherical => MetricParameters {
                 curvature: 1.0,
                 radius: 10.0,
                 custom_fn: None,
+                custom_name: None,
+                hyperbolic_model: HyperbolicModel::Poincare,
             },
             GeometryType::Hyperbolic => MetricParameters {
                 curvature: -1.0,
                 radius: 1.0,
                 custom_fn: None,
+                custom_name: None,
+                hyperbolic_model: HyperbolicModel::Poincare,
             },
             GeometryType::Custom => MetricParameters {
                 curvature: 0.0,
                 radius: 1.0,
                 custom_fn: None,
+                custom_name: None,
+                hyperbolic_model: HyperbolicModel::Poincare,
             },
         };
         
@@ -160,10 +323,36 @@ impl Metric {
             geometry,
             scale: 1.0,
             parameters,
+            resolved_custom: None,
         }
     }
     
+    /// Create a hyperbolic metric using hyperboloid coordinates
+    pub fn hyperboloid() -> Self {
+        let mut metric = Self::from_geometry(GeometryType::Hyperbolic);
+        metric.parameters.hyperbolic_model = HyperbolicModel::Hyperboloid;
+        metric
+    }
+    
+    /// Create a custom metric that evaluates the registry entry `name`
+    pub fn custom(name: impl Into<String>) -> Self {
+        let mut metric = Self::from_geometry(GeometryType::Custom);
+        metric.parameters.custom_name = Some(name.into());
+        metric.resolve_custom();
+        metric
+    }
+    
+    /// Bind `custom_name` to its current registry entry, if any
+    pub fn resolve_custom(&mut self) {
+        self.resolved_custom = self.parameters.custom_name.as_deref().and_then(CustomMetricRegistry::get);
+    }
+    
     /// Get metric tensor at a point
+    ///
+    /// Custom metrics use `custom_fn` if set, then the registry entry named by
+    /// `custom_name`, and fall back to Euclidean if neither is available. The
+    /// entry bound by [`resolve_custom`](Self::resolve_custom) is used without
+    /// touching the registry.
     pub fn tensor_at(&self, point: Point3<f32>) -> MetricTensor {
         match self.geometry {
             GeometryType::Euclidean => MetricTensor::identity(),
@@ -174,12 +363,17 @@ impl Metric {
                 let phi = point.y.atan2(point.x);
                 MetricTensor::spherical(self.parameters.radius, theta, phi)
             }
-            GeometryType::Hyperbolic => {
-                MetricTensor::hyperbolic_poincare(point.x, point.y)
-            }
+            GeometryType::Hyperbolic => match self.parameters.hyperbolic_model {
+                HyperbolicModel::Poincare => MetricTensor::hyperbolic_poincare(point.x, point.y),
+                HyperbolicModel::Hyperboloid => MetricTensor::hyperboloid(point.x, point.y),
+            },
             GeometryType::Custom => {
                 if let Some(custom_fn) = self.parameters.custom_fn {
                     custom_fn(point)
+                } else if let Some(resolved) = &self.resolved_custom {
+                    resolved(point)
+                } else if let Some(registered) = self.parameters.custom_name.as_deref().and_then(CustomMetricRegistry::get) {
+                    registered(point)
                 } else {
                     MetricTensor::identity()
                 }
@@ -187,6 +381,146 @@ impl Metric {
         }
     }
     
+    /// Get Christoffel symbols of this metric at a point
+    pub fn christoffel_symbols_at(&self, point: Point3<f32>) -> ChristoffelSymbols {
+        ChristoffelSymbols::from_metric_field(self, point)
+    }
+    
+    /// Sectional curvature of the plane through `point` with normal `plane_normal`
+    ///
+    /// The plane holds the chart-space directions perpendicular to
+    /// `plane_normal`, which must be nonzero. Built-in curved geometries are a
+    /// curved surface times a flat line (the radial direction for spherical,
+    /// z for hyperbolic), so planes containing that line have zero curvature.
+    /// They are evaluated analytically, with positions multiplied by `scale` as
+    /// when rendered; custom metrics use finite differences of `tensor_at`.
+    pub fn sectional_curvature(&self, point: Point3<f32>, plane_normal: Vector3<f32>) -> f32 {
+        match self.geometry {
+            GeometryType::Euclidean => 0.0,
+            GeometryType::Spherical | GeometryType::Hyperbolic => {
+                let flat = match self.geometry {
+                    GeometryType::Spherical if point.x != 0.0 || point.y != 0.0 || point.z != 0.0 => {
+                        let n = plane_normal.normalize();
+                        n.dot(Vector3::new(point.x, point.y, point.z).normalize()).powi(2)
+                    }
+                    GeometryType::Spherical => 0.0,
+                    _ => {
+                        let g_inv = self.tensor_at(point).g.invert()
+                            .unwrap_or(Matrix3::from_diagonal(Vector3::new(1.0, 1.0, 1.0)));
+                        plane_normal.z * plane_normal.z / plane_normal.dot(g_inv * plane_normal)
+                    }
+                };
+                self.surface_curvature() * flat
+            }
+            GeometryType::Custom => self.numeric_sectional_curvature(point, plane_normal),
+        }
+    }
+    
+    /// Ricci scalar curvature at `point`
+    ///
+    /// Twice the curvature of the curved surface for the built-in geometries:
+    /// 0 for Euclidean, 2/r² for spherical and −2 for hyperbolic, divided by
+    /// `scale²`. Custom metrics use finite differences of `tensor_at`.
+    pub fn ricci_scalar(&self, point: Point3<f32>) -> f32 {
+        match self.geometry {
+            GeometryType::Euclidean => 0.0,
+            GeometryType::Spherical | GeometryType::Hyperbolic => 2.0 * self.surface_curvature(),
+            GeometryType::Custom => {
+                let riemann = self.riemann_at(point);
+                let g_inv = self.tensor_at(point).g.invert()
+                    .unwrap_or(Matrix3::from_diagonal(Vector3::new(1.0, 1.0, 1.0)));
+                
+                // R = gˢⁿ Rᵘ_σμν
+                let mut scalar = 0.0;
+                for sigma in 0..3 {
+                    for nu in 0..3 {
+                        let ricci: f32 = (0..3).map(|mu| riemann[mu][sigma][mu][nu]).sum();
+                        scalar += g_inv[sigma][nu] * ricci;
+                    }
+                }
+                scalar
+            }
+        }
+    }
+    
+    /// Gaussian curvature of the curved surface factor of a built-in geometry
+    fn surface_curvature(&self) -> f32 {
+        let scale2 = self.scale * self.scale;
+        match self.geometry {
+            GeometryType::Spherical => 1.0 / (self.parameters.radius * self.parameters.radius * scale2),
+            GeometryType::Hyperbolic => -1.0 / scale2,
+            _ => 0.0,
+        }
+    }
+    
+    /// Sectional curvature from the Riemann tensor, K = ⟨R(u,v)v, u⟩ / (|u|²|v|² − ⟨u,v⟩²)
+    fn numeric_sectional_curvature(&self, point: Point3<f32>, plane_normal: Vector3<f32>) -> f32 {
+        let n = plane_normal.normalize();
+        let axis = if n.x.abs() < 0.9 { Vector3::unit_x() } else { Vector3::unit_y() };
+        let u = n.cross(axis).normalize();
+        let v = n.cross(u);
+        
+        let riemann = self.riemann_at(point);
+        let mut r_uvv = Vector3::new(0.0, 0.0, 0.0);
+        for (rho, riemann_rho) in riemann.iter().enumerate() {
+            for (sigma, riemann_sigma) in riemann_rho.iter().enumerate() {
+                for (mu, riemann_mu) in riemann_sigma.iter().enumerate() {
+                    for (nu, component) in riemann_mu.iter().enumerate() {
+                        r_uvv[rho] += component * v[sigma] * u[mu] * v[nu];
+                    }
+                }
+            }
+        }
+        
+        let tensor = self.tensor_at(point);
+        let area2 = tensor.inner_product(u, u) * tensor.inner_product(v, v) - tensor.inner_product(u, v).powi(2);
+        if area2.abs() < f32::EPSILON {
+            return 0.0;
+        }
+        tensor.inner_product(r_uvv, u) / area2
+    }
+    
+    /// Riemann tensor by central differences of the Christoffel symbols
+    ///
+    /// Rᵖ_σμν = ∂_μ Γᵖ_νσ − ∂_ν Γᵖ_μσ + Γᵖ_μλ Γˡ_νσ − Γᵖ_νλ Γˡ_μσ
+    fn riemann_at(&self, point: Point3<f32>) -> RiemannComponents {
+        let h = CURVATURE_STEP;
+        let gamma = ChristoffelSymbols::from_metric_field_with_step(self, point, h).gamma;
+        
+        // dgamma[m][r][a][b] = ∂ₘ Γʳ_ab
+        let mut dgamma = [[[[0.0; 3]; 3]; 3]; 3];
+        for (m, dgamma_m) in dgamma.iter_mut().enumerate() {
+            let mut offset = Vector3::new(0.0, 0.0, 0.0);
+            offset[m] = h;
+            let plus = ChristoffelSymbols::from_metric_field_with_step(self, point + offset, h).gamma;
+            let minus = ChristoffelSymbols::from_metric_field_with_step(self, point - offset, h).gamma;
+            for r in 0..3 {
+                for a in 0..3 {
+                    for b in 0..3 {
+                        dgamma_m[r][a][b] = (plus[r][a][b] - minus[r][a][b]) / (2.0 * h);
+                    }
+                }
+            }
+        }
+        
+        let mut riemann = [[[[0.0; 3]; 3]; 3]; 3];
+        for (rho, riemann_rho) in riemann.iter_mut().enumerate() {
+            for (sigma, riemann_sigma) in riemann_rho.iter_mut().enumerate() {
+                for (mu, riemann_mu) in riemann_sigma.iter_mut().enumerate() {
+                    for (nu, component) in riemann_mu.iter_mut().enumerate() {
+                        let mut value = dgamma[mu][rho][nu][sigma] - dgamma[nu][rho][mu][sigma];
+                        for (lambda, gamma_lambda) in gamma.iter().enumerate() {
+                            value += gamma[rho][mu][lambda] * gamma_lambda[nu][sigma]
+                                - gamma[rho][nu][lambda] * gamma_lambda[mu][sigma];
+                        }
+                        *component = value;
+                    }
+                }
+            }
+        }
+        riemann
+    }
+    
     /// Compute distance between two points
     pub fn distance(&self, a: Point3<f32>, b: Point3<f32>) -> f32 {
         match self.geometry {
@@ -198,9 +532,12 @@ impl Metric {
                 let r = self.parameters.radius;
                 let a_norm = Vector3::new(a.x, a.y, a.z).normalize();
                 let b_norm = Vector3::new(b.x, b.y, b.z).normalize();
-                let cos_angle = a_norm.dot(b_norm).min(1.0).max(-1.0);
+                let cos_angle = a_norm.dot(b_norm).clamp(-1.0, 1.0);
                 r * cos_angle.acos()
             }
+            GeometryType::Hyperbolic if self.parameters.hyperbolic_model == HyperbolicModel::Hyperboloid => {
+                hyperboloid::hyperboloid_distance(hyperboloid::lift(a.x, a.y), hyperboloid::lift(b.x, b.y))
+            }
             GeometryType::Hyperbolic => {
                 // Poincaré disk distance
                 let a_r = (a.x * a.x + a.y * a.y).sqrt();
@@ -242,7 +579,7 @@ impl Metric {
                 tangent,
             );
             
-            transported = transported - correction * 0.01; // Small step
+            transported -= correction * 0.01; // Small step
         }
         
         transported.normalize() * vector.magnitude()
@@ -1,26 +1,43 @@
 //! Manifold-based world representation for non-Euclidean spaces
 
-use cgmath::{Vector3, Matrix4, Point3, Quaternion};
-use std::collections::HashMap;
+use cgmath::{Vector3, Matrix4, InnerSpace, MetricSpace, Point3, Quaternion};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::sync::Arc;
+use serde::{Serialize, Deserialize};
 
 pub mod chart;
 pub mod portal;
 pub mod geodesic;
 pub mod metric;
+pub mod distance_cache;
+pub mod hyperboloid;
 
-pub use chart::{Chart, ChartId, LocalCoordinate};
-pub use portal::{Portal, PortalId, PortalConnection};
+pub use chart::{Chart, ChartBounds, ChartId, LocalCoordinate, WrapMode};
+pub use portal::{Portal, PortalId, PortalConnection, PortalBounds, PortalShape};
 pub use geodesic::{Geodesic, GeodesicPath};
-pub use metric::{Metric, MetricTensor, GeometryType};
+pub use metric::{Metric, MetricTensor, GeometryType, CustomMetricRegistry, CustomMetricFn};
+pub use distance_cache::{DistanceCache, DistanceCacheStats};
+pub use hyperboloid::HyperbolicModel;
 
 /// A manifold representing the entire non-Euclidean world
-#[derive(Clone)]
+///
+/// Serializable with serde. Custom charts keep only the name of their metric,
+/// which must be registered with [`CustomMetricRegistry`] after loading.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Manifold {
     charts: HashMap<ChartId, Arc<Chart>>,
     portals: HashMap<PortalId, Portal>,
     connections: Vec<PortalConnection>,
     active_chart: ChartId,
+    #[serde(skip)]
+    distance_cache: DistanceCache,
+}
+
+impl Default for Manifold {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Manifold {
@@ -35,6 +52,7 @@ impl Manifold {
             portals: HashMap::new(),
             connections: Vec::new(),
             active_chart: ChartId(0),
+            distance_cache: DistanceCache::default(),
         }
     }
     
@@ -46,6 +64,47 @@ impl Manifold {
         id
     }
     
+    /// Add a custom chart whose metric is the registry entry `metric_name`
+    ///
+    /// The name doesn't need to be registered yet; until it is, the chart is flat.
+    pub fn add_custom_chart(&mut self, metric_name: &str) -> ChartId {
+        let id = ChartId(self.charts.len() as u32);
+        let mut chart = Chart::new(id, GeometryType::Custom);
+        chart.set_metric(Metric::custom(metric_name));
+        self.charts.insert(id, Arc::new(chart));
+        id
+    }
+    
+    /// Replace a chart's metric, e.g. with [`Metric::hyperboloid`]
+    pub fn set_chart_metric(&mut self, chart_id: ChartId, metric: Metric) -> Result<(), String> {
+        let chart = self.charts.get_mut(&chart_id)
+            .ok_or_else(|| format!("Chart {:?} not found", chart_id))?;
+        Arc::make_mut(chart).set_metric(metric);
+        Ok(())
+    }
+    
+    /// Bind every custom chart to what is registered under its metric name now
+    ///
+    /// Needed for charts created before their metric was registered, or after
+    /// replacing a registered metric. See [`CustomMetricRegistry`].
+    pub fn resolve_custom_metrics(&mut self) {
+        for chart in self.charts.values_mut() {
+            if chart.metric().parameters.custom_name.is_some() {
+                let mut metric = chart.metric().clone();
+                metric.resolve_custom();
+                Arc::make_mut(chart).set_metric(metric);
+            }
+        }
+    }
+    
+    /// Replace a chart's bounds and wrap mode
+    pub fn set_chart_bounds(&mut self, chart_id: ChartId, bounds: ChartBounds) -> Result<(), String> {
+        let chart = self.charts.get_mut(&chart_id)
+            .ok_or_else(|| format!("Chart {:?} not found", chart_id))?;
+        Arc::make_mut(chart).set_bounds(bounds);
+        Ok(())
+    }
+    
     /// Create a portal connection between two charts
     pub fn create_portal(
         &mut self,
@@ -82,6 +141,45 @@ impl Manifold {
         Ok(id)
     }
     
+    /// Create a portal and its reverse, returning `(forward, reverse)`
+    ///
+    /// The reverse leads from `to_position` back to `from_position` with the
+    /// inverse transform, see [`Portal::create_reverse`].
+    pub fn create_portal_pair(
+        &mut self,
+        from_chart: ChartId,
+        to_chart: ChartId,
+        from_position: Point3<f32>,
+        to_position: Point3<f32>,
+        transform: Matrix4<f32>,
+    ) -> Result<(PortalId, PortalId), String> {
+        let forward = self.create_portal(from_chart, to_chart, from_position, to_position, transform)?;
+        let reverse = self.add_reverse_portal(forward)?;
+        Ok((forward, reverse))
+    }
+    
+    /// Add the reverse of an existing bidirectional portal
+    ///
+    /// Fails for unknown and one-way portals.
+    pub fn add_reverse_portal(&mut self, portal_id: PortalId) -> Result<PortalId, String> {
+        let portal = self.portals.get(&portal_id)
+            .ok_or_else(|| format!("Portal {:?} not found", portal_id))?;
+        if !portal.is_bidirectional() {
+            return Err(format!("Portal {:?} is one-way", portal_id));
+        }
+        
+        let id = PortalId(self.portals.len() as u32);
+        let reverse = portal.create_reverse(id);
+        self.connections.push(PortalConnection {
+            portal_id: id,
+            from_chart: reverse.source_chart(),
+            to_chart: reverse.target_chart(),
+        });
+        self.portals.insert(id, reverse);
+        
+        Ok(id)
+    }
+    
     /// Transform a point from one chart to another through portals
     pub fn transform_between_charts(
         &self,
@@ -95,16 +193,25 @@ impl Manifold {
         
         // Find portal path between charts (simplified - direct portal only)
         for connection in &self.connections {
-            if connection.from_chart == from_chart && connection.to_chart == to_chart {
-                if let Some(portal) = self.portals.get(&connection.portal_id) {
-                    return Some(portal.transform_point(point));
-                }
+            if connection.from_chart == from_chart
+                && connection.to_chart == to_chart
+                && let Some(portal) = self.portals.get(&connection.portal_id)
+            {
+                return Some(portal.transform_point(point));
             }
         }
         
         None
     }
     
+    /// Cheapest chain of active portals from one position to another, in the order they're crossed
+    ///
+    /// The same route [`geodesic_distance`](Self::geodesic_distance) measures.
+    /// Empty when staying in the chart is cheapest; `None` if `to` can't be reached.
+    pub fn portal_route(&self, from: ManifoldPosition, to: ManifoldPosition) -> Option<Vec<PortalId>> {
+        self.shortest_route(from, to).map(|(_, route)| route)
+    }
+    
     /// Get the metric tensor at a point in a specific chart
     pub fn metric_at(&self, chart_id: ChartId, point: Point3<f32>) -> Option<MetricTensor> {
         self.charts.get(&chart_id)
@@ -134,6 +241,144 @@ impl Manifold {
             .map(|chart| chart.parallel_transport(vector, path))
     }
     
+    /// Shortest distance between two positions, travelling through portals if needed
+    ///
+    /// Sums in-chart geodesic distances between portal crossings along the cheapest
+    /// chain of active portals. Returns `None` if the charts are not connected.
+    pub fn geodesic_distance(&self, from: ManifoldPosition, to: ManifoldPosition) -> Option<f32> {
+        self.shortest_route(from, to).map(|(distance, _)| distance)
+    }
+    
+    /// Length and portals of the cheapest route between two positions
+    ///
+    /// Ties between equally cheap portals go to the lowest ids.
+    fn shortest_route(&self, from: ManifoldPosition, to: ManifoldPosition) -> Option<(f32, Vec<PortalId>)> {
+        let in_chart = |chart_id: ChartId, a: Point3<f32>, b: Point3<f32>| {
+            self.charts.get(&chart_id).map(|chart| chart.metric().distance(a, b))
+        };
+        
+        // Cost so far and the last portal crossed
+        let mut best: Option<(f32, Option<PortalId>)> = if from.chart_id == to.chart_id {
+            in_chart(from.chart_id, from.local.to_point(), to.local.to_point()).map(|d| (d, None))
+        } else {
+            None
+        };
+        
+        // Dijkstra over portal exits: cost of arriving on the far side of each
+        // portal, and the portal crossed before it
+        let mut arrival: HashMap<PortalId, (f32, Option<PortalId>)> = HashMap::new();
+        for portal in self.portals_from_chart(from.chart_id) {
+            if portal.is_active()
+                && let Some(d) = in_chart(from.chart_id, from.local.to_point(), portal.source_position())
+            {
+                arrival.insert(portal.id(), (d, None));
+            }
+        }
+        
+        let mut settled: Vec<PortalId> = Vec::new();
+        loop {
+            let next = arrival
+                .iter()
+                .filter(|(id, _)| !settled.contains(id))
+                .min_by(|a, b| a.1.0.total_cmp(&b.1.0).then(a.0.0.cmp(&b.0.0)))
+                .map(|(id, (d, _))| (*id, *d));
+            let Some((id, cost)) = next else { break };
+            if best.is_some_and(|(b, _)| cost >= b) {
+                break;
+            }
+            settled.push(id);
+            
+            let portal = &self.portals[&id];
+            let chart_id = portal.target_chart();
+            let exit = portal.target_position();
+            
+            if chart_id == to.chart_id
+                && let Some(d) = in_chart(chart_id, exit, to.local.to_point())
+            {
+                let total = cost + d;
+                if best.is_none_or(|(b, _)| total < b) {
+                    best = Some((total, Some(id)));
+                }
+            }
+            
+            for onward in self.portals_from_chart(chart_id) {
+                if !onward.is_active() || settled.contains(&onward.id()) {
+                    continue;
+                }
+                if let Some(d) = in_chart(chart_id, exit, onward.source_position()) {
+                    let total = cost + d;
+                    let entry = arrival.entry(onward.id()).or_insert((f32::INFINITY, None));
+                    if total < entry.0 {
+                        *entry = (total, Some(id));
+                    }
+                }
+            }
+        }
+        
+        let (distance, mut last) = best.filter(|(d, _)| d.is_finite())?;
+        let mut route = Vec::new();
+        while let Some(id) = last {
+            route.push(id);
+            last = arrival[&id].1;
+        }
+        route.reverse();
+        Some((distance, route))
+    }
+    
+    /// Metric distance between two points in a chart, memoized on a quantized grid
+    ///
+    /// Points within the same grid cell share a result (see [`DistanceCache`]).
+    /// Cached values go stale when a chart's metric changes; call
+    /// [`clear_distance_cache`](Self::clear_distance_cache) after such changes.
+    pub fn distance_cached(&self, chart_id: ChartId, a: Point3<f32>, b: Point3<f32>) -> Option<f32> {
+        let chart = self.charts.get(&chart_id)?;
+        Some(self.distance_cache.get_or_insert_with(chart_id, a, b, || chart.metric().distance(a, b)))
+    }
+    
+    /// Set the grid resolution used by `distance_cached`, clearing the cache
+    pub fn set_distance_cache_resolution(&mut self, resolution: f32) {
+        self.distance_cache = DistanceCache::new(resolution);
+    }
+    
+    /// Forget all cached distances
+    pub fn clear_distance_cache(&self) {
+        self.distance_cache.clear();
+    }
+    
+    /// Get the distance cache, e.g. to inspect its hit rate
+    pub fn distance_cache(&self) -> &DistanceCache {
+        &self.distance_cache
+    }
+    
+    /// Find the nearest active portal in a chart and its distance
+    ///
+    /// Distances are measured to each portal's center with the chart metric.
+    pub fn nearest_portal(&self, position: Point3<f32>, chart: ChartId) -> Option<(PortalId, f32)> {
+        let metric = self.charts.get(&chart)?.metric();
+        self.portals_from_chart(chart)
+            .into_iter()
+            .filter(|portal| portal.is_active())
+            .map(|portal| (portal.id(), metric.distance(position, portal.bounds().center)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+    
+    /// Active portals in a chart whose centers lie within `radius`, nearest first
+    pub fn portals_within(&self, position: Point3<f32>, chart: ChartId, radius: f32) -> Vec<PortalId> {
+        let Some(chart_ref) = self.charts.get(&chart) else {
+            return Vec::new();
+        };
+        let metric = chart_ref.metric();
+        
+        let mut found: Vec<(PortalId, f32)> = self.portals_from_chart(chart)
+            .into_iter()
+            .filter(|portal| portal.is_active())
+            .map(|portal| (portal.id(), metric.distance(position, portal.bounds().center)))
+            .filter(|(_, distance)| *distance <= radius)
+            .collect();
+        found.sort_by(|a, b| a.1.total_cmp(&b.1));
+        found.into_iter().map(|(id, _)| id).collect()
+    }
+    
     /// Get all portals from a specific chart
     pub fn portals_from_chart(&self, chart_id: ChartId) -> Vec<&Portal> {
         self.connections
@@ -143,21 +388,161 @@ impl Manifold {
             .collect()
     }
     
-    /// Check if a ray intersects any portal
+    /// Find the nearest active portal a ray enters from the front side
     pub fn ray_portal_intersection(
         &self,
         origin: Point3<f32>,
         direction: Vector3<f32>,
         chart_id: ChartId,
     ) -> Option<(PortalId, Point3<f32>, ChartId)> {
-        for portal in self.portals_from_chart(chart_id) {
-            if let Some(intersection) = portal.ray_intersection(origin, direction) {
-                return Some((portal.id(), intersection, portal.target_chart()));
+        self.portals_from_chart(chart_id)
+            .into_iter()
+            .filter(|portal| portal.is_active() && portal.is_front_facing(direction))
+            .filter_map(|portal| {
+                portal.ray_hit(origin, direction)
+                    .map(|(t, intersection)| (t, portal, intersection))
+            })
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(_, portal, intersection)| (portal.id(), intersection, portal.target_chart()))
+    }
+    
+    /// Cast a ray through the manifold, following portals into their target charts
+    ///
+    /// Walks the geodesic from `origin` in `chart_id`; on entering an active portal
+    /// from the front, the ray is carried through it and continues in the target
+    /// chart with whatever distance is left. Gives up following portals after
+    /// [`Self::MAX_RAY_CROSSINGS`] crossings. Returns `None` for an unknown chart
+    /// or a zero direction.
+    pub fn cast_ray(
+        &self,
+        origin: Point3<f32>,
+        direction: Vector3<f32>,
+        chart_id: ChartId,
+        max_distance: f32,
+        steps: usize,
+    ) -> Option<ManifoldRay> {
+        self.charts.get(&chart_id)?;
+        if direction.magnitude2() <= f32::EPSILON * f32::EPSILON {
+            return None;
+        }
+        
+        let mut segments = Vec::new();
+        let mut origin = origin;
+        let mut direction = direction.normalize();
+        let mut chart_id = chart_id;
+        let mut remaining = max_distance.max(0.0);
+        
+        while let Some(chart) = self.charts.get(&chart_id) {
+            let path = geodesic::GeodesicRay::cast(origin, direction, chart.metric(), remaining, steps.max(1)).path;
+            
+            let Some((index, portal, hit)) = self.first_portal_crossing(&path, chart_id) else {
+                segments.push(RaySegment { chart: chart_id, path, portal: None });
+                break;
+            };
+            
+            // Cut the path at the portal surface
+            let tangent = path.tangents[index];
+            let mut truncated = GeodesicPath::new(path.geometry);
+            for (point, tangent) in path.points.iter().zip(&path.tangents).take(index + 1) {
+                truncated.add_point(*point, *tangent);
+            }
+            truncated.add_point(hit, tangent);
+            remaining -= truncated.arc_length;
+            segments.push(RaySegment { chart: chart_id, path: truncated, portal: Some(portal.id()) });
+            
+            if segments.len() >= Self::MAX_RAY_CROSSINGS || remaining <= 0.0 {
+                break;
             }
+            
+            // Step just past the exit so the target chart's reverse portal isn't hit again
+            direction = portal.transform_vector(tangent).normalize();
+            origin = portal.transform_point(hit) + direction * Self::RAY_EXIT_MARGIN;
+            chart_id = portal.target_chart();
         }
+        
+        Some(ManifoldRay { segments })
+    }
+    
+    /// Most portal crossings [`Self::cast_ray`] follows before stopping
+    pub const MAX_RAY_CROSSINGS: usize = 16;
+    
+    /// Distance a cast ray is pushed past a portal exit
+    const RAY_EXIT_MARGIN: f32 = 1e-3;
+    
+    /// First portal entered from the front along `path`: the index of the point
+    /// before the crossing, the portal and the hit point
+    fn first_portal_crossing(&self, path: &GeodesicPath, chart_id: ChartId) -> Option<(usize, &Portal, Point3<f32>)> {
+        let portals: Vec<&Portal> = self.portals_from_chart(chart_id)
+            .into_iter()
+            .filter(|portal| portal.is_active())
+            .collect();
+        
+        for (index, pair) in path.points.windows(2).enumerate() {
+            let step = pair[1] - pair[0];
+            let length = step.magnitude();
+            if length <= f32::EPSILON {
+                continue;
+            }
+            let direction = step / length;
+            
+            let nearest = portals.iter()
+                .filter(|portal| portal.is_front_facing(direction))
+                .filter_map(|portal| portal.ray_hit(pair[0], direction).map(|(t, hit)| (t, *portal, hit)))
+                .filter(|(t, _, _)| *t <= length)
+                .min_by(|a, b| a.0.total_cmp(&b.0));
+            
+            if let Some((_, portal, hit)) = nearest {
+                return Some((index, portal, hit));
+            }
+        }
+        
         None
     }
     
+    /// Get portal by ID
+    pub fn portal(&self, id: PortalId) -> Option<&Portal> {
+        self.portals.get(&id)
+    }
+    
+    /// Get mutable portal by ID
+    pub fn portal_mut(&mut self, id: PortalId) -> Option<&mut Portal> {
+        self.portals.get_mut(&id)
+    }
+    
+    /// Move a portal's entrance along with the exit of any reverse portal
+    ///
+    /// Reverse portals are those leading back between the same charts with
+    /// their ends on this portal's, as made by [`create_portal_pair`](Self::create_portal_pair).
+    /// Returns `false` if the portal doesn't exist.
+    pub fn move_portal(&mut self, id: PortalId, position: Point3<f32>) -> bool {
+        let Some(portal) = self.portals.get(&id) else {
+            return false;
+        };
+        let reverses: Vec<PortalId> = self.portals.values()
+            .filter(|back| back.id() != id && Self::is_reverse_of(back, portal))
+            .map(|back| back.id())
+            .collect();
+        
+        for reverse in reverses {
+            if let Some(back) = self.portals.get_mut(&reverse) {
+                back.set_target_position(position);
+            }
+        }
+        if let Some(portal) = self.portals.get_mut(&id) {
+            portal.set_source_position(position);
+        }
+        true
+    }
+    
+    /// Whether `back` leads from `portal`'s exit back to its entrance
+    fn is_reverse_of(back: &Portal, portal: &Portal) -> bool {
+        const TOLERANCE: f32 = 1e-3;
+        back.source_chart() == portal.target_chart()
+            && back.target_chart() == portal.source_chart()
+            && back.source_position().distance(portal.target_position()) < TOLERANCE
+            && back.target_position().distance(portal.source_position()) < TOLERANCE
+    }
+    
     /// Get chart by ID
     pub fn chart(&self, id: ChartId) -> Option<&Arc<Chart>> {
         self.charts.get(&id)
@@ -179,6 +564,162 @@ impl Manifold {
     pub fn charts(&self) -> &HashMap<ChartId, Arc<Chart>> {
         &self.charts
     }
+    
+    /// Get all portals
+    pub fn portals(&self) -> &HashMap<PortalId, Portal> {
+        &self.portals
+    }
+    
+    /// Check the manifold for inconsistencies, ordered by chart then portal id
+    ///
+    /// Reports portals whose charts are missing, bidirectional portals with no
+    /// portal leading back, portal ends outside their chart's bounds, and
+    /// connections that disagree with the portals, and custom charts whose
+    /// metric isn't registered. Runs in time linear in the number of portals
+    /// and charts, so it's fine to call after building a world.
+    pub fn validate(&self) -> Vec<ManifoldWarning> {
+        let mut warnings = Vec::new();
+        if !self.charts.contains_key(&self.active_chart) {
+            warnings.push(ManifoldWarning::MissingActiveChart(self.active_chart));
+        }
+        
+        let mut charts: Vec<&Arc<Chart>> = self.charts.values().collect();
+        charts.sort_by_key(|chart| chart.id().0);
+        for chart in charts {
+            if let Some(name) = &chart.metric().parameters.custom_name
+                && !CustomMetricRegistry::is_registered(name)
+            {
+                warnings.push(ManifoldWarning::UnregisteredMetric { chart: chart.id(), name: name.clone() });
+            }
+        }
+        
+        let mut portals: Vec<&Portal> = self.portals.values().collect();
+        portals.sort_by_key(|portal| portal.id().0);
+        
+        let mut by_charts: HashMap<(ChartId, ChartId), Vec<&Portal>> = HashMap::new();
+        for portal in &portals {
+            by_charts.entry((portal.source_chart(), portal.target_chart())).or_default().push(portal);
+        }
+        
+        let mut connected = HashSet::new();
+        for connection in &self.connections {
+            let matches = self.portals.get(&connection.portal_id).is_some_and(|portal| {
+                portal.source_chart() == connection.from_chart && portal.target_chart() == connection.to_chart
+            });
+            if matches {
+                connected.insert(connection.portal_id);
+            } else {
+                warnings.push(ManifoldWarning::ConnectionMismatch { portal: connection.portal_id });
+            }
+        }
+        
+        for portal in portals {
+            let id = portal.id();
+            if !connected.contains(&id) {
+                warnings.push(ManifoldWarning::ConnectionMismatch { portal: id });
+            }
+            
+            let ends = [
+                (portal.source_chart(), portal.source_position()),
+                (portal.target_chart(), portal.target_position()),
+            ];
+            let mut charts_exist = true;
+            for (chart_id, position) in ends {
+                match self.charts.get(&chart_id) {
+                    None => {
+                        charts_exist = false;
+                        warnings.push(ManifoldWarning::MissingChart { portal: id, chart: chart_id });
+                    }
+                    Some(chart) if !chart.contains(LocalCoordinate::from_point(position)) => {
+                        warnings.push(ManifoldWarning::PortalOutOfBounds { portal: id, chart: chart_id, position });
+                    }
+                    Some(_) => {}
+                }
+            }
+            
+            if charts_exist && portal.is_bidirectional() {
+                let reverse = by_charts.get(&(portal.target_chart(), portal.source_chart()));
+                let has_reverse = reverse.is_some_and(|candidates| {
+                    candidates.iter().any(|back| Self::is_reverse_of(back, portal))
+                });
+                if !has_reverse {
+                    warnings.push(ManifoldWarning::MissingReverse { portal: id });
+                }
+            }
+        }
+        
+        warnings
+    }
+}
+
+/// Part of a [`ManifoldRay`] lying in a single chart
+#[derive(Debug, Clone)]
+pub struct RaySegment {
+    pub chart: ChartId,
+    pub path: GeodesicPath,
+    /// Portal the segment ends at, if the ray left the chart through one
+    pub portal: Option<PortalId>,
+}
+
+/// Ray cast through the manifold by [`Manifold::cast_ray`], one segment per chart visited
+#[derive(Debug, Clone)]
+pub struct ManifoldRay {
+    pub segments: Vec<RaySegment>,
+}
+
+impl ManifoldRay {
+    /// Charts visited, in order
+    pub fn charts(&self) -> Vec<ChartId> {
+        self.segments.iter().map(|segment| segment.chart).collect()
+    }
+    
+    /// Portals passed through, in order
+    pub fn portals(&self) -> Vec<PortalId> {
+        self.segments.iter().filter_map(|segment| segment.portal).collect()
+    }
+    
+    /// Total length travelled across all segments
+    pub fn length(&self) -> f32 {
+        self.segments.iter().map(|segment| segment.path.arc_length).sum()
+    }
+    
+    /// Where the ray ended
+    pub fn end(&self) -> Option<ManifoldPosition> {
+        let last = self.segments.last()?;
+        last.path.points.last().map(|point| ManifoldPosition::new(last.chart, *point))
+    }
+}
+
+/// Inconsistency found by [`Manifold::validate`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ManifoldWarning {
+    /// The active chart doesn't exist
+    MissingActiveChart(ChartId),
+    /// A portal leads from or to a chart that doesn't exist
+    MissingChart { portal: PortalId, chart: ChartId },
+    /// A portal is marked bidirectional but no portal leads back from its exit
+    MissingReverse { portal: PortalId },
+    /// One end of a portal lies outside its chart's bounds
+    PortalOutOfBounds { portal: PortalId, chart: ChartId, position: Point3<f32> },
+    /// A connection names a missing portal or the wrong charts, or a portal has no connection
+    ConnectionMismatch { portal: PortalId },
+    /// A custom chart names a metric that isn't in the [`CustomMetricRegistry`]
+    UnregisteredMetric { chart: ChartId, name: String },
+}
+
+impl fmt::Display for ManifoldWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingActiveChart(chart) => write!(f, "active chart {:?} does not exist", chart),
+            Self::MissingChart { portal, chart } => write!(f, "portal {:?} references missing chart {:?}", portal, chart),
+            Self::MissingReverse { portal } => write!(f, "bidirectional portal {:?} has no reverse portal", portal),
+            Self::PortalOutOfBounds { portal, chart, position } => {
+                write!(f, "portal {:?} end at {:?} is outside the bounds of chart {:?}", portal, position, chart)
+            }
+            Self::ConnectionMismatch { portal } => write!(f, "connection for portal {:?} does not match the portal", portal),
+            Self::UnregisteredMetric { chart, name } => write!(f, "chart {:?} uses unregistered custom metric {:?}", chart, name),
+        }
+    }
 }
 
 /// Position in the manifold (chart + local coordinates)
@@ -201,6 +742,42 @@ impl ManifoldPosition {
         manifold.chart(self.chart_id)
             .map(|chart| chart.to_world(self.local))
     }
+    
+    /// World position as seen from `viewer`, looking through portals
+    ///
+    /// Follows [`Manifold::portal_route`] from the viewer to this position and
+    /// undoes each portal's transform, so something one portal away lands where it
+    /// appears through that portal rather than on top of the viewer chart's own
+    /// contents. This is the route [`LodSystem`](crate::ecs::LodSystem) measures
+    /// distances along. Returns `None` if the charts aren't connected.
+    pub fn to_world_relative_to(&self, viewer: ManifoldPosition, manifold: &Manifold) -> Option<Point3<f32>> {
+        let reference_chart = viewer.chart_id;
+        let route = manifold.portal_route(viewer, *self)?;
+        let local = route.iter()
+            .rev()
+            .filter_map(|id| manifold.portal(*id))
+            .fold(self.local.to_point(), |point, portal| portal.inverse_transform_point(point));
+        
+        manifold.chart(reference_chart)
+            .map(|chart| chart.to_world(LocalCoordinate(local)))
+    }
+    
+    /// Interpolate between two positions along the chart's geodesic
+    ///
+    /// Blending across charts is undefined, so `a` is returned unchanged unless
+    /// both positions share a chart that exists in the manifold.
+    pub fn lerp(a: ManifoldPosition, b: ManifoldPosition, t: f32, manifold: &Manifold) -> ManifoldPosition {
+        const STEPS: usize = 16;
+        
+        if a.chart_id != b.chart_id {
+            return a;
+        }
+        
+        manifold.compute_geodesic(a.local.to_point(), b.local.to_point(), a.chart_id, STEPS)
+            .and_then(|path| path.interpolate(t.clamp(0.0, 1.0)))
+            .map(|point| ManifoldPosition::new(a.chart_id, point))
+            .unwrap_or(a)
+    }
 }
 
 /// Orientation in the manifold with parallel transport
@@ -219,19 +796,42 @@ impl ManifoldOrientation {
     }
     
     /// Update orientation with parallel transport along a path
+    ///
+    /// The transported frame is re-orthonormalized so numerical error from
+    /// repeated transports can't accumulate into shear.
     pub fn transport_along(&mut self, path: &GeodesicPath, manifold: &Manifold, chart_id: ChartId) {
         if let Some(chart) = manifold.chart(chart_id) {
             // Apply parallel transport to maintain orientation consistency
             let transport_matrix = chart.compute_transport_matrix(path);
             self.tangent_space = transport_matrix * self.tangent_space;
-            // Update quaternion from the transported matrix (convert to Matrix3 first)
-            use cgmath::Matrix3;
-            let mat3 = Matrix3::new(
-                self.tangent_space.x.x, self.tangent_space.x.y, self.tangent_space.x.z,
-                self.tangent_space.y.x, self.tangent_space.y.y, self.tangent_space.y.z,
-                self.tangent_space.z.x, self.tangent_space.z.y, self.tangent_space.z.z,
-            );
-            self.quaternion = Quaternion::from(mat3);
+            self.orthonormalize();
         }
     }
+    
+    /// Gram-Schmidt the frame in `tangent_space` and resync a unit `quaternion` from it
+    ///
+    /// The first axis keeps its direction, the second is made perpendicular to
+    /// it and the third is rebuilt as their cross product, so the frame stays
+    /// right-handed. Degenerate axes are replaced by arbitrary perpendicular ones.
+    pub fn orthonormalize(&mut self) {
+        use cgmath::{InnerSpace, Matrix3};
+        
+        let x = self.tangent_space.x.truncate();
+        let x = if x.magnitude2() > f32::EPSILON { x.normalize() } else { Vector3::unit_x() };
+        
+        let y = self.tangent_space.y.truncate();
+        let y = y - x * x.dot(y);
+        let y = if y.magnitude2() > f32::EPSILON {
+            y.normalize()
+        } else {
+            // Any axis not parallel to x gives a valid perpendicular
+            let helper = if x.x.abs() < 0.9 { Vector3::unit_x() } else { Vector3::unit_y() };
+            x.cross(helper).normalize()
+        };
+        let z = x.cross(y);
+        
+        let frame = Matrix3::from_cols(x, y, z);
+        self.tangent_space = Matrix4::from(frame);
+        self.quaternion = Quaternion::from(frame).normalize();
+    }
 }
\ No newline at end of file
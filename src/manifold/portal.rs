@@ -1,14 +1,16 @@
 //! Portal system for connecting non-Euclidean spaces
 
-use cgmath::{Point3, Vector3, Matrix4, InnerSpace, Transform, SquareMatrix};
+use cgmath::{Point2, Point3, Vector2, Vector3, Matrix4, InnerSpace, Transform, SquareMatrix};
+use serde::{Serialize, Deserialize};
 use super::ChartId;
+use crate::math::{Obb, Plane, Ray};
 
 /// Unique identifier for a portal
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct PortalId(pub u32);
 
 /// Portal connection between two charts
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PortalConnection {
     pub portal_id: PortalId,
     pub from_chart: ChartId,
@@ -16,7 +18,7 @@ pub struct PortalConnection {
 }
 
 /// A portal that connects two charts in the manifold
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Portal {
     id: PortalId,
     from_chart: ChartId,
@@ -30,19 +32,107 @@ pub struct Portal {
 }
 
 /// Portal boundary for intersection testing
-#[derive(Debug, Clone)]
+///
+/// `right`, `up` and `normal` form an orthonormal basis; width runs along `right`
+/// and height along `up`. Build with [`PortalBounds::new`] to keep them consistent.
+/// Elliptical and polygonal shapes carry their own extent in the `(right, up)`
+/// frame and ignore width and height.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PortalBounds {
     pub center: Point3<f32>,
     pub normal: Vector3<f32>,
+    pub right: Vector3<f32>,
+    pub up: Vector3<f32>,
     pub width: f32,
     pub height: f32,
     pub shape: PortalShape,
 }
 
-#[derive(Debug, Clone, Copy)]
+impl PortalBounds {
+    /// Create bounds facing along `normal`, deriving the in-plane basis
+    pub fn new(center: Point3<f32>, normal: Vector3<f32>, width: f32, height: f32, shape: PortalShape) -> Self {
+        let normal = normal.normalize();
+        let (right, up) = Self::basis(normal);
+        Self { center, normal, right, up, width, height, shape }
+    }
+    
+    /// In-plane `(right, up)` axes for a unit normal, with `right × up = normal`
+    ///
+    /// Uses world up as the reference, switching to world Z when the portal faces
+    /// straight up or down so the basis never degenerates.
+    pub fn basis(normal: Vector3<f32>) -> (Vector3<f32>, Vector3<f32>) {
+        let reference = if normal.y.abs() > 0.999 {
+            Vector3::new(0.0, 0.0, 1.0)
+        } else {
+            Vector3::new(0.0, 1.0, 0.0)
+        };
+        let right = reference.cross(normal).normalize();
+        let up = normal.cross(right);
+        (right, up)
+    }
+    
+    /// Box spanning the portal rectangle, unbounded along the normal
+    pub fn obb(&self) -> Obb {
+        Obb::new(
+            self.center,
+            [self.right, self.up, self.normal],
+            Vector3::new(self.width / 2.0, self.height / 2.0, f32::INFINITY),
+        )
+    }
+    
+    /// Project a point into the portal plane's `(right, up)` frame around the center
+    pub fn to_local(&self, point: Point3<f32>) -> Point2<f32> {
+        let offset = point - self.center;
+        Point2::new(offset.dot(self.right), offset.dot(self.up))
+    }
+    
+    /// Map a point in the `(right, up)` frame back onto the portal plane
+    pub fn from_local(&self, local: Point2<f32>) -> Point3<f32> {
+        self.center + self.right * local.x + self.up * local.y
+    }
+    
+    /// Points around the edge of the portal, in order
+    ///
+    /// Curved shapes use [`OUTLINE_SEGMENTS`](Self::OUTLINE_SEGMENTS) points and
+    /// `Custom` falls back to the width × height rectangle.
+    pub fn outline(&self) -> Vec<Point3<f32>> {
+        let ellipse = |rx: f32, ry: f32| -> Vec<Point3<f32>> {
+            (0..Self::OUTLINE_SEGMENTS)
+                .map(|i| {
+                    let angle = i as f32 * std::f32::consts::TAU / Self::OUTLINE_SEGMENTS as f32;
+                    self.from_local(Point2::new(rx * angle.cos(), ry * angle.sin()))
+                })
+                .collect()
+        };
+        
+        match &self.shape {
+            PortalShape::Rectangular | PortalShape::Custom => {
+                let hw = self.width / 2.0;
+                let hh = self.height / 2.0;
+                [(-hw, -hh), (hw, -hh), (hw, hh), (-hw, hh)]
+                    .iter()
+                    .map(|&(x, y)| self.from_local(Point2::new(x, y)))
+                    .collect()
+            }
+            PortalShape::Circular => ellipse(self.width / 2.0, self.width / 2.0),
+            PortalShape::Elliptical { rx, ry } => ellipse(*rx, *ry),
+            PortalShape::Polygon(vertices) => vertices.iter().map(|&v| self.from_local(v)).collect(),
+        }
+    }
+    
+    /// Number of points in the outline of curved shapes
+    pub const OUTLINE_SEGMENTS: usize = 32;
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PortalShape {
     Rectangular,
+    /// Circle with a diameter of the bounds width
     Circular,
+    /// Ellipse with semi-axes `rx` along `right` and `ry` along `up`
+    Elliptical { rx: f32, ry: f32 },
+    /// Convex polygon with vertices in the `(right, up)` frame, in either winding order
+    Polygon(Vec<Point2<f32>>),
     Custom,
 }
 
@@ -56,13 +146,13 @@ impl Portal {
         to_position: Point3<f32>,
         transform: Matrix4<f32>,
     ) -> Self {
-        let bounds = PortalBounds {
-            center: from_position,
-            normal: Vector3::new(0.0, 0.0, 1.0),
-            width: 2.0,
-            height: 3.0,
-            shape: PortalShape::Rectangular,
-        };
+        let bounds = PortalBounds::new(
+            from_position,
+            Vector3::new(0.0, 0.0, 1.0),
+            2.0,
+            3.0,
+            PortalShape::Rectangular,
+        );
         
         Self {
             id,
@@ -92,18 +182,38 @@ impl Portal {
         self.to_chart
     }
     
+    /// Get the portal position in the source chart
+    pub fn source_position(&self) -> Point3<f32> {
+        self.from_position
+    }
+    
+    /// Get the exit position in the target chart
+    pub fn target_position(&self) -> Point3<f32> {
+        self.to_position
+    }
+    
+    /// Move the portal entrance, keeping its bounds centered on it
+    ///
+    /// A reverse portal's exit is left where it was; use
+    /// [`Manifold::move_portal`](super::Manifold::move_portal) to move both.
+    pub fn set_source_position(&mut self, position: Point3<f32>) {
+        self.from_position = position;
+        self.bounds.center = position;
+    }
+    
+    /// Move the portal exit
+    pub fn set_target_position(&mut self, position: Point3<f32>) {
+        self.to_position = position;
+    }
+    
     /// Transform a point through the portal
+    ///
+    /// The point is expressed relative to the source portal center, rotated by the
+    /// linear part of the portal transform, and re-anchored at the target portal
+    /// center. Any translation stored in the transform is ignored.
     pub fn transform_point(&self, point: Point3<f32>) -> Point3<f32> {
-        // Apply portal transformation matrix
-        let transformed = self.transform.transform_point(point);
-        
-        // Translate to target position
-        let offset = self.to_position - self.from_position;
-        Point3::new(
-            transformed.x + offset.x,
-            transformed.y + offset.y,
-            transformed.z + offset.z,
-        )
+        let local = point - self.from_position;
+        self.to_position + self.transform.transform_vector(local)
     }
     
     /// Transform a vector (direction) through the portal
@@ -111,52 +221,69 @@ impl Portal {
         self.transform.transform_vector(vector)
     }
     
+    /// Undo [`transform_point`](Self::transform_point), taking a point at the exit back to the entrance
+    pub fn inverse_transform_point(&self, point: Point3<f32>) -> Point3<f32> {
+        let inverse = self.transform.invert().unwrap_or(Matrix4::from_scale(1.0));
+        self.from_position + inverse.transform_vector(point - self.to_position)
+    }
+    
     /// Check if a ray intersects the portal
     pub fn ray_intersection(&self, origin: Point3<f32>, direction: Vector3<f32>) -> Option<Point3<f32>> {
-        // Plane equation: (p - center) · normal = 0
-        let denominator = direction.dot(self.bounds.normal);
-        
-        // Ray is parallel to portal plane
-        if denominator.abs() < 1e-6 {
-            return None;
-        }
-        
-        let t = (self.bounds.center - origin).dot(self.bounds.normal) / denominator;
-        
-        // Intersection is behind the ray origin
-        if t < 0.0 {
-            return None;
-        }
-        
+        self.ray_hit(origin, direction).map(|(_, point)| point)
+    }
+    
+    /// Intersect a ray with the portal, returning the ray parameter `t` and the hit point
+    pub fn ray_hit(&self, origin: Point3<f32>, direction: Vector3<f32>) -> Option<(f32, Point3<f32>)> {
+        // Misses parallel rays and planes behind the origin
+        let t = self.plane().intersect_ray(&Ray { origin, direction })?;
         let intersection = origin + direction * t;
         
         // Check if intersection is within portal bounds
         if self.contains_point(intersection) {
-            Some(intersection)
+            Some((t, intersection))
         } else {
             None
         }
     }
     
+    /// Check if a ray travelling along `direction` enters the portal from its front side
+    pub fn is_front_facing(&self, direction: Vector3<f32>) -> bool {
+        direction.dot(self.bounds.normal) < 0.0
+    }
+    
+    /// Get the portal surface normal (pointing out of the front side)
+    pub fn normal(&self) -> Vector3<f32> {
+        self.bounds.normal
+    }
+    
+    /// Get the plane the portal lies in, facing out of the front side
+    pub fn plane(&self) -> Plane {
+        Plane::from_point_normal(self.bounds.center, self.bounds.normal)
+    }
+    
+    /// Get the portal boundary
+    pub fn bounds(&self) -> &PortalBounds {
+        &self.bounds
+    }
+    
+    /// Replace the portal boundary
+    pub fn set_bounds(&mut self, bounds: PortalBounds) {
+        self.bounds = bounds;
+    }
+    
     /// Check if a point is within the portal bounds
+    ///
+    /// The test is done in the portal plane, ignoring distance along the normal.
     pub fn contains_point(&self, point: Point3<f32>) -> bool {
-        let local = point - self.bounds.center;
+        let local = self.bounds.to_local(point);
         
-        match self.bounds.shape {
-            PortalShape::Rectangular => {
-                // Project onto portal plane
-                let right = self.bounds.normal.cross(Vector3::new(0.0, 1.0, 0.0)).normalize();
-                let up = self.bounds.normal.cross(right);
-                
-                let x = local.dot(right);
-                let y = local.dot(up);
-                
-                x.abs() <= self.bounds.width / 2.0 && y.abs() <= self.bounds.height / 2.0
-            }
-            PortalShape::Circular => {
-                let distance = (local - local.dot(self.bounds.normal) * self.bounds.normal).magnitude();
-                distance <= self.bounds.width / 2.0
+        match &self.bounds.shape {
+            PortalShape::Rectangular => self.bounds.obb().contains(point),
+            PortalShape::Circular => local.x.hypot(local.y) <= self.bounds.width / 2.0,
+            PortalShape::Elliptical { rx, ry } => {
+                (local.x / rx).powi(2) + (local.y / ry).powi(2) <= 1.0
             }
+            PortalShape::Polygon(vertices) => convex_polygon_contains(vertices, local),
             PortalShape::Custom => {
                 // Custom shape logic would go here
                 true
@@ -191,6 +318,13 @@ impl Portal {
         self.bidirectional
     }
     
+    /// Mark the portal as two-way (the default) or one-way
+    ///
+    /// One-way portals get no reverse from [`Manifold::add_reverse_portal`](super::Manifold::add_reverse_portal).
+    pub fn set_bidirectional(&mut self, bidirectional: bool) {
+        self.bidirectional = bidirectional;
+    }
+    
     /// Create the reverse portal (for bidirectional connections)
     pub fn create_reverse(&self, id: PortalId) -> Portal {
         let inverse_transform = self.transform.invert()
@@ -203,19 +337,45 @@ impl Portal {
             from_position: self.to_position,
             to_position: self.from_position,
             transform: inverse_transform,
-            bounds: PortalBounds {
-                center: self.to_position,
-                normal: self.transform_vector(self.bounds.normal),
-                width: self.bounds.width,
-                height: self.bounds.height,
-                shape: self.bounds.shape,
-            },
+            bounds: PortalBounds::new(
+                self.to_position,
+                self.transform_vector(self.bounds.normal),
+                self.bounds.width,
+                self.bounds.height,
+                self.bounds.shape.clone(),
+            ),
             active: self.active,
             bidirectional: self.bidirectional,
         }
     }
 }
 
+/// Check if a point lies inside (or on the edge of) a convex polygon
+///
+/// Works for either winding order; polygons with fewer than 3 vertices contain nothing.
+fn convex_polygon_contains(vertices: &[Point2<f32>], point: Point2<f32>) -> bool {
+    if vertices.len() < 3 {
+        return false;
+    }
+    
+    let mut sign = 0.0f32;
+    for (i, &a) in vertices.iter().enumerate() {
+        let b = vertices[(i + 1) % vertices.len()];
+        let edge: Vector2<f32> = b - a;
+        let to_point: Vector2<f32> = point - a;
+        let cross = edge.perp_dot(to_point);
+        if cross == 0.0 {
+            continue;
+        }
+        if sign == 0.0 {
+            sign = cross.signum();
+        } else if cross.signum() != sign {
+            return false;
+        }
+    }
+    true
+}
+
 /// Portal renderer for visualizing portal edges and transitions
 #[allow(dead_code)]
 pub struct PortalRenderer {
@@ -224,6 +384,12 @@ pub struct PortalRenderer {
     depth_fade: f32,
 }
 
+impl Default for PortalRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PortalRenderer {
     pub fn new() -> Self {
         Self {
@@ -235,40 +401,10 @@ impl PortalRenderer {
     
     /// Generate portal edge geometry for rendering
     pub fn generate_edge_mesh(&self, portal: &Portal) -> Vec<Point3<f32>> {
-        let mut vertices = Vec::new();
-        
         match portal.bounds.shape {
-            PortalShape::Rectangular => {
-                let half_width = portal.bounds.width / 2.0;
-                let half_height = portal.bounds.height / 2.0;
-                let center = portal.bounds.center;
-                
-                // Generate rectangle corners
-                vertices.push(Point3::new(center.x - half_width, center.y - half_height, center.z));
-                vertices.push(Point3::new(center.x + half_width, center.y - half_height, center.z));
-                vertices.push(Point3::new(center.x + half_width, center.y + half_height, center.z));
-                vertices.push(Point3::new(center.x - half_width, center.y + half_height, center.z));
-            }
-            PortalShape::Circular => {
-                let center = portal.bounds.center;
-                let radius = portal.bounds.width / 2.0;
-                let segments = 32;
-                
-                for i in 0..segments {
-                    let angle = (i as f32) * 2.0 * std::f32::consts::PI / segments as f32;
-                    vertices.push(Point3::new(
-                        center.x + radius * angle.cos(),
-                        center.y + radius * angle.sin(),
-                        center.z,
-                    ));
-                }
-            }
-            PortalShape::Custom => {
-                // Custom shape vertices
-                vertices.push(portal.bounds.center);
-            }
+            // Custom shape vertices
+            PortalShape::Custom => vec![portal.bounds.center],
+            _ => portal.bounds.outline(),
         }
-        
-        vertices
     }
 }
\ No newline at end of file
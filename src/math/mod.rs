@@ -19,7 +19,8 @@ pub use cgmath::{
     Transform as CgTransform,
 };
 
-use cgmath::{Matrix4, Vector3};
+use cgmath::{Matrix, Matrix3, Matrix4, Vector3, Vector4};
+use serde::{Serialize, Deserialize};
 
 /// Transform wrapper for non-Euclidean spaces
 #[derive(Debug, Clone, Copy)]
@@ -72,6 +73,48 @@ impl Transform {
     pub fn inverse(&self) -> Option<Transform> {
         self.matrix.invert().map(|matrix| Transform { matrix })
     }
+    
+    /// Decompose into translation, rotation and per-axis scale
+    ///
+    /// Mirrored transforms (negative determinant) are reported with a negative X scale
+    /// so the rotation stays proper.
+    pub fn decompose(&self) -> (Point3<f32>, Quaternion<f32>, Vector3<f32>) {
+        let translation = self.position();
+        
+        let mut x = self.matrix.x.truncate();
+        let y = self.matrix.y.truncate();
+        let z = self.matrix.z.truncate();
+        let mut scale = Vector3::new(x.magnitude(), y.magnitude(), z.magnitude());
+        
+        if Matrix3::from_cols(x, y, z).determinant() < 0.0 {
+            scale.x = -scale.x;
+            x = -x;
+        }
+        
+        let axis = |v: Vector3<f32>, s: f32| if s.abs() > f32::EPSILON { v / s.abs() } else { v };
+        let rotation = Matrix3::from_cols(axis(x, scale.x), axis(y, scale.y), axis(z, scale.z));
+        
+        (translation, Quaternion::from(rotation).normalize(), scale)
+    }
+    
+    /// Interpolate towards another transform
+    ///
+    /// Translation and scale are interpolated linearly and rotation is slerped,
+    /// so the result stays a rigid-plus-scale transform without shearing.
+    pub fn lerp(&self, other: &Transform, t: f32) -> Transform {
+        let (pa, ra, sa) = self.decompose();
+        let (pb, rb, sb) = other.decompose();
+        
+        let position = pa + (pb - pa) * t;
+        let rotation = Interpolation::slerp(ra, rb, t);
+        let scale = sa + (sb - sa) * t;
+        
+        Transform {
+            matrix: Matrix4::from_translation(position.to_vec())
+                * Matrix4::from(rotation)
+                * Matrix4::from_nonuniform_scale(scale.x, scale.y, scale.z),
+        }
+    }
 }
 
 /// Interpolation utilities for smooth transitions
@@ -120,9 +163,333 @@ impl Interpolation {
     
     /// Smooth step interpolation
     pub fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
-        let t = ((x - edge0) / (edge1 - edge0)).max(0.0).min(1.0);
+        let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
         t * t * (3.0 - 2.0 * t)
     }
+    
+    /// Uniform Catmull-Rom interpolation between `p1` (t = 0) and `p2` (t = 1)
+    ///
+    /// `p0` and `p3` are the neighbouring knots that shape the tangents.
+    pub fn catmull_rom(
+        p0: Point3<f32>,
+        p1: Point3<f32>,
+        p2: Point3<f32>,
+        p3: Point3<f32>,
+        t: f32,
+    ) -> Point3<f32> {
+        let (p0, p1, p2, p3) = (p0.to_vec(), p1.to_vec(), p2.to_vec(), p3.to_vec());
+        let t2 = t * t;
+        let t3 = t2 * t;
+        
+        let v = (p1 * 2.0
+            + (p2 - p0) * t
+            + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2
+            + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * t3)
+            * 0.5;
+        Point3::from_vec(v)
+    }
+    
+    /// Cubic Bézier curve from `p0` (t = 0) to `p3` (t = 1) with control points `p1` and `p2`
+    pub fn cubic_bezier(
+        p0: Point3<f32>,
+        p1: Point3<f32>,
+        p2: Point3<f32>,
+        p3: Point3<f32>,
+        t: f32,
+    ) -> Point3<f32> {
+        let u = 1.0 - t;
+        let v = p0.to_vec() * (u * u * u)
+            + p1.to_vec() * (3.0 * u * u * t)
+            + p2.to_vec() * (3.0 * u * t * t)
+            + p3.to_vec() * (t * t * t);
+        Point3::from_vec(v)
+    }
+    
+    /// Accelerate from zero velocity
+    pub fn ease_in_quad(t: f32) -> f32 {
+        t * t
+    }
+    
+    /// Decelerate to zero velocity
+    pub fn ease_out_quad(t: f32) -> f32 {
+        t * (2.0 - t)
+    }
+    
+    /// Accelerate, then decelerate
+    pub fn ease_in_out_quad(t: f32) -> f32 {
+        if t < 0.5 {
+            2.0 * t * t
+        } else {
+            1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+        }
+    }
+    
+    /// Cubic acceleration from zero velocity
+    pub fn ease_in_cubic(t: f32) -> f32 {
+        t * t * t
+    }
+    
+    /// Cubic deceleration to zero velocity
+    pub fn ease_out_cubic(t: f32) -> f32 {
+        1.0 - (1.0 - t).powi(3)
+    }
+    
+    /// Cubic acceleration, then deceleration
+    pub fn ease_in_out_cubic(t: f32) -> f32 {
+        if t < 0.5 {
+            4.0 * t * t * t
+        } else {
+            1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+        }
+    }
+    
+    /// Sinusoidal acceleration
+    pub fn ease_in_sine(t: f32) -> f32 {
+        1.0 - (t * std::f32::consts::FRAC_PI_2).cos()
+    }
+    
+    /// Sinusoidal deceleration
+    pub fn ease_out_sine(t: f32) -> f32 {
+        (t * std::f32::consts::FRAC_PI_2).sin()
+    }
+    
+    /// Sinusoidal acceleration, then deceleration
+    pub fn ease_in_out_sine(t: f32) -> f32 {
+        -((std::f32::consts::PI * t).cos() - 1.0) / 2.0
+    }
+    
+    /// Exponential acceleration
+    pub fn ease_in_expo(t: f32) -> f32 {
+        if t <= 0.0 { 0.0 } else { 2f32.powf(10.0 * t - 10.0) }
+    }
+    
+    /// Exponential deceleration
+    pub fn ease_out_expo(t: f32) -> f32 {
+        if t >= 1.0 { 1.0 } else { 1.0 - 2f32.powf(-10.0 * t) }
+    }
+    
+    /// Overshoot slightly past the target, then settle
+    pub fn ease_out_back(t: f32) -> f32 {
+        const C1: f32 = 1.70158;
+        const C3: f32 = C1 + 1.0;
+        1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2)
+    }
+    
+    /// Spring past the target and oscillate into place
+    pub fn ease_out_elastic(t: f32) -> f32 {
+        const C4: f32 = 2.0 * std::f32::consts::PI / 3.0;
+        if t <= 0.0 {
+            0.0
+        } else if t >= 1.0 {
+            1.0
+        } else {
+            2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * C4).sin() + 1.0
+        }
+    }
+    
+    /// Bounce against the target like a dropped ball
+    pub fn ease_out_bounce(t: f32) -> f32 {
+        const N1: f32 = 7.5625;
+        const D1: f32 = 2.75;
+        if t < 1.0 / D1 {
+            N1 * t * t
+        } else if t < 2.0 / D1 {
+            let t = t - 1.5 / D1;
+            N1 * t * t + 0.75
+        } else if t < 2.5 / D1 {
+            let t = t - 2.25 / D1;
+            N1 * t * t + 0.9375
+        } else {
+            let t = t - 2.625 / D1;
+            N1 * t * t + 0.984375
+        }
+    }
+    
+    /// Bounce away from the start, mirroring `ease_out_bounce`
+    pub fn ease_in_bounce(t: f32) -> f32 {
+        1.0 - Self::ease_out_bounce(1.0 - t)
+    }
+    
+    /// Apply an easing curve, clamping `t` to `[0, 1]`
+    pub fn apply(easing: Easing, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match easing {
+            Easing::Linear => t,
+            Easing::InQuad => Self::ease_in_quad(t),
+            Easing::OutQuad => Self::ease_out_quad(t),
+            Easing::InOutQuad => Self::ease_in_out_quad(t),
+            Easing::InCubic => Self::ease_in_cubic(t),
+            Easing::OutCubic => Self::ease_out_cubic(t),
+            Easing::InOutCubic => Self::ease_in_out_cubic(t),
+            Easing::InSine => Self::ease_in_sine(t),
+            Easing::OutSine => Self::ease_out_sine(t),
+            Easing::InOutSine => Self::ease_in_out_sine(t),
+            Easing::InExpo => Self::ease_in_expo(t),
+            Easing::OutExpo => Self::ease_out_expo(t),
+            Easing::OutBack => Self::ease_out_back(t),
+            Easing::OutElastic => Self::ease_out_elastic(t),
+            Easing::InBounce => Self::ease_in_bounce(t),
+            Easing::OutBounce => Self::ease_out_bounce(t),
+        }
+    }
+}
+
+/// Easing curve selection, for storing transitions in data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Easing {
+    #[default]
+    Linear,
+    InQuad,
+    OutQuad,
+    InOutQuad,
+    InCubic,
+    OutCubic,
+    InOutCubic,
+    InSine,
+    OutSine,
+    InOutSine,
+    InExpo,
+    OutExpo,
+    OutBack,
+    OutElastic,
+    InBounce,
+    OutBounce,
+}
+
+impl Easing {
+    /// Every easing curve
+    pub const ALL: [Easing; 16] = [
+        Easing::Linear,
+        Easing::InQuad,
+        Easing::OutQuad,
+        Easing::InOutQuad,
+        Easing::InCubic,
+        Easing::OutCubic,
+        Easing::InOutCubic,
+        Easing::InSine,
+        Easing::OutSine,
+        Easing::InOutSine,
+        Easing::InExpo,
+        Easing::OutExpo,
+        Easing::OutBack,
+        Easing::OutElastic,
+        Easing::InBounce,
+        Easing::OutBounce,
+    ];
+}
+
+/// Catmull-Rom spline passing through a sequence of control points
+///
+/// `evaluate(t)` maps `t` in `[0, 1]` over the whole curve, one equal slice of `t`
+/// per segment. With arc-length reparameterization enabled, equal steps in `t`
+/// instead cover equal distances along the curve.
+#[derive(Debug, Clone)]
+pub struct Spline {
+    points: Vec<Point3<f32>>,
+    /// Cumulative length at evenly spaced parameter samples
+    arc_lengths: Option<Vec<f32>>,
+}
+
+impl Spline {
+    /// Samples per segment used when estimating length without an arc-length table
+    const LENGTH_SAMPLES: usize = 32;
+    
+    /// Create a spline through the given control points
+    pub fn new(points: Vec<Point3<f32>>) -> Self {
+        Self {
+            points,
+            arc_lengths: None,
+        }
+    }
+    
+    /// Enable constant-speed traversal using a table of `samples_per_segment` samples per segment
+    pub fn with_arc_length(mut self, samples_per_segment: usize) -> Self {
+        self.arc_lengths = Some(self.build_arc_lengths(samples_per_segment.max(1)));
+        self
+    }
+    
+    /// Get the control points
+    pub fn points(&self) -> &[Point3<f32>] {
+        &self.points
+    }
+    
+    /// Number of curve segments between consecutive control points
+    pub fn segment_count(&self) -> usize {
+        self.points.len().saturating_sub(1)
+    }
+    
+    /// Approximate length of the curve
+    pub fn length(&self) -> f32 {
+        match &self.arc_lengths {
+            Some(table) => table.last().copied().unwrap_or(0.0),
+            None => self.build_arc_lengths(Self::LENGTH_SAMPLES).last().copied().unwrap_or(0.0),
+        }
+    }
+    
+    /// Evaluate the spline at `t` in `[0, 1]`
+    ///
+    /// An empty spline evaluates to the origin and a single point to itself.
+    pub fn evaluate(&self, t: f32) -> Point3<f32> {
+        let t = t.clamp(0.0, 1.0);
+        let u = match &self.arc_lengths {
+            Some(table) => Self::parameter_at_length(table, t),
+            None => t,
+        };
+        self.evaluate_parameter(u)
+    }
+    
+    /// Evaluate at the raw (non arc-length) parameter
+    fn evaluate_parameter(&self, u: f32) -> Point3<f32> {
+        let n = self.points.len();
+        match n {
+            0 => return Point3::new(0.0, 0.0, 0.0),
+            1 => return self.points[0],
+            _ => {}
+        }
+        
+        let scaled = u * (n - 1) as f32;
+        let i = (scaled.floor() as usize).min(n - 2);
+        let local = scaled - i as f32;
+        
+        Interpolation::catmull_rom(
+            self.points[i.saturating_sub(1)],
+            self.points[i],
+            self.points[i + 1],
+            self.points[(i + 2).min(n - 1)],
+            local,
+        )
+    }
+    
+    fn build_arc_lengths(&self, samples_per_segment: usize) -> Vec<f32> {
+        let samples = self.segment_count() * samples_per_segment;
+        let mut table = Vec::with_capacity(samples + 1);
+        table.push(0.0);
+        
+        let mut previous = self.evaluate_parameter(0.0);
+        let mut total = 0.0;
+        for k in 1..=samples {
+            let point = self.evaluate_parameter(k as f32 / samples as f32);
+            total += (point - previous).magnitude();
+            table.push(total);
+            previous = point;
+        }
+        table
+    }
+    
+    /// Invert the arc-length table: raw parameter covering fraction `t` of the length
+    fn parameter_at_length(table: &[f32], t: f32) -> f32 {
+        let samples = table.len().saturating_sub(1);
+        let total = table.last().copied().unwrap_or(0.0);
+        if samples == 0 || total <= 0.0 {
+            return t;
+        }
+        
+        let target = t * total;
+        let k = table.partition_point(|&length| length < target).clamp(1, samples);
+        let span = table[k] - table[k - 1];
+        let fraction = if span > 0.0 { (target - table[k - 1]) / span } else { 0.0 };
+        ((k - 1) as f32 + fraction) / samples as f32
+    }
 }
 
 /// Ray for ray casting
@@ -143,6 +510,82 @@ impl Ray {
     pub fn point_at(&self, t: f32) -> Point3<f32> {
         self.origin + self.direction * t
     }
+    
+    /// Distance to the nearest sphere hit in front of the origin
+    ///
+    /// A ray starting inside the sphere hits its far side; tangent rays count as hits.
+    pub fn intersect_sphere(&self, center: Point3<f32>, radius: f32) -> Option<f32> {
+        let oc = self.origin - center;
+        let a = self.direction.magnitude2();
+        let half_b = oc.dot(self.direction);
+        let c = oc.magnitude2() - radius * radius;
+        
+        let discriminant = half_b * half_b - a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        
+        let sqrt_d = discriminant.sqrt();
+        let near = (-half_b - sqrt_d) / a;
+        let far = (-half_b + sqrt_d) / a;
+        
+        if near >= 0.0 {
+            Some(near)
+        } else if far >= 0.0 {
+            Some(far)
+        } else {
+            None
+        }
+    }
+    
+    /// Möller–Trumbore ray-triangle intersection
+    ///
+    /// Returns the hit distance and the barycentric weights `(u, v)` of `b` and `c`.
+    /// Triangles are front-facing when `a, b, c` wind counter-clockwise towards the
+    /// ray; back faces are not hit.
+    pub fn intersect_triangle(
+        &self,
+        a: Point3<f32>,
+        b: Point3<f32>,
+        c: Point3<f32>,
+    ) -> Option<(f32, f32, f32)> {
+        const EPSILON: f32 = 1e-7;
+        
+        let edge1 = b - a;
+        let edge2 = c - a;
+        let p = self.direction.cross(edge2);
+        let det = edge1.dot(p);
+        if det < EPSILON {
+            return None;
+        }
+        
+        let inv_det = 1.0 / det;
+        let s = self.origin - a;
+        let u = s.dot(p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        
+        let q = s.cross(edge1);
+        let v = self.direction.dot(q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        
+        let t = edge2.dot(q) * inv_det;
+        if t > EPSILON {
+            Some((t, u, v))
+        } else {
+            None
+        }
+    }
+    
+    /// Distance to a plane through `point` with the given `normal`
+    ///
+    /// Both sides of the plane are hit; parallel rays and planes behind the origin are not.
+    pub fn intersect_plane(&self, point: Point3<f32>, normal: Vector3<f32>) -> Option<f32> {
+        Plane::from_point_normal(point, normal).intersect_ray(self)
+    }
 }
 
 /// Bounding box for spatial queries
@@ -178,6 +621,62 @@ impl BoundingBox {
         Some(Self { min, max })
     }
     
+    /// Smallest box enclosing both boxes
+    pub fn merge(&self, other: &BoundingBox) -> BoundingBox {
+        BoundingBox {
+            min: Point3::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y), self.min.z.min(other.min.z)),
+            max: Point3::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y), self.max.z.max(other.max.z)),
+        }
+    }
+    
+    /// Grow the box to contain `point`
+    pub fn expand_to_include(&mut self, point: Point3<f32>) {
+        self.min = Point3::new(self.min.x.min(point.x), self.min.y.min(point.y), self.min.z.min(point.z));
+        self.max = Point3::new(self.max.x.max(point.x), self.max.y.max(point.y), self.max.z.max(point.z));
+    }
+    
+    /// The eight corners of the box
+    pub fn corners(&self) -> [Point3<f32>; 8] {
+        let (a, b) = (self.min, self.max);
+        [
+            Point3::new(a.x, a.y, a.z),
+            Point3::new(b.x, a.y, a.z),
+            Point3::new(a.x, b.y, a.z),
+            Point3::new(b.x, b.y, a.z),
+            Point3::new(a.x, a.y, b.z),
+            Point3::new(b.x, a.y, b.z),
+            Point3::new(a.x, b.y, b.z),
+            Point3::new(b.x, b.y, b.z),
+        ]
+    }
+    
+    /// Axis-aligned box enclosing all eight corners after `matrix` is applied
+    pub fn transform(&self, matrix: &Matrix4<f32>) -> BoundingBox {
+        let corners = self.corners().map(|corner| {
+            let p = matrix * corner.to_homogeneous();
+            Point3::new(p.x / p.w, p.y / p.w, p.z / p.w)
+        });
+        let mut bounds = BoundingBox::new(corners[0], corners[0]);
+        for &corner in &corners[1..] {
+            bounds.expand_to_include(corner);
+        }
+        bounds
+    }
+    
+    pub fn center(&self) -> Point3<f32> {
+        self.min.midpoint(self.max)
+    }
+    
+    /// Half the size of the box along each axis
+    pub fn extents(&self) -> Vector3<f32> {
+        (self.max - self.min) * 0.5
+    }
+    
+    pub fn surface_area(&self) -> f32 {
+        let size = self.max - self.min;
+        2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
+    
     pub fn contains(&self, point: Point3<f32>) -> bool {
         point.x >= self.min.x && point.x <= self.max.x &&
         point.y >= self.min.y && point.y <= self.max.y &&
@@ -207,4 +706,170 @@ impl BoundingBox {
             Some(if tmin < 0.0 { tmax } else { tmin })
         }
     }
-}
\ No newline at end of file
+}
+
+/// Oriented bounding box
+///
+/// `axes` must be orthonormal; `half_extents` are measured along each axis.
+#[derive(Debug, Clone, Copy)]
+pub struct Obb {
+    pub center: Point3<f32>,
+    pub axes: [Vector3<f32>; 3],
+    pub half_extents: Vector3<f32>,
+}
+
+impl Obb {
+    pub fn new(center: Point3<f32>, axes: [Vector3<f32>; 3], half_extents: Vector3<f32>) -> Self {
+        Self { center, axes, half_extents }
+    }
+    
+    /// Express a point in box coordinates (distance along each axis from the center)
+    pub fn to_local(&self, point: Point3<f32>) -> Vector3<f32> {
+        let offset = point - self.center;
+        Vector3::new(
+            offset.dot(self.axes[0]),
+            offset.dot(self.axes[1]),
+            offset.dot(self.axes[2]),
+        )
+    }
+    
+    pub fn contains(&self, point: Point3<f32>) -> bool {
+        let local = self.to_local(point);
+        local.x.abs() <= self.half_extents.x
+            && local.y.abs() <= self.half_extents.y
+            && local.z.abs() <= self.half_extents.z
+    }
+    
+    /// Closest point inside the box
+    pub fn closest_point(&self, point: Point3<f32>) -> Point3<f32> {
+        let local = self.to_local(point);
+        self.center
+            + self.axes[0] * local.x.clamp(-self.half_extents.x, self.half_extents.x)
+            + self.axes[1] * local.y.clamp(-self.half_extents.y, self.half_extents.y)
+            + self.axes[2] * local.z.clamp(-self.half_extents.z, self.half_extents.z)
+    }
+}
+
+/// Which side of a plane a point lies on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Front,
+    Back,
+    On,
+}
+
+/// Plane `normal · p + d = 0` with a unit normal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    pub normal: Vector3<f32>,
+    pub d: f32,
+}
+
+impl Plane {
+    /// Distance within which points are classified as lying on the plane
+    pub const EPSILON: f32 = 1e-5;
+    
+    /// Create a plane from `normal · p + d = 0`, normalizing the equation
+    pub fn new(normal: Vector3<f32>, d: f32) -> Self {
+        let length = normal.magnitude();
+        if length > f32::EPSILON {
+            Self { normal: normal / length, d: d / length }
+        } else {
+            Self { normal, d }
+        }
+    }
+    
+    /// Create a plane through `point` facing along `normal`
+    pub fn from_point_normal(point: Point3<f32>, normal: Vector3<f32>) -> Self {
+        let normal = normal.normalize();
+        Self { normal, d: -normal.dot(point.to_vec()) }
+    }
+    
+    /// Create a plane from homogeneous coefficients `(a, b, c, d)`
+    pub fn from_vec4(plane: Vector4<f32>) -> Self {
+        Self::new(plane.truncate(), plane.w)
+    }
+    
+    /// Distance from the plane, positive on the side the normal points to
+    pub fn signed_distance(&self, point: Point3<f32>) -> f32 {
+        self.normal.dot(point.to_vec()) + self.d
+    }
+    
+    /// Classify a point as in front of, behind, or on the plane
+    pub fn classify(&self, point: Point3<f32>) -> Side {
+        let distance = self.signed_distance(point);
+        if distance > Self::EPSILON {
+            Side::Front
+        } else if distance < -Self::EPSILON {
+            Side::Back
+        } else {
+            Side::On
+        }
+    }
+    
+    /// Ray parameter where the ray crosses the plane from either side
+    pub fn intersect_ray(&self, ray: &Ray) -> Option<f32> {
+        let denom = ray.direction.dot(self.normal);
+        if denom.abs() < 1e-6 {
+            return None;
+        }
+        
+        let t = -self.signed_distance(ray.origin) / denom;
+        if t >= 0.0 { Some(t) } else { None }
+    }
+    
+    /// Closest point on the plane
+    pub fn project_point(&self, point: Point3<f32>) -> Point3<f32> {
+        point - self.normal * self.signed_distance(point)
+    }
+}
+
+/// View frustum as six inward-facing planes
+///
+/// Points inside have a non-negative signed distance to every plane.
+/// In non-Euclidean charts culling is only valid within the chart the camera is in;
+/// geometry seen through portals must be tested against the portal's virtual camera.
+#[derive(Debug, Clone, Copy)]
+pub struct Frustum {
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    /// Extract the planes from a view-projection matrix with OpenGL-style clip depth
+    pub fn from_matrix(view_projection: Matrix4<f32>) -> Self {
+        let m = view_projection.transpose();
+        let (r0, r1, r2, r3) = (m.x, m.y, m.z, m.w);
+        
+        Self {
+            planes: [
+                Plane::from_vec4(r3 + r0), // left
+                Plane::from_vec4(r3 - r0), // right
+                Plane::from_vec4(r3 + r1), // bottom
+                Plane::from_vec4(r3 - r1), // top
+                Plane::from_vec4(r3 + r2), // near
+                Plane::from_vec4(r3 - r2), // far
+            ],
+        }
+    }
+    
+    pub fn contains_point(&self, point: Point3<f32>) -> bool {
+        self.planes.iter().all(|plane| plane.signed_distance(point) >= 0.0)
+    }
+    
+    pub fn intersects_sphere(&self, center: Point3<f32>, radius: f32) -> bool {
+        self.planes.iter().all(|plane| plane.signed_distance(center) >= -radius)
+    }
+    
+    /// Conservative box test; may report boxes near frustum corners as visible
+    pub fn intersects_aabb(&self, bounds: &BoundingBox) -> bool {
+        self.planes.iter().all(|plane| {
+            // Corner furthest along the plane normal
+            let positive = Point3::new(
+                if plane.normal.x >= 0.0 { bounds.max.x } else { bounds.min.x },
+                if plane.normal.y >= 0.0 { bounds.max.y } else { bounds.min.y },
+                if plane.normal.z >= 0.0 { bounds.max.z } else { bounds.min.z },
+            );
+            plane.signed_distance(positive) >= 0.0
+        })
+    }
+}
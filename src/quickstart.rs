@@ -471,11 +471,11 @@ pub fn run_game(mut app: impl GameApp + 'static) {
     let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
         label: Some("Game Pipeline"), layout: Some(&pipeline_layout),
         vertex: wgpu::VertexState {
-            module: &shader_module, entry_point: "vs_main",
+            module: &shader_module, entry_point: crate::graphics::shader::VERTEX_ENTRY_POINT,
             buffers: &[GameVertex::LAYOUT],
         },
         fragment: Some(wgpu::FragmentState {
-            module: &shader_module, entry_point: "fs_main",
+            module: &shader_module, entry_point: crate::graphics::shader::FRAGMENT_ENTRY_POINT,
             targets: &[Some(wgpu::ColorTargetState {
                 format: config.format, blend: Some(wgpu::BlendState::REPLACE),
                 write_mask: wgpu::ColorWrites::ALL,
@@ -521,12 +521,10 @@ pub fn run_game(mut app: impl GameApp + 'static) {
         match event {
             Event::WindowEvent { event: ref we, .. } => match we {
                 WindowEvent::CloseRequested => { quit = true; }
-                WindowEvent::Resized(s) => {
-                    if s.width > 0 && s.height > 0 {
-                        config.width = s.width; config.height = s.height;
-                        surface.configure(&device, &config);
-                        depth_view = create_depth_texture(&device, s.width, s.height);
-                    }
+                WindowEvent::Resized(s) if s.width > 0 && s.height > 0 => {
+                    config.width = s.width; config.height = s.height;
+                    surface.configure(&device, &config);
+                    depth_view = create_depth_texture(&device, s.width, s.height);
                 }
                 WindowEvent::KeyboardInput { event: ke, .. } => {
                     if let PhysicalKey::Code(code) = ke.physical_key {
@@ -636,14 +634,12 @@ pub fn run_game(mut app: impl GameApp + 'static) {
                 }
                 _ => {}
             },
-            Event::DeviceEvent { event: DeviceEvent::MouseMotion { delta }, .. } => {
-                if cursor_grabbed {
-                    camera.yaw += delta.0 as f32 * camera.look_sensitivity;
-                    camera.pitch = (camera.pitch - delta.1 as f32 * camera.look_sensitivity)
-                        .clamp(-1.5, 1.5);
-                    mouse_delta.0 += delta.0 as f32;
-                    mouse_delta.1 += delta.1 as f32;
-                }
+            Event::DeviceEvent { event: DeviceEvent::MouseMotion { delta }, .. } if cursor_grabbed => {
+                camera.yaw += delta.0 as f32 * camera.look_sensitivity;
+                camera.pitch = (camera.pitch - delta.1 as f32 * camera.look_sensitivity)
+                    .clamp(-1.5, 1.5);
+                mouse_delta.0 += delta.0 as f32;
+                mouse_delta.1 += delta.1 as f32;
             }
             Event::AboutToWait => { window.request_redraw(); }
             _ => {}
@@ -732,6 +728,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn camera_pitch_clamp() {
         let mut cam = FpsCamera::default();
         cam.pitch = 2.0; // beyond 1.5
@@ -0,0 +1,151 @@
+//! glTF/GLB model loading (requires the `gltf` feature)
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+use gltf::mesh::Mode;
+use crate::graphics::Vertex;
+use super::{AssetLoader, MeshResource, compute_normals};
+
+/// Meshes loaded from a glTF file, keyed by name
+///
+/// A single-primitive mesh is stored under its mesh name. Multi-primitive meshes
+/// add `/<primitive>` to the name. Unnamed meshes are named `mesh<index>`.
+#[derive(Clone, Default)]
+pub struct GltfModel {
+    pub meshes: HashMap<String, MeshResource>,
+}
+
+impl GltfModel {
+    /// Load a model from a `.gltf` or `.glb` file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let (document, buffers, _images) = gltf::import(path)?;
+        Self::from_document(&document, &buffers)
+    }
+    
+    /// Load a model from in-memory glTF JSON or GLB bytes
+    ///
+    /// External buffer files cannot be resolved; use embedded data URIs or GLB.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        let (document, buffers, _images) = gltf::import_slice(bytes)?;
+        Self::from_document(&document, &buffers)
+    }
+    
+    /// Get a mesh by name
+    pub fn mesh(&self, name: &str) -> Option<&MeshResource> {
+        self.meshes.get(name)
+    }
+    
+    fn from_document(
+        document: &gltf::Document,
+        buffers: &[gltf::buffer::Data],
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut meshes = HashMap::new();
+        
+        for mesh in document.meshes() {
+            let name = mesh.name()
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("mesh{}", mesh.index()));
+            let primitive_count = mesh.primitives().len();
+            
+            for primitive in mesh.primitives() {
+                let Some((vertices, indices)) = read_primitive(&primitive, buffers)? else {
+                    eprintln!(
+                        "Skipping non-triangle primitive {} of mesh '{}' ({:?})",
+                        primitive.index(), name, primitive.mode()
+                    );
+                    continue;
+                };
+                
+                let key = if primitive_count > 1 {
+                    format!("{}/{}", name, primitive.index())
+                } else {
+                    name.clone()
+                };
+                meshes.insert(key, MeshResource { vertices, indices });
+            }
+        }
+        
+        Ok(Self { meshes })
+    }
+}
+
+/// Asset loader for `.gltf` and `.glb` files, producing a [`GltfModel`]
+pub struct GltfLoader;
+
+impl AssetLoader for GltfLoader {
+    type Asset = Box<dyn Any + Send + Sync>;
+    
+    fn load(&self, path: &Path) -> Result<Self::Asset, Box<dyn std::error::Error>> {
+        Ok(Box::new(GltfModel::load(path)?))
+    }
+    
+    fn extensions(&self) -> &[&str] {
+        &["gltf", "glb"]
+    }
+}
+
+/// Vertices plus triangle-list indices
+type TriangleList = (Vec<Vertex>, Vec<u32>);
+
+/// Read a primitive into engine vertices and triangle-list indices
+///
+/// Returns `None` for point and line primitives.
+fn read_primitive(
+    primitive: &gltf::Primitive,
+    buffers: &[gltf::buffer::Data],
+) -> Result<Option<TriangleList>, Box<dyn std::error::Error>> {
+    let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|data| &data.0[..]));
+    
+    let positions: Vec<[f32; 3]> = reader.read_positions()
+        .ok_or("Primitive has no POSITION attribute")?
+        .collect();
+    let raw_indices: Vec<u32> = match reader.read_indices() {
+        Some(indices) => indices.into_u32().collect(),
+        None => (0..positions.len() as u32).collect(),
+    };
+    
+    let indices = match primitive.mode() {
+        Mode::Triangles => raw_indices,
+        Mode::TriangleStrip => (2..raw_indices.len())
+            .flat_map(|i| {
+                // Alternate winding so every triangle keeps the strip's orientation
+                if i % 2 == 0 {
+                    [raw_indices[i - 2], raw_indices[i - 1], raw_indices[i]]
+                } else {
+                    [raw_indices[i - 1], raw_indices[i - 2], raw_indices[i]]
+                }
+            })
+            .collect(),
+        Mode::TriangleFan => (2..raw_indices.len())
+            .flat_map(|i| [raw_indices[0], raw_indices[i - 1], raw_indices[i]])
+            .collect(),
+        _ => return Ok(None),
+    };
+    
+    if let Some(&bad) = indices.iter().find(|&&i| i as usize >= positions.len()) {
+        return Err(format!("Index {} out of range for {} vertices", bad, positions.len()).into());
+    }
+    
+    let normals: Vec<[f32; 3]> = match reader.read_normals() {
+        Some(normals) => normals.collect(),
+        None => compute_normals(&positions, &indices),
+    };
+    let tex_coords: Option<Vec<[f32; 2]>> = reader.read_tex_coords(0)
+        .map(|coords| coords.into_f32().collect());
+    let colors: Option<Vec<[f32; 4]>> = reader.read_colors(0)
+        .map(|colors| colors.into_rgba_f32().collect());
+    
+    let vertices = positions
+        .iter()
+        .enumerate()
+        .map(|(i, position)| Vertex::new(
+            *position,
+            tex_coords.as_ref().and_then(|t| t.get(i).copied()).unwrap_or([0.0, 0.0]),
+            normals.get(i).copied().unwrap_or([0.0, 0.0, 1.0]),
+            colors.as_ref().and_then(|c| c.get(i).copied()).unwrap_or([1.0, 1.0, 1.0, 1.0]),
+        ))
+        .collect();
+    
+    Ok(Some((vertices, indices)))
+}
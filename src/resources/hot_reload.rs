@@ -0,0 +1,112 @@
+//! File watching for asset hot-reloading (requires the `hot-reload` feature)
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use notify::{EventKind, RecursiveMode, Watcher};
+use super::ResourceManager;
+
+/// Watches the directories of loaded resources for changes
+///
+/// Directories are watched rather than files so that editors which save by
+/// replacing the file are still picked up.
+pub(super) struct ReloadWatcher {
+    watcher: notify::RecommendedWatcher,
+    events: Receiver<notify::Result<notify::Event>>,
+    directories: HashSet<PathBuf>,
+}
+
+impl ReloadWatcher {
+    fn new() -> notify::Result<Self> {
+        let (sender, events) = channel();
+        let watcher = notify::recommended_watcher(move |event| {
+            let _ = sender.send(event);
+        })?;
+        
+        Ok(Self {
+            watcher,
+            events,
+            directories: HashSet::new(),
+        })
+    }
+    
+    fn watch_directory_of(&mut self, path: &Path) -> notify::Result<()> {
+        let Some(directory) = canonical(path).parent().map(Path::to_path_buf) else {
+            return Ok(());
+        };
+        if self.directories.contains(&directory) {
+            return Ok(());
+        }
+        
+        self.watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+        self.directories.insert(directory);
+        Ok(())
+    }
+    
+    /// Drain pending events into the set of files whose contents changed
+    fn changed_files(&self) -> HashSet<PathBuf> {
+        let mut changed = HashSet::new();
+        for event in self.events.try_iter() {
+            match event {
+                Ok(event) if matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_)) => {
+                    changed.extend(event.paths.iter().map(|path| canonical(path)));
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Asset watcher error: {}", e),
+            }
+        }
+        changed
+    }
+}
+
+impl ResourceManager {
+    /// Start watching a loaded resource's file
+    pub(super) fn watch(&mut self, path: &Path) {
+        if self.watcher.is_none() {
+            match ReloadWatcher::new() {
+                Ok(watcher) => self.watcher = Some(watcher),
+                Err(e) => {
+                    eprintln!("Failed to start asset watcher: {}", e);
+                    return;
+                }
+            }
+        }
+        
+        if let Some(watcher) = &mut self.watcher
+            && let Err(e) = watcher.watch_directory_of(path)
+        {
+            eprintln!("Failed to watch {}: {}", path.display(), e);
+        }
+    }
+    
+    /// Reload every resource whose source file changed since the last poll
+    ///
+    /// New data is swapped into the existing handles. Reload failures are logged
+    /// and leave the old data intact. Returns the ids that were reloaded.
+    pub fn poll_reloads(&mut self) -> Vec<String> {
+        let Some(watcher) = &self.watcher else {
+            return Vec::new();
+        };
+        let changed = watcher.changed_files();
+        if changed.is_empty() {
+            return Vec::new();
+        }
+        
+        let mut reloaded = Vec::new();
+        for ((_, id), source) in &self.sources {
+            if !changed.contains(&canonical(&source.path)) {
+                continue;
+            }
+            match self.reload_source(id, source) {
+                Ok(()) => reloaded.push(id.clone()),
+                Err(e) => eprintln!("Failed to reload '{}' from {}: {}", id, source.path.display(), e),
+            }
+        }
+        reloaded
+    }
+}
+
+/// Canonical form of a path, falling back to the path itself if it can't be resolved
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
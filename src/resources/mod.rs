@@ -1,9 +1,99 @@
 //! Resource and asset management for the non-Euclidean engine
 
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
-use std::sync::{Arc, RwLock};
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::sync::mpsc::{channel, Receiver, Sender};
 use std::any::{Any, TypeId};
+use serde::{Serialize, Deserialize};
+
+#[cfg(feature = "gltf")]
+pub mod gltf_loader;
+
+#[cfg(feature = "gltf")]
+pub use gltf_loader::{GltfLoader, GltfModel};
+
+#[cfg(feature = "tobj")]
+pub mod obj_loader;
+
+#[cfg(feature = "tobj")]
+pub use obj_loader::{ObjLoader, ObjModel};
+
+#[cfg(feature = "hot-reload")]
+mod hot_reload;
+
+/// Error returned by [`ResourceManager::load`]
+#[derive(Debug)]
+pub enum ResourceError {
+    /// The asset file doesn't exist
+    NotFound(PathBuf),
+    /// The path is absolute or uses `..` to leave the asset root
+    InvalidPath(PathBuf),
+    /// No loader is registered for the file extension (empty if there is none)
+    NoLoader(String),
+    /// The loader produced an asset of a different type than requested
+    TypeMismatch(&'static str),
+    /// Reading the asset failed
+    Io(std::io::Error),
+    /// The loader couldn't decode the asset
+    Decode(String),
+    /// An `add` or `remove` of the same id replaced an async load before it finished
+    Superseded,
+}
+
+impl fmt::Display for ResourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(path) => write!(f, "asset not found: {}", path.display()),
+            Self::InvalidPath(path) => write!(f, "asset path escapes the asset root: {}", path.display()),
+            Self::NoLoader(extension) if extension.is_empty() => write!(f, "no file extension"),
+            Self::NoLoader(extension) => write!(f, "no loader for extension: {}", extension),
+            Self::TypeMismatch(expected) => write!(f, "loaded asset is not a {}", expected),
+            Self::Io(e) => write!(f, "failed to read asset: {}", e),
+            Self::Decode(message) => write!(f, "failed to decode asset: {}", message),
+            Self::Superseded => write!(f, "load superseded by a later add or remove"),
+        }
+    }
+}
+
+impl std::error::Error for ResourceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl ResourceError {
+    /// Classify an error returned by an [`AssetLoader`] for `path`
+    fn from_loader(path: &Path, error: Box<dyn std::error::Error>) -> Self {
+        match error.downcast::<std::io::Error>() {
+            Ok(e) if e.kind() == std::io::ErrorKind::NotFound => Self::NotFound(path.to_path_buf()),
+            Ok(e) => Self::Io(*e),
+            Err(e) => Self::Decode(e.to_string()),
+        }
+    }
+}
+
+/// Check that a relative asset path stays inside the asset root
+///
+/// The check is lexical: `a/../b.png` is fine, `../b.png` and `/b.png` are not.
+fn check_asset_path(path: &Path) -> Result<(), ResourceError> {
+    let mut depth = 0usize;
+    for component in path.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => depth -= 1,
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(ResourceError::InvalidPath(path.to_path_buf()));
+            }
+        }
+    }
+    Ok(())
+}
 
 /// Asset loader trait
 pub trait AssetLoader: Send + Sync {
@@ -14,12 +104,24 @@ pub trait AssetLoader: Send + Sync {
 }
 
 /// Resource handle
-#[derive(Debug, Clone)]
+///
+/// Handles share their data through an `Arc`, so each live handle counts as a
+/// reference; see [`ResourceManager::strong_count`] and [`ResourceManager::collect_unused`].
+#[derive(Debug)]
 pub struct ResourceHandle<T> {
     pub id: String,
     pub data: Arc<RwLock<T>>,
 }
 
+impl<T> Clone for ResourceHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            data: self.data.clone(),
+        }
+    }
+}
+
 impl<T> ResourceHandle<T> {
     pub fn new(id: String, data: T) -> Self {
         Self {
@@ -37,9 +139,63 @@ impl<T> ResourceHandle<T> {
     }
 }
 
+/// State of a resource started with [`ResourceManager::load_async`]
+#[derive(Debug)]
+pub enum Loading<T> {
+    /// The loader is still running
+    Pending,
+    /// The resource finished loading and is stored in the manager
+    Loaded(ResourceHandle<T>),
+    /// The load failed
+    Failed(ResourceError),
+}
+
+impl<T> Loading<T> {
+    /// Check if the loader is still running
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Self::Pending)
+    }
+    
+    /// Check if the resource finished loading
+    pub fn is_loaded(&self) -> bool {
+        matches!(self, Self::Loaded(_))
+    }
+}
+
+impl<T> ResourceHandle<Loading<T>> {
+    /// Check if the resource finished loading
+    pub fn is_loaded(&self) -> bool {
+        self.read().is_loaded()
+    }
+    
+    /// Handle to the loaded resource, once it is ready
+    ///
+    /// Returns `None` while pending or after a failure; match on
+    /// [`read`](Self::read) to get the [`ResourceError`].
+    pub fn poll(&self) -> Option<ResourceHandle<T>> {
+        match &*self.read() {
+            Loading::Loaded(handle) => Some(handle.clone()),
+            _ => None,
+        }
+    }
+}
+
 /// Resource storage
+///
+/// Each resource lives behind the same `Arc<RwLock<T>>` that its handles share,
+/// so replacing the data in place is visible through every handle.
 struct ResourceStorage {
-    resources: HashMap<TypeId, HashMap<String, Box<dyn Any + Send + Sync>>>,
+    resources: HashMap<TypeId, HashMap<String, StoredResource>>,
+}
+
+/// A type-erased `Arc<RwLock<T>>` plus a way to read its reference count
+struct StoredResource {
+    shared: Box<dyn Any + Send + Sync>,
+    strong_count: fn(&(dyn Any + Send + Sync)) -> usize,
+}
+
+fn strong_count_of<T: Any + Send + Sync + 'static>(shared: &(dyn Any + Send + Sync)) -> usize {
+    shared.downcast_ref::<Arc<RwLock<T>>>().map_or(0, Arc::strong_count)
 }
 
 impl ResourceStorage {
@@ -49,127 +205,421 @@ impl ResourceStorage {
         }
     }
     
-    fn insert<T: Any + Send + Sync + 'static>(&mut self, id: String, resource: T) {
+    fn insert<T: Any + Send + Sync + 'static>(&mut self, id: String, resource: T) -> Arc<RwLock<T>> {
         let type_id = TypeId::of::<T>();
+        let shared = Arc::new(RwLock::new(resource));
         self.resources
             .entry(type_id)
-            .or_insert_with(HashMap::new)
-            .insert(id, Box::new(resource));
+            .or_default()
+            .insert(id, StoredResource {
+                shared: Box::new(shared.clone()),
+                strong_count: strong_count_of::<T>,
+            });
+        shared
     }
     
-    fn get<T: Any + Send + Sync + 'static>(&self, id: &str) -> Option<&T> {
+    fn get<T: Any + Send + Sync + 'static>(&self, id: &str) -> Option<&Arc<RwLock<T>>> {
         let type_id = TypeId::of::<T>();
         self.resources
             .get(&type_id)?
             .get(id)?
-            .downcast_ref::<T>()
+            .shared
+            .downcast_ref::<Arc<RwLock<T>>>()
     }
     
-    #[allow(dead_code)]
-    fn get_mut<T: Any + Send + Sync + 'static>(&mut self, id: &str) -> Option<&mut T> {
-        let type_id = TypeId::of::<T>();
-        self.resources
-            .get_mut(&type_id)?
-            .get_mut(id)?
-            .downcast_mut::<T>()
-    }
-    
-    fn remove<T: Any + Send + Sync + 'static>(&mut self, id: &str) -> Option<Box<T>> {
+    fn remove<T: Any + Send + Sync + 'static>(&mut self, id: &str) -> Option<Arc<RwLock<T>>> {
         let type_id = TypeId::of::<T>();
         self.resources
             .get_mut(&type_id)?
             .remove(id)?
-            .downcast::<T>()
+            .shared
+            .downcast::<Arc<RwLock<T>>>()
             .ok()
+            .map(|shared| *shared)
+    }
+    
+    /// Swap freshly loaded data into an existing resource
+    fn replace<T: Any + Send + Sync + 'static>(
+        &self,
+        id: &str,
+        asset: Box<dyn Any + Send + Sync>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let shared = self.get::<T>(id).ok_or_else(|| format!("Resource '{}' is no longer loaded", id))?;
+        let asset = asset.downcast::<T>().map_err(|_| "Type mismatch")?;
+        *shared.write().unwrap() = *asset;
+        Ok(())
+    }
+    
+    /// Drop every resource referenced only by the storage, returning their keys
+    fn collect_unused(&mut self) -> Vec<(TypeId, String)> {
+        let mut freed = Vec::new();
+        for (type_id, resources) in &mut self.resources {
+            resources.retain(|id, stored| {
+                let unused = (stored.strong_count)(stored.shared.as_ref()) <= 1;
+                if unused {
+                    freed.push((*type_id, id.clone()));
+                }
+                !unused
+            });
+        }
+        freed
     }
 }
 
+/// Run a loader and downcast its asset to the requested type
+fn run_loader<T: Any + Send + Sync + 'static>(
+    loader: &dyn AssetLoader<Asset = Box<dyn Any + Send + Sync>>,
+    path: &Path,
+) -> Result<T, ResourceError> {
+    let asset = loader.load(path).map_err(|e| ResourceError::from_loader(path, e))?;
+    asset.downcast::<T>()
+        .map(|asset| *asset)
+        .map_err(|_| ResourceError::TypeMismatch(std::any::type_name::<T>()))
+}
+
+/// Swaps a type-erased asset into the stored resource of the matching type
+type ReplaceFn = fn(&ResourceStorage, &str, Box<dyn Any + Send + Sync>) -> Result<(), Box<dyn std::error::Error>>;
+
+/// Where a loaded resource came from, so it can be reloaded in place
+#[derive(Clone)]
+struct LoadedSource {
+    path: PathBuf,
+    extension: String,
+    replace: ReplaceFn,
+}
+
+/// A registered loader, shared between extensions and loading threads
+type SharedLoader = Arc<dyn AssetLoader<Asset = Box<dyn Any + Send + Sync>>>;
+
+/// An async load that finished, reported back so its source can be tracked
+struct FinishedLoad {
+    key: (TypeId, String),
+    source: LoadedSource,
+}
+
+/// Async loads still running, each holding the type-erased
+/// `ResourceHandle<Loading<T>>` handed out for it
+///
+/// Lock this before the storage when taking both.
+type PendingLoads = HashMap<(TypeId, String), Box<dyn Any + Send + Sync>>;
+
 /// Resource manager
 pub struct ResourceManager {
     storage: Arc<RwLock<ResourceStorage>>,
+    pending: Arc<Mutex<PendingLoads>>,
     asset_path: PathBuf,
-    loaders: HashMap<String, Box<dyn AssetLoader<Asset = Box<dyn Any + Send + Sync>>>>,
+    loaders: HashMap<String, SharedLoader>,
+    sources: HashMap<(TypeId, String), LoadedSource>,
+    load_sender: Sender<FinishedLoad>,
+    finished_loads: Receiver<FinishedLoad>,
+    #[cfg(feature = "hot-reload")]
+    watcher: Option<hot_reload::ReloadWatcher>,
+}
+
+impl Default for ResourceManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ResourceManager {
     /// Create a new resource manager
+    ///
+    /// Built-in loaders for enabled features (such as `gltf` and `tobj`) are registered automatically.
     pub fn new() -> Self {
-        Self {
+        let (load_sender, finished_loads) = channel();
+        #[allow(unused_mut)]
+        let mut manager = Self {
             storage: Arc::new(RwLock::new(ResourceStorage::new())),
+            pending: Arc::new(Mutex::new(HashMap::new())),
             asset_path: PathBuf::from("assets"),
             loaders: HashMap::new(),
+            sources: HashMap::new(),
+            load_sender,
+            finished_loads,
+            #[cfg(feature = "hot-reload")]
+            watcher: None,
+        };
+        
+        #[cfg(feature = "gltf")]
+        manager.register_loader(GltfLoader);
+        #[cfg(feature = "tobj")]
+        manager.register_loader(ObjLoader);
+        
+        manager
+    }
+    
+    /// Register a loader for each of its file extensions
+    ///
+    /// Replaces any loader previously registered for the same extension.
+    pub fn register_loader<L>(&mut self, loader: L)
+    where
+        L: AssetLoader<Asset = Box<dyn Any + Send + Sync>> + 'static,
+    {
+        let loader: SharedLoader = Arc::new(loader);
+        for extension in loader.extensions() {
+            self.loaders.insert(extension.to_string(), loader.clone());
         }
     }
     
+    /// Check if a loader is registered for a file extension
+    pub fn has_loader(&self, extension: &str) -> bool {
+        self.loaders.contains_key(extension)
+    }
+    
     /// Set the base asset path
     pub fn set_asset_path(&mut self, path: impl Into<PathBuf>) {
         self.asset_path = path.into();
     }
     
     /// Load a resource from file
-    pub fn load<T: Any + Send + Sync + Clone + 'static>(
+    ///
+    /// `path` is relative to the asset path and may not leave it. Loading an id
+    /// that is already loaded returns a handle to the existing data.
+    pub fn load<T: Any + Send + Sync + 'static>(
         &mut self,
         id: &str,
         path: &str,
-    ) -> Result<ResourceHandle<T>, Box<dyn std::error::Error>> {
-        let full_path = self.asset_path.join(path);
+    ) -> Result<ResourceHandle<T>, ResourceError> {
+        check_asset_path(Path::new(path))?;
         
         // Check if already loaded
-        if let Ok(storage) = self.storage.read() {
-            if let Some(resource) = storage.get::<T>(id) {
-                return Ok(ResourceHandle::new(id.to_string(), resource.clone()));
-            }
+        if let Some(handle) = self.get::<T>(id) {
+            return Ok(handle);
         }
         
         // Load from file
-        let extension = full_path.extension()
-            .and_then(|ext| ext.to_str())
-            .ok_or("No file extension")?;
+        let (loader, source) = self.resolve::<T>(path)?;
+        let resource = run_loader::<T>(loader.as_ref(), &source.path)?;
+        
+        let data = self.storage.write().unwrap().insert(id.to_string(), resource);
+        self.track_source((TypeId::of::<T>(), id.to_string()), source);
         
-        if let Some(loader) = self.loaders.get(extension) {
-            let asset = loader.load(&full_path)?;
+        Ok(ResourceHandle { id: id.to_string(), data })
+    }
+    
+    /// Load a resource from file on a background thread
+    ///
+    /// The returned handle starts out [`Loading::Pending`] and switches to
+    /// `Loaded` or `Failed` when the loader finishes; path and loader errors
+    /// fail it straight away. Loaded resources are stored like those from
+    /// [`load`](Self::load), but are only tracked for [`reload`](Self::reload)
+    /// and hot-reloading once [`poll_loads`](Self::poll_loads) has seen them.
+    ///
+    /// Asking again for an id that is still loading returns the same pending
+    /// handle. An [`add`](Self::add) or [`remove`](Self::remove) of the id
+    /// while it loads wins: the load's result is dropped and its handle fails
+    /// with [`ResourceError::Superseded`]. Each load runs on a thread of its
+    /// own rather than a pool, so starting many at once starts as many threads.
+    pub fn load_async<T: Any + Send + Sync + 'static>(&mut self, id: &str, path: &str) -> ResourceHandle<Loading<T>> {
+        let key = (TypeId::of::<T>(), id.to_string());
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(handle) = self.get::<T>(id) {
+            return ResourceHandle::new(id.to_string(), Loading::Loaded(handle));
+        }
+        if let Some(loading) = pending.get(&key).and_then(|loading| loading.downcast_ref::<ResourceHandle<Loading<T>>>()) {
+            return loading.clone();
+        }
+        
+        let (loader, source) = match check_asset_path(Path::new(path)).and_then(|()| self.resolve::<T>(path)) {
+            Ok(resolved) => resolved,
+            Err(e) => return ResourceHandle::new(id.to_string(), Loading::Failed(e)),
+        };
+        
+        let loading = ResourceHandle::new(id.to_string(), Loading::Pending);
+        pending.insert(key.clone(), Box::new(loading.clone()));
+        drop(pending);
+        
+        let state = loading.clone();
+        let pending = self.pending.clone();
+        let storage = self.storage.clone();
+        let finished = self.load_sender.clone();
+        std::thread::spawn(move || {
+            let loaded = run_loader::<T>(loader.as_ref(), &source.path);
             
-            if let Ok(resource) = asset.downcast::<T>() {
-                let resource = *resource;
-                self.storage.write().unwrap().insert(id.to_string(), resource.clone());
-                Ok(ResourceHandle::new(id.to_string(), resource))
+            let mut pending = pending.lock().unwrap_or_else(|e| e.into_inner());
+            let current = pending.get(&key)
+                .and_then(|loading| loading.downcast_ref::<ResourceHandle<Loading<T>>>())
+                .is_some_and(|loading| Arc::ptr_eq(&loading.data, &state.data));
+            let result = if !current {
+                Loading::Failed(ResourceError::Superseded)
             } else {
-                Err("Type mismatch".into())
-            }
-        } else {
-            Err(format!("No loader for extension: {}", extension).into())
-        }
+                pending.remove(&key);
+                match loaded {
+                    Ok(resource) => {
+                        let id = key.1.clone();
+                        let data = storage.write().unwrap().insert(id.clone(), resource);
+                        let _ = finished.send(FinishedLoad { key, source });
+                        Loading::Loaded(ResourceHandle { id, data })
+                    }
+                    Err(e) => Loading::Failed(e),
+                }
+            };
+            drop(pending);
+            *state.write() = result;
+        });
+        
+        loading
+    }
+    
+    /// Forget a pending async load of `id`, so its result is dropped
+    ///
+    /// Returns the lock on the pending loads; hold it while changing the
+    /// storage so the load can't finish in between.
+    fn cancel_load<T: Any + Send + Sync + 'static>(&self, id: &str) -> std::sync::MutexGuard<'_, PendingLoads> {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.remove(&(TypeId::of::<T>(), id.to_string()));
+        pending
+    }
+    
+    /// Start tracking async loads that finished since the last poll
+    ///
+    /// Returns the ids of the newly loaded resources.
+    pub fn poll_loads(&mut self) -> Vec<String> {
+        let finished: Vec<FinishedLoad> = self.finished_loads.try_iter().collect();
+        finished
+            .into_iter()
+            .map(|load| {
+                let id = load.key.1.clone();
+                self.track_source(load.key, load.source);
+                id
+            })
+            .collect()
+    }
+    
+    /// Find the loader for an asset path, and where the result should be reloaded from
+    fn resolve<T: Any + Send + Sync + 'static>(&self, path: &str) -> Result<(SharedLoader, LoadedSource), ResourceError> {
+        let full_path = self.asset_path.join(path);
+        let extension = full_path.extension()
+            .and_then(|ext| ext.to_str())
+            .ok_or_else(|| ResourceError::NoLoader(String::new()))?;
+        
+        let loader = self.loaders.get(extension)
+            .ok_or_else(|| ResourceError::NoLoader(extension.to_string()))?;
+        let source = LoadedSource {
+            path: full_path.clone(),
+            extension: extension.to_string(),
+            replace: ResourceStorage::replace::<T>,
+        };
+        Ok((loader.clone(), source))
+    }
+    
+    /// Remember where a resource was loaded from, and watch it for changes
+    fn track_source(&mut self, key: (TypeId, String), source: LoadedSource) {
+        #[cfg(feature = "hot-reload")]
+        self.watch(&source.path);
+        
+        self.sources.insert(key, source);
+    }
+    
+    /// Re-run the loader for a resource and swap the new data into its existing handles
+    ///
+    /// On failure the old data is left intact.
+    pub fn reload<T: Any + Send + Sync + 'static>(&self, id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let source = self.sources.get(&(TypeId::of::<T>(), id.to_string()))
+            .ok_or_else(|| format!("Resource '{}' was not loaded from a file", id))?;
+        self.reload_source(id, source)
+    }
+    
+    fn reload_source(&self, id: &str, source: &LoadedSource) -> Result<(), Box<dyn std::error::Error>> {
+        let loader = self.loaders.get(&source.extension)
+            .ok_or_else(|| format!("No loader for extension: {}", source.extension))?;
+        let asset = loader.load(&source.path)?;
+        (source.replace)(&self.storage.read().unwrap(), id, asset)
     }
     
     /// Add a resource directly
-    pub fn add<T: Any + Send + Sync + Clone + 'static>(&mut self, id: &str, resource: T) -> ResourceHandle<T> {
-        self.storage.write().unwrap().insert(id.to_string(), resource.clone());
-        ResourceHandle::new(id.to_string(), resource)
+    ///
+    /// Replaces any resource with the same id, and any async load of it still
+    /// running.
+    pub fn add<T: Any + Send + Sync + 'static>(&mut self, id: &str, resource: T) -> ResourceHandle<T> {
+        self.sources.remove(&(TypeId::of::<T>(), id.to_string()));
+        let _pending = self.cancel_load::<T>(id);
+        let data = self.storage.write().unwrap().insert(id.to_string(), resource);
+        ResourceHandle { id: id.to_string(), data }
     }
     
     /// Get a resource by ID
-    pub fn get<T: Any + Send + Sync + Clone + 'static>(&self, id: &str) -> Option<ResourceHandle<T>> {
+    pub fn get<T: Any + Send + Sync + 'static>(&self, id: &str) -> Option<ResourceHandle<T>> {
         self.storage.read().unwrap().get::<T>(id)
-            .cloned()
-            .map(|resource| ResourceHandle::new(id.to_string(), resource))
+            .map(|data| ResourceHandle { id: id.to_string(), data: data.clone() })
     }
     
     /// Remove a resource
-    pub fn remove<T: Any + Send + Sync + 'static>(&mut self, id: &str) -> Option<Box<T>> {
+    ///
+    /// Returns a handle to the removed data; other handles keep it alive until
+    /// dropped. An async load of the id still running is cancelled.
+    pub fn remove<T: Any + Send + Sync + 'static>(&mut self, id: &str) -> Option<ResourceHandle<T>> {
+        self.sources.remove(&(TypeId::of::<T>(), id.to_string()));
+        let _pending = self.cancel_load::<T>(id);
         self.storage.write().unwrap().remove::<T>(id)
+            .map(|data| ResourceHandle { id: id.to_string(), data })
     }
     
     /// Check if a resource exists
     pub fn exists<T: Any + Send + Sync + 'static>(&self, id: &str) -> bool {
         self.storage.read().unwrap().get::<T>(id).is_some()
     }
+    
+    /// Number of live references to a resource, including the manager's own
+    ///
+    /// A count of 1 means no handles are held outside the manager.
+    pub fn strong_count<T: Any + Send + Sync + 'static>(&self, id: &str) -> Option<usize> {
+        self.storage.read().unwrap().get::<T>(id).map(Arc::strong_count)
+    }
+    
+    /// Free every resource no longer referenced by any handle
+    ///
+    /// Returns the ids of the freed resources.
+    pub fn collect_unused(&mut self) -> Vec<String> {
+        let freed = self.storage.write().unwrap().collect_unused();
+        freed
+            .into_iter()
+            .map(|key| {
+                self.sources.remove(&key);
+                key.1
+            })
+            .collect()
+    }
 }
 
 /// Mesh resource
 #[derive(Clone)]
 pub struct MeshResource {
     pub vertices: Vec<crate::graphics::Vertex>,
-    pub indices: Vec<u16>,
+    pub indices: Vec<u32>,
+}
+
+impl MeshResource {
+    /// Copy the CPU-side vertex and index data of a GPU mesh
+    pub fn from_mesh(mesh: &crate::graphics::Mesh) -> Self {
+        Self {
+            vertices: mesh.vertices.clone(),
+            indices: mesh.indices.clone(),
+        }
+    }
+}
+
+/// Area-weighted smooth normals for loaded meshes that don't provide any
+#[cfg(any(feature = "gltf", feature = "tobj"))]
+fn compute_normals(positions: &[[f32; 3]], indices: &[u32]) -> Vec<[f32; 3]> {
+    use cgmath::InnerSpace;
+    
+    let mut normals = vec![cgmath::Vector3::new(0.0, 0.0, 0.0); positions.len()];
+    
+    for triangle in indices.chunks_exact(3) {
+        let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|i| i as usize);
+        let pa = cgmath::Vector3::from(positions[a]);
+        let face = (cgmath::Vector3::from(positions[b]) - pa).cross(cgmath::Vector3::from(positions[c]) - pa);
+        for i in [a, b, c] {
+            normals[i] += face;
+        }
+    }
+    
+    normals
+        .into_iter()
+        .map(|n| if n.magnitude2() > 0.0 { n.normalize().into() } else { [0.0, 0.0, 1.0] })
+        .collect()
 }
 
 /// Shader resource
@@ -197,20 +647,182 @@ pub enum TextureFormat {
     Rgba16F,
 }
 
+/// Named place where players or entities can enter a world
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpawnPoint {
+    pub name: String,
+    pub chart: crate::manifold::ChartId,
+    pub position: cgmath::Point3<f32>,
+    #[serde(default = "identity_orientation")]
+    pub orientation: cgmath::Quaternion<f32>,
+}
+
+fn identity_orientation() -> cgmath::Quaternion<f32> {
+    cgmath::Quaternion::new(1.0, 0.0, 0.0, 0.0)
+}
+
+impl SpawnPoint {
+    /// Create a spawn point facing the default direction
+    pub fn new(name: &str, chart: crate::manifold::ChartId, position: cgmath::Point3<f32>) -> Self {
+        Self {
+            name: name.to_string(),
+            chart,
+            position,
+            orientation: identity_orientation(),
+        }
+    }
+    
+    pub fn with_orientation(mut self, orientation: cgmath::Quaternion<f32>) -> Self {
+        self.orientation = orientation;
+        self
+    }
+    
+    /// Where the spawn point is, as a manifold position
+    pub fn manifold_position(&self) -> crate::manifold::ManifoldPosition {
+        crate::manifold::ManifoldPosition::new(self.chart, self.position)
+    }
+}
+
+/// Spawn point as saved: named, or an unnamed `(chart, position)` pair from
+/// worlds saved before spawn points had names
+enum SavedSpawnPoint {
+    Named(SpawnPoint),
+    Unnamed(crate::manifold::ChartId, cgmath::Point3<f32>),
+}
+
+impl<'de> Deserialize<'de> for SavedSpawnPoint {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SavedSpawnPointVisitor;
+        
+        impl<'de> serde::de::Visitor<'de> for SavedSpawnPointVisitor {
+            type Value = SavedSpawnPoint;
+            
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a spawn point or a (chart, position) pair")
+            }
+            
+            fn visit_map<A: serde::de::MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                SpawnPoint::deserialize(serde::de::value::MapAccessDeserializer::new(map)).map(SavedSpawnPoint::Named)
+            }
+            
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let chart = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+                let position = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+                Ok(SavedSpawnPoint::Unnamed(chart, position))
+            }
+        }
+        
+        deserializer.deserialize_any(SavedSpawnPointVisitor)
+    }
+}
+
+/// Read spawn points in either saved form, keeping the last of any repeated name
+///
+/// Unnamed spawn points are called `spawn_<index>` after their place in the list.
+fn deserialize_spawn_points<'de, D>(deserializer: D) -> Result<Vec<SpawnPoint>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let saved = Vec::<SavedSpawnPoint>::deserialize(deserializer)?;
+    let mut spawn_points: Vec<SpawnPoint> = Vec::with_capacity(saved.len());
+    for (index, spawn) in saved.into_iter().enumerate() {
+        let spawn = match spawn {
+            SavedSpawnPoint::Named(spawn) => spawn,
+            SavedSpawnPoint::Unnamed(chart, position) => SpawnPoint::new(&format!("spawn_{}", index), chart, position),
+        };
+        match spawn_points.iter_mut().find(|existing| existing.name == spawn.name) {
+            Some(existing) => *existing = spawn,
+            None => spawn_points.push(spawn),
+        }
+    }
+    Ok(spawn_points)
+}
+
 /// World resource for non-Euclidean levels
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct WorldResource {
     pub manifold: crate::manifold::Manifold,
-    pub spawn_points: Vec<(crate::manifold::ChartId, cgmath::Point3<f32>)>,
+    /// Spawn points in the order they were added, with unique names
+    #[serde(deserialize_with = "deserialize_spawn_points")]
+    spawn_points: Vec<SpawnPoint>,
     pub metadata: WorldMetadata,
 }
 
-#[derive(Clone)]
+impl WorldResource {
+    /// Create a world around a manifold, without spawn points
+    pub fn new(manifold: crate::manifold::Manifold) -> Self {
+        Self {
+            manifold,
+            spawn_points: Vec::new(),
+            metadata: WorldMetadata::default(),
+        }
+    }
+    
+    /// Spawn points in the order they were added
+    pub fn spawn_points(&self) -> &[SpawnPoint] {
+        &self.spawn_points
+    }
+    
+    /// Add a spawn point, replacing any with the same name
+    pub fn add_spawn_point(&mut self, spawn: SpawnPoint) {
+        match self.spawn_points.iter_mut().find(|existing| existing.name == spawn.name) {
+            Some(existing) => *existing = spawn,
+            None => self.spawn_points.push(spawn),
+        }
+    }
+    
+    /// Remove a spawn point by name, returning it
+    pub fn remove_spawn_point(&mut self, name: &str) -> Option<SpawnPoint> {
+        let index = self.spawn_points.iter().position(|spawn| spawn.name == name)?;
+        Some(self.spawn_points.remove(index))
+    }
+    
+    /// Look up a spawn point by name
+    pub fn spawn_point(&self, name: &str) -> Option<&SpawnPoint> {
+        self.spawn_points.iter().find(|spawn| spawn.name == name)
+    }
+    
+    /// The spawn point named by `metadata.default_spawn`, or else the first one added
+    pub fn default_spawn(&self) -> Option<&SpawnPoint> {
+        self.metadata.default_spawn.as_deref()
+            .and_then(|name| self.spawn_point(name))
+            .or_else(|| self.spawn_points.first())
+    }
+    
+    /// Save the world to a RON file
+    ///
+    /// Custom metric functions are not saved; re-register them after loading.
+    pub fn save_ron(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+    
+    /// Load a world from a RON file
+    ///
+    /// Debug builds log any [`Manifold::validate`](crate::manifold::Manifold::validate) warnings.
+    pub fn load_ron(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let text = std::fs::read_to_string(path.as_ref())?;
+        let world: Self = ron::from_str(&text)?;
+        
+        #[cfg(debug_assertions)]
+        for warning in world.manifold.validate() {
+            eprintln!("{}: {}", path.as_ref().display(), warning);
+        }
+        
+        Ok(world)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct WorldMetadata {
     pub name: String,
     pub description: String,
     pub author: String,
     pub version: String,
+    /// Name of the spawn point new players start at, see [`WorldResource::default_spawn`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_spawn: Option<String>,
 }
 
 impl Default for WorldMetadata {
@@ -220,6 +832,7 @@ impl Default for WorldMetadata {
             description: String::new(),
             author: "Unknown".to_string(),
             version: "1.0.0".to_string(),
+            default_spawn: None,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,130 @@
+//! Wavefront OBJ model loading (requires the `tobj` feature)
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::path::Path;
+use crate::graphics::Vertex;
+use super::{AssetLoader, MeshResource, compute_normals};
+
+/// Meshes loaded from an OBJ file, keyed by object or group name
+///
+/// Unnamed groups are named `mesh<index>`. Materials are ignored.
+#[derive(Clone, Default)]
+pub struct ObjModel {
+    pub meshes: HashMap<String, MeshResource>,
+}
+
+impl ObjModel {
+    /// Load a model from an `.obj` file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let (models, _materials) = tobj::load_obj(path.as_ref(), &tobj::GPU_LOAD_OPTIONS)?;
+        Self::from_models(models)
+    }
+    
+    /// Load a model from in-memory OBJ text
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::from_reader(&mut std::io::Cursor::new(bytes))
+    }
+    
+    fn from_reader(reader: &mut impl BufRead) -> Result<Self, Box<dyn std::error::Error>> {
+        // Material libraries are not resolved
+        let (models, _materials) = tobj::load_obj_buf(reader, &tobj::GPU_LOAD_OPTIONS, |_| {
+            Ok((Vec::new(), Default::default()))
+        })?;
+        Self::from_models(models)
+    }
+    
+    /// Get a mesh by name
+    pub fn mesh(&self, name: &str) -> Option<&MeshResource> {
+        self.meshes.get(name)
+    }
+    
+    /// Flatten every group into one mesh
+    ///
+    /// Groups are appended in name order, so the result is deterministic.
+    pub fn merged(&self) -> MeshResource {
+        let mut names: Vec<&String> = self.meshes.keys().collect();
+        names.sort();
+        
+        let mut merged = MeshResource { vertices: Vec::new(), indices: Vec::new() };
+        for name in names {
+            let mesh = &self.meshes[name];
+            let offset = merged.vertices.len() as u32;
+            merged.vertices.extend_from_slice(&mesh.vertices);
+            merged.indices.extend(mesh.indices.iter().map(|i| i + offset));
+        }
+        merged
+    }
+    
+    fn from_models(models: Vec<tobj::Model>) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut meshes = HashMap::new();
+        
+        for (index, model) in models.into_iter().enumerate() {
+            let name = if model.name.is_empty() || model.name == "unnamed_object" {
+                format!("mesh{}", index)
+            } else {
+                model.name
+            };
+            meshes.insert(name, read_mesh(&model.mesh)?);
+        }
+        
+        Ok(Self { meshes })
+    }
+}
+
+/// Asset loader for `.obj` files, producing a single [`MeshResource`]
+///
+/// All groups are flattened with [`ObjModel::merged`]; use [`ObjModel::load`]
+/// to keep them apart.
+pub struct ObjLoader;
+
+impl AssetLoader for ObjLoader {
+    type Asset = Box<dyn Any + Send + Sync>;
+    
+    fn load(&self, path: &Path) -> Result<Self::Asset, Box<dyn std::error::Error>> {
+        Ok(Box::new(ObjModel::load(path)?.merged()))
+    }
+    
+    fn extensions(&self) -> &[&str] {
+        &["obj"]
+    }
+}
+
+/// Convert a triangulated, single-index tobj mesh into engine vertices
+///
+/// Missing normals are generated. OBJ texture coordinates start at the bottom
+/// left, so `v` is flipped to match the engine's top-left origin.
+fn read_mesh(mesh: &tobj::Mesh) -> Result<MeshResource, Box<dyn std::error::Error>> {
+    let positions: Vec<[f32; 3]> = mesh.positions
+        .chunks_exact(3)
+        .map(|p| [p[0], p[1], p[2]])
+        .collect();
+    
+    if let Some(&bad) = mesh.indices.iter().find(|&&i| i as usize >= positions.len()) {
+        return Err(format!("Index {} out of range for {} vertices", bad, positions.len()).into());
+    }
+    
+    let normals: Vec<[f32; 3]> = if mesh.normals.len() == mesh.positions.len() {
+        mesh.normals.chunks_exact(3).map(|n| [n[0], n[1], n[2]]).collect()
+    } else {
+        compute_normals(&positions, &mesh.indices)
+    };
+    let tex_coords: Vec<[f32; 2]> = mesh.texcoords
+        .chunks_exact(2)
+        .map(|t| [t[0], 1.0 - t[1]])
+        .collect();
+    
+    let vertices = positions
+        .iter()
+        .enumerate()
+        .map(|(i, position)| Vertex::new(
+            *position,
+            tex_coords.get(i).copied().unwrap_or([0.0, 0.0]),
+            normals[i],
+            [1.0, 1.0, 1.0, 1.0],
+        ))
+        .collect();
+    
+    Ok(MeshResource { vertices, indices: mesh.indices.clone() })
+}
@@ -1,7 +1,14 @@
 //! Time management for the non-Euclidean engine
 
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
+/// Largest delta time reported by default, in seconds
+pub const DEFAULT_MAX_DELTA: f32 = 0.1;
+
+/// Number of frame times kept for statistics by default
+pub const DEFAULT_FRAME_HISTORY: usize = 120;
+
 /// Time tracking for the engine
 #[derive(Debug, Clone)]
 pub struct Time {
@@ -9,11 +16,21 @@ pub struct Time {
     last_frame_time: Instant,
     current_time: Instant,
     delta_time: f32,
+    raw_delta_time: f32,
+    max_delta: f32,
     total_time: f32,
     frame_count: u64,
     fps: f32,
     fps_update_time: Instant,
     fps_frame_count: u32,
+    frame_times: VecDeque<f32>,
+    frame_history: usize,
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Time {
@@ -25,22 +42,39 @@ impl Time {
             last_frame_time: now,
             current_time: now,
             delta_time: 0.0,
+            raw_delta_time: 0.0,
+            max_delta: DEFAULT_MAX_DELTA,
             total_time: 0.0,
             frame_count: 0,
             fps: 0.0,
             fps_update_time: now,
             fps_frame_count: 0,
+            frame_times: VecDeque::with_capacity(DEFAULT_FRAME_HISTORY),
+            frame_history: DEFAULT_FRAME_HISTORY,
         }
     }
     
     /// Update time tracking
+    ///
+    /// The reported delta is clamped to `max_delta`, so a hitch (e.g. loading a
+    /// space) advances gameplay by at most one capped step instead of teleporting
+    /// entities or tunnelling them through portals. Total time advances by the
+    /// clamped delta as well.
     pub fn update(&mut self, dt: f32) {
         self.current_time = Instant::now();
-        self.delta_time = dt;
-        self.total_time += dt;
+        self.raw_delta_time = dt;
+        self.delta_time = dt.min(self.max_delta);
+        self.total_time += self.delta_time;
         self.frame_count += 1;
         self.fps_frame_count += 1;
         
+        if self.frame_history > 0 {
+            if self.frame_times.len() == self.frame_history {
+                self.frame_times.pop_front();
+            }
+            self.frame_times.push_back(dt);
+        }
+        
         // Update FPS every second
         let fps_elapsed = self.current_time.duration_since(self.fps_update_time);
         if fps_elapsed >= Duration::from_secs(1) {
@@ -52,11 +86,26 @@ impl Time {
         self.last_frame_time = self.current_time;
     }
     
-    /// Get delta time in seconds
+    /// Get delta time in seconds, clamped to `max_delta`
     pub fn delta_time(&self) -> f32 {
         self.delta_time
     }
     
+    /// Get the unclamped delta time in seconds, for diagnostics
+    pub fn raw_delta_time(&self) -> f32 {
+        self.raw_delta_time
+    }
+    
+    /// Set the largest delta time `update` will report
+    pub fn set_max_delta(&mut self, max_delta: f32) {
+        self.max_delta = max_delta.max(0.0);
+    }
+    
+    /// Get the largest delta time `update` will report
+    pub fn max_delta(&self) -> f32 {
+        self.max_delta
+    }
+    
     /// Get total elapsed time in seconds
     pub fn total_time(&self) -> f32 {
         self.total_time
@@ -76,6 +125,51 @@ impl Time {
     pub fn elapsed(&self) -> Duration {
         self.current_time.duration_since(self.start_time)
     }
+    
+    /// Set how many recent frame times are kept for statistics
+    pub fn set_frame_history(&mut self, frames: usize) {
+        self.frame_history = frames;
+        while self.frame_times.len() > frames {
+            self.frame_times.pop_front();
+        }
+    }
+    
+    /// Recent unclamped frame times in seconds, oldest first
+    pub fn frame_times(&self) -> impl Iterator<Item = f32> + '_ {
+        self.frame_times.iter().copied()
+    }
+    
+    /// Average of the recent frame times, or 0 before the first frame
+    pub fn avg_frame_time(&self) -> f32 {
+        if self.frame_times.is_empty() {
+            return 0.0;
+        }
+        self.frame_times.iter().sum::<f32>() / self.frame_times.len() as f32
+    }
+    
+    /// Shortest recent frame time, or 0 before the first frame
+    pub fn min_frame_time(&self) -> f32 {
+        self.frame_times.iter().copied().reduce(f32::min).unwrap_or(0.0)
+    }
+    
+    /// Longest recent frame time, or 0 before the first frame
+    pub fn max_frame_time(&self) -> f32 {
+        self.frame_times.iter().copied().reduce(f32::max).unwrap_or(0.0)
+    }
+    
+    /// Frame time at percentile `p` (0–100) of the recent window, by nearest rank
+    ///
+    /// `percentile(99.0)` is the time 99% of recent frames stayed within.
+    pub fn percentile(&self, p: f32) -> f32 {
+        if self.frame_times.is_empty() {
+            return 0.0;
+        }
+        
+        let mut sorted: Vec<f32> = self.frame_times.iter().copied().collect();
+        sorted.sort_by(f32::total_cmp);
+        let rank = ((p.clamp(0.0, 100.0) / 100.0) * sorted.len() as f32).ceil() as usize;
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    }
 }
 
 /// Timer for measuring intervals
@@ -165,6 +259,130 @@ impl Timer {
     }
 }
 
+/// Free-running stopwatch for measuring how long things take
+///
+/// Starts stopped at zero. Time only accumulates while running, so `stop` and
+/// `start` pause and resume it.
+#[derive(Debug, Clone, Default)]
+pub struct Stopwatch {
+    running_since: Option<Instant>,
+    accumulated: Duration,
+    last_lap: Duration,
+}
+
+impl Stopwatch {
+    /// Create a stopped stopwatch at zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Create a stopwatch that is already running
+    pub fn started() -> Self {
+        let mut stopwatch = Self::new();
+        stopwatch.start();
+        stopwatch
+    }
+    
+    /// Start or resume counting
+    pub fn start(&mut self) {
+        if self.running_since.is_none() {
+            self.running_since = Some(Instant::now());
+        }
+    }
+    
+    /// Stop counting, keeping the elapsed time
+    pub fn stop(&mut self) {
+        if let Some(since) = self.running_since.take() {
+            self.accumulated += since.elapsed();
+        }
+    }
+    
+    /// Stop and clear the elapsed time and laps
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+    
+    /// Check if the stopwatch is counting
+    pub fn is_running(&self) -> bool {
+        self.running_since.is_some()
+    }
+    
+    /// Total time spent running
+    pub fn elapsed(&self) -> Duration {
+        self.accumulated + self.running_since.map_or(Duration::ZERO, |since| since.elapsed())
+    }
+    
+    /// Running time since the previous lap (or the start), beginning a new lap
+    pub fn lap(&mut self) -> Duration {
+        let elapsed = self.elapsed();
+        let lap = elapsed.saturating_sub(self.last_lap);
+        self.last_lap = elapsed;
+        lap
+    }
+}
+
+/// Caps the frame rate by waiting out the rest of each frame's budget
+///
+/// Sleeps until [`SPIN_MARGIN`](Self::SPIN_MARGIN) before the deadline, then
+/// busy-waits the remainder, since OS sleeps tend to overshoot by a millisecond
+/// or more. Deadlines advance by whole budgets so the average rate stays on
+/// target; a frame that overruns starts the next budget from when it ended.
+#[derive(Debug, Clone)]
+pub struct FrameLimiter {
+    budget: Option<Duration>,
+    frame_start: Instant,
+}
+
+impl FrameLimiter {
+    /// How long before the deadline to stop sleeping and start spinning
+    pub const SPIN_MARGIN: Duration = Duration::from_millis(2);
+    
+    /// Create a limiter for a frame rate (None or 0 = unlimited)
+    pub fn new(target_fps: Option<u32>) -> Self {
+        let mut limiter = Self {
+            budget: None,
+            frame_start: Instant::now(),
+        };
+        limiter.set_target_fps(target_fps);
+        limiter
+    }
+    
+    /// Change the frame rate (None or 0 = unlimited)
+    pub fn set_target_fps(&mut self, target_fps: Option<u32>) {
+        self.budget = target_fps
+            .filter(|fps| *fps > 0)
+            .map(|fps| Duration::from_secs_f64(1.0 / fps as f64));
+    }
+    
+    /// Time allowed per frame, if limited
+    pub fn budget(&self) -> Option<Duration> {
+        self.budget
+    }
+    
+    /// Wait until the current frame's budget is used up, returning how long it waited
+    pub fn wait(&mut self) -> Duration {
+        let now = Instant::now();
+        let deadline = match self.budget {
+            Some(budget) if now < self.frame_start + budget => self.frame_start + budget,
+            _ => {
+                self.frame_start = now;
+                return Duration::ZERO;
+            }
+        };
+        
+        let remaining = deadline - now;
+        if remaining > Self::SPIN_MARGIN {
+            std::thread::sleep(remaining - Self::SPIN_MARGIN);
+        }
+        while Instant::now() < deadline {
+            std::hint::spin_loop();
+        }
+        
+        self.frame_start = deadline;
+        remaining
+    }
+}
+
 /// Fixed timestep accumulator for physics
 pub struct FixedTimestep {
     accumulator: f32,
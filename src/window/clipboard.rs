@@ -0,0 +1,46 @@
+//! System clipboard access, available with the `clipboard` feature
+//!
+//! Without the feature, or when the platform has no clipboard (e.g. no
+//! display server), reads return `None` and writes fail.
+
+#[cfg(feature = "clipboard")]
+use std::sync::{Mutex, OnceLock};
+
+/// Clipboard shared by the whole process
+///
+/// Kept alive between calls because on some platforms the copied text is only
+/// served while the clipboard object exists.
+#[cfg(feature = "clipboard")]
+fn clipboard() -> &'static Mutex<Option<arboard::Clipboard>> {
+    static CLIPBOARD: OnceLock<Mutex<Option<arboard::Clipboard>>> = OnceLock::new();
+    CLIPBOARD.get_or_init(|| Mutex::new(arboard::Clipboard::new().ok()))
+}
+
+/// Read text from the clipboard
+pub fn get_text() -> Option<String> {
+    #[cfg(feature = "clipboard")]
+    {
+        let mut clipboard = clipboard().lock().unwrap_or_else(|e| e.into_inner());
+        clipboard.as_mut()?.get_text().ok()
+    }
+    #[cfg(not(feature = "clipboard"))]
+    {
+        None
+    }
+}
+
+/// Replace the clipboard contents with `text`
+pub fn set_text(text: &str) -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "clipboard")]
+    {
+        let mut clipboard = clipboard().lock().unwrap_or_else(|e| e.into_inner());
+        let clipboard = clipboard.as_mut().ok_or("No system clipboard found")?;
+        clipboard.set_text(text)?;
+        Ok(())
+    }
+    #[cfg(not(feature = "clipboard"))]
+    {
+        let _ = text;
+        Err("Clipboard support requires the `clipboard` feature".into())
+    }
+}
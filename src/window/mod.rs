@@ -3,11 +3,14 @@
 use winit::{
     event::{Event, WindowEvent as WinitWindowEvent},
     event_loop::{EventLoop, EventLoopBuilder},
-    window::{Window as WinitWindow, WindowBuilder as WinitWindowBuilder},
+    window::{Fullscreen, Window as WinitWindow, WindowBuilder as WinitWindowBuilder},
     dpi::LogicalSize,
 };
+use std::path::Path;
 use std::sync::Arc;
 
+pub mod clipboard;
+
 /// Window event types
 #[derive(Debug, Clone)]
 pub enum WindowEvent {
@@ -35,6 +38,64 @@ pub enum MouseButton {
     Other(u16),
 }
 
+/// RGBA pixels for the window icon
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowIcon {
+    pub rgba: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl WindowIcon {
+    /// Convert to a winit icon, failing if the pixels don't match the size
+    pub fn to_winit(&self) -> Result<winit::window::Icon, winit::window::BadIcon> {
+        winit::window::Icon::from_rgba(self.rgba.clone(), self.width, self.height)
+    }
+}
+
+/// Mouse cursor shapes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CursorIcon {
+    #[default]
+    Default,
+    Pointer,
+    Crosshair,
+    Text,
+    Move,
+    Grab,
+    Grabbing,
+    NotAllowed,
+    Wait,
+    Progress,
+    Help,
+    EwResize,
+    NsResize,
+    NeswResize,
+    NwseResize,
+}
+
+impl From<CursorIcon> for winit::window::CursorIcon {
+    fn from(icon: CursorIcon) -> Self {
+        match icon {
+            CursorIcon::Default => Self::Default,
+            CursorIcon::Pointer => Self::Pointer,
+            CursorIcon::Crosshair => Self::Crosshair,
+            CursorIcon::Text => Self::Text,
+            CursorIcon::Move => Self::Move,
+            CursorIcon::Grab => Self::Grab,
+            CursorIcon::Grabbing => Self::Grabbing,
+            CursorIcon::NotAllowed => Self::NotAllowed,
+            CursorIcon::Wait => Self::Wait,
+            CursorIcon::Progress => Self::Progress,
+            CursorIcon::Help => Self::Help,
+            CursorIcon::EwResize => Self::EwResize,
+            CursorIcon::NsResize => Self::NsResize,
+            CursorIcon::NeswResize => Self::NeswResize,
+            CursorIcon::NwseResize => Self::NwseResize,
+        }
+    }
+}
+
 /// Window builder for configuring window creation
 pub struct WindowBuilder {
     title: String,
@@ -44,6 +105,7 @@ pub struct WindowBuilder {
     maximized: bool,
     fullscreen: bool,
     vsync: bool,
+    icon: Option<WindowIcon>,
 }
 
 impl Default for WindowBuilder {
@@ -56,6 +118,7 @@ impl Default for WindowBuilder {
             maximized: false,
             fullscreen: false,
             vsync: true,
+            icon: None,
         }
     }
 }
@@ -65,6 +128,15 @@ impl WindowBuilder {
         Self::default()
     }
     
+    /// Start from the title, size, resizability and vsync of an engine configuration
+    pub fn from_config(config: &crate::core::EngineConfig) -> Self {
+        Self::new()
+            .with_title(&config.title)
+            .with_dimensions(config.width, config.height)
+            .with_resizable(config.resizable)
+            .with_vsync(config.vsync)
+    }
+    
     pub fn with_title(mut self, title: impl Into<String>) -> Self {
         self.title = title.into();
         self
@@ -96,9 +168,48 @@ impl WindowBuilder {
         self
     }
     
+    /// Set the window icon from `width` × `height` RGBA pixels, checked when the window is built
+    pub fn with_icon(mut self, rgba: &[u8], width: u32, height: u32) -> Self {
+        self.icon = Some(WindowIcon { rgba: rgba.to_vec(), width, height });
+        self
+    }
+    
+    /// Set the window icon from an image file
+    pub fn with_icon_file(self, path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let image = image::open(path)?.to_rgba8();
+        let (width, height) = image.dimensions();
+        Ok(self.with_icon(image.as_raw(), width, height))
+    }
+    
+    /// Get the window icon, if set
+    pub fn icon(&self) -> Option<&WindowIcon> {
+        self.icon.as_ref()
+    }
+    
     pub fn build(self) -> Result<Window, Box<dyn std::error::Error>> {
         Window::from_builder(self)
     }
+    
+    /// Create a window with no OS window behind it, for headless engines
+    ///
+    /// Title, size and fullscreen state are recorded but nothing is shown,
+    /// and it has no surface to render to.
+    pub fn build_headless(self) -> Window {
+        Window::from_parts(None, &self)
+    }
+    
+    /// Settings shared by every way of creating the winit window
+    fn winit_builder(&self) -> Result<WinitWindowBuilder, Box<dyn std::error::Error>> {
+        let icon = self.icon.as_ref().map(WindowIcon::to_winit).transpose()?;
+        
+        Ok(WinitWindowBuilder::new()
+            .with_title(&self.title)
+            .with_inner_size(LogicalSize::new(self.width, self.height))
+            .with_resizable(self.resizable)
+            .with_maximized(self.maximized)
+            .with_fullscreen(self.fullscreen.then_some(Fullscreen::Borderless(None)))
+            .with_window_icon(icon))
+    }
 }
 
 /// Event loop wrapper that can be extracted
@@ -114,16 +225,16 @@ impl EventLoopWrapper {
     }
     
     pub fn create_window(&self, builder: &WindowBuilder) -> Result<Arc<WinitWindow>, Box<dyn std::error::Error>> {
-        let window = WinitWindowBuilder::new()
-            .with_title(&builder.title)
-            .with_inner_size(LogicalSize::new(builder.width, builder.height))
-            .with_resizable(builder.resizable)
-            .with_maximized(builder.maximized)
-            .build(&self.event_loop)?;
+        let window = builder.winit_builder()?.build(&self.event_loop)?;
         
         Ok(Arc::new(window))
     }
     
+    /// Create a [`Window`] on this event loop, to hand to [`Engine::with_window`](crate::core::Engine::with_window)
+    pub fn build_window(&self, builder: WindowBuilder) -> Result<Window, Box<dyn std::error::Error>> {
+        Window::with_event_loop(builder, &self.event_loop)
+    }
+    
     pub fn run<F>(self, mut event_handler: F) -> Result<(), Box<dyn std::error::Error>>
     where
         F: FnMut(Event<()>, &winit::event_loop::EventLoopWindowTarget<()>) + 'static,
@@ -137,23 +248,32 @@ impl EventLoopWrapper {
 
 /// Main window struct
 pub struct Window {
-    window: Arc<WinitWindow>,
+    /// `None` for headless windows
+    window: Option<Arc<WinitWindow>>,
     events: Vec<WindowEvent>,
     should_close: bool,
     width: u32,
     height: u32,
+    title: String,
+    fullscreen: bool,
 }
 
 impl Window {
     /// Create a new window with default settings
     pub fn new(config: &crate::core::EngineConfig) -> Result<Self, Box<dyn std::error::Error>> {
-        let builder = WindowBuilder::new()
-            .with_title(&config.title)
-            .with_dimensions(config.width, config.height)
-            .with_resizable(config.resizable)
-            .with_vsync(config.vsync);
-        
-        Self::from_builder(builder)
+        Self::from_builder(WindowBuilder::from_config(config))
+    }
+    
+    fn from_parts(window: Option<Arc<WinitWindow>>, builder: &WindowBuilder) -> Self {
+        Self {
+            window,
+            events: Vec::new(),
+            should_close: false,
+            width: builder.width,
+            height: builder.height,
+            title: builder.title.clone(),
+            fullscreen: builder.fullscreen,
+        }
     }
     
     /// Create a window from a builder (requires event loop to be created separately)
@@ -161,45 +281,19 @@ impl Window {
         // Create a temporary event loop just for window creation
         let event_loop = EventLoop::new()?;
         
-        let window = WinitWindowBuilder::new()
-            .with_title(&builder.title)
-            .with_inner_size(LogicalSize::new(builder.width, builder.height))
-            .with_resizable(builder.resizable)
-            .with_maximized(builder.maximized)
-            .build(&event_loop)?;
-        
-        let window = Arc::new(window);
+        let window = builder.winit_builder()?.build(&event_loop)?;
         
         // Note: The event loop is dropped here, which is not ideal but allows
         // the window to be created. In production, use with_event_loop instead.
         
-        Ok(Self {
-            window,
-            events: Vec::new(),
-            should_close: false,
-            width: builder.width,
-            height: builder.height,
-        })
+        Ok(Self::from_parts(Some(Arc::new(window)), &builder))
     }
     
     /// Create window with existing event loop
     pub fn with_event_loop(builder: WindowBuilder, event_loop: &EventLoop<()>) -> Result<Self, Box<dyn std::error::Error>> {
-        let window = WinitWindowBuilder::new()
-            .with_title(&builder.title)
-            .with_inner_size(LogicalSize::new(builder.width, builder.height))
-            .with_resizable(builder.resizable)
-            .with_maximized(builder.maximized)
-            .build(event_loop)?;
-        
-        let window = Arc::new(window);
+        let window = builder.winit_builder()?.build(event_loop)?;
         
-        Ok(Self {
-            window,
-            events: Vec::new(),
-            should_close: false,
-            width: builder.width,
-            height: builder.height,
-        })
+        Ok(Self::from_parts(Some(Arc::new(window)), &builder))
     }
     
     /// Poll and process window events (stub for compatibility)
@@ -212,23 +306,23 @@ impl Window {
     pub fn handle_event(&mut self, event: &Event<()>) {
         self.events.clear();
         
-        if let Event::WindowEvent { event, window_id } = event {
-            if window_id == &self.window.id() {
-                match event {
-                    WinitWindowEvent::CloseRequested => {
-                        self.should_close = true;
-                        self.events.push(WindowEvent::CloseRequested);
-                    }
-                    WinitWindowEvent::Resized(size) => {
-                        self.width = size.width;
-                        self.height = size.height;
-                        self.events.push(WindowEvent::Resized(size.width, size.height));
-                    }
-                    WinitWindowEvent::Focused(focused) => {
-                        self.events.push(WindowEvent::Focused(*focused));
-                    }
-                    _ => {}
+        if let Event::WindowEvent { event, window_id } = event
+            && self.window.as_ref().is_some_and(|window| window_id == &window.id())
+        {
+            match event {
+                WinitWindowEvent::CloseRequested => {
+                    self.should_close = true;
+                    self.events.push(WindowEvent::CloseRequested);
+                }
+                WinitWindowEvent::Resized(size) => {
+                    self.width = size.width;
+                    self.height = size.height;
+                    self.events.push(WindowEvent::Resized(size.width, size.height));
                 }
+                WinitWindowEvent::Focused(focused) => {
+                    self.events.push(WindowEvent::Focused(*focused));
+                }
+                _ => {}
             }
         }
     }
@@ -259,22 +353,69 @@ impl Window {
     }
     
     /// Set window title
-    pub fn set_title(&self, title: &str) {
-        self.window.set_title(title);
+    pub fn set_title(&mut self, title: &str) {
+        self.title = title.to_string();
+        if let Some(window) = &self.window {
+            window.set_title(title);
+        }
+    }
+    
+    /// Get the title last set on the window
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+    
+    /// Switch between borderless fullscreen on the current monitor and windowed
+    pub fn set_fullscreen(&mut self, fullscreen: bool) {
+        self.fullscreen = fullscreen;
+        if let Some(window) = &self.window {
+            window.set_fullscreen(fullscreen.then_some(Fullscreen::Borderless(None)));
+        }
+    }
+    
+    pub fn is_fullscreen(&self) -> bool {
+        self.fullscreen
     }
     
-    /// Get the underlying winit window for wgpu surface creation
-    pub fn winit_window(&self) -> &WinitWindow {
-        &self.window
+    /// Check if there is no OS window behind this one, see [`WindowBuilder::build_headless`]
+    pub fn is_headless(&self) -> bool {
+        self.window.is_none()
     }
     
-    /// Get window handle as Arc for wgpu
-    pub fn window_arc(&self) -> Arc<WinitWindow> {
+    /// Get the underlying winit window for wgpu surface creation, `None` if headless
+    pub fn winit_window(&self) -> Option<&WinitWindow> {
+        self.window.as_deref()
+    }
+    
+    /// Get window handle as Arc for wgpu, `None` if headless
+    pub fn window_arc(&self) -> Option<Arc<WinitWindow>> {
         self.window.clone()
     }
     
+    /// Change the shape of the mouse cursor over the window
+    pub fn set_cursor_icon(&self, icon: CursorIcon) {
+        if let Some(window) = &self.window {
+            window.set_cursor_icon(icon.into());
+        }
+    }
+    
+    /// Read text from the system clipboard
+    ///
+    /// `None` if the clipboard is empty, holds no text, or is unavailable
+    /// (including builds without the `clipboard` feature).
+    pub fn clipboard_get(&self) -> Option<String> {
+        clipboard::get_text()
+    }
+    
+    /// Copy text to the system clipboard
+    pub fn clipboard_set(&self, text: &str) -> Result<(), Box<dyn std::error::Error>> {
+        clipboard::set_text(text)
+    }
+    
     /// Request a redraw
     pub fn request_redraw(&self) {
-        self.window.request_redraw();
+        if let Some(window) = &self.window {
+            window.request_redraw();
+        }
     }
 }
\ No newline at end of file
@@ -2,7 +2,7 @@
 
 use metatopia_engine::prelude::*;
 use metatopia_engine::*;
-use cgmath::{Point3, Vector3};
+use cgmath::{EuclideanSpace, InnerSpace, Point3, Rad, Rotation3, Vector3};
 
 // ─── Manifold ──────────────────────────────────────────────────────────────
 
@@ -78,6 +78,402 @@ mod manifold_tests {
         let from_h = m.portals_from_chart(h);
         assert_eq!(from_h.len(), 1, "Hyperbolic chart should have 1 outbound portal");
     }
+
+    #[test]
+    fn rotated_portal_maps_source_plane_onto_destination_plane() {
+        let from = Point3::new(5.0, 0.0, 0.0);
+        let to = Point3::new(0.0, 0.0, -10.0);
+        let portal = Portal::new(PortalId(0), ChartId(0), ChartId(1), from, to,
+            Mat4::from_angle_y(cgmath::Deg(90.0)));
+
+        // The portal center maps onto the destination center
+        let center = portal.transform_point(from);
+        assert!((center - to).magnitude() < 1e-4, "center mapped to {center:?}");
+
+        // Points on the source plane (normal +Z) land on the destination plane,
+        // whose normal is the rotated source normal
+        let dest_normal = portal.transform_vector(Vector3::new(0.0, 0.0, 1.0));
+        for offset in [Vector3::new(1.0, 0.5, 0.0), Vector3::new(-0.8, -1.2, 0.0)] {
+            let mapped = portal.transform_point(from + offset);
+            assert!((mapped - to).dot(dest_normal).abs() < 1e-4, "{mapped:?} is off the destination plane");
+            assert!(((mapped - to).magnitude() - offset.magnitude()).abs() < 1e-4, "rotation should preserve distances");
+        }
+    }
+
+    #[test]
+    fn portal_transform_translation_is_not_double_counted() {
+        let from = Point3::new(5.0, 0.0, 0.0);
+        let to = Point3::new(0.0, 2.0, 0.0);
+        let portal = Portal::new(PortalId(0), ChartId(0), ChartId(1), from, to,
+            Mat4::from_translation(Vector3::new(3.0, 0.0, 0.0)));
+
+        let mapped = portal.transform_point(Point3::new(5.0, 1.0, 0.0));
+        assert!((mapped - Point3::new(0.0, 3.0, 0.0)).magnitude() < 1e-4, "got {mapped:?}");
+    }
+
+    #[test]
+    fn ray_portal_intersection_picks_nearest_portal() {
+        let mut m = Manifold::new();
+        let h = m.add_chart(GeometryType::Hyperbolic);
+        let s = m.add_chart(GeometryType::Spherical);
+        // Created far-first so iteration order alone would pick the wrong one
+        m.create_portal(ChartId(0), s, Point3::new(0.0, 0.0, -10.0), Point3::new(0.0, 0.0, 0.0), Mat4::from_scale(1.0)).unwrap();
+        let near = m.create_portal(ChartId(0), h, Point3::new(0.0, 0.0, -5.0), Point3::new(0.0, 0.0, 0.0), Mat4::from_scale(1.0)).unwrap();
+
+        let hit = m.ray_portal_intersection(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -1.0), ChartId(0));
+        let (id, point, chart) = hit.expect("ray should hit a portal");
+        assert_eq!(id, near);
+        assert_eq!(chart, h);
+        assert!((point.z + 5.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn ray_portal_intersection_skips_inactive_portal() {
+        let mut m = Manifold::new();
+        let h = m.add_chart(GeometryType::Hyperbolic);
+        let s = m.add_chart(GeometryType::Spherical);
+        let near = m.create_portal(ChartId(0), h, Point3::new(0.0, 0.0, -5.0), Point3::new(0.0, 0.0, 0.0), Mat4::from_scale(1.0)).unwrap();
+        let far = m.create_portal(ChartId(0), s, Point3::new(0.0, 0.0, -10.0), Point3::new(0.0, 0.0, 0.0), Mat4::from_scale(1.0)).unwrap();
+        m.portal_mut(near).unwrap().set_active(false);
+
+        let hit = m.ray_portal_intersection(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -1.0), ChartId(0));
+        assert_eq!(hit.map(|(id, _, _)| id), Some(far));
+    }
+
+    #[test]
+    fn ray_portal_intersection_ignores_back_facing_hits() {
+        let mut m = Manifold::new();
+        let h = m.add_chart(GeometryType::Hyperbolic);
+        m.create_portal(ChartId(0), h, Point3::new(0.0, 0.0, 5.0), Point3::new(0.0, 0.0, 0.0), Mat4::from_scale(1.0)).unwrap();
+
+        // Portal normal is +Z, so a ray travelling along +Z approaches from behind
+        let hit = m.ray_portal_intersection(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 1.0), ChartId(0));
+        assert!(hit.is_none());
+    }
+
+    #[test]
+    fn cast_ray_continues_through_portal_into_target_chart() {
+        let mut m = Manifold::new();
+        let other = m.add_chart(GeometryType::Euclidean);
+        let portal = m.create_portal(ChartId(0), other, Point3::new(0.0, 0.0, -5.0), Point3::new(10.0, 0.0, 0.0), Mat4::from_scale(1.0)).unwrap();
+
+        let ray = m.cast_ray(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -1.0), ChartId(0), 8.0, 32)
+            .expect("start chart exists");
+
+        assert_eq!(ray.charts(), vec![ChartId(0), other]);
+        assert_eq!(ray.portals(), vec![portal]);
+        let exit = ray.segments[0].path.points.last().unwrap();
+        assert!((exit.z + 5.0).abs() < 1e-3, "first segment should stop at the portal, got {:?}", exit);
+        let end = ray.end().unwrap();
+        assert_eq!(end.chart_id, other);
+        // 5 units to the portal, the remaining 3 carried on from the exit
+        assert!((end.local.0.z + 3.0).abs() < 0.01, "ended at {:?}", end.local.0);
+        assert!((end.local.0.x - 10.0).abs() < 1e-3);
+        assert!((ray.length() - 8.0).abs() < 0.01);
+
+        // A ray with no direction has nowhere to go
+        assert!(m.cast_ray(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 0.0), ChartId(0), 8.0, 32).is_none());
+    }
+
+    #[test]
+    fn upward_facing_portal_containment() {
+        use metatopia_engine::manifold::{PortalBounds, PortalShape};
+
+        let mut m = Manifold::new();
+        let h = m.add_chart(GeometryType::Hyperbolic);
+        let id = m.create_portal(ChartId(0), h, Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, 0.0), Mat4::from_scale(1.0)).unwrap();
+        let portal = m.portal_mut(id).unwrap();
+        portal.set_bounds(PortalBounds::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            2.0,
+            4.0,
+            PortalShape::Rectangular,
+        ));
+
+        let bounds = portal.bounds();
+        assert!((bounds.right.magnitude() - 1.0).abs() < 1e-5, "basis must not degenerate");
+        assert!(bounds.right.dot(bounds.up).abs() < 1e-5);
+        assert!((bounds.right.cross(bounds.up) - bounds.normal).magnitude() < 1e-5);
+
+        // Width (2) runs along right and height (4) along up, both in the XZ plane
+        assert!(portal.contains_point(Point3::new(0.0, 0.0, 0.0)));
+        assert!(portal.contains_point(Point3::new(0.0, 0.0, 0.0) + bounds.right * 0.9 + bounds.up * 1.9));
+        assert!(!portal.contains_point(Point3::new(0.0, 0.0, 0.0) + bounds.right * 1.1));
+        assert!(!portal.contains_point(Point3::new(0.0, 0.0, 0.0) + bounds.up * 2.1));
+
+        // A ray falling straight down hits it inside the rectangle and misses outside
+        assert!(portal.ray_hit(Point3::new(0.5, 3.0, 0.5), Vector3::new(0.0, -1.0, 0.0)).is_some());
+        assert!(portal.ray_hit(Point3::new(5.0, 3.0, 5.0), Vector3::new(0.0, -1.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn elliptical_and_polygon_portal_containment() {
+        use metatopia_engine::manifold::portal::PortalRenderer;
+        use metatopia_engine::manifold::{PortalBounds, PortalShape};
+        use cgmath::Point2;
+
+        let mut m = Manifold::new();
+        let h = m.add_chart(GeometryType::Hyperbolic);
+        let id = m.create_portal(ChartId(0), h, Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, 0.0), Mat4::from_scale(1.0)).unwrap();
+        let center = Point3::new(1.0, 2.0, 3.0);
+        let portal = m.portal_mut(id).unwrap();
+
+        // Facing +Z, so right is -X and up is +Y
+        portal.set_bounds(PortalBounds::new(center, Vector3::new(0.0, 0.0, 1.0), 1.0, 1.0, PortalShape::Elliptical { rx: 2.0, ry: 0.5 }));
+        let bounds = portal.bounds().clone();
+        assert!(portal.contains_point(center));
+        assert!(portal.contains_point(center + bounds.right * 1.9));
+        assert!(portal.contains_point(center + bounds.up * 0.45 + bounds.normal * 3.0));
+        assert!(portal.contains_point(center + bounds.right * 1.2 + bounds.up * 0.35));
+        assert!(!portal.contains_point(center + bounds.right * 2.1));
+        assert!(!portal.contains_point(center + bounds.up * 0.6));
+        assert!(!portal.contains_point(center + bounds.right * 1.5 + bounds.up * 0.4));
+
+        let edge = PortalRenderer::new().generate_edge_mesh(portal);
+        assert_eq!(edge.len(), PortalBounds::OUTLINE_SEGMENTS);
+        for point in &edge {
+            let local = bounds.to_local(*point);
+            assert!(((local.x / 2.0).powi(2) + (local.y / 0.5).powi(2) - 1.0).abs() < 1e-4);
+        }
+
+        // A triangle off to one side of the center, wound clockwise
+        let triangle = vec![Point2::new(1.0, 0.0), Point2::new(2.0, 2.0), Point2::new(3.0, 0.0)];
+        portal.set_bounds(PortalBounds::new(center, Vector3::new(0.0, 0.0, 1.0), 1.0, 1.0, PortalShape::Polygon(triangle.clone())));
+        let at = |x: f32, y: f32| bounds.from_local(Point2::new(x, y));
+        assert!(portal.contains_point(at(2.0, 0.5)));
+        assert!(portal.contains_point(at(2.0, 1.9)));
+        assert!(portal.contains_point(at(1.0, 0.0)));
+        assert!(!portal.contains_point(center));
+        assert!(!portal.contains_point(at(1.2, 1.0)));
+        assert!(!portal.contains_point(at(2.0, -0.1)));
+        assert!(!portal.contains_point(at(3.5, 0.5)));
+
+        let edge = PortalRenderer::new().generate_edge_mesh(portal);
+        let expected: Vec<Point3<f32>> = triangle.iter().map(|&v| bounds.from_local(v)).collect();
+        assert_eq!(edge, expected);
+
+        // Degenerate polygons contain nothing
+        portal.set_bounds(PortalBounds::new(center, Vector3::new(0.0, 0.0, 1.0), 1.0, 1.0, PortalShape::Polygon(triangle[..2].to_vec())));
+        assert!(!portal.contains_point(at(1.5, 1.0)));
+    }
+
+    #[test]
+    fn repeated_distance_queries_hit_the_cache() {
+        let mut m = Manifold::new();
+        let hyp = m.add_chart(GeometryType::Hyperbolic);
+        let pests: Vec<Point3<f32>> = (0..10).map(|i| Point3::new(0.05 * i as f32, 0.1, 0.0)).collect();
+        let player = Point3::new(-0.3, 0.2, 0.0);
+
+        let exact: Vec<f32> = pests.iter()
+            .map(|p| m.chart(hyp).unwrap().metric().distance(*p, player))
+            .collect();
+
+        let frames = 100;
+        for _ in 0..frames {
+            for (pest, expected) in pests.iter().zip(&exact) {
+                let d = m.distance_cached(hyp, *pest, player).unwrap();
+                assert!((d - expected).abs() < 1e-6);
+            }
+        }
+
+        let stats = m.distance_cache().stats();
+        assert_eq!(stats.misses, pests.len() as u64);
+        assert_eq!(stats.hits, (frames - 1) * pests.len() as u64);
+        assert_eq!(stats.entries, pests.len());
+
+        // The pair is unordered
+        m.distance_cached(hyp, player, pests[3]).unwrap();
+        assert_eq!(m.distance_cache().stats().misses, pests.len() as u64);
+
+        m.clear_distance_cache();
+        assert_eq!(m.distance_cache().stats().entries, 0);
+        m.distance_cached(hyp, pests[0], player).unwrap();
+        assert_eq!(m.distance_cache().stats().misses, pests.len() as u64 + 1);
+
+        assert!(m.distance_cached(ChartId(99), player, player).is_none());
+    }
+
+    #[test]
+    fn distance_cache_computations_may_query_the_cache() {
+        use metatopia_engine::manifold::DistanceCache;
+
+        let cache = DistanceCache::default();
+        let (a, b, c) = (Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0), Point3::new(3.0, 0.0, 0.0));
+        // Builds a distance out of other cached distances, which used to deadlock
+        let total = cache.get_or_insert_with(ChartId(0), a, c, || {
+            cache.get_or_insert_with(ChartId(0), a, b, || 1.0) + cache.get_or_insert_with(ChartId(0), b, c, || 2.0)
+        });
+        assert_eq!(total, 3.0);
+        assert_eq!(cache.get_or_insert_with(ChartId(0), c, a, || unreachable!()), 3.0);
+        assert_eq!(cache.stats().entries, 3);
+    }
+
+    #[test]
+    fn geodesic_distance_through_portal() {
+        let mut m = Manifold::new();
+        let other = m.add_chart(GeometryType::Euclidean);
+        let isolated = m.add_chart(GeometryType::Euclidean);
+        m.create_portal(ChartId(0), other, Point3::new(3.0, 0.0, 0.0), Point3::new(0.0, 0.0, 0.0), Mat4::from_scale(1.0)).unwrap();
+
+        let from = ManifoldPosition::new(ChartId(0), Point3::new(0.0, 4.0, 0.0));
+        let to = ManifoldPosition::new(other, Point3::new(0.0, 0.0, 2.0));
+        // |(0,4,0) → (3,0,0)| = 5, then |(0,0,0) → (0,0,2)| = 2
+        let d = m.geodesic_distance(from, to).expect("charts are connected");
+        assert!((d - 7.0).abs() < 1e-4, "got {d}");
+
+        let same = ManifoldPosition::new(ChartId(0), Point3::new(0.0, 0.0, 0.0));
+        assert!((m.geodesic_distance(from, same).unwrap() - 4.0).abs() < 1e-4);
+
+        let unreachable = ManifoldPosition::new(isolated, Point3::new(0.0, 0.0, 0.0));
+        assert!(m.geodesic_distance(from, unreachable).is_none());
+        assert!(m.geodesic_distance(to, from).is_none(), "portal is one-way in the graph");
+    }
+
+    #[test]
+    fn nearest_portal_and_radius_query() {
+        let mut m = Manifold::new();
+        let other = m.add_chart(GeometryType::Euclidean);
+        let identity = Mat4::from_scale(1.0);
+        let far = m.create_portal(ChartId(0), other, Point3::new(10.0, 0.0, 0.0), Point3::new(0.0, 0.0, 0.0), identity).unwrap();
+        let near = m.create_portal(ChartId(0), other, Point3::new(0.0, 3.0, 0.0), Point3::new(0.0, 0.0, 0.0), identity).unwrap();
+        let mid = m.create_portal(ChartId(0), other, Point3::new(0.0, 0.0, -6.0), Point3::new(0.0, 0.0, 0.0), identity).unwrap();
+        let disabled = m.create_portal(ChartId(0), other, Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 0.0, 0.0), identity).unwrap();
+        m.portal_mut(disabled).unwrap().set_active(false);
+
+        let origin = Point3::new(0.0, 0.0, 0.0);
+        let (id, d) = m.nearest_portal(origin, ChartId(0)).unwrap();
+        assert_eq!(id, near);
+        assert!((d - 3.0).abs() < 1e-5);
+
+        assert_eq!(m.portals_within(origin, ChartId(0), 7.0), vec![near, mid]);
+        assert_eq!(m.portals_within(origin, ChartId(0), 20.0), vec![near, mid, far]);
+        assert!(m.portals_within(origin, ChartId(0), 1.0).is_empty());
+        assert!(m.nearest_portal(origin, other).is_none());
+    }
+
+    #[test]
+    fn geodesic_distance_prefers_shorter_portal_chain() {
+        let mut m = Manifold::new();
+        let mid = m.add_chart(GeometryType::Euclidean);
+        let goal = m.add_chart(GeometryType::Euclidean);
+        // Direct but distant portal
+        m.create_portal(ChartId(0), goal, Point3::new(100.0, 0.0, 0.0), Point3::new(0.0, 0.0, 0.0), Mat4::from_scale(1.0)).unwrap();
+        // Two hops, each close by
+        m.create_portal(ChartId(0), mid, Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 0.0, 0.0), Mat4::from_scale(1.0)).unwrap();
+        m.create_portal(mid, goal, Point3::new(0.0, 2.0, 0.0), Point3::new(0.0, 0.0, 0.0), Mat4::from_scale(1.0)).unwrap();
+
+        let from = ManifoldPosition::new(ChartId(0), Point3::new(0.0, 0.0, 0.0));
+        let to = ManifoldPosition::new(goal, Point3::new(0.0, 0.0, 3.0));
+        let d = m.geodesic_distance(from, to).unwrap();
+        assert!((d - 6.0).abs() < 1e-4, "1 + 2 + 3, got {d}");
+    }
+
+    #[test]
+    fn validate_reports_broken_manifolds() {
+        let mut m = Manifold::new();
+        let sphere = m.add_chart(GeometryType::Spherical);
+        let identity = Mat4::from_scale(1.0);
+        m.create_portal(ChartId(0), sphere, Point3::new(0.0, 0.0, 0.0), Point3::new(0.5, 0.0, 0.0), identity).unwrap();
+        m.create_portal(sphere, ChartId(0), Point3::new(0.5, 0.0, 0.0), Point3::new(0.0, 0.0, 0.0), identity).unwrap();
+        assert!(m.validate().is_empty(), "{:?}", m.validate());
+
+        // No way back, and the exit lies outside the unit sphere
+        let stray = m.create_portal(ChartId(0), sphere, Point3::new(9.0, 0.0, 0.0), Point3::new(3.0, 0.0, 0.0), identity).unwrap();
+        assert_eq!(m.validate(), vec![
+            ManifoldWarning::PortalOutOfBounds { portal: stray, chart: sphere, position: Point3::new(3.0, 0.0, 0.0) },
+            ManifoldWarning::MissingReverse { portal: stray },
+        ]);
+
+        // Drop the spherical chart behind the manifold's back, as a hand-edited file might
+        let mut json = serde_json::to_value(&m).unwrap();
+        json["charts"].as_object_mut().unwrap().remove("1").expect("chart 1 serialized");
+        let broken: Manifold = serde_json::from_value(json).unwrap();
+        let warnings = broken.validate();
+        assert_eq!(warnings.len(), 3, "{:?}", warnings);
+        assert!(warnings.iter().all(|w| matches!(w, ManifoldWarning::MissingChart { chart, .. } if *chart == sphere)));
+        assert!(warnings[0].to_string().contains("missing chart"));
+    }
+
+    #[test]
+    fn portal_pair_maps_points_there_and_back() {
+        let mut m = Manifold::new();
+        let other = m.add_chart(GeometryType::Euclidean);
+        let turn = Mat4::from_angle_y(cgmath::Deg(90.0)) * Mat4::from_scale(2.0);
+        let (there, back) = m.create_portal_pair(ChartId(0), other, Point3::new(5.0, 0.0, 0.0), Point3::new(-3.0, 1.0, 0.0), turn).unwrap();
+        assert_ne!(there, back);
+        assert_eq!(m.portal(back).unwrap().source_chart(), other);
+        assert_eq!(m.portal(back).unwrap().target_chart(), ChartId(0));
+        assert!(m.validate().is_empty(), "{:?}", m.validate());
+
+        let start = Point3::new(5.5, 0.25, -0.5);
+        let moved = m.transform_between_charts(start, ChartId(0), other).unwrap();
+        assert!((moved - start).magnitude() > 1.0);
+        let returned = m.transform_between_charts(moved, other, ChartId(0)).unwrap();
+        assert!((returned - start).magnitude() < 1e-4, "{:?} vs {:?}", returned, start);
+
+        // One-way portals don't get a reverse
+        let one_way = m.create_portal(ChartId(0), other, Point3::new(-5.0, 0.0, 0.0), Point3::new(0.0, 0.0, 0.0), turn).unwrap();
+        m.portal_mut(one_way).unwrap().set_bidirectional(false);
+        assert!(m.add_reverse_portal(one_way).is_err());
+        assert!(m.add_reverse_portal(PortalId(99)).is_err());
+        assert!(m.validate().is_empty(), "{:?}", m.validate());
+    }
+
+    #[test]
+    fn chart_to_local_undoes_to_world_for_every_geometry() {
+        let placement = Mat4::from_translation(Vector3::new(3.0, -1.0, 2.0))
+            * Mat4::from_angle_z(cgmath::Deg(30.0))
+            * Mat4::from_scale(1.5);
+        let cases = [
+            (GeometryType::Euclidean, Point3::new(4.0, -2.0, 7.5)),
+            (GeometryType::Custom, Point3::new(-12.0, 0.5, 3.0)),
+            // Spherical charts keep only the direction, so start on the unit sphere
+            (GeometryType::Spherical, Point3::from_vec(Vector3::new(0.3, -0.5, 0.8).normalize())),
+            (GeometryType::Hyperbolic, Point3::new(0.4, -0.3, 2.0)),
+        ];
+
+        for (geometry, point) in cases {
+            for transform in [Mat4::from_scale(1.0), placement] {
+                let mut chart = Chart::new(ChartId(0), geometry);
+                chart.set_transform(transform);
+                let world = chart.to_world(LocalCoordinate::from_point(point));
+                let back = chart.to_local(world).to_point();
+                assert!((back - point).magnitude() < 1e-4, "{:?}: {:?} came back as {:?}", geometry, point, back);
+            }
+        }
+
+        // The transform places curved charts too
+        let mut sphere = Chart::new(ChartId(0), GeometryType::Spherical);
+        let plain = sphere.to_world(LocalCoordinate::new(0.0, 0.0, 1.0));
+        sphere.set_transform(Mat4::from_translation(Vector3::new(100.0, 0.0, 0.0)));
+        assert_eq!(sphere.to_world(LocalCoordinate::new(0.0, 0.0, 1.0)), plain + Vector3::new(100.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn metric_grid_scale_grows_toward_the_disk_boundary() {
+        let chart = Chart::new(ChartId(0), GeometryType::Hyperbolic);
+        let samples = chart.sample_metric_grid(Point3::new(-0.9, -0.9, 0.0), Point3::new(0.9, 0.9, 0.0), 7);
+
+        // The corners of the square fall outside the disk
+        assert!(!samples.is_empty() && samples.len() < 49, "{} samples", samples.len());
+        assert!(samples.iter().all(|(p, _)| p.x * p.x + p.y * p.y < 1.0 && p.z == 0.0));
+
+        let mut by_radius: Vec<(f32, f32)> = samples.iter()
+            .map(|(p, tensor)| ((p.x * p.x + p.y * p.y).sqrt(), tensor.g.x.x))
+            .collect();
+        by_radius.sort_by(|a, b| a.0.total_cmp(&b.0));
+        assert!((by_radius[0].0).abs() < 1e-6 && (by_radius[0].1 - 4.0).abs() < 1e-4, "λ² = 4 at the origin");
+        for pair in by_radius.windows(2) {
+            if pair[1].0 > pair[0].0 + 1e-4 {
+                assert!(pair[1].1 > pair[0].1, "scale {:?} then {:?}", pair[0], pair[1]);
+            }
+        }
+
+        let clipped = Chart::new(ChartId(1), GeometryType::Euclidean)
+            .sample_metric_grid(Point3::new(0.0, 0.0, 0.0), Point3::new(5000.0, 0.0, 0.0), 3);
+        assert_eq!(clipped.iter().map(|(p, _)| p.x).collect::<Vec<_>>(), vec![0.0, 500.0, 1000.0]);
+    }
 }
 
 // ─── Geodesic ──────────────────────────────────────────────────────────────
@@ -85,6 +481,56 @@ mod manifold_tests {
 mod geodesic_tests {
     use super::*;
 
+    #[test]
+    fn zero_steps_gives_two_point_path() {
+        let start = Point3::new(0.1, 0.2, 0.0);
+        let end = Point3::new(0.4, -0.3, 0.0);
+        for geometry in [GeometryType::Euclidean, GeometryType::Spherical, GeometryType::Hyperbolic, GeometryType::Custom] {
+            let path = Geodesic::compute(start, end, &Metric::from_geometry(geometry), 0);
+            assert_eq!(path.points.len(), 2, "{:?}", geometry);
+            assert!(path.arc_length.is_finite(), "{:?}", geometry);
+            assert!(path.points.iter().all(|p| p.x.is_finite() && p.y.is_finite() && p.z.is_finite()), "{:?}", geometry);
+        }
+    }
+
+    #[test]
+    fn poincare_geodesic_is_the_hyperbolic_shortest_path() {
+        use metatopia_engine::manifold::hyperboloid::poincare_distance;
+
+        let (a, b) = (cgmath::Point2::new(-0.3, 0.6), cgmath::Point2::new(0.5, 0.4));
+        let path = Geodesic::compute(Point3::new(a.x, a.y, 0.0), Point3::new(b.x, b.y, 0.0), &Metric::from_geometry(GeometryType::Hyperbolic), 200);
+        let length: f32 = path.points.windows(2)
+            .map(|w| poincare_distance(cgmath::Point2::new(w[0].x, w[0].y), cgmath::Point2::new(w[1].x, w[1].y)))
+            .sum();
+        let direct = poincare_distance(a, b);
+        assert!((length - direct).abs() < 1e-3 * direct, "path {} vs distance {}", length, direct);
+    }
+
+    #[test]
+    fn adaptive_sampling_adds_points_near_poincare_boundary() {
+        let metric = Metric::from_geometry(GeometryType::Hyperbolic);
+        let max_error = 1e-3;
+        // Chords of equal Euclidean length, one through the middle of the disk and one near its edge
+        let center = Geodesic::compute_adaptive(Point3::new(-0.1, 0.05, 0.0), Point3::new(0.1, 0.05, 0.0), &metric, max_error);
+        let edge = Geodesic::compute_adaptive(Point3::new(-0.1, 0.95, 0.0), Point3::new(0.1, 0.95, 0.0), &metric, max_error);
+        assert!(edge.points.len() > center.points.len(), "edge {} vs center {}", edge.points.len(), center.points.len());
+
+        // Every point of a dense reference path lies within the tolerance of the adaptive polyline
+        let reference = Geodesic::compute(Point3::new(-0.1, 0.95, 0.0), Point3::new(0.1, 0.95, 0.0), &metric, 512);
+        for p in &reference.points {
+            let nearest = edge.points.windows(2)
+                .map(|w| {
+                    let chord = w[1] - w[0];
+                    let t = ((p - w[0]).dot(chord) / chord.magnitude2()).clamp(0.0, 1.0);
+                    (p - (w[0] + chord * t)).magnitude()
+                })
+                .fold(f32::INFINITY, f32::min);
+            assert!(nearest <= max_error * 1.1, "reference point {:?} is {} off", p, nearest);
+        }
+        assert_eq!(edge.points.first(), reference.points.first());
+        assert!((edge.points.last().unwrap() - reference.points.last().unwrap()).magnitude() < 1e-5);
+    }
+
     #[test]
     fn euclidean_geodesic_is_straight_line() {
         let metric = Metric::from_geometry(GeometryType::Euclidean);
@@ -127,6 +573,149 @@ mod geodesic_tests {
         );
         assert_eq!(path.points.len(), 16);
     }
+
+    #[test]
+    fn custom_identity_metric_geodesic_is_straight_line() {
+        let mut metric = Metric::from_geometry(GeometryType::Custom);
+        metric.parameters.custom_fn = Some(|_| MetricTensor::identity());
+        let start = Point3::new(1.0, -2.0, 0.5);
+        let end = Point3::new(4.0, 2.0, -1.5);
+        let path = Geodesic::compute(start, end, &metric, 10);
+
+        assert_eq!(path.points.len(), 11);
+        for (i, p) in path.points.iter().enumerate() {
+            let t = i as f32 / 10.0;
+            let expected = start + (end - start) * t;
+            assert!((p - expected).magnitude() < 1e-3, "deviation at step {i}: {p:?} vs {expected:?}");
+        }
+    }
+
+    #[test]
+    fn custom_curved_metric_geodesic_reaches_endpoint() {
+        let mut metric = Metric::from_geometry(GeometryType::Custom);
+        metric.parameters.custom_fn = Some(|p| {
+            let mut tensor = MetricTensor::identity();
+            tensor.g *= 1.0 + 0.5 * p.y * p.y;
+            tensor
+        });
+        let start = Point3::new(-1.0, 0.5, 0.0);
+        let end = Point3::new(1.0, 0.5, 0.0);
+        let path = Geodesic::compute(start, end, &metric, 40);
+
+        let last = *path.points.last().unwrap();
+        assert!((last - end).magnitude() < 1e-2, "shooting should land on the endpoint, got {last:?}");
+        // The conformal factor grows with |y|, so the geodesic bends toward y = 0
+        let mid = path.points[20];
+        assert!(mid.y < 0.5, "geodesic should bow toward lower metric cost, mid = {mid:?}");
+    }
+
+    #[test]
+    fn integrating_a_zero_direction_stays_at_the_start() {
+        let mut metric = Metric::from_geometry(GeometryType::Custom);
+        metric.parameters.custom_fn = Some(|_| MetricTensor::identity());
+        let start = Point3::new(1.0, 2.0, 3.0);
+
+        let path = Geodesic::integrate(start, Vector3::new(0.0, 0.0, 0.0), &metric, 5.0, 10);
+        assert_eq!(path.points, vec![start]);
+        assert_eq!(path.arc_length, 0.0);
+
+        let moving = Geodesic::integrate(start, Vector3::new(2.0, 0.0, 0.0), &metric, 5.0, 10);
+        assert!((moving.points.last().unwrap() - Point3::new(6.0, 2.0, 3.0)).magnitude() < 1e-3);
+    }
+
+    fn assert_finite(path: &GeodesicPath) {
+        for (p, t) in path.points.iter().zip(&path.tangents) {
+            assert!(p.x.is_finite() && p.y.is_finite() && p.z.is_finite(), "non-finite point {p:?}");
+            assert!(t.x.is_finite() && t.y.is_finite() && t.z.is_finite(), "non-finite tangent {t:?}");
+        }
+        assert!(path.arc_length.is_finite());
+    }
+
+    #[test]
+    fn reversed_path_interpolates_backwards() {
+        let metric = Metric::from_geometry(GeometryType::Hyperbolic);
+        let path = Geodesic::compute(Point3::new(-0.4, 0.2, 0.0), Point3::new(0.5, 0.3, 0.0), &metric, 20);
+        let reversed = path.reversed();
+
+        assert_eq!(reversed.arc_length, path.arc_length);
+        for i in 0..=10 {
+            let t = i as f32 / 10.0;
+            let a = reversed.interpolate(t).unwrap();
+            let b = path.interpolate(1.0 - t).unwrap();
+            assert!((a - b).magnitude() < 1e-4, "mismatch at t = {t}");
+        }
+        assert!((reversed.tangents[0] + *path.tangents.last().unwrap()).magnitude() < 1e-6);
+    }
+
+    #[test]
+    fn concat_joins_matching_paths() {
+        let metric = Metric::from_geometry(GeometryType::Euclidean);
+        let mid = Point3::new(3.0, 0.0, 0.0);
+        let first = Geodesic::compute(Point3::new(0.0, 0.0, 0.0), mid, &metric, 5);
+        let second = Geodesic::compute(mid, Point3::new(3.0, 4.0, 0.0), &metric, 5);
+
+        let joined = first.concat(&second).unwrap();
+        assert_eq!(joined.points.len(), 11);
+        assert!((joined.arc_length - (first.arc_length + second.arc_length)).abs() < 1e-4);
+        assert!((joined.arc_length - 7.0).abs() < 1e-4);
+
+        assert!(second.concat(&first).is_err(), "endpoints do not match");
+        let curved = Geodesic::compute(mid, Point3::new(0.0, 3.0, 0.0), &Metric::from_geometry(GeometryType::Spherical), 5);
+        assert!(first.concat(&curved).is_err(), "geometries differ");
+    }
+
+    #[test]
+    fn tangent_matches_interpolation_derivative() {
+        let metric = Metric::from_geometry(GeometryType::Hyperbolic);
+        let path = Geodesic::compute(Point3::new(0.5, 0.3, 0.0), Point3::new(-0.5, 0.3, 0.0), &metric, 30);
+
+        let h = 1e-3;
+        for i in 1..10 {
+            let t = i as f32 / 10.0;
+            let derivative = (path.interpolate(t + h).unwrap() - path.interpolate(t - h).unwrap()).normalize();
+            let tangent = path.tangent_at(t).unwrap();
+            assert!(tangent.dot(derivative) > 0.995, "tangent {tangent:?} vs derivative {derivative:?} at t = {t}");
+        }
+    }
+
+    #[test]
+    fn spherical_geodesic_identical_points() {
+        let metric = Metric::from_geometry(GeometryType::Spherical);
+        let p = Point3::new(0.0, 0.0, metric.parameters.radius);
+        let path = Geodesic::compute(p, p, &metric, 8);
+        assert_finite(&path);
+        assert!(path.arc_length.abs() < 1e-6);
+        assert!(path.points.iter().all(|q| (q - p).magnitude() < 1e-6));
+    }
+
+    #[test]
+    fn spherical_geodesic_antipodal_points() {
+        let metric = Metric::from_geometry(GeometryType::Spherical);
+        let radius = metric.parameters.radius;
+        let start = Point3::new(radius, 0.0, 0.0);
+        let end = Point3::new(-radius, 0.0, 0.0);
+        let path = Geodesic::compute(start, end, &metric, 64);
+        assert_finite(&path);
+        // Chord sum of a fine sampling approaches the half great circle
+        assert!((path.arc_length - std::f32::consts::PI * radius).abs() < 1e-2 * radius, "length {}", path.arc_length);
+        assert!((path.points.last().unwrap() - end).magnitude() < 1e-4);
+        for p in &path.points {
+            assert!((p.to_vec().magnitude() - radius).abs() < 1e-4, "point off the sphere");
+        }
+    }
+
+    #[test]
+    fn spherical_geodesic_near_antipodal_points() {
+        let metric = Metric::from_geometry(GeometryType::Spherical);
+        let radius = metric.parameters.radius;
+        let start = Point3::new(radius, 0.0, 0.0);
+        let end = Point3::new(-radius, 1e-4 * radius, 0.0);
+        let path = Geodesic::compute(start, end, &metric, 64);
+        assert_finite(&path);
+        assert!((path.arc_length - std::f32::consts::PI * radius).abs() < 1e-2 * radius, "length {}", path.arc_length);
+        let last = path.points.last().unwrap();
+        assert!((last.to_vec().normalize() - end.to_vec().normalize()).magnitude() < 1e-3);
+    }
 }
 
 // ─── Metric ────────────────────────────────────────────────────────────────
@@ -171,12 +760,169 @@ mod metric_tests {
         let tensor = metric.tensor_at(Point3::new(0.0, 0.0, 0.0));
         assert!(tensor.curvature < 0.0, "Hyperbolic curvature should be negative");
     }
+
+    #[test]
+    fn hyperboloid_and_poincare_models_agree() {
+        use cgmath::Point2;
+        use metatopia_engine::manifold::hyperboloid::*;
+
+        let disk_points = [
+            Point2::new(0.0, 0.0),
+            Point2::new(0.3, -0.2),
+            Point2::new(-0.7, 0.1),
+            Point2::new(0.05, 0.9),
+        ];
+        for &point in &disk_points {
+            let lifted = poincare_to_hyperboloid(point);
+            assert!((minkowski_inner(lifted, lifted) + 1.0).abs() < 1e-3, "{:?} is off the hyperboloid", lifted);
+            let back = hyperboloid_to_poincare(lifted);
+            assert!((back - point).magnitude() < 1e-5, "{:?} came back as {:?}", point, back);
+        }
+
+        for &a in &disk_points {
+            for &b in &disk_points {
+                let poincare = poincare_distance(a, b);
+                let minkowski = hyperboloid_distance(poincare_to_hyperboloid(a), poincare_to_hyperboloid(b));
+                assert!((poincare - minkowski).abs() < 1e-3 * poincare.max(1.0), "{:?}-{:?}: {} vs {}", a, b, poincare, minkowski);
+            }
+        }
+
+        // The metric measures hyperboloid charts by their spatial coordinates
+        let (a, b) = (poincare_to_hyperboloid(disk_points[1]), poincare_to_hyperboloid(disk_points[2]));
+        let distance = Metric::hyperboloid().distance(Point3::new(a.x, a.y, 0.0), Point3::new(b.x, b.y, 0.0));
+        assert!((distance - poincare_distance(disk_points[1], disk_points[2])).abs() < 1e-3);
+    }
+
+    #[test]
+    fn hyperboloid_geodesics_are_shortest_paths() {
+        use metatopia_engine::manifold::hyperboloid::{hyperboloid_distance, lift};
+
+        let metric = Metric::hyperboloid();
+        let (start, end) = (Point3::new(-3.0, 1.0, 0.0), Point3::new(4.0, 2.5, 0.0));
+        let path = Geodesic::compute(start, end, &metric, 20);
+        assert_eq!(path.points.len(), 21);
+        assert!((path.points[0] - start).magnitude() < 1e-3);
+        assert!((path.points[20] - end).magnitude() < 1e-3);
+
+        // Steps are equal in hyperbolic length and add up to the distance
+        let total = metric.distance(start, end);
+        let steps: Vec<f32> = path.points.windows(2)
+            .map(|pair| hyperboloid_distance(lift(pair[0].x, pair[0].y), lift(pair[1].x, pair[1].y)))
+            .collect();
+        assert!((steps.iter().sum::<f32>() - total).abs() < 1e-2 * total);
+        assert!(steps.iter().all(|step| (step - total / 20.0).abs() < 1e-2));
+
+        // Far from the origin the chart still projects inside the disk and back
+        let mut manifold = Manifold::new();
+        let chart = manifold.add_chart(GeometryType::Hyperbolic);
+        manifold.set_chart_metric(chart, Metric::hyperboloid()).unwrap();
+        let chart = manifold.chart(chart).unwrap();
+        let local = LocalCoordinate::new(5.0, -2.0, 1.5);
+        assert!(chart.contains(local));
+        let world = chart.to_world(local);
+        assert!(world.x * world.x + world.y * world.y < 1.0);
+        assert_eq!(world.z, 1.5);
+        assert!((chart.to_local(world).to_point() - local.to_point()).magnitude() < 1e-2);
+    }
+
+    #[test]
+    fn registered_custom_metric_survives_serialization_by_name() {
+        CustomMetricRegistry::register("test_stretched_x", |p: Point3<f32>| {
+            let mut tensor = MetricTensor::identity();
+            tensor.g.x.x = 1.0 + p.x * p.x;
+            tensor
+        });
+        let metric = Metric::custom("test_stretched_x");
+        assert_eq!(metric.tensor_at(Point3::new(2.0, 0.0, 0.0)).g.x.x, 5.0);
+        // Unknown names fall back to flat space
+        assert_eq!(Metric::custom("test_not_registered").tensor_at(Point3::new(2.0, 0.0, 0.0)).g.x.x, 1.0);
+
+        let mut manifold = Manifold::new();
+        let chart = manifold.add_custom_chart("test_stretched_x");
+        assert!(manifold.validate().is_empty());
+
+        let saved = ron::to_string(&manifold).unwrap();
+        let loaded: Manifold = ron::from_str(&saved).unwrap();
+        let metric = loaded.chart(chart).unwrap().metric();
+        assert_eq!(loaded.chart(chart).unwrap().geometry(), GeometryType::Custom);
+        assert_eq!(metric.parameters.custom_name.as_deref(), Some("test_stretched_x"));
+        assert_eq!(metric.tensor_at(Point3::new(3.0, 0.0, 0.0)).g.x.x, 10.0);
+
+        let mut orphaned = Manifold::new();
+        let orphan = orphaned.add_custom_chart("test_missing_metric");
+        assert_eq!(
+            orphaned.validate(),
+            vec![ManifoldWarning::UnregisteredMetric { chart: orphan, name: "test_missing_metric".into() }]
+        );
+        assert!(CustomMetricRegistry::unregister("test_stretched_x"));
+        assert!(!CustomMetricRegistry::is_registered("test_stretched_x"));
+    }
+
+    #[test]
+    fn custom_metrics_bind_to_the_registry_entry_when_created() {
+        let stretch = |factor: f32| move |_: Point3<f32>| {
+            let mut tensor = MetricTensor::identity();
+            tensor.g.x.x = factor;
+            tensor
+        };
+        let point = Point3::new(1.0, 0.0, 0.0);
+
+        // Charts made before the name is registered look it up until bound
+        let mut manifold = Manifold::new();
+        let chart = manifold.add_custom_chart("test_bound_late");
+        CustomMetricRegistry::register("test_bound_late", stretch(2.0));
+        assert_eq!(manifold.chart(chart).unwrap().metric().tensor_at(point).g.x.x, 2.0);
+
+        // Once bound, replacing the entry takes effect only after rebinding
+        manifold.resolve_custom_metrics();
+        CustomMetricRegistry::register("test_bound_late", stretch(3.0));
+        assert_eq!(manifold.chart(chart).unwrap().metric().tensor_at(point).g.x.x, 2.0);
+        assert_eq!(Metric::custom("test_bound_late").tensor_at(point).g.x.x, 3.0);
+        manifold.resolve_custom_metrics();
+        assert_eq!(manifold.chart(chart).unwrap().metric().tensor_at(point).g.x.x, 3.0);
+        assert!(CustomMetricRegistry::unregister("test_bound_late"));
+    }
+
+    #[test]
+    fn built_in_geometries_report_their_known_curvatures() {
+        let point = Point3::new(0.2, -0.1, 0.5);
+        let horizontal = Vector3::new(0.0, 0.0, 1.0);
+
+        let euclidean = Metric::from_geometry(GeometryType::Euclidean);
+        assert_eq!(euclidean.sectional_curvature(point, horizontal), 0.0);
+        assert_eq!(euclidean.ricci_scalar(point), 0.0);
+
+        let mut spherical = Metric::from_geometry(GeometryType::Spherical);
+        spherical.parameters.radius = 2.0;
+        assert!((spherical.ricci_scalar(point) - 0.5).abs() < 1e-6, "2/r²");
+        let radial = Vector3::new(point.x, point.y, point.z);
+        assert!((spherical.sectional_curvature(point, radial) - 0.25).abs() < 1e-6, "tangent plane is 1/r²");
+        assert!(spherical.sectional_curvature(point, radial.cross(Vector3::unit_x())).abs() < 1e-6);
+
+        for hyperbolic in [Metric::from_geometry(GeometryType::Hyperbolic), Metric::hyperboloid()] {
+            assert!((hyperbolic.ricci_scalar(point) + 2.0).abs() < 1e-6);
+            assert!((hyperbolic.sectional_curvature(point, horizontal) + 1.0).abs() < 1e-6);
+            assert!(hyperbolic.sectional_curvature(point, Vector3::unit_x()).abs() < 1e-6, "planes along z are flat");
+        }
+        let mut scaled = Metric::from_geometry(GeometryType::Hyperbolic);
+        scaled.scale = 2.0;
+        assert!((scaled.sectional_curvature(point, horizontal) + 0.25).abs() < 1e-6);
+
+        // A custom copy of the Poincaré metric is differentiated numerically
+        let mut custom = Metric::from_geometry(GeometryType::Custom);
+        custom.parameters.custom_fn = Some(|p| MetricTensor::hyperbolic_poincare(p.x, p.y));
+        let numeric = custom.sectional_curvature(point, horizontal);
+        assert!((numeric + 1.0).abs() < 0.05, "numeric sectional curvature {numeric}");
+        assert!((custom.ricci_scalar(point) + 2.0).abs() < 0.1, "numeric Ricci scalar {}", custom.ricci_scalar(point));
+        assert!(custom.sectional_curvature(point, Vector3::unit_y()).abs() < 0.05);
+    }
 }
 
 // ─── ECS ───────────────────────────────────────────────────────────────────
 
 mod ecs_tests {
     use super::*;
+    use metatopia_engine::ecs::System;
 
     #[test]
     fn create_and_destroy_entity() {
@@ -239,40 +985,827 @@ mod ecs_tests {
         let results = world.query::<Velocity>();
         assert_eq!(results.len(), 2);
     }
-}
 
-// ─── Math ──────────────────────────────────────────────────────────────────
+    #[test]
+    fn spawn_bundle_inserts_all_components() {
+        let mut world = World::new();
+        let e = world.spawn((
+            EcsTransform::new(ChartId(0), Point3::new(1.0, 2.0, 3.0)),
+            Velocity {
+                linear: Vector3::new(1.0, 0.0, 0.0),
+                angular: Vector3::new(0.0, 0.0, 0.0),
+            },
+            Renderable::new("pest", "default"),
+        ));
+        let _partial = world.spawn((Velocity {
+            linear: Vector3::new(0.0, 0.0, 0.0),
+            angular: Vector3::new(0.0, 0.0, 0.0),
+        },));
 
-mod math_tests {
-    use super::*;
-    use metatopia_engine::math::{Interpolation, Ray, BoundingBox};
+        assert!(world.get_component::<EcsTransform>(e).is_some());
+        assert!(world.get_component::<Velocity>(e).is_some());
+        assert_eq!(world.get_component::<Renderable>(e).unwrap().mesh_id, "pest");
+        assert_eq!(world.query3::<EcsTransform, Velocity, Renderable>(), vec![e]);
+        assert_eq!(world.entities().len(), 2);
+    }
 
-    #[test]
-    fn lerp_at_boundaries() {
-        assert!((Interpolation::lerp(0.0, 10.0, 0.0) - 0.0).abs() < 1e-6);
-        assert!((Interpolation::lerp(0.0, 10.0, 1.0) - 10.0).abs() < 1e-6);
-        assert!((Interpolation::lerp(0.0, 10.0, 0.5) - 5.0).abs() < 1e-6);
+    struct Transitioning;
+
+    impl Component for Transitioning {
+        fn as_any(&self) -> &dyn std::any::Any { self }
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
     }
 
-    #[test]
-    fn smoothstep_at_boundaries() {
-        assert!((Interpolation::smoothstep(0.0, 1.0, -1.0)).abs() < 1e-6, "below edge0 → 0");
-        assert!((Interpolation::smoothstep(0.0, 1.0, 2.0) - 1.0).abs() < 1e-6, "above edge1 → 1");
-        assert!((Interpolation::smoothstep(0.0, 1.0, 0.5) - 0.5).abs() < 1e-6, "midpoint → 0.5");
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health(i32);
+
+    impl Component for Health {
+        fn as_any(&self) -> &dyn std::any::Any { self }
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
     }
 
     #[test]
-    fn ray_point_at() {
-        let ray = Ray::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
-        let p = ray.point_at(5.0);
-        assert!((p.x - 5.0).abs() < 1e-6);
-        assert!(p.y.abs() < 1e-6);
+    fn billboards_turn_to_face_the_camera() {
+        use metatopia_engine::ecs::{Billboard, BillboardAxis, BillboardSystem, CameraPosition};
+
+        let forward = |world: &World, e: Entity| world.get_component::<EcsTransform>(e).unwrap().orientation.quaternion * Vector3::unit_z();
+        let mut world = World::new();
+        world.add_system(Box::new(BillboardSystem));
+        let screen = world.spawn((EcsTransform::new(ChartId(0), Point3::new(0.0, 0.0, 0.0)), Billboard::default()));
+        let upright = world.spawn((EcsTransform::new(ChartId(0), Point3::new(0.0, 0.0, 0.0)), Billboard::new(BillboardAxis::Y)));
+        world.insert_resource(CameraPosition(ManifoldPosition::new(ChartId(0), Point3::new(3.0, 5.0, 4.0))));
+        world.update(0.016);
+
+        let expected = Vector3::new(3.0, 5.0, 4.0).normalize();
+        assert!((forward(&world, screen) - expected).magnitude() < 1e-5, "{:?}", forward(&world, screen));
+        assert!((forward(&world, upright) - Vector3::new(0.6, 0.0, 0.8)).magnitude() < 1e-5, "{:?}", forward(&world, upright));
+        let up = world.get_component::<EcsTransform>(upright).unwrap().orientation.quaternion * Vector3::unit_y();
+        assert!((up - Vector3::unit_y()).magnitude() < 1e-5, "Y-locked billboards stay upright");
+
+        // In a hyperbolic chart the billboard faces along the geodesic, not the coordinate line
+        let mut manifold = Manifold::new();
+        let h = manifold.add_chart(GeometryType::Hyperbolic);
+        let (from, camera) = (Point3::new(0.5, 0.0, 0.0), Point3::new(0.0, 0.5, 0.0));
+        let sprite = world.spawn((EcsTransform::new(h, from), Billboard::default()));
+        world.insert_resource(CameraPosition(ManifoldPosition::new(h, camera)));
+        world.insert_resource(manifold.clone());
+        world.update(0.016);
+
+        let geodesic = Geodesic::compute(from, camera, manifold.chart(h).unwrap().metric(), 8).tangents[0];
+        assert!((forward(&world, sprite) - geodesic).magnitude() < 1e-4);
+        assert!((forward(&world, sprite) - (camera - from).normalize()).magnitude() > 0.05, "geodesic bends away from the chord");
     }
 
     #[test]
-    fn bounding_box_contains() {
-        let bb = BoundingBox::new(
-            Point3::new(-1.0, -1.0, -1.0),
+    fn lod_system_picks_mesh_by_camera_distance() {
+        use metatopia_engine::ecs::{CameraPosition, LodSystem};
+
+        let screen = Renderable::new("screen", "default").with_lod(50.0, "screen_far").with_lod(10.0, "screen_mid");
+        assert_eq!(screen.current_mesh_id(), "screen");
+        assert_eq!(screen.lod_for_distance(5.0), None);
+        assert_eq!(screen.lod_for_distance(10.0), Some(1));
+        assert_eq!(screen.lod_for_distance(400.0), Some(0));
+
+        let mut world = World::new();
+        world.add_system(Box::new(LodSystem));
+        world.insert_resource(Manifold::new());
+        let e = world.spawn((EcsTransform::new(ChartId(0), Point3::new(0.0, 0.0, 0.0)), screen));
+        let plain = world.spawn((EcsTransform::new(ChartId(0), Point3::new(0.0, 0.0, 0.0)), Renderable::new("pest", "default")));
+
+        let mut mesh_at = |z: f32| {
+            world.insert_resource(CameraPosition(ManifoldPosition::new(ChartId(0), Point3::new(0.0, 0.0, z))));
+            world.update(0.016);
+            assert_eq!(world.get_component::<Renderable>(plain).unwrap().current_mesh_id(), "pest", "no LODs, no swap");
+            world.get_component::<Renderable>(e).unwrap().current_mesh_id().to_string()
+        };
+        assert_eq!(mesh_at(3.0), "screen");
+        assert_eq!(mesh_at(20.0), "screen_mid");
+        assert_eq!(mesh_at(80.0), "screen_far");
+        assert_eq!(mesh_at(3.0), "screen", "coming back restores full detail");
+    }
+
+    #[test]
+    fn restoring_a_snapshot_reverts_entities_and_components() {
+        let mut world = World::new();
+        world.register_snapshot_component::<Health>();
+        assert!(world.is_snapshot_component::<EcsTransform>());
+        assert!(!world.is_snapshot_component::<Transitioning>());
+
+        let player = world.spawn((EcsTransform::new(ChartId(0), Point3::new(1.0, 2.0, 3.0)), Health(100)));
+        let crate_ = world.spawn((EcsTransform::new(ChartId(0), Point3::new(-4.0, 0.0, 0.0)),));
+        let snapshot = world.snapshot();
+        assert_eq!(snapshot.entity_count(), 2);
+
+        world.get_component_mut::<EcsTransform>(player).unwrap().position.local = LocalCoordinate::new(9.0, 9.0, 9.0);
+        world.get_component_mut::<Health>(player).unwrap().0 = 5;
+        world.add_component(player, Transitioning);
+        world.add_component(crate_, Health(1));
+        world.destroy_entity(crate_);
+        let spawned = world.spawn((Health(7), Transitioning));
+
+        world.restore(&snapshot);
+
+        assert_eq!(world.entities(), &[player, crate_]);
+        assert_eq!(world.get_component::<EcsTransform>(player).unwrap().position.local.0, Point3::new(1.0, 2.0, 3.0));
+        assert_eq!(world.get_component::<EcsTransform>(crate_).unwrap().position.local.0, Point3::new(-4.0, 0.0, 0.0));
+        assert_eq!(world.get_component::<Health>(player), Some(&Health(100)));
+        assert!(world.get_component::<Health>(crate_).is_none());
+        assert!(world.get_component::<Health>(spawned).is_none());
+        assert_eq!(world.component_count::<Health>(), 1);
+        // Unregistered components survive on entities the snapshot kept and go with the rest
+        assert!(world.get_component::<Transitioning>(player).is_some());
+        assert_eq!(world.component_count::<Transitioning>(), 1);
+        // Ids pick up where they were, so the next entity reuses the discarded one's id
+        assert_eq!(world.create_entity(), spawned);
+
+        // The snapshot is untouched by restoring and can be applied again
+        let copy = snapshot.clone();
+        world.get_component_mut::<Health>(player).unwrap().0 = 1;
+        world.restore(&copy);
+        assert_eq!(world.get_component::<Health>(player), Some(&Health(100)));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct PestKilled(u32);
+
+    struct Exterminator;
+
+    impl System for Exterminator {
+        fn update(&self, world: &mut World, _dt: f32) {
+            if world.entities().is_empty() {
+                let e = world.create_entity();
+                world.send_event(PestKilled(e.0));
+            }
+        }
+
+        fn clone_box(&self) -> Box<dyn System> {
+            Box::new(Exterminator)
+        }
+    }
+
+    #[derive(Clone)]
+    struct ScoreKeeper(std::sync::Arc<std::sync::Mutex<Vec<PestKilled>>>);
+
+    impl System for ScoreKeeper {
+        fn update(&self, world: &mut World, _dt: f32) {
+            self.0.lock().unwrap().extend(world.drain_events::<PestKilled>());
+        }
+
+        fn clone_box(&self) -> Box<dyn System> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn events_are_delivered_once_next_frame() {
+        let received = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut world = World::new();
+        world.add_system(Box::new(ScoreKeeper(received.clone())));
+        world.add_system(Box::new(Exterminator));
+
+        world.update(0.016);
+        assert!(received.lock().unwrap().is_empty(), "sent this frame, not yet readable");
+        world.update(0.016);
+        assert_eq!(*received.lock().unwrap(), vec![PestKilled(0)]);
+        world.update(0.016);
+        assert_eq!(received.lock().unwrap().len(), 1, "consumed exactly once");
+    }
+
+    struct FrameCounter(u32);
+
+    struct CountFrames;
+
+    impl System for CountFrames {
+        fn update(&self, world: &mut World, _dt: f32) {
+            if let Some(counter) = world.get_resource_mut::<FrameCounter>() {
+                counter.0 += 1;
+            }
+        }
+
+        fn clone_box(&self) -> Box<dyn System> {
+            Box::new(CountFrames)
+        }
+    }
+
+    #[test]
+    fn systems_mutate_resources() {
+        let mut world = World::new();
+        assert!(world.insert_resource(FrameCounter(0)).is_none());
+        world.add_system(Box::new(CountFrames));
+
+        world.update(0.016);
+        world.update(0.016);
+        assert_eq!(world.get_resource::<FrameCounter>().unwrap().0, 2);
+
+        let previous = world.insert_resource(FrameCounter(10)).expect("replaced value");
+        assert_eq!(previous.0, 2);
+        world.update(0.016);
+        assert_eq!(world.remove_resource::<FrameCounter>().unwrap().0, 11);
+        assert!(world.get_resource::<FrameCounter>().is_none());
+    }
+
+    #[derive(Clone)]
+    struct Record(&'static str, std::sync::Arc<std::sync::Mutex<Vec<&'static str>>>);
+
+    impl System for Record {
+        fn update(&self, _world: &mut World, _dt: f32) {
+            self.1.lock().unwrap().push(self.0);
+        }
+
+        fn clone_box(&self) -> Box<dyn System> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn stages_run_in_order_then_insertion_order() {
+        use metatopia_engine::ecs::Stage;
+
+        let log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut world = World::new();
+        world.add_system_to_stage(Stage::PostUpdate, Box::new(Record("portals", log.clone())));
+        world.add_system(Box::new(Record("movement", log.clone())));
+        world.add_system_to_stage(Stage::PreUpdate, Box::new(Record("input", log.clone())));
+        world.add_system_to_stage(Stage::PostUpdate, Box::new(Record("spatial", log.clone())));
+        world.add_system_to_stage(Stage::Update, Box::new(Record("ai", log.clone())));
+
+        world.update(0.016);
+        assert_eq!(*log.lock().unwrap(), vec!["input", "movement", "ai", "portals", "spatial"]);
+    }
+
+    #[test]
+    fn clear_drops_entities_but_keeps_systems() {
+        let mut world = World::new();
+        world.insert_resource(FrameCounter(0));
+        world.add_system(Box::new(CountFrames));
+        for i in 0..5 {
+            let entity = world.spawn((EcsTransform::new(ChartId(0), Point3::new(i as f32, 0.0, 0.0)),));
+            if i % 2 == 0 {
+                world.add_component(entity, Transitioning);
+            }
+        }
+        assert_eq!(world.entity_count(), 5);
+        assert_eq!(world.component_count::<EcsTransform>(), 5);
+        assert_eq!(world.component_count::<Transitioning>(), 3);
+        assert_eq!(world.component_count::<Velocity>(), 0);
+        assert!(!world.reset_entity_ids(), "ids can't restart while entities live");
+
+        world.clear();
+        assert_eq!(world.entity_count(), 0);
+        assert_eq!(world.component_count::<EcsTransform>(), 0);
+        assert_eq!(world.component_count::<Transitioning>(), 0);
+        assert!(world.query::<EcsTransform>().is_empty());
+        assert_eq!(world.create_entity(), Entity(5), "ids keep counting after a clear");
+
+        world.update(0.016);
+        assert_eq!(world.get_resource::<FrameCounter>().unwrap().0, 1, "systems and resources survive");
+
+        world.clear_all();
+        assert!(world.reset_entity_ids());
+        assert_eq!(world.create_entity(), Entity(0));
+        world.update(0.016);
+        assert_eq!(world.get_resource::<FrameCounter>().unwrap().0, 1, "systems are gone");
+    }
+
+    #[test]
+    fn iter_matches_query_without_allocating_entities() {
+        let mut world = World::new();
+        for i in 0..10 {
+            world.spawn((EcsTransform::new(ChartId(0), Point3::new(i as f32, 0.0, 0.0)),));
+        }
+        world.spawn((Transitioning,));
+
+        let queried: f32 = world.query::<EcsTransform>()
+            .into_iter()
+            .map(|e| world.get_component::<EcsTransform>(e).unwrap().position.local.to_point().x)
+            .sum();
+        let iterated: f32 = world.iter::<EcsTransform>()
+            .map(|(_, transform)| transform.position.local.to_point().x)
+            .sum();
+        assert_eq!(iterated, queried);
+        assert_eq!(iterated, 45.0);
+
+        for (entity, transform) in world.iter::<EcsTransform>() {
+            assert!(std::ptr::eq(transform, world.get_component::<EcsTransform>(entity).unwrap()));
+        }
+        assert_eq!(world.iter::<Velocity>().count(), 0);
+    }
+
+    #[test]
+    fn query_without_excludes_tagged_entities() {
+        let mut world = World::new();
+        let still = Velocity {
+            linear: Vector3::new(0.0, 0.0, 0.0),
+            angular: Vector3::new(0.0, 0.0, 0.0),
+        };
+        let a = world.spawn((EcsTransform::new(ChartId(0), Point3::new(0.0, 0.0, 0.0)), still.clone()));
+        let b = world.spawn((EcsTransform::new(ChartId(0), Point3::new(1.0, 0.0, 0.0)), still.clone(), Transitioning));
+        let c = world.spawn((EcsTransform::new(ChartId(0), Point3::new(2.0, 0.0, 0.0)),));
+
+        let mut settled = world.query_without::<EcsTransform, Transitioning>();
+        settled.sort_by_key(|e| e.0);
+        assert_eq!(settled, vec![a, c]);
+        assert_eq!(world.query2_without::<EcsTransform, Velocity, Transitioning>(), vec![a]);
+
+        world.remove_component::<Transitioning>(b);
+        assert_eq!(world.query_without::<EcsTransform, Transitioning>().len(), 3);
+    }
+
+    #[test]
+    fn spatial_grid_radius_query_returns_nearby_entities() {
+        use metatopia_engine::ecs::{SpatialGrid, SpatialIndexSystem, Transform};
+        use metatopia_engine::math::Ray;
+
+        let mut grid = SpatialGrid::new(1.0);
+        let cluster: Vec<Entity> = (0..5).map(Entity).collect();
+        for (i, &entity) in cluster.iter().enumerate() {
+            grid.insert(entity, ChartId(0), Point3::new(0.2 * i as f32, -0.3, 0.1));
+        }
+        grid.insert(Entity(10), ChartId(0), Point3::new(8.0, 0.0, 0.0));
+        grid.insert(Entity(11), ChartId(0), Point3::new(2.5, 0.0, 0.0));
+        grid.insert(Entity(12), ChartId(1), Point3::new(0.0, 0.0, 0.0));
+        assert_eq!(grid.len(), 8);
+
+        let mut nearby = grid.query_radius(ChartId(0), Point3::new(0.4, 0.0, 0.0), 1.5);
+        nearby.sort_by_key(|e| e.0);
+        assert_eq!(nearby, cluster, "only the cluster, not far or other-chart entities");
+
+        // The ray passes the cluster and the entity at x = 8 but stops short of nothing else
+        let ray = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
+        let candidates = grid.query_ray(ChartId(0), &ray, 20.0);
+        for entity in cluster.iter().chain([&Entity(10), &Entity(11)]) {
+            assert!(candidates.contains(entity));
+        }
+        assert!(!candidates.contains(&Entity(12)));
+        let short = grid.query_ray(ChartId(0), &ray, 3.0);
+        assert!(short.is_empty(), "nothing within a cell of the first 3 units");
+
+        // Huge radii scan the occupied cells instead of every cell in range
+        for radius in [1e9, f32::INFINITY] {
+            let everything = grid.query_radius(ChartId(0), Point3::new(0.0, 0.0, 0.0), radius);
+            assert_eq!(everything.len(), 7);
+            assert!(!everything.contains(&Entity(12)));
+        }
+
+        // The system rebuilds the grid resource from transforms
+        let mut world = World::new();
+        let near = world.spawn((Transform::new(ChartId(0), Point3::new(1.0, 1.0, 1.0)),));
+        world.spawn((Transform::new(ChartId(0), Point3::new(30.0, 0.0, 0.0)),));
+        SpatialIndexSystem::new(2.0).update(&mut world, 0.0);
+        let grid = world.get_resource::<SpatialGrid>().unwrap();
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.query_radius(ChartId(0), Point3::new(0.0, 0.0, 0.0), 2.0), vec![near]);
+    }
+
+    #[test]
+    fn moving_a_portal_entity_moves_the_manifold_portal() {
+        use metatopia_engine::ecs::{PortalEntity, PortalSyncSystem, Transform};
+        use std::sync::{Arc, RwLock};
+
+        let mut manifold = Manifold::new();
+        let other = manifold.add_chart(GeometryType::Euclidean);
+        let portal_id = manifold.create_portal(
+            ChartId(0),
+            other,
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(5.0, 0.0, 0.0),
+            Mat4::from_scale(1.0),
+        ).unwrap();
+        let manifold = Arc::new(RwLock::new(manifold));
+        let system = PortalSyncSystem::new();
+
+        let mut world = World::new();
+        world.insert_resource(manifold.clone());
+        let door = world.spawn((
+            PortalEntity { portal_id, active: true },
+            Transform::new(ChartId(0), Point3::new(0.0, 0.0, 0.0)),
+        ));
+        // Names a portal the manifold doesn't have: warned about and skipped
+        world.spawn((
+            PortalEntity { portal_id: PortalId(42), active: true },
+            Transform::new(ChartId(0), Point3::new(1.0, 0.0, 0.0)),
+        ));
+
+        world.get_component_mut::<Transform>(door).unwrap().position =
+            ManifoldPosition::new(ChartId(0), Point3::new(3.0, 1.0, -2.0));
+        system.update(&mut world, 0.016);
+        {
+            let manifold = manifold.read().unwrap();
+            let portal = manifold.portal(portal_id).unwrap();
+            assert_eq!(portal.bounds().center, Point3::new(3.0, 1.0, -2.0));
+            assert_eq!(portal.source_position(), Point3::new(3.0, 1.0, -2.0));
+            assert_eq!(portal.transform_point(Point3::new(3.0, 1.0, -1.0)), Point3::new(5.0, 0.0, 1.0));
+            assert!(portal.is_active());
+        }
+
+        world.get_component_mut::<PortalEntity>(door).unwrap().active = false;
+        system.update(&mut world, 0.016);
+        assert!(!manifold.read().unwrap().portal(portal_id).unwrap().is_active());
+    }
+
+    #[test]
+    fn systems_find_the_manifold_resource_before_the_shared_one() {
+        use metatopia_engine::ecs::{with_manifold, with_manifold_mut};
+        use std::sync::{Arc, RwLock};
+
+        let mut world = World::new();
+        assert!(with_manifold(&world, |_| ()).is_none());
+
+        let mut shared = Manifold::new();
+        shared.add_chart(GeometryType::Hyperbolic);
+        let shared = Arc::new(RwLock::new(shared));
+        world.insert_resource(shared.clone());
+        assert_eq!(with_manifold(&world, |m| m.charts().len()), Some(2));
+        with_manifold_mut(&mut world, |m| m.add_chart(GeometryType::Spherical));
+        assert_eq!(shared.read().unwrap().charts().len(), 3);
+
+        world.insert_resource(Manifold::new());
+        assert_eq!(with_manifold(&world, |m| m.charts().len()), Some(1), "the plain resource wins");
+        with_manifold_mut(&mut world, |m| m.add_chart(GeometryType::Euclidean));
+        assert_eq!(world.get_resource::<Manifold>().unwrap().charts().len(), 2);
+        assert_eq!(shared.read().unwrap().charts().len(), 3);
+    }
+
+    #[test]
+    fn portal_sync_warnings_are_logged_once_across_frames() {
+        use metatopia_engine::ecs::{PortalEntity, PortalSyncSystem, Stage, Transform};
+
+        let system = PortalSyncSystem::new();
+        let mut world = World::new();
+        world.insert_resource(Manifold::new());
+        world.add_system_to_stage(Stage::PostUpdate, Box::new(system.clone()));
+        world.spawn((
+            PortalEntity { portal_id: PortalId(7), active: true },
+            Transform::new(ChartId(0), Point3::new(0.0, 0.0, 0.0)),
+        ));
+
+        world.update(0.016);
+        world.update(0.016);
+        assert_eq!(system.warning_count(), 1);
+    }
+
+    #[test]
+    fn moving_a_paired_portal_moves_the_reverse_exit() {
+        use metatopia_engine::ecs::{PortalEntity, PortalSyncSystem, Transform};
+
+        let mut manifold = Manifold::new();
+        let other = manifold.add_chart(GeometryType::Euclidean);
+        let (forward, reverse) = manifold.create_portal_pair(
+            ChartId(0),
+            other,
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(5.0, 0.0, 0.0),
+            Mat4::from_scale(1.0),
+        ).unwrap();
+
+        let mut world = World::new();
+        world.insert_resource(manifold);
+        world.spawn((
+            PortalEntity { portal_id: forward, active: true },
+            Transform::new(ChartId(0), Point3::new(2.0, 0.0, 1.0)),
+        ));
+        PortalSyncSystem::new().update(&mut world, 0.016);
+
+        let manifold = world.get_resource::<Manifold>().unwrap();
+        let back = manifold.portal(reverse).unwrap();
+        assert_eq!(back.source_position(), Point3::new(5.0, 0.0, 0.0));
+        assert_eq!(back.target_position(), Point3::new(2.0, 0.0, 1.0));
+        assert_eq!(back.transform_point(Point3::new(5.0, 1.0, 0.0)), Point3::new(2.0, 1.0, 1.0));
+        assert!(manifold.validate().is_empty());
+    }
+
+    #[test]
+    fn portal_transition_rotates_velocity() {
+        use metatopia_engine::ecs::{PortalTransitionSystem, Transform};
+        use std::sync::{Arc, RwLock};
+
+        let mut manifold = Manifold::new();
+        let other = manifold.add_chart(GeometryType::Euclidean);
+        // Quarter turn about y: entering along -Z exits along -X
+        manifold.create_portal(
+            ChartId(0),
+            other,
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(5.0, 0.0, 0.0),
+            Mat4::from_angle_y(cgmath::Deg(90.0)),
+        ).unwrap();
+        let system = PortalTransitionSystem::new(Arc::new(RwLock::new(manifold)));
+
+        let mut world = World::new();
+        let mover = world.spawn((
+            Transform::new(ChartId(0), Point3::new(0.0, 0.0, 2.0)),
+            Velocity { linear: Vector3::new(0.0, 0.0, -3.0), angular: Vector3::new(0.0, 0.0, 1.0) },
+        ));
+        // Too far to reach the portal this frame
+        system.update(&mut world, 0.016);
+        assert_eq!(world.get_component::<Transform>(mover).unwrap().position.chart_id, ChartId(0));
+        world.get_component_mut::<Transform>(mover).unwrap().position =
+            ManifoldPosition::new(ChartId(0), Point3::new(0.0, 0.0, 0.03));
+        let resting = world.spawn((
+            Transform::new(ChartId(0), Point3::new(0.5, 0.0, 2.0)),
+            Velocity { linear: Vector3::new(0.0, 0.0, 0.0), angular: Vector3::new(0.0, 0.0, 0.0) },
+        ));
+        system.update(&mut world, 0.016);
+
+        assert_eq!(world.get_component::<Transform>(mover).unwrap().position.chart_id, other);
+        let velocity = world.get_component::<Velocity>(mover).unwrap();
+        assert!((velocity.linear - Vector3::new(-3.0, 0.0, 0.0)).magnitude() < 1e-5);
+        assert!((velocity.angular - Vector3::new(1.0, 0.0, 0.0)).magnitude() < 1e-5);
+
+        // Zero velocity stays put instead of producing NaNs
+        let transform = world.get_component::<Transform>(resting).unwrap();
+        assert_eq!(transform.position.chart_id, ChartId(0));
+        assert!(!world.get_component::<Velocity>(resting).unwrap().linear.x.is_nan());
+    }
+
+    #[test]
+    fn portal_exit_margin_prevents_ping_pong() {
+        use metatopia_engine::ecs::{PortalTransitionSystem, Transform};
+        use metatopia_engine::manifold::{PortalBounds, PortalShape};
+        use std::sync::{Arc, RwLock};
+
+        // Bidirectional pair: the partner sits at the exit, facing along the rotated normal
+        let rotation = Mat4::from_angle_y(cgmath::Deg(90.0));
+        let mut manifold = Manifold::new();
+        let other = manifold.add_chart(GeometryType::Euclidean);
+        let exit = Point3::new(5.0, 0.0, 0.0);
+        manifold.create_portal(ChartId(0), other, Point3::new(0.0, 0.0, 0.0), exit, rotation).unwrap();
+        let back = manifold.create_portal(other, ChartId(0), exit, Point3::new(0.0, 0.0, 0.0), Mat4::from_angle_y(cgmath::Deg(-90.0))).unwrap();
+        manifold.portal_mut(back).unwrap().set_bounds(
+            PortalBounds::new(exit, Vector3::new(1.0, 0.0, 0.0), 2.0, 3.0, PortalShape::Rectangular),
+        );
+        let manifold = Arc::new(RwLock::new(manifold));
+
+        let run = |system: PortalTransitionSystem| {
+            let mut world = World::new();
+            let entity = world.spawn((
+                Transform::new(ChartId(0), Point3::new(0.0, 0.0, 0.45)),
+                Velocity { linear: Vector3::new(0.0, 0.0, -1.0), angular: Vector3::new(0.0, 0.0, 0.0) },
+            ));
+            let mut charts = Vec::new();
+            for _ in 0..10 {
+                system.update(&mut world, 0.1);
+                let velocity = world.get_component::<Velocity>(entity).unwrap().linear;
+                let transform = world.get_component_mut::<Transform>(entity).unwrap();
+                let moved = transform.position.local.to_point() + velocity * 0.1;
+                transform.position.local = metatopia_engine::manifold::LocalCoordinate::from_point(moved);
+                charts.push(transform.position.chart_id);
+            }
+            (charts, world.get_component::<Transform>(entity).unwrap().position.local.to_point())
+        };
+
+        // Steps up to the portal, crosses in the frame it reaches it, then stays through
+        let (charts, position) = run(PortalTransitionSystem::new(manifold.clone()));
+        assert!(charts[..4].iter().all(|&chart| chart == ChartId(0)), "approaches first: {:?}", charts);
+        assert!(charts[4..].iter().all(|&chart| chart == other), "stays in the destination: {:?}", charts);
+        assert!(position.x < exit.x, "keeps moving away from the partner portal");
+
+        // Re-running the system before the entity moves: landing exactly on the
+        // exit bounces it straight back through the partner, the margin doesn't
+        let charts_after_two_updates = |system: PortalTransitionSystem| {
+            let mut world = World::new();
+            let entity = world.spawn((
+                Transform::new(ChartId(0), Point3::new(0.0, 0.0, 0.05)),
+                Velocity { linear: Vector3::new(0.0, 0.0, -1.0), angular: Vector3::new(0.0, 0.0, 0.0) },
+            ));
+            system.update(&mut world, 0.1);
+            system.update(&mut world, 0.1);
+            world.get_component::<Transform>(entity).unwrap().position.chart_id
+        };
+        assert_eq!(charts_after_two_updates(PortalTransitionSystem::new(manifold.clone())), other);
+        assert_eq!(charts_after_two_updates(PortalTransitionSystem::new(manifold).with_exit_margin(0.0)), ChartId(0));
+    }
+
+    #[test]
+    fn transform_system_wraps_positions_at_chart_boundaries() {
+        use metatopia_engine::ecs::TransformSystem;
+        use metatopia_engine::manifold::{ChartBounds, WrapMode};
+
+        let mut manifold = Manifold::new();
+        let torus = manifold.add_chart(GeometryType::Euclidean);
+        manifold.set_chart_bounds(torus, ChartBounds {
+            min: Point3::new(-5.0, -5.0, -5.0),
+            max: Point3::new(5.0, 5.0, 5.0),
+            wrap_mode: WrapMode::Periodic,
+        }).unwrap();
+        let sphere = manifold.add_chart(GeometryType::Spherical);
+        assert_eq!(manifold.chart(sphere).unwrap().bounds().wrap_mode, WrapMode::Spherical);
+
+        let mut world = World::new();
+        world.insert_resource(manifold);
+        world.add_system(Box::new(TransformSystem));
+        let walker = world.spawn((EcsTransform::new(torus, Point3::new(5.5, -16.0, 2.0)),));
+        let inside = world.spawn((EcsTransform::new(torus, Point3::new(1.0, 2.0, 3.0)),));
+        let floater = world.spawn((EcsTransform::new(sphere, Point3::new(0.0, 3.0, 4.0)),));
+        world.update(0.1);
+
+        let position = |entity| world.get_component::<EcsTransform>(entity).unwrap().position.local.to_point();
+        let wrapped = position(walker);
+        assert!((wrapped.x + 4.5).abs() < 1e-5, "x wraps to the far side: {:?}", wrapped);
+        assert!((wrapped.y - 4.0).abs() < 1e-5, "wraps across several periods: {:?}", wrapped);
+        assert_eq!(wrapped.z, 2.0);
+        assert_eq!(position(inside), Point3::new(1.0, 2.0, 3.0));
+        let projected = position(floater);
+        assert!((projected.to_vec().magnitude() - 1.0).abs() < 1e-5, "back on the sphere: {:?}", projected);
+        assert!((projected.y - 0.6).abs() < 1e-5 && (projected.z - 0.8).abs() < 1e-5);
+    }
+
+    #[test]
+    fn wrapped_positions_stay_inside_custom_spherical_bounds() {
+        use metatopia_engine::manifold::{ChartBounds, LocalCoordinate, WrapMode};
+
+        let mut manifold = Manifold::new();
+        let sphere = manifold.add_chart(GeometryType::Spherical);
+        manifold.set_chart_bounds(sphere, ChartBounds {
+            min: Point3::new(5.0, -3.0, -3.0),
+            max: Point3::new(11.0, 3.0, 3.0),
+            wrap_mode: WrapMode::Spherical,
+        }).unwrap();
+        let chart = manifold.chart(sphere).unwrap();
+
+        assert!(chart.contains(LocalCoordinate::new(10.0, 1.0, 0.0)), "inside the radius-3 sphere at x = 8");
+        assert!(!chart.contains(LocalCoordinate::new(0.0, 0.0, 0.0)), "the origin is outside it");
+        let wrapped = chart.wrap_coordinates(LocalCoordinate::new(8.0, 0.0, 20.0));
+        assert!((wrapped.to_point() - Point3::new(8.0, 0.0, 3.0)).magnitude() < 1e-4, "{:?}", wrapped.to_point());
+        assert!(chart.contains(wrapped));
+    }
+}
+
+// ─── Math ──────────────────────────────────────────────────────────────────
+
+mod math_tests {
+    use super::*;
+    use metatopia_engine::math::{Interpolation, Ray, BoundingBox};
+
+    #[test]
+    fn lerp_at_boundaries() {
+        assert!((Interpolation::lerp(0.0, 10.0, 0.0) - 0.0).abs() < 1e-6);
+        assert!((Interpolation::lerp(0.0, 10.0, 1.0) - 10.0).abs() < 1e-6);
+        assert!((Interpolation::lerp(0.0, 10.0, 0.5) - 5.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn smoothstep_at_boundaries() {
+        assert!((Interpolation::smoothstep(0.0, 1.0, -1.0)).abs() < 1e-6, "below edge0 → 0");
+        assert!((Interpolation::smoothstep(0.0, 1.0, 2.0) - 1.0).abs() < 1e-6, "above edge1 → 1");
+        assert!((Interpolation::smoothstep(0.0, 1.0, 0.5) - 0.5).abs() < 1e-6, "midpoint → 0.5");
+    }
+
+    #[test]
+    fn transform_lerp_blends_translation_rotation_and_scale() {
+        use metatopia_engine::math::Transform;
+
+        let a = Transform::from_trs(Point3::new(0.0, 0.0, 0.0), Quaternion::from_angle_y(Rad(0.0)), 1.0);
+        let b = Transform::from_trs(Point3::new(10.0, 0.0, 0.0), Quaternion::from_angle_y(Rad(std::f32::consts::FRAC_PI_2)), 3.0);
+
+        let start = a.lerp(&b, 0.0);
+        let end = a.lerp(&b, 1.0);
+        for (x, y) in [(start, a), (end, b)] {
+            for c in 0..4 {
+                assert!((x.matrix[c] - y.matrix[c]).magnitude() < 1e-4);
+            }
+        }
+
+        let (position, rotation, scale) = a.lerp(&b, 0.5).decompose();
+        assert!((position - Point3::new(5.0, 0.0, 0.0)).magnitude() < 1e-4);
+        assert!((scale - Vector3::new(2.0, 2.0, 2.0)).magnitude() < 1e-4);
+        let expected = Quaternion::from_angle_y(Rad(std::f32::consts::FRAC_PI_4));
+        assert!(rotation.dot(expected).abs() > 1.0 - 1e-4);
+    }
+
+    #[test]
+    fn easings_map_unit_interval_endpoints() {
+        use metatopia_engine::math::Easing;
+
+        for easing in Easing::ALL {
+            let start = Interpolation::apply(easing, 0.0);
+            let end = Interpolation::apply(easing, 1.0);
+            assert!(start.abs() < 1e-3, "{:?}(0) = {}", easing, start);
+            assert!((end - 1.0).abs() < 1e-3, "{:?}(1) = {}", easing, end);
+
+            for k in 0..=100 {
+                let v = Interpolation::apply(easing, k as f32 / 100.0);
+                assert!(v.is_finite() && (-0.5..=1.5).contains(&v), "{:?} out of bounds: {}", easing, v);
+            }
+            assert_eq!(Interpolation::apply(easing, -2.0), start, "t is clamped");
+        }
+
+        assert!(Interpolation::ease_in_quad(0.5) < 0.5);
+        assert!(Interpolation::ease_out_quad(0.5) > 0.5);
+        assert!((Interpolation::ease_in_out_cubic(0.5) - 0.5).abs() < 1e-6);
+        assert!((0.0..=1.0).contains(&Interpolation::ease_out_bounce(0.7)));
+    }
+
+    #[test]
+    fn catmull_rom_and_bezier_hit_their_endpoints() {
+        let p = [
+            Point3::new(-1.0, 0.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+            Point3::new(2.0, 1.0, 3.0),
+            Point3::new(3.0, 0.0, 1.0),
+        ];
+        assert_eq!(Interpolation::catmull_rom(p[0], p[1], p[2], p[3], 0.0), p[1]);
+        assert_eq!(Interpolation::catmull_rom(p[0], p[1], p[2], p[3], 1.0), p[2]);
+        assert_eq!(Interpolation::cubic_bezier(p[0], p[1], p[2], p[3], 0.0), p[0]);
+        assert_eq!(Interpolation::cubic_bezier(p[0], p[1], p[2], p[3], 1.0), p[3]);
+
+        // A straight Bézier with evenly spaced controls moves at constant speed
+        let line = [0.0, 1.0, 2.0, 3.0].map(|x| Point3::new(x, 0.0, 0.0));
+        let mid = Interpolation::cubic_bezier(line[0], line[1], line[2], line[3], 0.5);
+        assert!((mid.x - 1.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn spline_passes_through_every_knot() {
+        use metatopia_engine::math::Spline;
+
+        let knots = vec![
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 2.0, 0.0),
+            Point3::new(3.0, 2.0, -1.0),
+            Point3::new(4.0, 0.0, 0.0),
+            Point3::new(6.0, 1.0, 2.0),
+        ];
+        let spline = Spline::new(knots.clone());
+        assert_eq!(spline.segment_count(), 4);
+        for (i, knot) in knots.iter().enumerate() {
+            let p = spline.evaluate(i as f32 / 4.0);
+            assert!((p - knot).magnitude() < 1e-5, "knot {} missed: {:?}", i, p);
+        }
+        assert_eq!(spline.evaluate(-1.0), knots[0]);
+        assert_eq!(spline.evaluate(2.0), knots[4]);
+    }
+
+    #[test]
+    fn arc_length_spline_moves_at_constant_speed() {
+        use metatopia_engine::math::Spline;
+
+        // Segments of growing length: uniform parameters would crawl at the start
+        let knots = [0.0, 1.0, 2.0, 4.0, 7.0].map(|x| Point3::new(x, 0.0, 0.0)).to_vec();
+        let uniform = Spline::new(knots.clone());
+        let spline = Spline::new(knots).with_arc_length(64);
+        let length = spline.length();
+        assert!((length - 7.0).abs() < 1e-3, "length {}", length);
+
+        let steps = 20;
+        let chords: Vec<f32> = (0..steps)
+            .map(|k| (spline.evaluate((k + 1) as f32 / steps as f32) - spline.evaluate(k as f32 / steps as f32)).magnitude())
+            .collect();
+        for chord in &chords {
+            assert!((chord - length / steps as f32).abs() < 0.03 * length / steps as f32, "uneven step {}", chord);
+        }
+        let first_uniform = (uniform.evaluate(1.0 / steps as f32) - uniform.evaluate(0.0)).magnitude();
+        assert!(first_uniform < 0.5 * chords[0], "uniform parameters should crawl at the start");
+        assert_eq!(spline.evaluate(1.0), Point3::new(7.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn plane_signed_distance_and_classification() {
+        use metatopia_engine::math::{Plane, Side};
+
+        let plane = Plane::from_point_normal(Point3::new(0.0, 2.0, 0.0), Vector3::new(0.0, 3.0, 0.0));
+        assert!((plane.normal.magnitude() - 1.0).abs() < 1e-6, "normal is normalized");
+        assert!((plane.signed_distance(Point3::new(5.0, 7.0, 1.0)) - 5.0).abs() < 1e-6);
+        assert!((plane.signed_distance(Point3::new(0.0, -1.0, 0.0)) + 3.0).abs() < 1e-6);
+        assert_eq!(plane.classify(Point3::new(0.0, 3.0, 0.0)), Side::Front);
+        assert_eq!(plane.classify(Point3::new(0.0, 1.0, 0.0)), Side::Back);
+        assert_eq!(plane.classify(Point3::new(9.0, 2.0, -4.0)), Side::On);
+
+        let scaled = Plane::new(Vector3::new(0.0, 2.0, 0.0), -4.0);
+        assert!((scaled.d - plane.d).abs() < 1e-6 && scaled.normal == plane.normal);
+
+        let projected = plane.project_point(Point3::new(1.0, 10.0, -2.0));
+        assert!((projected - Point3::new(1.0, 2.0, -2.0)).magnitude() < 1e-6);
+    }
+
+    #[test]
+    fn plane_ray_intersection() {
+        use metatopia_engine::math::Plane;
+
+        let plane = Plane::from_point_normal(Point3::new(0.0, 0.0, -5.0), Vector3::new(0.0, 0.0, 1.0));
+        let toward = Ray::new(Point3::new(1.0, 1.0, 0.0), Vector3::new(0.0, 0.0, -1.0));
+        let t = plane.intersect_ray(&toward).expect("should hit");
+        assert!((t - 5.0).abs() < 1e-6);
+        assert!((toward.point_at(t) - Point3::new(1.0, 1.0, -5.0)).magnitude() < 1e-6);
+
+        let away = Ray::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 1.0));
+        assert!(plane.intersect_ray(&away).is_none());
+        let parallel = Ray::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
+        assert!(plane.intersect_ray(&parallel).is_none());
+    }
+
+    #[test]
+    fn ray_point_at() {
+        let ray = Ray::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
+        let p = ray.point_at(5.0);
+        assert!((p.x - 5.0).abs() < 1e-6);
+        assert!(p.y.abs() < 1e-6);
+    }
+
+    #[test]
+    fn bounding_box_contains() {
+        let bb = BoundingBox::new(
+            Point3::new(-1.0, -1.0, -1.0),
             Point3::new(1.0, 1.0, 1.0),
         );
         assert!(bb.contains(Point3::new(0.0, 0.0, 0.0)));
@@ -319,6 +1852,57 @@ mod math_tests {
         assert!(bb.intersects_ray(&ray).is_none(), "Ray should miss the box");
     }
 
+    #[test]
+    fn ray_sphere_hit_graze_miss() {
+        let ray = Ray::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -1.0));
+        let hit = ray.intersect_sphere(Point3::new(0.0, 0.0, -10.0), 2.0).expect("should hit");
+        assert!((hit - 8.0).abs() < 1e-4);
+
+        let graze = ray.intersect_sphere(Point3::new(2.0, 0.0, -10.0), 2.0).expect("tangent ray should hit");
+        assert!((graze - 10.0).abs() < 1e-3);
+
+        assert!(ray.intersect_sphere(Point3::new(3.0, 0.0, -10.0), 2.0).is_none());
+        assert!(ray.intersect_sphere(Point3::new(0.0, 0.0, 10.0), 2.0).is_none(), "sphere behind the ray");
+
+        let inside = ray.intersect_sphere(Point3::new(0.0, 0.0, 0.0), 2.0).expect("inside hits far side");
+        assert!((inside - 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn ray_triangle_hit_graze_miss() {
+        let a = Point3::new(-1.0, -1.0, -5.0);
+        let b = Point3::new(1.0, -1.0, -5.0);
+        let c = Point3::new(0.0, 1.0, -5.0);
+
+        let ray = Ray::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -1.0));
+        let (t, u, v) = ray.intersect_triangle(a, b, c).expect("should hit");
+        assert!((t - 5.0).abs() < 1e-4);
+        let p = a + (b - a) * u + (c - a) * v;
+        assert!((p - Point3::new(0.0, 0.0, -5.0)).magnitude() < 1e-4);
+
+        let edge = Ray::new(Point3::new(0.0, -1.0, 0.0), Vector3::new(0.0, 0.0, -1.0));
+        assert!(edge.intersect_triangle(a, b, c).is_some(), "ray through an edge should hit");
+
+        let miss = Ray::new(Point3::new(2.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -1.0));
+        assert!(miss.intersect_triangle(a, b, c).is_none());
+
+        assert!(ray.intersect_triangle(a, c, b).is_none(), "back face should be culled");
+    }
+
+    #[test]
+    fn ray_plane_hit_graze_miss() {
+        let ray = Ray::new(Point3::new(0.0, 5.0, 0.0), Vector3::new(0.0, -1.0, 0.0));
+        let up = Vector3::new(0.0, 1.0, 0.0);
+        let t = ray.intersect_plane(Point3::new(0.0, 0.0, 0.0), up).expect("should hit");
+        assert!((t - 5.0).abs() < 1e-4);
+
+        let parallel = Ray::new(Point3::new(0.0, 5.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
+        assert!(parallel.intersect_plane(Point3::new(0.0, 0.0, 0.0), up).is_none());
+
+        let away = Ray::new(Point3::new(0.0, 5.0, 0.0), Vector3::new(0.0, 1.0, 0.0));
+        assert!(away.intersect_plane(Point3::new(0.0, 0.0, 0.0), up).is_none());
+    }
+
     #[test]
     fn transform_identity_preserves_point() {
         let t = Transform::identity();
@@ -361,7 +1945,82 @@ mod math_tests {
         assert!((p.y).abs() < 1e-4);
         assert!((p.z).abs() < 1e-4);
     }
-}
+
+    fn same_rotation(a: Quaternion<f32>, b: Quaternion<f32>) -> bool {
+        // q and -q describe the same rotation
+        (a.s * b.s + a.v.dot(b.v)).abs() > 1.0 - 1e-4
+    }
+
+    #[test]
+    fn transform_decompose_recovers_trs() {
+        let rotation = Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, 3.0).normalize(), Rad(0.8));
+        let t = Transform::from_trs(Point3::new(3.0, -4.0, 5.0), rotation, 2.5);
+        let (position, r, scale) = t.decompose();
+        assert!((position - Point3::new(3.0, -4.0, 5.0)).magnitude() < 1e-4);
+        assert!(same_rotation(r, rotation));
+        assert!((scale - Vector3::new(2.5, 2.5, 2.5)).magnitude() < 1e-4);
+    }
+
+    #[test]
+    fn transform_decompose_non_uniform_scale() {
+        let rotation = Quaternion::from_angle_y(Rad(1.2));
+        let matrix = Mat4::from_translation(Vector3::new(1.0, 2.0, 3.0))
+            * Mat4::from(rotation)
+            * Mat4::from_nonuniform_scale(1.0, 2.0, 3.0);
+        let (position, r, scale) = Transform { matrix }.decompose();
+        assert!((position - Point3::new(1.0, 2.0, 3.0)).magnitude() < 1e-4);
+        assert!(same_rotation(r, rotation));
+        assert!((scale - Vector3::new(1.0, 2.0, 3.0)).magnitude() < 1e-4);
+    }
+
+    #[test]
+    fn transform_decompose_mirrored() {
+        let rotation = Quaternion::from_angle_z(Rad(0.5));
+        let matrix = Mat4::from(rotation) * Mat4::from_nonuniform_scale(-2.0, 1.0, 1.0);
+        let (_, r, scale) = Transform { matrix }.decompose();
+        assert!((scale - Vector3::new(-2.0, 1.0, 1.0)).magnitude() < 1e-4);
+        assert!(same_rotation(r, rotation));
+        let rebuilt = Mat4::from(r) * Mat4::from_nonuniform_scale(scale.x, scale.y, scale.z);
+        for (a, b) in [(rebuilt.x, matrix.x), (rebuilt.y, matrix.y), (rebuilt.z, matrix.z)] {
+            assert!((a - b).magnitude() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn bounding_boxes_merge_and_measure() {
+        use metatopia_engine::math::BoundingBox;
+
+        let a = BoundingBox::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0));
+        let b = BoundingBox::new(Point3::new(3.0, -2.0, 0.5), Point3::new(4.0, -1.0, 2.0));
+        let merged = a.merge(&b);
+        assert_eq!(merged.min, Point3::new(0.0, -2.0, 0.0));
+        assert_eq!(merged.max, Point3::new(4.0, 1.0, 2.0));
+        assert_eq!(merged.center(), Point3::new(2.0, -0.5, 1.0));
+        assert_eq!(merged.extents(), Vector3::new(2.0, 1.5, 1.0));
+        assert_eq!(a.surface_area(), 6.0);
+
+        let mut grown = a;
+        grown.expand_to_include(Point3::new(-1.0, 0.5, 3.0));
+        assert_eq!(grown.min, Point3::new(-1.0, 0.0, 0.0));
+        assert_eq!(grown.max, Point3::new(1.0, 1.0, 3.0));
+    }
+
+    #[test]
+    fn rotated_bounding_box_encloses_all_corners() {
+        use metatopia_engine::math::BoundingBox;
+
+        let unit = BoundingBox::new(Point3::new(-0.5, -0.5, -0.5), Point3::new(0.5, 0.5, 0.5));
+        let rotated = unit.transform(&Mat4::from_angle_y(cgmath::Deg(45.0)));
+        let half_diagonal = 0.5 * std::f32::consts::SQRT_2;
+        assert!((rotated.max.x - half_diagonal).abs() < 1e-5);
+        assert!((rotated.max.z - half_diagonal).abs() < 1e-5);
+        assert!((rotated.min.x + half_diagonal).abs() < 1e-5);
+        assert!((rotated.max.y - 0.5).abs() < 1e-5, "rotation about y leaves y alone");
+
+        let moved = unit.transform(&Mat4::from_translation(Vector3::new(10.0, 0.0, 0.0)));
+        assert_eq!(moved.center(), Point3::new(10.0, 0.0, 0.0));
+    }
+}
 
 // ─── Time / Timer ──────────────────────────────────────────────────────────
 
@@ -381,6 +2040,50 @@ mod time_tests {
         assert!((t.total_time() - 2.0 / 60.0).abs() < 1e-4);
     }
 
+    #[test]
+    fn huge_delta_is_clamped() {
+        let mut t = Time::new();
+        assert!((t.max_delta() - 0.1).abs() < 1e-6, "default cap");
+
+        t.update(0.5);
+        assert!((t.delta_time() - 0.1).abs() < 1e-6);
+        assert!((t.raw_delta_time() - 0.5).abs() < 1e-6);
+        assert!((t.total_time() - 0.1).abs() < 1e-6);
+
+        t.set_max_delta(0.25);
+        t.update(0.5);
+        assert!((t.delta_time() - 0.25).abs() < 1e-6);
+        t.update(0.01);
+        assert!((t.delta_time() - 0.01).abs() < 1e-6, "small deltas pass through");
+    }
+
+    #[test]
+    fn frame_time_statistics_over_rolling_window() {
+        let mut t = Time::new();
+        assert_eq!(t.avg_frame_time(), 0.0);
+        assert_eq!(t.percentile(99.0), 0.0);
+
+        t.set_frame_history(10);
+        // Two stale frames that fall out of the window, then 1..=10 ms
+        t.update(0.5);
+        t.update(0.5);
+        for ms in 1..=10 {
+            t.update(ms as f32 / 1000.0);
+        }
+
+        assert_eq!(t.frame_times().count(), 10);
+        assert!((t.min_frame_time() - 0.001).abs() < 1e-6);
+        assert!((t.max_frame_time() - 0.010).abs() < 1e-6);
+        assert!((t.avg_frame_time() - 0.0055).abs() < 1e-6);
+        assert!((t.percentile(50.0) - 0.005).abs() < 1e-6);
+        assert!((t.percentile(90.0) - 0.009).abs() < 1e-6);
+        assert!((t.percentile(99.0) - 0.010).abs() < 1e-6);
+        assert!((t.percentile(0.0) - 0.001).abs() < 1e-6);
+
+        t.set_frame_history(3);
+        assert!((t.min_frame_time() - 0.008).abs() < 1e-6, "shrinking keeps the newest frames");
+    }
+
     #[test]
     fn timer_from_seconds_progress() {
         let timer = Timer::from_seconds(1.0);
@@ -410,6 +2113,83 @@ mod time_tests {
         assert!(timer.progress() < 0.01);
     }
 
+    #[test]
+    fn stopwatch_counts_only_while_running() {
+        use std::time::Duration;
+
+        let mut watch = Stopwatch::new();
+        assert!(!watch.is_running());
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(watch.elapsed(), Duration::ZERO, "starts stopped");
+
+        watch.start();
+        std::thread::sleep(Duration::from_millis(20));
+        let first = watch.elapsed();
+        assert!(first >= Duration::from_millis(20));
+
+        // Stopped time doesn't count, and starting again resumes from there
+        watch.stop();
+        let stopped = watch.elapsed();
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(watch.elapsed(), stopped);
+        watch.start();
+        watch.start();
+        std::thread::sleep(Duration::from_millis(10));
+        let resumed = watch.elapsed();
+        assert!(resumed >= stopped + Duration::from_millis(10));
+
+        watch.reset();
+        assert!(!watch.is_running());
+        assert_eq!(watch.elapsed(), Duration::ZERO);
+    }
+
+    #[test]
+    fn frame_limiter_waits_out_each_budget() {
+        use metatopia_engine::time::FrameLimiter;
+        use std::time::{Duration, Instant};
+
+        let mut unlimited = FrameLimiter::new(None);
+        assert_eq!(unlimited.budget(), None);
+        assert_eq!(unlimited.wait(), Duration::ZERO);
+        assert_eq!(FrameLimiter::new(Some(0)).budget(), None);
+
+        let mut limiter = FrameLimiter::new(Some(200));
+        assert_eq!(limiter.budget(), Some(Duration::from_millis(5)));
+        limiter.wait();
+        let start = Instant::now();
+        for _ in 0..20 {
+            limiter.wait();
+        }
+        let elapsed = start.elapsed();
+        // `start` is taken just after the first deadline, so allow a little slack
+        assert!(elapsed >= Duration::from_millis(95), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(400), "{:?}", elapsed);
+
+        // A frame that overruns its budget doesn't make the next ones rush
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(limiter.wait(), Duration::ZERO);
+        assert!(limiter.wait() > Duration::from_millis(4));
+    }
+
+    #[test]
+    fn stopwatch_laps_measure_time_since_previous_lap() {
+        use std::time::Duration;
+
+        let mut watch = Stopwatch::started();
+        std::thread::sleep(Duration::from_millis(15));
+        let first = watch.lap();
+        assert!(first >= Duration::from_millis(15));
+
+        std::thread::sleep(Duration::from_millis(5));
+        watch.stop();
+        std::thread::sleep(Duration::from_millis(20));
+        let second = watch.lap();
+        assert!(second >= Duration::from_millis(5));
+        assert_eq!(first + second, watch.elapsed(), "laps add up to the total");
+
+        assert_eq!(watch.lap(), Duration::ZERO, "no time passes while stopped");
+    }
+
     #[test]
     fn fixed_timestep_calculates_steps() {
         let mut ts = FixedTimestep::new(60.0); // 60 Hz
@@ -438,6 +2218,45 @@ mod time_tests {
 mod core_tests {
     use super::*;
 
+    #[test]
+    fn engine_config_builder_sets_fields() {
+        let config = EngineConfig::builder()
+            .with_title("Builder")
+            .with_dimensions(800, 600)
+            .with_vsync(false)
+            .with_target_fps(Some(90))
+            .with_resizable(false)
+            .with_headless(true)
+            .with_fixed_update_rate(120.0)
+            .build()
+            .unwrap();
+
+        assert_eq!(config.title, "Builder");
+        assert_eq!((config.width, config.height), (800, 600));
+        assert!(!config.vsync && !config.resizable && config.headless);
+        assert_eq!(config.target_fps, Some(90));
+        assert_eq!(config.fixed_update_rate, 120.0);
+
+        let defaults = EngineConfig::builder().build().unwrap();
+        let expected = EngineConfig::default();
+        assert_eq!((defaults.title, defaults.width, defaults.height), (expected.title, expected.width, expected.height));
+        assert_eq!((defaults.vsync, defaults.target_fps), (true, None));
+    }
+
+    #[test]
+    fn engine_config_validate_rejects_bad_settings() {
+        assert!(EngineConfig::default().validate().is_ok());
+        assert!(EngineConfig { width: 0, ..Default::default() }.validate().is_err());
+        assert!(EngineConfig { height: 0, ..Default::default() }.validate().is_err());
+        assert!(EngineConfig { target_fps: Some(0), ..Default::default() }.validate().is_err());
+        assert!(EngineConfig { fixed_update_rate: 0.0, ..Default::default() }.validate().is_err());
+        assert!(EngineConfig { fixed_update_rate: f32::NAN, ..Default::default() }.validate().is_err());
+
+        assert!(EngineConfig::builder().with_dimensions(1280, 0).build().is_err());
+        let error = EngineConfig::builder().with_target_fps(Some(0)).build().unwrap_err();
+        assert!(error.contains("target_fps"), "{}", error);
+    }
+
     #[test]
     fn engine_config_defaults() {
         let config = EngineConfig::default();
@@ -464,6 +2283,148 @@ mod core_tests {
         assert_eq!(engine.time.frame_count(), 0);
         assert_eq!(engine.world.query::<Velocity>().len(), 0);
     }
+
+    #[derive(Default)]
+    struct CountingGame {
+        inits: u32,
+        updates: u32,
+        fixed_updates: u32,
+        cleanups: u32,
+        quit_after: u32,
+    }
+
+    impl GameState for CountingGame {
+        fn on_init(&mut self, _engine: &mut Engine) {
+            self.inits += 1;
+        }
+
+        fn on_update(&mut self, engine: &mut Engine, _dt: f32) {
+            self.updates += 1;
+            if self.updates >= self.quit_after {
+                engine.quit();
+            }
+        }
+
+        fn on_fixed_update(&mut self, _engine: &mut Engine, _dt: f32) {
+            self.fixed_updates += 1;
+        }
+
+        fn on_cleanup(&mut self, _engine: &mut Engine) {
+            self.cleanups += 1;
+        }
+    }
+
+    #[test]
+    fn headless_game_runs_until_quit() {
+        let mut engine = Engine::headless(EngineConfig { target_fps: Some(500), ..Default::default() });
+        assert!(engine.is_headless());
+
+        let mut game = CountingGame { quit_after: 30, ..Default::default() };
+        engine.run_headless(&mut game);
+
+        assert_eq!((game.inits, game.updates, game.cleanups), (1, 30, 1));
+        assert_eq!(engine.time.frame_count(), 30);
+        assert!(engine.time.total_time() > 0.0);
+        assert!(!engine.is_running());
+    }
+
+    #[test]
+    fn headless_frames_are_limited_to_target_fps() {
+        let start = std::time::Instant::now();
+        let mut engine = Engine::headless(EngineConfig { target_fps: Some(100), ..Default::default() });
+        let mut game = CountingGame { quit_after: 20, ..Default::default() };
+        engine.run_headless(&mut game);
+        let elapsed = start.elapsed().as_secs_f32();
+
+        assert_eq!(game.updates, 20);
+        assert!(elapsed >= 0.2 - 0.002, "20 frames at 100 fps took {}s", elapsed);
+        assert!(elapsed < 1.0, "limiter overslept: {}s", elapsed);
+    }
+
+    #[test]
+    fn vsync_disables_the_frame_limiter() {
+        let mut engine = Engine::new(EngineConfig { target_fps: Some(2), vsync: true, ..Default::default() });
+        let start = std::time::Instant::now();
+        engine.limit_frame_rate();
+        engine.limit_frame_rate();
+        assert!(start.elapsed() < std::time::Duration::from_millis(100), "vsync paces windowed frames");
+
+        engine.config.vsync = false;
+        engine.config.target_fps = Some(50);
+        engine.limit_frame_rate();
+        let start = std::time::Instant::now();
+        engine.limit_frame_rate();
+        assert!(start.elapsed() >= std::time::Duration::from_millis(19));
+    }
+
+    #[test]
+    fn quit_finishes_the_frame_then_cleans_up_once() {
+        let mut engine = Engine::headless(EngineConfig { fixed_update_rate: 60.0, ..Default::default() });
+        let mut game = CountingGame { quit_after: 3, ..Default::default() };
+
+        for frame in 1..=6 {
+            engine.tick(&mut game, 1.0 / 60.0);
+            assert_eq!(engine.is_quitting(), frame >= 3);
+        }
+        assert_eq!(game.updates, 3, "no updates after the quitting frame");
+        assert_eq!(game.fixed_updates, 3, "the quitting frame still ran its fixed update");
+        assert_eq!(game.cleanups, 0);
+
+        engine.shutdown(&mut game);
+        engine.shutdown(&mut game);
+        assert_eq!(game.cleanups, 1);
+        assert!(!engine.is_running());
+    }
+
+    #[test]
+    fn tick_runs_fixed_updates_at_configured_rate() {
+        let mut engine = Engine::headless(EngineConfig { fixed_update_rate: 64.0, ..Default::default() });
+        let mut game = CountingGame { quit_after: u32::MAX, ..Default::default() };
+
+        for _ in 0..10 {
+            engine.tick(&mut game, 1.0 / 32.0);
+        }
+        assert_eq!(game.updates, 10);
+        assert_eq!(game.fixed_updates, 20);
+    }
+
+    #[derive(Default)]
+    struct ResizeAwareGame {
+        last_size: Option<(u32, u32)>,
+        focused: bool,
+        saw_close: bool,
+    }
+
+    impl GameState for ResizeAwareGame {
+        fn on_init(&mut self, _engine: &mut Engine) {}
+
+        fn on_update(&mut self, _engine: &mut Engine, _dt: f32) {}
+
+        fn on_window_event(&mut self, engine: &mut Engine, event: &WindowEvent) {
+            match *event {
+                WindowEvent::Resized(width, height) => self.last_size = Some((width, height)),
+                WindowEvent::Focused(focused) => self.focused = focused,
+                WindowEvent::CloseRequested => self.saw_close = engine.is_running(),
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn window_events_reach_the_game() {
+        let mut engine = Engine::headless(EngineConfig::default());
+        let mut game = ResizeAwareGame::default();
+
+        engine.handle_window_event(&mut game, &WindowEvent::Resized(800, 600));
+        engine.handle_window_event(&mut game, &WindowEvent::Focused(true));
+        assert_eq!(game.last_size, Some((800, 600)));
+        assert!(game.focused);
+        assert_eq!((engine.config.width, engine.config.height), (800, 600));
+
+        engine.handle_window_event(&mut game, &WindowEvent::CloseRequested);
+        assert!(game.saw_close, "the game sees the close request before the engine quits");
+        assert!(!engine.is_running());
+    }
 }
 
 // ─── ManifoldPosition ──────────────────────────────────────────────────────
@@ -471,6 +2432,58 @@ mod core_tests {
 mod position_tests {
     use super::*;
 
+    #[test]
+    fn to_world_relative_to_maps_through_portals_into_the_reference_frame() {
+        let mut m = Manifold::new();
+        let room = m.add_chart(GeometryType::Euclidean);
+        let attic = m.add_chart(GeometryType::Euclidean);
+        let island = m.add_chart(GeometryType::Euclidean);
+        let origin = ManifoldPosition::new(ChartId(0), Point3::new(0.0, 0.0, 0.0));
+        // Quarter turn about Y: the hallway's -Z continues as the room's -X
+        let turn = Mat4::from_angle_y(Rad(std::f32::consts::FRAC_PI_2));
+        m.create_portal(ChartId(0), room, Point3::new(0.0, 0.0, -5.0), Point3::new(10.0, 0.0, 0.0), turn).unwrap();
+        m.create_portal(room, attic, Point3::new(10.0, 3.0, 0.0), Point3::new(0.0, 0.0, 0.0), Mat4::from_scale(1.0)).unwrap();
+
+        // Two units beyond the portal in the room, along its exit direction
+        let beyond = Point3::new(10.0, 0.0, 0.0) + cgmath::Transform::transform_vector(&turn, Vector3::new(0.0, 0.0, -2.0));
+        let seen = ManifoldPosition::new(room, beyond).to_world_relative_to(origin, &m).unwrap();
+        assert!((seen - Point3::new(0.0, 0.0, -7.0)).magnitude() < 1e-5, "{:?}", seen);
+
+        // Two hops compose, and positions in the reference chart are unchanged
+        let upstairs = ManifoldPosition::new(attic, Point3::new(1.0, 0.0, 0.0));
+        let seen = upstairs.to_world_relative_to(origin, &m).unwrap();
+        let via_room = m.portal(PortalId(0)).unwrap().inverse_transform_point(Point3::new(11.0, 3.0, 0.0));
+        assert!((seen - via_room).magnitude() < 1e-5);
+        assert_eq!(m.portal_route(origin, upstairs), Some(vec![PortalId(0), PortalId(1)]));
+        let here = ManifoldPosition::new(ChartId(0), Point3::new(1.0, 2.0, 3.0));
+        assert_eq!(here.to_world_relative_to(origin, &m), Some(Point3::new(1.0, 2.0, 3.0)));
+
+        // No portal leads to the island, and portals are one-way
+        assert!(ManifoldPosition::new(island, Point3::new(0.0, 0.0, 0.0)).to_world_relative_to(origin, &m).is_none());
+        assert!(here.to_world_relative_to(ManifoldPosition::new(room, Point3::new(0.0, 0.0, 0.0)), &m).is_none());
+    }
+
+    #[test]
+    fn portal_route_follows_the_cheapest_path_not_the_fewest_portals() {
+        let mut m = Manifold::new();
+        let hall = m.add_chart(GeometryType::Euclidean);
+        let room = m.add_chart(GeometryType::Euclidean);
+        let here = ManifoldPosition::new(ChartId(0), Point3::new(0.0, 0.0, 0.0));
+        let there = ManifoldPosition::new(room, Point3::new(0.0, 0.0, 0.0));
+        let identity = Mat4::from_scale(1.0);
+        let far = m.create_portal(ChartId(0), room, Point3::new(50.0, 0.0, 0.0), Point3::new(0.0, 0.0, 0.0), identity).unwrap();
+        let near = m.create_portal(ChartId(0), hall, Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 0.0, 0.0), identity).unwrap();
+        let onward = m.create_portal(hall, room, Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 0.0, 0.0), identity).unwrap();
+
+        // Two short hops beat one long one, for both routing and distance
+        assert_eq!(m.portal_route(here, there), Some(vec![near, onward]));
+        assert!((m.geodesic_distance(here, there).unwrap() - 2.0).abs() < 1e-5);
+
+        m.portal_mut(near).unwrap().set_active(false);
+        assert_eq!(m.portal_route(here, there), Some(vec![far]));
+        assert_eq!(m.portal_route(here, here), Some(vec![]));
+    }
+
     #[test]
     fn manifold_position_creation() {
         let pos = ManifoldPosition::new(ChartId(0), Point3::new(1.0, 2.0, 3.0));
@@ -489,4 +2502,1908 @@ mod position_tests {
         // Should succeed for the default chart
         assert!(world_pos.is_some(), "to_world should succeed for default chart");
     }
+
+    #[test]
+    fn lerp_within_chart() {
+        let m = Manifold::new();
+        let a = ManifoldPosition::new(ChartId(0), Point3::new(0.0, 0.0, 0.0));
+        let b = ManifoldPosition::new(ChartId(0), Point3::new(4.0, 2.0, 0.0));
+
+        let mid = ManifoldPosition::lerp(a, b, 0.5, &m);
+        assert_eq!(mid.chart_id, ChartId(0));
+        assert!((mid.local.to_point() - Point3::new(2.0, 1.0, 0.0)).magnitude() < 1e-4);
+
+        let end = ManifoldPosition::lerp(a, b, 1.0, &m);
+        assert!((end.local.to_point() - b.local.to_point()).magnitude() < 1e-4);
+    }
+
+    #[test]
+    fn lerp_across_charts_returns_start() {
+        let mut m = Manifold::new();
+        let other = m.add_chart(GeometryType::Hyperbolic);
+        let a = ManifoldPosition::new(ChartId(0), Point3::new(1.0, 0.0, 0.0));
+        let b = ManifoldPosition::new(other, Point3::new(0.2, 0.0, 0.0));
+
+        let blended = ManifoldPosition::lerp(a, b, 0.5, &m);
+        assert_eq!(blended.chart_id, ChartId(0));
+        assert_eq!(blended.local.to_point(), a.local.to_point());
+    }
+
+    #[test]
+    fn orientation_stays_orthonormal_through_repeated_transport() {
+        use metatopia_engine::manifold::{GeodesicPath, ManifoldOrientation};
+
+        let mut m = Manifold::new();
+        let disk = m.add_chart(GeometryType::Hyperbolic);
+
+        // Small square loop inside the Poincaré disk
+        let corners = [
+            Point3::new(0.1, 0.1, 0.0),
+            Point3::new(0.3, 0.1, 0.0),
+            Point3::new(0.3, 0.3, 0.0),
+            Point3::new(0.1, 0.3, 0.0),
+        ];
+        let legs: Vec<GeodesicPath> = (0..4)
+            .map(|i| {
+                let (a, b) = (corners[i], corners[(i + 1) % 4]);
+                let mut path = GeodesicPath::new(GeometryType::Hyperbolic);
+                for step in 0..=4 {
+                    path.add_point(a + (b - a) * (step as f32 / 4.0), b - a);
+                }
+                path
+            })
+            .collect();
+
+        let mut orientation = ManifoldOrientation::new(Quaternion::from_angle_y(cgmath::Deg(30.0)));
+        // Start from a frame that has already drifted into shear
+        orientation.tangent_space.y += orientation.tangent_space.x * 0.2;
+
+        for _ in 0..250 {
+            for leg in &legs {
+                orientation.transport_along(leg, &m, disk);
+            }
+        }
+
+        assert!((orientation.quaternion.magnitude() - 1.0).abs() < 1e-5);
+        let axes = [orientation.tangent_space.x, orientation.tangent_space.y, orientation.tangent_space.z]
+            .map(|axis| axis.truncate());
+        for (i, a) in axes.iter().enumerate() {
+            assert!((a.magnitude() - 1.0).abs() < 1e-5, "axis {} has length {}", i, a.magnitude());
+            for b in &axes[i + 1..] {
+                assert!(a.dot(*b).abs() < 1e-5, "axes are not perpendicular");
+            }
+        }
+        assert!((axes[0].cross(axes[1]) - axes[2]).magnitude() < 1e-5, "frame stays right-handed");
+
+        // The quaternion describes the same frame as the matrix
+        let rotated = orientation.quaternion * Vector3::unit_x();
+        assert!((rotated - axes[0]).magnitude() < 1e-4);
+    }
+}
+
+// ─── Resources ─────────────────────────────────────────────────────────────
+
+mod resources_tests {
+    use super::*;
+    use metatopia_engine::resources::{SpawnPoint, WorldMetadata, WorldResource};
+
+    #[test]
+    fn world_resource_ron_round_trip() {
+        let mut m = Manifold::new();
+        let hyp = m.add_chart(GeometryType::Hyperbolic);
+        let sph = m.add_chart(GeometryType::Spherical);
+        m.create_portal(ChartId(0), hyp, Point3::new(5.0, 0.0, 0.0), Point3::new(0.0, 0.0, 0.0), Mat4::from_scale(1.0)).unwrap();
+        m.create_portal(hyp, sph, Point3::new(0.5, 0.0, 0.0), Point3::new(0.0, 0.0, 1.0), Mat4::from_scale(1.0)).unwrap();
+
+        let mut world = WorldResource::new(m);
+        world.add_spawn_point(SpawnPoint::new("start", ChartId(0), Point3::new(1.0, 2.0, 3.0)));
+        world.metadata = WorldMetadata { name: "round trip".to_string(), ..Default::default() };
+
+        let path = std::env::temp_dir().join(format!("metatopia_world_{}.ron", std::process::id()));
+        world.save_ron(&path).unwrap();
+        let loaded = WorldResource::load_ron(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded.manifold.charts().len(), 3);
+        assert_eq!(loaded.manifold.portals().len(), world.manifold.portals().len());
+        for (id, chart) in world.manifold.charts() {
+            assert_eq!(loaded.manifold.chart(*id).unwrap().geometry(), chart.geometry());
+        }
+        assert_eq!(loaded.spawn_points(), world.spawn_points());
+        assert_eq!(loaded.metadata.name, "round trip");
+    }
+
+    #[test]
+    fn named_spawn_points_are_looked_up_and_saved() {
+        let mut m = Manifold::new();
+        let hyp = m.add_chart(GeometryType::Hyperbolic);
+        let mut world = WorldResource::new(m);
+        assert!(world.default_spawn().is_none());
+
+        let facing_back = Quaternion::from_angle_y(Rad(std::f32::consts::PI));
+        world.add_spawn_point(SpawnPoint::new("lobby", ChartId(0), Point3::new(0.0, 1.0, 0.0)));
+        world.add_spawn_point(SpawnPoint::new("theater", hyp, Point3::new(0.2, 0.0, 0.0)).with_orientation(facing_back));
+        assert_eq!(world.default_spawn().unwrap().name, "lobby", "first spawn by default");
+
+        world.metadata.default_spawn = Some("theater".to_string());
+        assert_eq!(world.default_spawn().unwrap().chart, hyp);
+        world.metadata.default_spawn = Some("missing".to_string());
+        assert_eq!(world.default_spawn().unwrap().name, "lobby", "unknown default falls back to the first");
+        world.metadata.default_spawn = Some("theater".to_string());
+
+        // Re-adding a name moves the spawn instead of duplicating it
+        world.add_spawn_point(SpawnPoint::new("lobby", ChartId(0), Point3::new(5.0, 1.0, 0.0)));
+        assert_eq!(world.spawn_points().len(), 2);
+        assert_eq!(world.spawn_point("lobby").unwrap().position, Point3::new(5.0, 1.0, 0.0));
+        assert_eq!(world.spawn_point("theater").unwrap().manifold_position().local.0, Point3::new(0.2, 0.0, 0.0));
+        assert!(world.spawn_point("roof").is_none());
+
+        let text = ron::ser::to_string(&world).unwrap();
+        let loaded: WorldResource = ron::from_str(&text).unwrap();
+        assert_eq!(loaded.spawn_points(), world.spawn_points());
+        assert_eq!(loaded.default_spawn().unwrap().orientation, facing_back);
+
+        assert_eq!(world.remove_spawn_point("theater").unwrap().chart, hyp);
+        assert_eq!(world.default_spawn().unwrap().name, "lobby");
+    }
+
+    #[test]
+    fn unnamed_spawn_points_from_older_worlds_still_load() {
+        let world = WorldResource::new(Manifold::new());
+        let text = ron::ser::to_string(&world).unwrap();
+        let old = text.replace("spawn_points:[]", "spawn_points:[((0),(x:1.0,y:2.0,z:3.0)),((0),(x:0.0,y:0.0,z:-4.0))]");
+        assert_ne!(old, text);
+
+        let loaded: WorldResource = ron::from_str(&old).unwrap();
+        let names: Vec<&str> = loaded.spawn_points().iter().map(|spawn| spawn.name.as_str()).collect();
+        assert_eq!(names, vec!["spawn_0", "spawn_1"]);
+        assert_eq!(loaded.spawn_point("spawn_1").unwrap().position, Point3::new(0.0, 0.0, -4.0));
+        assert_eq!(loaded.default_spawn().unwrap().position, Point3::new(1.0, 2.0, 3.0));
+
+        // Repeated names keep only the last spawn point
+        let mixed = text.replace(
+            "spawn_points:[]",
+            "spawn_points:[(name:\"a\",chart:(0),position:(x:1.0,y:0.0,z:0.0)),((0),(x:9.0,y:0.0,z:0.0)),(name:\"a\",chart:(0),position:(x:2.0,y:0.0,z:0.0))]",
+        );
+        let loaded: WorldResource = ron::from_str(&mixed).unwrap();
+        assert_eq!(loaded.spawn_points().len(), 2);
+        assert_eq!(loaded.spawn_point("a").unwrap().position, Point3::new(2.0, 0.0, 0.0));
+        assert_eq!(loaded.spawn_points()[1].name, "spawn_1");
+    }
+
+    struct TextLoader;
+
+    impl metatopia_engine::resources::AssetLoader for TextLoader {
+        type Asset = Box<dyn std::any::Any + Send + Sync>;
+
+        fn load(&self, path: &std::path::Path) -> Result<Self::Asset, Box<dyn std::error::Error>> {
+            Ok(Box::new(std::fs::read_to_string(path)?))
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt", "text"]
+        }
+    }
+
+    #[test]
+    fn registered_loader_handles_its_extensions() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("metatopia_loader_{}.text", std::process::id()));
+        std::fs::write(&path, "hello").unwrap();
+
+        let mut resources = ResourceManager::new();
+        resources.set_asset_path(&dir);
+        assert!(resources.load::<String>("greeting", path.file_name().unwrap().to_str().unwrap()).is_err());
+
+        resources.register_loader(TextLoader);
+        assert!(resources.has_loader("txt") && resources.has_loader("text"));
+        let handle = resources.load::<String>("greeting", path.file_name().unwrap().to_str().unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(*handle.read(), "hello");
+        assert!(resources.exists::<String>("greeting"));
+    }
+
+    #[test]
+    fn reload_updates_existing_handles() {
+        let dir = std::env::temp_dir();
+        let name = format!("metatopia_reload_{}.txt", std::process::id());
+        std::fs::write(dir.join(&name), "first").unwrap();
+
+        let mut resources = ResourceManager::new();
+        resources.set_asset_path(&dir);
+        resources.register_loader(TextLoader);
+        let handle = resources.load::<String>("note", &name).unwrap();
+        let again = resources.get::<String>("note").unwrap();
+
+        std::fs::write(dir.join(&name), "second").unwrap();
+        resources.reload::<String>("note").unwrap();
+        assert_eq!(*handle.read(), "second");
+        assert_eq!(*again.read(), "second");
+
+        // A failed reload keeps the old data
+        std::fs::remove_file(dir.join(&name)).unwrap();
+        assert!(resources.reload::<String>("note").is_err());
+        assert_eq!(*handle.read(), "second");
+    }
+
+    #[test]
+    fn collect_unused_frees_unreferenced_resources() {
+        let dir = std::env::temp_dir();
+        let name = format!("metatopia_unused_{}.txt", std::process::id());
+        std::fs::write(dir.join(&name), "screen").unwrap();
+
+        let mut resources = ResourceManager::new();
+        resources.set_asset_path(&dir);
+        resources.register_loader(TextLoader);
+        let loaded = resources.load::<String>("screen", &name).unwrap();
+        let _ = std::fs::remove_file(dir.join(&name));
+        let kept = resources.add("kept", 42u32);
+        let copy = loaded.clone();
+
+        assert_eq!(resources.strong_count::<String>("screen"), Some(3));
+        drop(loaded);
+        assert!(resources.collect_unused().is_empty());
+        assert_eq!(resources.strong_count::<String>("screen"), Some(2));
+
+        drop(copy);
+        assert_eq!(resources.strong_count::<String>("screen"), Some(1));
+        assert_eq!(resources.collect_unused(), vec!["screen".to_string()]);
+        assert!(!resources.exists::<String>("screen"));
+        assert_eq!(resources.strong_count::<String>("screen"), None);
+        assert!(resources.reload::<String>("screen").is_err());
+
+        assert!(resources.exists::<u32>("kept"));
+        assert_eq!(*kept.read(), 42);
+    }
+
+    struct NumberLoader;
+
+    impl metatopia_engine::resources::AssetLoader for NumberLoader {
+        type Asset = Box<dyn std::any::Any + Send + Sync>;
+
+        fn load(&self, path: &std::path::Path) -> Result<Self::Asset, Box<dyn std::error::Error>> {
+            Ok(Box::new(std::fs::read_to_string(path)?.trim().parse::<u32>()?))
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["num"]
+        }
+    }
+
+    #[test]
+    fn load_errors_can_be_matched_on() {
+        use metatopia_engine::ResourceError;
+
+        let root = std::env::temp_dir().join(format!("metatopia_errors_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("nested/folder.num")).unwrap();
+        std::fs::write(root.join("nested/seven.num"), "7").unwrap();
+        std::fs::write(root.join("word.num"), "seven").unwrap();
+        std::fs::write(root.join("blob.bin"), "").unwrap();
+
+        let mut resources = ResourceManager::new();
+        resources.set_asset_path(&root);
+        resources.register_loader(NumberLoader);
+
+        assert!(matches!(resources.load::<u32>("missing", "missing.num"), Err(ResourceError::NotFound(_))));
+        assert!(matches!(resources.load::<u32>("blob", "blob.bin"), Err(ResourceError::NoLoader(ext)) if ext == "bin"));
+        assert!(matches!(resources.load::<u32>("bare", "nested"), Err(ResourceError::NoLoader(ext)) if ext.is_empty()));
+        assert!(matches!(resources.load::<String>("seven", "nested/seven.num"), Err(ResourceError::TypeMismatch(_))));
+        assert!(matches!(resources.load::<u32>("folder", "nested/folder.num"), Err(ResourceError::Io(_))));
+        assert!(matches!(resources.load::<u32>("word", "word.num"), Err(ResourceError::Decode(_))));
+
+        // Paths may move around inside the asset root but never leave it
+        let escape = format!("../{}/word.num", root.file_name().unwrap().to_str().unwrap());
+        assert!(matches!(resources.load::<u32>("escape", &escape), Err(ResourceError::InvalidPath(_))));
+        assert!(matches!(resources.load::<u32>("deep", "nested/../../x.num"), Err(ResourceError::InvalidPath(_))));
+        let absolute = root.join("nested/seven.num");
+        assert!(matches!(resources.load::<u32>("absolute", absolute.to_str().unwrap()), Err(ResourceError::InvalidPath(_))));
+        assert_eq!(*resources.load::<u32>("seven", "nested/../nested/./seven.num").unwrap().read(), 7);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    /// Produces whatever number it's sent, so tests control when a load finishes
+    struct GatedLoader(std::sync::Mutex<std::sync::mpsc::Receiver<u32>>);
+
+    impl metatopia_engine::resources::AssetLoader for GatedLoader {
+        type Asset = Box<dyn std::any::Any + Send + Sync>;
+
+        fn load(&self, _path: &std::path::Path) -> Result<Self::Asset, Box<dyn std::error::Error>> {
+            Ok(Box::new(self.0.lock().unwrap().recv()?))
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["gate"]
+        }
+    }
+
+    #[test]
+    fn async_load_transitions_to_loaded() {
+        use metatopia_engine::resources::Loading;
+
+        let (send, receive) = std::sync::mpsc::channel();
+        let mut resources = ResourceManager::new();
+        resources.register_loader(GatedLoader(std::sync::Mutex::new(receive)));
+
+        let loading = resources.load_async::<u32>("answer", "answer.gate");
+        assert!(loading.read().is_pending());
+        assert!(!loading.is_loaded() && loading.poll().is_none());
+        assert!(resources.poll_loads().is_empty());
+
+        send.send(42).unwrap();
+        let start = std::time::Instant::now();
+        while !loading.is_loaded() {
+            assert!(start.elapsed() < std::time::Duration::from_secs(5), "load never finished");
+            std::thread::yield_now();
+        }
+        assert_eq!(*loading.poll().unwrap().read(), 42);
+        assert_eq!(*resources.get::<u32>("answer").unwrap().read(), 42);
+        assert_eq!(resources.poll_loads(), vec!["answer".to_string()]);
+
+        // Loading it again resolves immediately to the stored resource
+        assert!(resources.load_async::<u32>("answer", "answer.gate").is_loaded());
+
+        let wrong_type = resources.load_async::<String>("text", "text.gate");
+        send.send(7).unwrap();
+        while wrong_type.read().is_pending() {
+            std::thread::yield_now();
+        }
+        assert!(matches!(&*wrong_type.read(), Loading::Failed(ResourceError::TypeMismatch(_))));
+        assert!(matches!(&*resources.load_async::<u32>("up", "../up.gate").read(), Loading::Failed(ResourceError::InvalidPath(_))));
+    }
+
+    #[test]
+    fn async_loads_of_a_pending_id_are_shared_or_superseded() {
+        use metatopia_engine::resources::Loading;
+
+        let wait_for = |loading: &metatopia_engine::resources::ResourceHandle<Loading<u32>>| {
+            let start = std::time::Instant::now();
+            while loading.read().is_pending() {
+                assert!(start.elapsed() < std::time::Duration::from_secs(5), "load never finished");
+                std::thread::yield_now();
+            }
+        };
+        let (send, receive) = std::sync::mpsc::channel();
+        let mut resources = ResourceManager::new();
+        resources.register_loader(GatedLoader(std::sync::Mutex::new(receive)));
+
+        // A second request while pending gets the same handle and runs no second loader
+        let first = resources.load_async::<u32>("answer", "answer.gate");
+        let second = resources.load_async::<u32>("answer", "answer.gate");
+        assert!(std::sync::Arc::ptr_eq(&first.data, &second.data));
+        send.send(42).unwrap();
+        wait_for(&second);
+        assert_eq!(*second.poll().unwrap().read(), 42);
+        assert_eq!(resources.poll_loads(), vec!["answer".to_string()]);
+
+        // Adding the id while it loads keeps the added value
+        let loading = resources.load_async::<u32>("late", "late.gate");
+        resources.add("late", 1u32);
+        send.send(2).unwrap();
+        wait_for(&loading);
+        assert!(matches!(&*loading.read(), Loading::Failed(ResourceError::Superseded)));
+        assert_eq!(*resources.get::<u32>("late").unwrap().read(), 1);
+
+        // So does removing it, and a fresh load can then start
+        let loading = resources.load_async::<u32>("gone", "gone.gate");
+        assert!(resources.remove::<u32>("gone").is_none());
+        send.send(3).unwrap();
+        wait_for(&loading);
+        assert!(matches!(&*loading.read(), Loading::Failed(ResourceError::Superseded)));
+        assert!(!resources.exists::<u32>("gone"));
+        let again = resources.load_async::<u32>("gone", "gone.gate");
+        send.send(4).unwrap();
+        wait_for(&again);
+        assert_eq!(*resources.get::<u32>("gone").unwrap().read(), 4);
+        assert!(resources.poll_loads().contains(&"gone".to_string()));
+    }
+
+    #[cfg(feature = "hot-reload")]
+    #[test]
+    fn poll_reloads_picks_up_file_changes() {
+        let dir = std::env::temp_dir().join(format!("metatopia_hot_reload_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("note.txt"), "before").unwrap();
+
+        let mut resources = ResourceManager::new();
+        resources.set_asset_path(&dir);
+        resources.register_loader(TextLoader);
+        let handle = resources.load::<String>("note", "note.txt").unwrap();
+
+        std::fs::write(dir.join("note.txt"), "after").unwrap();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let mut reloaded = Vec::new();
+        while reloaded.is_empty() && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            reloaded = resources.poll_reloads();
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(reloaded, vec!["note".to_string()]);
+        assert_eq!(*handle.read(), "after");
+    }
+
+    #[cfg(feature = "gltf")]
+    const TRIANGLE_GLTF: &str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{
+            "byteLength": 44,
+            "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAAAAABAAIAAAA="
+        }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": 36 },
+            { "buffer": 0, "byteOffset": 36, "byteLength": 6 }
+        ],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+              "min": [0.0, 0.0, 0.0], "max": [1.0, 1.0, 0.0] },
+            { "bufferView": 1, "componentType": 5123, "count": 3, "type": "SCALAR" }
+        ],
+        "meshes": [{
+            "name": "triangle",
+            "primitives": [{ "attributes": { "POSITION": 0 }, "indices": 1 }]
+        }]
+    }"#;
+
+    #[cfg(feature = "gltf")]
+    #[test]
+    fn gltf_triangle_loads_into_mesh_resource() {
+        use metatopia_engine::resources::GltfModel;
+
+        let model = GltfModel::from_slice(TRIANGLE_GLTF.as_bytes()).unwrap();
+        let mesh = model.mesh("triangle").unwrap();
+        assert_eq!(mesh.vertices.len(), 3);
+        assert_eq!(mesh.indices, vec![0, 1, 2]);
+        assert_eq!(mesh.vertices[1].position, [1.0, 0.0, 0.0]);
+        // No normals in the file, so they are generated from the CCW face
+        for vertex in &mesh.vertices {
+            assert!((vertex.normal[2] - 1.0).abs() < 1e-5);
+        }
+
+        let dir = std::env::temp_dir();
+        let name = format!("metatopia_triangle_{}.gltf", std::process::id());
+        std::fs::write(dir.join(&name), TRIANGLE_GLTF).unwrap();
+        let mut resources = ResourceManager::new();
+        resources.set_asset_path(&dir);
+        let handle = resources.load::<GltfModel>("triangle", &name).unwrap();
+        let _ = std::fs::remove_file(dir.join(&name));
+        assert!(handle.read().mesh("triangle").is_some());
+    }
+
+    #[cfg(feature = "tobj")]
+    const CUBE_OBJ: &str = "\
+o cube
+v -1 -1 -1
+v 1 -1 -1
+v 1 1 -1
+v -1 1 -1
+v -1 -1 1
+v 1 -1 1
+v 1 1 1
+v -1 1 1
+f 1 4 3 2
+f 5 6 7 8
+f 1 2 6 5
+f 2 3 7 6
+f 3 4 8 7
+f 4 1 5 8
+";
+
+    #[cfg(feature = "tobj")]
+    #[test]
+    fn obj_cube_loads_into_mesh_resource() {
+        use cgmath::{InnerSpace, Vector3};
+        use metatopia_engine::resources::{MeshResource, ObjModel};
+
+        let model = ObjModel::from_slice(CUBE_OBJ.as_bytes()).unwrap();
+        let cube = model.mesh("cube").unwrap();
+        assert_eq!(cube.vertices.len(), 8);
+        assert_eq!(cube.indices.len(), 36, "six quads triangulate into twelve triangles");
+        // No normals in the file, so generated ones point outwards
+        for vertex in &cube.vertices {
+            let outward = Vector3::from(vertex.position).normalize();
+            assert!(Vector3::from(vertex.normal).dot(outward) > 0.5);
+        }
+
+        let dir = std::env::temp_dir();
+        let name = format!("metatopia_cube_{}.obj", std::process::id());
+        std::fs::write(dir.join(&name), CUBE_OBJ).unwrap();
+        let mut resources = ResourceManager::new();
+        resources.set_asset_path(&dir);
+        let handle = resources.load::<MeshResource>("cube", &name).unwrap();
+        let _ = std::fs::remove_file(dir.join(&name));
+        assert_eq!(handle.read().indices.len(), 36);
+    }
+}
+
+// ─── Portal Views ──────────────────────────────────────────────────────────
+
+mod portal_view_tests {
+    use super::*;
+    use metatopia_engine::graphics::{Camera, build_portal_views};
+
+    fn world_with_portal() -> (Manifold, ChartId, PortalId) {
+        let mut m = Manifold::new();
+        let target = m.add_chart(GeometryType::Hyperbolic);
+        let portal = m.create_portal(
+            ChartId(0),
+            target,
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(10.0, 0.0, 0.0),
+            Mat4::from_scale(1.0),
+        ).unwrap();
+        (m, target, portal)
+    }
+
+    #[test]
+    fn virtual_camera_for_visible_portal() {
+        let (m, target, portal) = world_with_portal();
+        let camera = Camera::new(ChartId(0), Point3::new(0.0, 0.0, 5.0), Point3::new(0.0, 0.0, 0.0), 1.0);
+
+        let views = build_portal_views(&m, &camera, 1);
+        assert_eq!(views.len(), 1);
+
+        let view = &views[0];
+        assert_eq!(view.portal_id, portal);
+        assert_eq!(view.chart_id, target);
+        assert_eq!(view.depth, 1);
+        assert!(view.parent.is_none());
+        assert!((view.eye - Point3::new(10.0, 0.0, 5.0)).magnitude() < 1e-5);
+        assert!((view.forward - Vector3::new(0.0, 0.0, -1.0)).magnitude() < 1e-5);
+
+        let expected = m.portal(portal).unwrap().get_view_matrix(Point3::new(0.0, 0.0, 5.0));
+        assert_eq!(view.view_matrix, expected);
+        assert_eq!(view.parent_view_projection, camera.view_projection());
+    }
+
+    #[test]
+    fn portal_behind_camera_or_disabled_is_skipped() {
+        let (mut m, _, portal) = world_with_portal();
+        let looking_away = Camera::new(ChartId(0), Point3::new(0.0, 0.0, 5.0), Point3::new(0.0, 0.0, 10.0), 1.0);
+        assert!(build_portal_views(&m, &looking_away, 1).is_empty());
+
+        let behind = Camera::new(ChartId(0), Point3::new(0.0, 0.0, -5.0), Point3::new(0.0, 0.0, 0.0), 1.0);
+        assert!(build_portal_views(&m, &behind, 1).is_empty());
+
+        let camera = Camera::new(ChartId(0), Point3::new(0.0, 0.0, 5.0), Point3::new(0.0, 0.0, 0.0), 1.0);
+        assert!(build_portal_views(&m, &camera, 0).is_empty());
+
+        m.portal_mut(portal).unwrap().set_active(false);
+        assert!(build_portal_views(&m, &camera, 1).is_empty());
+    }
+
+    #[test]
+    fn recursion_depth_is_bounded() {
+        let (mut m, target, _) = world_with_portal();
+        // A second portal in the target chart, in front of the virtual eye
+        m.create_portal(target, ChartId(0), Point3::new(10.0, 0.0, -5.0), Point3::new(0.0, 0.0, 0.0), Mat4::from_scale(1.0)).unwrap();
+        let camera = Camera::new(ChartId(0), Point3::new(0.0, 0.0, 5.0), Point3::new(0.0, 0.0, 0.0), 1.0);
+
+        assert_eq!(build_portal_views(&m, &camera, 1).len(), 1);
+
+        let views = build_portal_views(&m, &camera, 2);
+        assert_eq!(views.len(), 2);
+        assert_eq!(views[1].depth, 2);
+        assert_eq!(views[1].parent, Some(0));
+        assert_eq!(views[1].chart_id, ChartId(0));
+    }
+}
+
+// ─── Camera ────────────────────────────────────────────────────────────────
+
+mod camera_tests {
+    use super::*;
+    use metatopia_engine::graphics::Camera;
+
+    #[test]
+    fn gaze_ray_matches_forward() {
+        let camera = Camera::new(ChartId(0), Point3::new(1.0, 2.0, 5.0), Point3::new(1.0, 2.0, 0.0), 16.0 / 9.0);
+        let ray = camera.gaze_ray();
+        assert!((ray.direction - camera.forward()).magnitude() < 1e-4);
+        assert!((ray.direction - Vector3::new(0.0, 0.0, -1.0)).magnitude() < 1e-4);
+        // Starts on the near plane in front of the eye
+        assert!((ray.origin - Point3::new(1.0, 2.0, 5.0 - camera.znear)).magnitude() < 1e-3);
+    }
+
+    #[test]
+    fn screen_ray_edges_follow_fov() {
+        let camera = Camera::new(ChartId(0), Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, -1.0), 1.0);
+        let top = camera.screen_ray(0.0, 1.0);
+        let half_fov = top.direction.y.atan2(-top.direction.z);
+        assert!((half_fov - camera.fovy.0 / 2.0).abs() < 1e-3);
+
+        let right = camera.screen_ray(1.0, 0.0);
+        assert!(right.direction.x > 0.0);
+        assert!(right.direction.y.abs() < 1e-4);
+    }
+
+    #[test]
+    fn fov_scales_and_limits_shape_the_projection() {
+        let mut manifold = Manifold::new();
+        let h = manifold.add_chart(GeometryType::Hyperbolic);
+        let s = manifold.add_chart(GeometryType::Spherical);
+        let mut camera = Camera::new(h, Point3::new(0.0, 0.0, 0.5), Point3::new(0.0, 0.0, 0.0), 1.0);
+        camera.update(&manifold);
+        assert!((camera.projection_fovy().0 - camera.fovy.0 * 1.5).abs() < 1e-6, "default hyperbolic scale");
+
+        camera.set_fov_scales(2.0, 0.5);
+        let expected = cgmath::perspective(Rad(camera.fovy.0 * 2.0), 1.0, camera.znear, camera.zfar);
+        assert_eq!(camera.projection_matrix, expected);
+
+        camera.set_fov_limits(Rad(0.1), Rad(1.0));
+        assert_eq!(camera.projection_fovy(), Rad(1.0), "90° is capped at max_fov");
+        assert_eq!(camera.projection_matrix, cgmath::perspective(Rad(1.0), 1.0, camera.znear, camera.zfar));
+
+        camera.set_position(s, Point3::new(0.0, 0.0, 1.0));
+        camera.update(&manifold);
+        assert!((camera.projection_fovy().0 - camera.fovy.0 * 0.5).abs() < 1e-6, "max_fov only caps hyperbolic charts");
+        camera.set_fov_limits(Rad(1.0), Rad(1.0));
+        assert_eq!(camera.projection_fovy(), Rad(1.0), "45° is floored at min_fov");
+
+        camera.set_position(h, Point3::new(0.0, 0.0, 0.5));
+        camera.update(&manifold);
+        camera.set_fov_scales(0.1, 0.5);
+        assert!((camera.projection_fovy().0 - camera.fovy.0 * 0.1).abs() < 1e-6, "min_fov only floors spherical charts");
+        camera.set_position(ChartId(0), Point3::new(0.0, 0.0, 1.0));
+        camera.update(&manifold);
+        assert_eq!(camera.projection_fovy(), camera.fovy, "flat charts ignore the scales");
+    }
+
+    #[test]
+    fn orbit_controller_rotates_around_focus() {
+        use metatopia_engine::OrbitCameraController;
+        use metatopia_engine::input::{InputEvent, InputManager, MouseButton};
+
+        let focus = Point3::new(1.0, 0.0, -2.0);
+        let mut camera = Camera::new(ChartId(0), Point3::new(0.0, 0.0, 0.0), focus, 1.0);
+        let mut orbit = OrbitCameraController::new(focus, 5.0).with_distance_limits(1.0, 10.0);
+
+        orbit.apply(&mut camera);
+        assert!((camera.position.local.to_point() - Point3::new(1.0, 0.0, 3.0)).magnitude() < 1e-5);
+        assert_eq!(camera.target, focus);
+
+        // A quarter turn swings the camera from +Z to +X of the focus
+        orbit.rotate(Rad(std::f32::consts::FRAC_PI_2), Rad(0.0));
+        orbit.apply(&mut camera);
+        assert!((camera.position.local.to_point() - Point3::new(6.0, 0.0, -2.0)).magnitude() < 1e-5);
+        assert!((camera.forward() - Vector3::new(-1.0, 0.0, 0.0)).magnitude() < 1e-5);
+
+        orbit.rotate(Rad(0.0), Rad(10.0));
+        assert_eq!(orbit.pitch, OrbitCameraController::MAX_PITCH, "pitch is clamped");
+        orbit.zoom(100.0);
+        assert_eq!(orbit.distance, 1.0);
+
+        // Wheel events from the input manager zoom out
+        let mut input = InputManager::new();
+        input.process_event(InputEvent::MouseWheel(-4.0));
+        orbit.update(&mut camera, &input);
+        assert_eq!(orbit.distance, 3.0);
+        assert!(((camera.position.local.to_point() - focus).magnitude() - 3.0).abs() < 1e-4);
+        input.poll_events();
+
+        // Dragging only rotates while the drag button is held
+        let yaw = orbit.yaw;
+        input.process_event(InputEvent::MouseMoved(10.0, 0.0));
+        orbit.update(&mut camera, &input);
+        assert_eq!(orbit.yaw, yaw);
+        input.poll_events();
+        input.process_event(InputEvent::MouseButtonPressed(MouseButton::Left));
+        input.process_event(InputEvent::MouseMoved(20.0, 0.0));
+        orbit.update(&mut camera, &input);
+        assert!(orbit.yaw.0 < yaw.0);
+    }
+
+    #[test]
+    fn move_towards_converges_to_target_pose() {
+        use metatopia_engine::CameraRig;
+
+        let mut camera = Camera::new(ChartId(0), Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, -1.0), 1.0);
+        let goal = ManifoldPosition::new(ChartId(0), Point3::new(4.0, 2.0, 0.0));
+        let look = Point3::new(4.0, 2.0, 10.0);
+
+        let mut previous = f32::INFINITY;
+        for _ in 0..120 {
+            camera.move_towards(goal, look, 5.0, 1.0 / 60.0);
+            let remaining = (camera.position.local.to_point() - Point3::new(4.0, 2.0, 0.0)).magnitude();
+            assert!(remaining < previous, "approaches monotonically");
+            previous = remaining;
+        }
+        assert!(previous < 0.01);
+        assert!((camera.forward() - Vector3::new(0.0, 0.0, 1.0)).magnitude() < 0.01);
+        assert!((camera.target - look).magnitude() < 0.1);
+
+        // A goal in another chart is snapped to
+        let mut rig = CameraRig::new(&camera, 5.0);
+        rig.set_pose(ManifoldPosition::new(ChartId(1), Point3::new(0.0, 0.0, 0.0)), Point3::new(1.0, 0.0, 0.0));
+        assert!(!rig.is_settled(&camera, 0.01));
+        rig.update(&mut camera, 1.0 / 60.0);
+        assert_eq!(camera.position.chart_id, ChartId(1));
+        assert!(rig.is_settled(&camera, 1e-6));
+    }
+
+    #[test]
+    fn move_towards_a_degenerate_look_keeps_the_direction() {
+        let mut camera = Camera::new(ChartId(0), Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, -1.0), 1.0);
+        let goal = ManifoldPosition::new(ChartId(0), Point3::new(2.0, 0.0, 0.0));
+        // The goal look-at point sits on the goal position
+        camera.move_towards(goal, Point3::new(2.0, 0.0, 0.0), 5.0, 1.0 / 60.0);
+        let eye = camera.position.local.to_point();
+        assert!(eye.x > 0.0 && eye.x.is_finite());
+        assert_eq!(camera.target - eye, Vector3::new(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn geodesic_move_stays_inside_the_poincare_disk() {
+        let mut m = Manifold::new();
+        let disk = m.add_chart(GeometryType::Hyperbolic);
+        let mut camera = Camera::new(disk, Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0), 1.0);
+
+        // Hyperbolic distance 3 from the centre is Euclidean radius tanh(3/2) in the disk
+        camera.move_geodesic(Vector3::unit_x(), 3.0, &m);
+        let reached = camera.position.local.to_point();
+        assert!(reached.x < 1.0, "left the disk at {:?}", reached);
+        assert!((reached.x - 1.5f32.tanh()).abs() < 1e-2, "reached {:?}", reached);
+        assert!(reached.y.abs() < 1e-4 && reached.z.abs() < 1e-4);
+        assert!((camera.target - reached).x > 0.0, "still looking along the direction of travel");
+
+        // Repeated moves approach the boundary without crossing it
+        for _ in 0..5 {
+            camera.move_geodesic(Vector3::unit_x(), 0.5, &m);
+        }
+        let reached = camera.position.local.to_point();
+        assert!(reached.x > 0.95 && reached.x < 1.0, "reached {:?}", reached);
+
+        // Flat charts move in a straight line
+        let mut flat = Camera::new(ChartId(0), Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, -1.0), 1.0);
+        flat.move_geodesic(Vector3::new(0.0, 0.0, -2.0), 3.0, &m);
+        assert!((flat.position.local.to_point() - Point3::new(0.0, 0.0, -3.0)).magnitude() < 1e-5);
+        assert!((flat.target - Point3::new(0.0, 0.0, -4.0)).magnitude() < 1e-5);
+    }
+
+    #[test]
+    fn geodesic_move_follows_great_circles_on_spheres() {
+        let mut m = Manifold::new();
+        let sphere = m.add_chart(GeometryType::Spherical);
+        let radius = m.chart(sphere).unwrap().metric().parameters.radius;
+        let start = Point3::new(radius, 0.0, 0.0);
+        let mut camera = Camera::new(sphere, start, start + Vector3::unit_y(), 1.0);
+
+        // A quarter of the circumference
+        camera.move_geodesic(Vector3::unit_y(), radius * std::f32::consts::FRAC_PI_2, &m);
+        let reached = camera.position.local.to_point();
+        assert!((reached - Point3::new(0.0, radius, 0.0)).magnitude() < 1e-4, "reached {:?}", reached);
+        let look = (camera.target - reached).normalize();
+        assert!((look - -Vector3::unit_x()).magnitude() < 1e-4, "look turned with the path: {:?}", look);
+    }
+
+    #[test]
+    fn resize_event_updates_aspect_ratio() {
+        let mut camera = Camera::new(ChartId(0), Point3::new(0.0, 0.0, 5.0), Point3::new(0.0, 0.0, 0.0), 16.0 / 9.0);
+        let before = camera.projection_matrix;
+
+        camera.handle_window_event(&WindowEvent::Resized(600, 600));
+        assert_eq!(camera.aspect, 1.0);
+        assert_ne!(camera.projection_matrix, before, "projection is rebuilt");
+
+        camera.handle_window_event(&WindowEvent::Resized(0, 0));
+        assert_eq!(camera.aspect, 1.0, "minimizing keeps the last aspect ratio");
+        camera.handle_window_event(&WindowEvent::Focused(false));
+        assert_eq!(camera.aspect, 1.0);
+    }
+
+    #[test]
+    fn frustum_culls_boxes() {
+        use metatopia_engine::math::BoundingBox;
+
+        let camera = Camera::new(ChartId(0), Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, -1.0), 1.0);
+        let frustum = camera.frustum();
+
+        let inside = BoundingBox::new(Point3::new(-1.0, -1.0, -11.0), Point3::new(1.0, 1.0, -9.0));
+        assert!(frustum.intersects_aabb(&inside));
+
+        let behind = BoundingBox::new(Point3::new(-1.0, -1.0, 5.0), Point3::new(1.0, 1.0, 7.0));
+        assert!(!frustum.intersects_aabb(&behind));
+
+        let beside = BoundingBox::new(Point3::new(50.0, -1.0, -11.0), Point3::new(52.0, 1.0, -9.0));
+        assert!(!frustum.intersects_aabb(&beside));
+
+        let straddling_near = BoundingBox::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0));
+        assert!(frustum.intersects_aabb(&straddling_near));
+
+        assert!(frustum.contains_point(Point3::new(0.0, 0.0, -10.0)));
+        assert!(!frustum.contains_point(Point3::new(0.0, 0.0, -0.01)), "in front of the near plane");
+        assert!(frustum.intersects_sphere(Point3::new(0.0, 0.0, 1.0), 1.5));
+        assert!(!frustum.intersects_sphere(Point3::new(0.0, 0.0, 5.0), 1.0));
+    }
+}
+
+// ─── Input ─────────────────────────────────────────────────────────────────
+
+mod input_tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn click(input: &mut InputManager, at: Instant) {
+        input.tick(at);
+        input.process_event(InputEvent::MouseButtonPressed(MouseButton::Left));
+        input.process_event(InputEvent::MouseButtonReleased(MouseButton::Left));
+    }
+
+    #[test]
+    fn quick_clicks_double_click() {
+        let mut input = InputManager::new();
+        let t0 = Instant::now();
+        let window = Duration::from_millis(300);
+
+        click(&mut input, t0);
+        assert!(!input.mouse_double_click(MouseButton::Left, window), "single click");
+        click(&mut input, t0 + Duration::from_millis(150));
+        assert!(input.mouse_double_click(MouseButton::Left, window));
+
+        input.tick(t0 + Duration::from_millis(160));
+        assert!(!input.mouse_double_click(MouseButton::Left, window), "fires only on the second press");
+    }
+
+    #[test]
+    fn polling_advances_the_clock_without_tick() {
+        let mut input = InputManager::new();
+        input.process_event(InputEvent::KeyPressed(KeyCode::W));
+        input.poll_events();
+        std::thread::sleep(Duration::from_millis(20));
+        input.poll_events();
+        assert!(input.key_held_for(KeyCode::W).unwrap() >= Duration::from_millis(20));
+
+        let press = |input: &mut InputManager| {
+            input.process_event(InputEvent::MouseButtonPressed(MouseButton::Left));
+            input.process_event(InputEvent::MouseButtonReleased(MouseButton::Left));
+        };
+        press(&mut input);
+        input.poll_events();
+        assert!(!input.mouse_double_click(MouseButton::Left, Duration::from_secs(1)), "press was last frame");
+        std::thread::sleep(Duration::from_millis(30));
+        input.poll_events();
+        press(&mut input);
+        assert!(!input.mouse_double_click(MouseButton::Left, Duration::from_millis(10)), "too slow for the window");
+        assert!(input.mouse_double_click(MouseButton::Left, Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn scroll_accumulates_until_events_are_polled() {
+        use winit::dpi::PhysicalPosition;
+        use winit::event::MouseScrollDelta;
+        use metatopia_engine::input::PIXELS_PER_LINE;
+
+        let mut input = InputManager::new();
+        assert_eq!(input.scroll_delta(), 0.0);
+        input.process_event(InputEvent::MouseWheel(1.0));
+        input.process_event(MouseScrollDelta::LineDelta(0.0, -0.5).into());
+        input.process_event(MouseScrollDelta::PixelDelta(PhysicalPosition::new(0.0, 2.0 * PIXELS_PER_LINE as f64)).into());
+        assert_eq!(input.scroll_delta(), 2.5);
+        assert_eq!(input.pending_events().len(), 3, "wheel events are still queued");
+
+        input.poll_events();
+        assert_eq!(input.scroll_delta(), 0.0);
+        input.process_event(InputEvent::MouseWheel(-1.0));
+        assert_eq!(input.scroll_delta(), -1.0);
+    }
+
+    #[test]
+    fn raw_mouse_motion_accumulates_while_cursor_is_grabbed() {
+        use cgmath::{Point2, Vector2};
+        use winit::event::DeviceEvent;
+
+        let mut input = InputManager::new();
+        input.process_event(InputEvent::MouseMotionRaw(3.0, -1.0));
+        input.process_event(InputEvent::MouseMotionRaw(2.5, 4.0));
+        let from_winit = InputEvent::from_device_event(&DeviceEvent::MouseMotion { delta: (-0.5, 1.0) }).unwrap();
+        input.process_event(from_winit);
+        assert!(InputEvent::from_device_event(&DeviceEvent::Added).is_none());
+
+        assert_eq!(input.raw_mouse_delta(), Vector2::new(5.0, 4.0));
+        // A grabbed cursor doesn't move, so the absolute delta stays put
+        assert_eq!(input.mouse_delta(), Vector2::new(0.0, 0.0));
+        assert_eq!(input.mouse_position(), Point2::new(0.0, 0.0));
+
+        input.poll_events();
+        assert_eq!(input.raw_mouse_delta(), Vector2::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn slow_clicks_do_not_double_click() {
+        let mut input = InputManager::new();
+        let t0 = Instant::now();
+        let window = Duration::from_millis(300);
+
+        click(&mut input, t0);
+        click(&mut input, t0 + Duration::from_millis(600));
+        assert!(!input.mouse_double_click(MouseButton::Left, window));
+        assert!(!input.mouse_double_click(MouseButton::Right, window));
+    }
+
+    #[test]
+    fn key_hold_duration() {
+        let mut input = InputManager::new();
+        let t0 = Instant::now();
+
+        input.tick(t0);
+        assert!(input.key_held_for(KeyCode::Space).is_none());
+        input.process_event(InputEvent::KeyPressed(KeyCode::Space));
+
+        input.tick(t0 + Duration::from_millis(500));
+        // Key repeat must not restart the hold
+        input.process_event(InputEvent::KeyPressed(KeyCode::Space));
+        input.tick(t0 + Duration::from_millis(1200));
+        assert_eq!(input.key_held_for(KeyCode::Space), Some(Duration::from_millis(1200)));
+
+        input.process_event(InputEvent::KeyReleased(KeyCode::Space));
+        assert!(input.key_held_for(KeyCode::Space).is_none());
+    }
+
+    #[test]
+    fn action_axis_from_keys_gamepad_and_both() {
+        use metatopia_engine::input::InputAction;
+
+        let action = InputAction::new("move_forward")
+            .with_key_axis(KeyCode::W, KeyCode::S)
+            .with_axis(GamepadAxis::LeftStickY);
+
+        let mut input = InputManager::new();
+        assert_eq!(action.axis_value(&input), 0.0);
+
+        input.process_event(InputEvent::KeyPressed(KeyCode::W));
+        assert_eq!(action.axis_value(&input), 1.0);
+        input.process_event(InputEvent::KeyPressed(KeyCode::S));
+        assert_eq!(action.axis_value(&input), 0.0, "opposing keys cancel");
+        input.process_event(InputEvent::KeyReleased(KeyCode::W));
+        assert_eq!(action.axis_value(&input), -1.0);
+        input.process_event(InputEvent::KeyReleased(KeyCode::S));
+
+        input.process_event(InputEvent::GamepadAxisMoved(GamepadAxis::LeftStickY, 0.1));
+        assert_eq!(action.axis_value(&input), 0.0, "inside deadzone");
+        input.process_event(InputEvent::GamepadAxisMoved(GamepadAxis::LeftStickY, -1.0));
+        assert!((action.axis_value(&input) + 1.0).abs() < 1e-6);
+        input.process_event(InputEvent::GamepadAxisMoved(GamepadAxis::LeftStickY, 0.575));
+        assert!((action.axis_value(&input) - 0.5).abs() < 1e-4);
+
+        input.process_event(InputEvent::KeyPressed(KeyCode::W));
+        assert_eq!(action.axis_value(&input), 1.0, "combined value is clamped");
+    }
+
+    #[test]
+    fn input_map_loads_and_rebinds() {
+        use metatopia_engine::input::{Binding, InputAction, InputMap};
+
+        let defaults = InputMap::new()
+            .with_action(InputAction::new("jump").with_key(KeyCode::Space))
+            .with_action(InputAction::new("fire").with_mouse_button(MouseButton::Left));
+
+        let mut map = InputMap::from_ron(r#"{
+            "jump": (keys: [J]),
+            "move": (key_axes: [(W, S)]),
+        }"#).unwrap();
+        assert_eq!(map.action("jump").unwrap().name, "jump");
+
+        let mut merged = defaults.clone();
+        merged.merge(map.clone());
+
+        let mut input = InputManager::new();
+        input.process_event(InputEvent::KeyPressed(KeyCode::Space));
+        assert!(defaults.pressed("jump", &input));
+        assert!(!merged.pressed("jump", &input), "file overrides the default binding");
+        input.process_event(InputEvent::KeyPressed(KeyCode::J));
+        assert!(merged.pressed("jump", &input));
+        assert!(!merged.pressed("fire", &input), "untouched defaults survive the merge");
+        assert!(!merged.pressed("missing", &input));
+
+        map.unbind("jump", Binding::Key(KeyCode::J));
+        map.bind("jump", Binding::Key(KeyCode::W));
+        let conflicts = map.conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].0, Binding::Key(KeyCode::W));
+        assert_eq!(conflicts[0].1, vec!["jump".to_string(), "move".to_string()]);
+
+        let reloaded = InputMap::from_ron(&map.to_ron().unwrap()).unwrap();
+        assert_eq!(reloaded.action("jump").unwrap().keys, vec![KeyCode::W]);
+    }
+
+    #[test]
+    fn recorded_frames_replay_identically() {
+        let frames: Vec<Vec<InputEvent>> = vec![
+            vec![InputEvent::KeyPressed(KeyCode::W), InputEvent::MouseMoved(10.0, 20.0)],
+            vec![
+                InputEvent::MouseButtonPressed(MouseButton::Left),
+                InputEvent::GamepadAxisMoved(GamepadAxis::LeftStickX, 0.5),
+                InputEvent::MouseWheel(2.0),
+            ],
+            vec![InputEvent::KeyReleased(KeyCode::W), InputEvent::MouseMotionRaw(3.0, -1.0)],
+        ];
+        let query = |input: &InputManager| (
+            input.is_key_pressed(KeyCode::W),
+            input.is_mouse_button_pressed(MouseButton::Left),
+            input.gamepad_axis(GamepadAxis::LeftStickX),
+            input.mouse_position(),
+            input.mouse_delta(),
+            input.raw_mouse_delta(),
+            input.scroll_delta(),
+        );
+
+        let mut live = InputManager::new();
+        let mut recorder = InputRecorder::new();
+        let mut expected = Vec::new();
+        let t0 = Instant::now();
+        for (i, events) in frames.into_iter().enumerate() {
+            live.tick(t0 + Duration::from_millis(16 * i as u64));
+            for event in events {
+                live.process_event(event);
+            }
+            recorder.record(&live);
+            expected.push(query(&live));
+            live.poll_events();
+        }
+        assert_eq!(recorder.len(), 3);
+
+        let saved = ron::to_string(&recorder).unwrap();
+        let loaded: InputRecorder = ron::from_str(&saved).unwrap();
+        assert_eq!(loaded.frames(), recorder.frames());
+
+        let mut replayed = InputManager::new();
+        replayed.start_replay(loaded.frames().to_vec());
+        for (i, want) in expected.iter().enumerate() {
+            replayed.tick(t0 + Duration::from_millis(16 * i as u64));
+            replayed.process_event(InputEvent::KeyPressed(KeyCode::Q));
+            assert!(!replayed.is_key_pressed(KeyCode::Q), "live events are ignored during replay");
+            assert_eq!(&query(&replayed), want, "frame {i}");
+            replayed.poll_events();
+        }
+
+        replayed.tick(t0 + Duration::from_millis(64));
+        assert!(!replayed.is_replaying());
+        replayed.process_event(InputEvent::KeyPressed(KeyCode::Q));
+        assert!(replayed.is_key_pressed(KeyCode::Q), "live input resumes after the log runs out");
+    }
+
+    #[test]
+    fn replay_advances_with_poll_events_when_not_ticked() {
+        let mut live = InputManager::new();
+        let mut recorder = InputRecorder::new();
+        for x in [1.0, 2.0, 3.0] {
+            live.process_event(InputEvent::MouseMoved(x, 0.0));
+            live.process_event(InputEvent::MouseWheel(x));
+            recorder.record(&live);
+            live.poll_events();
+        }
+
+        let mut replayed = InputManager::new();
+        replayed.start_replay(recorder.frames().to_vec());
+        let mut positions = Vec::new();
+        for _ in 0..3 {
+            replayed.poll_events();
+            positions.push((replayed.mouse_position().x, replayed.scroll_delta()));
+        }
+        assert_eq!(positions, vec![(1.0, 1.0), (2.0, 2.0), (3.0, 3.0)]);
+        assert!(replayed.is_replaying());
+        replayed.poll_events();
+        assert!(!replayed.is_replaying());
+    }
+}
+
+// ─── Window ────────────────────────────────────────────────────────────────
+
+mod window_tests {
+    use super::*;
+
+    #[test]
+    fn builder_stores_the_window_icon() {
+        let pixels: Vec<u8> = (0..2 * 2 * 4).map(|i| i as u8).collect();
+        let builder = WindowBuilder::new().with_icon(&pixels, 2, 2);
+        let icon = builder.icon().unwrap();
+        assert_eq!(icon, &WindowIcon { rgba: pixels.clone(), width: 2, height: 2 });
+        assert!(icon.to_winit().is_ok());
+        assert!(WindowBuilder::new().icon().is_none());
+
+        // The size has to match the pixels
+        let wrong = WindowIcon { rgba: pixels, width: 3, height: 2 };
+        assert!(wrong.to_winit().is_err());
+
+        let path = std::env::temp_dir().join(format!("metatopia_icon_{}.png", std::process::id()));
+        image::RgbaImage::from_pixel(4, 3, image::Rgba([10, 20, 30, 255])).save(&path).unwrap();
+        let from_file = WindowBuilder::new().with_icon_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        let icon = from_file.icon().unwrap();
+        assert_eq!((icon.width, icon.height), (4, 3));
+        assert_eq!(&icon.rgba[..4], &[10, 20, 30, 255]);
+        assert!(WindowBuilder::new().with_icon_file("does/not/exist.png").is_err());
+    }
+
+    #[test]
+    fn clipboard_round_trips_text_when_available() {
+        use metatopia_engine::window::clipboard;
+
+        let text = format!("metatopia clipboard {}", std::process::id());
+        if let Err(e) = clipboard::set_text(&text) {
+            eprintln!("No clipboard available ({}); skipping", e);
+            return;
+        }
+        assert_eq!(clipboard::get_text().as_deref(), Some(text.as_str()));
+    }
+
+    #[test]
+    fn cursor_icons_map_to_winit() {
+        use winit::window::CursorIcon as Winit;
+
+        assert_eq!(Winit::from(CursorIcon::default()), Winit::Default);
+        assert_eq!(Winit::from(CursorIcon::Pointer), Winit::Pointer);
+        assert_eq!(Winit::from(CursorIcon::Crosshair), Winit::Crosshair);
+        assert_eq!(Winit::from(CursorIcon::Grabbing), Winit::Grabbing);
+        assert_eq!(Winit::from(CursorIcon::NwseResize), Winit::NwseResize);
+    }
+    struct ScoreInTitle {
+        score: u32,
+    }
+
+    impl GameState for ScoreInTitle {
+        fn on_init(&mut self, _engine: &mut Engine) {}
+
+        fn on_update(&mut self, engine: &mut Engine, _dt: f32) {
+            self.score += 10;
+            let title = format!("Score: {}", self.score);
+            engine.window_mut().set_title(&title);
+        }
+    }
+
+    #[test]
+    fn engine_window_title_can_be_set_from_the_game() {
+        let config = EngineConfig::builder().with_title("Portals").with_dimensions(640, 480).build().unwrap();
+        let mut engine = Engine::headless(config);
+        assert!(engine.window().is_headless());
+        assert_eq!(engine.window().title(), "Portals");
+        assert_eq!(engine.window().dimensions(), (640, 480));
+        assert!(engine.window().window_arc().is_none(), "nothing to render to");
+
+        let mut game = ScoreInTitle { score: 0 };
+        engine.tick(&mut game, 0.016);
+        engine.tick(&mut game, 0.016);
+        assert_eq!(engine.window().title(), "Score: 20");
+
+        assert!(!engine.window().is_fullscreen());
+        engine.window_mut().set_fullscreen(true);
+        assert!(engine.window().is_fullscreen());
+    }
+}
+
+// ─── Renderer ──────────────────────────────────────────────────────────────
+
+mod renderer_tests {
+    use metatopia_engine::graphics::supported_sample_count;
+    use wgpu::TextureFormatFeatureFlags as Flags;
+
+    #[test]
+    fn sample_count_is_clamped_to_supported_value() {
+        let up_to_4 = Flags::MULTISAMPLE_X2 | Flags::MULTISAMPLE_X4;
+        assert_eq!(supported_sample_count(4, up_to_4), 4);
+        assert_eq!(supported_sample_count(8, up_to_4), 4);
+        assert_eq!(supported_sample_count(3, up_to_4), 2);
+        assert_eq!(supported_sample_count(1, up_to_4), 1);
+        assert_eq!(supported_sample_count(0, up_to_4), 1);
+        assert_eq!(supported_sample_count(8, Flags::empty()), 1);
+        assert_eq!(supported_sample_count(16, Flags::MULTISAMPLE_X8), 8);
+    }
+
+    #[test]
+    fn unsupported_present_mode_falls_back_to_fifo() {
+        use metatopia_engine::graphics::resolve_present_mode;
+        use wgpu::PresentMode;
+
+        let supported = [PresentMode::Fifo, PresentMode::Mailbox];
+        assert_eq!(resolve_present_mode(PresentMode::Mailbox, &supported), PresentMode::Mailbox);
+        assert_eq!(resolve_present_mode(PresentMode::Immediate, &supported), PresentMode::Fifo);
+        assert_eq!(resolve_present_mode(PresentMode::Immediate, &[]), PresentMode::Fifo);
+    }
+
+    #[test]
+    fn clear_color_defaults_and_converts_to_wgpu() {
+        use metatopia_engine::graphics::DEFAULT_CLEAR_COLOR;
+        use metatopia_engine::Color;
+
+        assert_eq!(DEFAULT_CLEAR_COLOR, Color::new(0.1, 0.2, 0.3, 1.0));
+
+        let clear: wgpu::Color = Color::new(0.25, 0.5, 0.75, 1.0).into();
+        assert_eq!(clear, wgpu::Color { r: 0.25, g: 0.5, b: 0.75, a: 1.0 });
+        assert_eq!(wgpu::Color::from(Color::BLACK), wgpu::Color::BLACK);
+    }
+
+    #[test]
+    fn instance_data_layout() {
+        use metatopia_engine::graphics::{Instance, InstanceRaw};
+
+        let instances: Vec<Instance> = (0..20)
+            .map(|i| Instance::new(metatopia_engine::Mat4::from_translation(cgmath::Vector3::new(i as f32, 0.0, 0.0))))
+            .collect();
+        let raw = InstanceRaw::from_instances(&instances);
+        let bytes: &[u8] = bytemuck::cast_slice(&raw);
+
+        assert_eq!(std::mem::size_of::<InstanceRaw>(), 80);
+        assert_eq!(bytes.len(), 20 * 80);
+        assert_eq!(raw[7].model[3], [7.0, 0.0, 0.0, 1.0], "translation is the last column");
+        assert_eq!(raw[7].color, [1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(InstanceRaw::desc().step_mode, wgpu::VertexStepMode::Instance);
+    }
+
+    /// A device from any available adapter, or `None` on machines without one
+    fn test_device() -> Option<(wgpu::Device, wgpu::Queue)> {
+        let instance = wgpu::Instance::default();
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            force_fallback_adapter: false,
+            compatible_surface: None,
+            power_preference: wgpu::PowerPreference::LowPower,
+        }))?;
+        pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None)).ok()
+    }
+
+    #[test]
+    fn mesh_indices_address_more_than_u16_vertices() {
+        use metatopia_engine::graphics::{Mesh, Vertex};
+        use metatopia_engine::resources::MeshResource;
+
+        // 300 x 300 grid of vertices, two triangles per cell
+        let side = 300u32;
+        let vertices: Vec<Vertex> = (0..side * side)
+            .map(|i| Vertex::new([(i % side) as f32, (i / side) as f32, 0.0], [0.0, 0.0], [0.0, 0.0, 1.0], [1.0; 4]))
+            .collect();
+        let indices: Vec<u32> = (0..side - 1)
+            .flat_map(|y| (0..side - 1).map(move |x| y * side + x))
+            .flat_map(|i| [i, i + 1, i + side + 1, i, i + side + 1, i + side])
+            .collect();
+
+        let resource = MeshResource { vertices: vertices.clone(), indices: indices.clone() };
+        assert!(resource.vertices.len() > 65_536);
+        assert_eq!(*resource.indices.iter().max().unwrap(), side * side - 1);
+
+        let Some((device, _queue)) = test_device() else {
+            eprintln!("No wgpu adapter available; skipping GPU upload");
+            return;
+        };
+        let mesh = Mesh::new(&device, vertices, indices);
+        assert_eq!(mesh.num_indices as usize, 6 * 299 * 299);
+        assert_eq!(mesh.index_buffer.size(), 4 * 6 * 299 * 299);
+
+        let quad = Mesh::from_u16(&device, mesh.vertices[..4].to_vec(), &[0, 1, 2, 0, 2, 3]);
+        assert_eq!(quad.indices, vec![0u32, 1, 2, 0, 2, 3]);
+    }
+
+    #[test]
+    fn cube_local_bounds_span_half_size() {
+        use metatopia_engine::graphics::Mesh;
+
+        let Some((device, _queue)) = test_device() else {
+            eprintln!("No wgpu adapter available; skipping mesh bounds");
+            return;
+        };
+        let bounds = Mesh::create_cube(&device, 3.0).local_bounds();
+        assert_eq!(bounds.min, cgmath::Point3::new(-1.5, -1.5, -1.5));
+        assert_eq!(bounds.max, cgmath::Point3::new(1.5, 1.5, 1.5));
+    }
+
+    #[test]
+    fn merging_offset_cubes_concatenates_and_moves_vertices() {
+        use metatopia_engine::graphics::Mesh;
+        use metatopia_engine::math::Transform;
+
+        let Some((device, _queue)) = test_device() else {
+            eprintln!("No wgpu adapter available; skipping mesh merge");
+            return;
+        };
+        let cube = Mesh::create_cube(&device, 2.0);
+        let left = Transform { matrix: cgmath::Matrix4::from_translation(cgmath::Vector3::new(-5.0, 0.0, 0.0)) };
+        let right = Transform { matrix: cgmath::Matrix4::from_translation(cgmath::Vector3::new(5.0, 0.0, 0.0))
+            * cgmath::Matrix4::from_nonuniform_scale(1.0, 4.0, 1.0) };
+
+        let merged = Mesh::merge(&device, &[(&cube, left), (&cube, right)]);
+        let (nv, ni) = (cube.vertices.len(), cube.indices.len());
+        assert_eq!(merged.vertices.len(), 2 * nv);
+        assert_eq!(merged.indices.len(), 2 * ni);
+        assert_eq!(merged.num_indices as usize, 2 * ni);
+        assert_eq!(&merged.indices[ni..], &cube.indices.iter().map(|i| i + nv as u32).collect::<Vec<_>>()[..]);
+
+        for (i, source) in cube.vertices.iter().enumerate() {
+            let a = merged.vertices[i].position;
+            let b = merged.vertices[nv + i].position;
+            assert_eq!(a, [source.position[0] - 5.0, source.position[1], source.position[2]]);
+            assert_eq!(b, [source.position[0] + 5.0, source.position[1] * 4.0, source.position[2]]);
+            // Axis-aligned cube normals survive the scale unchanged once renormalized
+            let n = merged.vertices[nv + i].normal;
+            assert!(n.iter().zip(source.normal).all(|(a, b)| (a - b).abs() < 1e-5));
+        }
+
+        let bounds = merged.local_bounds();
+        assert_eq!(bounds.min, cgmath::Point3::new(-6.0, -4.0, -1.0));
+        assert_eq!(bounds.max, cgmath::Point3::new(6.0, 4.0, 1.0));
+    }
+
+    #[test]
+    fn mesh_resource_round_trips_through_gpu_mesh() {
+        use metatopia_engine::graphics::Mesh;
+        use metatopia_engine::resources::MeshResource;
+
+        let Some((device, _queue)) = test_device() else {
+            eprintln!("No wgpu adapter available; skipping mesh resource round trip");
+            return;
+        };
+        let quad = MeshResource::from_mesh(&Mesh::create_quad(&device, 2.0));
+        assert_eq!((quad.vertices.len(), quad.indices.len()), (4, 6));
+
+        let mesh = Mesh::from_resource(&device, &quad);
+        assert_eq!(mesh.num_indices, 6);
+        let back = MeshResource::from_mesh(&mesh);
+        assert_eq!(back.indices, quad.indices);
+        for (a, b) in back.vertices.iter().zip(&quad.vertices) {
+            assert_eq!(bytemuck::bytes_of(a), bytemuck::bytes_of(b));
+        }
+    }
+
+    #[test]
+    fn dynamic_mesh_updates_vertices_in_place() {
+        use metatopia_engine::graphics::{Mesh, Vertex};
+
+        let Some((device, queue)) = test_device() else {
+            eprintln!("No wgpu adapter available; skipping dynamic mesh");
+            return;
+        };
+        let static_quad = Mesh::create_quad(&device, 1.0);
+        assert!(!static_quad.is_dynamic());
+
+        let mut mesh = Mesh::new_dynamic(&device, static_quad.vertices.clone(), static_quad.indices.clone());
+        assert!(mesh.is_dynamic());
+        let buffer_size = mesh.vertex_buffer.size();
+
+        let rippled: Vec<Vertex> = mesh.vertices
+            .iter()
+            .map(|v| Vertex { position: [v.position[0], v.position[1], 0.25], ..*v })
+            .collect();
+        mesh.update_vertices(&queue, &rippled).unwrap();
+        queue.submit(std::iter::empty());
+        assert!(mesh.vertices.iter().all(|v| v.position[2] == 0.25));
+        assert_eq!(mesh.vertex_buffer.size(), buffer_size, "buffer is reused");
+        assert!(mesh.vertex_buffer.usage().contains(wgpu::BufferUsages::COPY_DST));
+
+        assert_eq!(mesh.local_bounds().max.z, 0.25, "bounds follow vertex updates");
+        assert!(mesh.update_vertices(&queue, &rippled[..3]).is_err(), "length must match");
+        let mut static_quad = static_quad;
+        assert!(static_quad.update_vertices(&queue, &rippled).is_err(), "static meshes can't be updated");
+    }
+
+    #[test]
+    fn metric_normals_follow_the_chart_geometry() {
+        use metatopia_engine::graphics::{metric_normal, geometry_code, NonEuclideanUniform, Shader};
+        use metatopia_engine::manifold::{ChartId, GeometryType, Metric};
+        use cgmath::{InnerSpace, Vector3};
+
+        // Spherical charts light with the radial direction whatever the mesh normal says
+        let sphere = Metric::from_geometry(GeometryType::Spherical);
+        let position = cgmath::Point3::new(3.0, 4.0, 0.0);
+        let normal = metric_normal(&sphere, position, Vector3::unit_z());
+        assert!((normal - Vector3::new(0.6, 0.8, 0.0)).magnitude() < 1e-6);
+
+        // Euclidean normals pass through unchanged
+        let flat = Metric::from_geometry(GeometryType::Euclidean);
+        let tilted = Vector3::new(1.0, 1.0, 0.0).normalize();
+        assert!((metric_normal(&flat, position, tilted) - tilted).magnitude() < 1e-6);
+
+        // The Poincaré metric shrinks the in-plane components relative to z
+        let hyperbolic = Metric::from_geometry(GeometryType::Hyperbolic);
+        let raised = metric_normal(&hyperbolic, cgmath::Point3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 1.0));
+        assert!((raised - Vector3::new(0.25, 0.0, 1.0).normalize()).magnitude() < 1e-6);
+
+        let uniforms = NonEuclideanUniform::new(
+            cgmath::Matrix4::from_scale(1.0),
+            cgmath::Matrix4::from_scale(1.0),
+            ChartId(2),
+            &sphere,
+        );
+        assert_eq!(uniforms.chart_id, 2.0);
+        assert_eq!(uniforms.geometry, [geometry_code(GeometryType::Spherical), 1.0, 0.0, 0.0]);
+        assert_eq!(uniforms.metric_params[0], 10.0);
+
+        let Some((device, _queue)) = test_device() else {
+            eprintln!("No wgpu adapter available; skipping shader compilation");
+            return;
+        };
+        let mut shader = Shader::new(std::sync::Arc::new(device));
+        shader.create_geometry_shaders();
+        assert!(shader.get_program("spherical").is_some());
+    }
+
+    #[test]
+    fn non_euclidean_uniforms_match_wgsl_layout() {
+        use metatopia_engine::graphics::{NonEuclideanUniform, PortalUniform, Shader, Texture};
+        use metatopia_engine::manifold::{ChartId, GeometryType, Metric};
+        use std::mem::{offset_of, size_of};
+        use wgpu::util::DeviceExt;
+
+        // struct Uniforms { view_proj: mat4x4, model: mat4x4, chart_id: f32, metric_params: vec4, geometry: vec4 }
+        assert_eq!(offset_of!(NonEuclideanUniform, view_proj), 0);
+        assert_eq!(offset_of!(NonEuclideanUniform, model), 64);
+        assert_eq!(offset_of!(NonEuclideanUniform, chart_id), 128);
+        assert_eq!(offset_of!(NonEuclideanUniform, metric_params), 144);
+        assert_eq!(offset_of!(NonEuclideanUniform, geometry), 160);
+        assert_eq!(size_of::<NonEuclideanUniform>(), 176);
+
+        // struct PortalData { is_active: f32, target_chart: f32, transform: mat4x4 }
+        assert_eq!(offset_of!(PortalUniform, is_active), 0);
+        assert_eq!(offset_of!(PortalUniform, target_chart), 4);
+        assert_eq!(offset_of!(PortalUniform, transform), 16);
+        assert_eq!(size_of::<PortalUniform>(), 80);
+        assert_eq!(PortalUniform::active(ChartId(3), cgmath::Matrix4::from_scale(1.0)).target_chart, 3.0);
+
+        let Some((device, queue)) = test_device() else {
+            eprintln!("No wgpu adapter available; skipping bind group creation");
+            return;
+        };
+        let buffer = |contents: &[u8]| device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: None,
+            contents,
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let uniform_buffer = buffer(bytemuck::bytes_of(&NonEuclideanUniform::new(
+            cgmath::Matrix4::from_scale(1.0),
+            cgmath::Matrix4::from_scale(1.0),
+            ChartId(0),
+            &Metric::from_geometry(GeometryType::Euclidean),
+        )));
+        let portal_buffer = buffer(bytemuck::bytes_of(&PortalUniform::inactive()));
+        let light_buffer = buffer(bytemuck::bytes_of(&metatopia_engine::graphics::LightUniform::default()));
+        let fog_buffer = buffer(bytemuck::bytes_of(&metatopia_engine::graphics::FogUniform::default()));
+        let texture = Texture::from_color(&device, &queue, [255, 255, 255, 255], None).unwrap();
+
+        let layout = Shader::bind_group_layout(&device);
+        Shader::create_bind_group(&device, &layout, &uniform_buffer, &portal_buffer, &texture, &light_buffer, &fog_buffer);
+    }
+
+    #[test]
+    fn light_uniform_packs_up_to_four_lights() {
+        use metatopia_engine::graphics::{Color, Light, LightRaw, LightUniform, MAX_LIGHTS};
+        use std::mem::{offset_of, size_of};
+
+        // struct Light { position: vec4, color: vec4, params: vec4 }
+        // struct Lights { lights: array<Light, 4>, count: u32 }
+        assert_eq!(size_of::<LightRaw>(), 48);
+        assert_eq!(offset_of!(LightUniform, count), 48 * MAX_LIGHTS);
+        assert_eq!(size_of::<LightUniform>(), 208);
+
+        let sun = Light::directional(cgmath::Vector3::new(0.0, -2.0, 0.0), Color::rgb(1.0, 0.9, 0.8), 0.5);
+        let lamp = Light::point(cgmath::Point3::new(1.0, 2.0, 3.0), 10.0, Color::RED, 2.0);
+        let lights = [sun, lamp, lamp, sun, lamp];
+
+        for n in 0..=lights.len() {
+            let uniform = LightUniform::new(&lights[..n]);
+            assert_eq!(uniform.count(), n.min(MAX_LIGHTS));
+            for (i, raw) in uniform.lights.iter().enumerate() {
+                if i < n {
+                    assert_eq!(*raw, lights[i].to_raw());
+                } else {
+                    assert_eq!(*raw, LightRaw::default(), "unused slot {} is zeroed", i);
+                }
+            }
+        }
+
+        let sun = sun.to_raw();
+        assert_eq!(sun.position, [0.0, -1.0, 0.0, 0.0], "directions are normalized, w = 0");
+        assert_eq!(sun.color, [1.0, 0.9, 0.8, 0.5]);
+        let lamp_raw = lamp.to_raw();
+        assert_eq!(lamp_raw.position, [1.0, 2.0, 3.0, 1.0]);
+        assert_eq!(lamp_raw.params[0], 10.0);
+
+        assert_eq!(lamp.attenuation(0.0), 1.0);
+        assert!((lamp.attenuation(5.0) - 0.25).abs() < 1e-6);
+        assert_eq!(lamp.attenuation(12.0), 0.0);
+        assert_eq!(LightUniform::default().count(), 1);
+    }
+
+    #[test]
+    fn fog_factor_is_exponential_in_hyperbolic_and_linear_in_euclidean() {
+        use metatopia_engine::graphics::{Color, FogSettings, FogUniform};
+        use metatopia_engine::manifold::GeometryType;
+        use std::mem::{offset_of, size_of};
+
+        // struct Fog { fog_color: vec4, camera_position: vec4, fog_density: f32, geometry_type: f32 }
+        assert_eq!(offset_of!(FogUniform, fog_density), 32);
+        assert_eq!(offset_of!(FogUniform, geometry_type), 36);
+        assert_eq!(size_of::<FogUniform>(), 48);
+
+        let grey = Color::rgb(0.5, 0.5, 0.5);
+        let flat = FogSettings::new(grey, 0.1, GeometryType::Euclidean);
+        let curved = FogSettings::new(grey, 0.1, GeometryType::Hyperbolic);
+
+        for (distance, linear, exponential) in [(0.0, 1.0, 1.0), (2.5, 0.75, 0.778_800_8), (5.0, 0.5, 0.606_530_7), (10.0, 0.0, 0.367_879_4), (30.0, 0.0, 0.049_787_07)] {
+            assert!((flat.factor(distance) - linear).abs() < 1e-5, "linear fog at {}", distance);
+            assert!((curved.factor(distance) - exponential).abs() < 1e-5, "exponential fog at {}", distance);
+        }
+        assert_eq!(FogSettings::default().factor(1000.0), 1.0, "no fog by default");
+
+        let faded = flat.apply(Color::new(1.0, 0.0, 0.0, 0.25), 5.0);
+        assert_eq!(faded, Color::new(0.75, 0.25, 0.25, 0.25));
+
+        let uniform = curved.to_uniform(cgmath::Point3::new(1.0, 2.0, 3.0));
+        assert_eq!(uniform.geometry_type, 2.0);
+        assert_eq!(uniform.fog_density, 0.1);
+        assert_eq!(uniform.camera_position, [1.0, 2.0, 3.0, 1.0]);
+    }
+
+    #[test]
+    fn geometry_shader_pipelines_build() {
+        use metatopia_engine::graphics::{Shader, Vertex};
+
+        let Some((device, _queue)) = test_device() else {
+            eprintln!("No wgpu adapter available; skipping pipeline creation");
+            return;
+        };
+        let device = std::sync::Arc::new(device);
+
+        let bind_group_layout = Shader::bind_group_layout(&device);
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let mut shader = Shader::new(device.clone());
+        shader.create_geometry_shaders();
+        for name in ["euclidean", "hyperbolic", "spherical"] {
+            let program = shader.get_program_mut(name).unwrap();
+            program.create_pipeline(
+                &device,
+                &layout,
+                Vertex::desc(),
+                wgpu::TextureFormat::Rgba8UnormSrgb,
+                1,
+                wgpu::PolygonMode::Fill,
+            );
+            assert!(program.pipeline.is_some(), "{} pipeline", name);
+        }
+    }
+
+    #[test]
+    fn wireframe_toggle_without_line_feature_stays_filled() {
+        use metatopia_engine::graphics::{resolve_polygon_mode, ShaderProgram};
+        use metatopia_engine::graphics::shader::GeometryType;
+        use wgpu::{Features, PolygonMode};
+
+        assert_eq!(resolve_polygon_mode(true, Features::POLYGON_MODE_LINE), PolygonMode::Line);
+        assert_eq!(resolve_polygon_mode(false, Features::POLYGON_MODE_LINE), PolygonMode::Fill);
+        assert_eq!(resolve_polygon_mode(true, Features::empty()), PolygonMode::Fill);
+
+        // The default device descriptor requests no features, so line mode is missing
+        let Some((device, _queue)) = test_device() else {
+            eprintln!("No wgpu adapter available; skipping pipeline creation");
+            return;
+        };
+        assert!(!device.features().contains(Features::POLYGON_MODE_LINE));
+
+        let source = r#"
+            @vertex
+            fn vs_main(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+                return vec4<f32>(position, 1.0);
+            }
+
+            @fragment
+            fn fs_main() -> @location(0) vec4<f32> {
+                return vec4<f32>(1.0);
+            }
+        "#;
+        let mut program = ShaderProgram::from_wgsl(&device, source, source, GeometryType::Euclidean);
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+        let vertex_layout = || wgpu::VertexBufferLayout {
+            array_stride: 12,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &wgpu::vertex_attr_array![0 => Float32x3],
+        };
+        let format = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+        program.create_pipeline(&device, &layout, vertex_layout(), format, 1, PolygonMode::Fill);
+        program.create_pipeline(&device, &layout, vertex_layout(), format, 1, PolygonMode::Line);
+        assert!(program.pipeline.is_some());
+        assert!(program.wireframe_pipeline.is_none());
+        assert!(program.active_pipeline(PolygonMode::Line).is_some(), "falls back to the fill pipeline");
+    }
+
+    #[test]
+    fn captured_offscreen_clear_matches_clear_color() {
+        use metatopia_engine::graphics::{capture_texture, padded_bytes_per_row};
+
+        assert_eq!(padded_bytes_per_row(37 * 4), 256);
+        assert_eq!(padded_bytes_per_row(64 * 4), 256);
+        assert_eq!(padded_bytes_per_row(65 * 4), 512);
+
+        let Some((device, queue)) = test_device() else {
+            eprintln!("No wgpu adapter available; skipping capture");
+            return;
+        };
+
+        // Odd width so every row is padded in the readback buffer
+        for format in [wgpu::TextureFormat::Rgba8UnormSrgb, wgpu::TextureFormat::Bgra8UnormSrgb] {
+            let texture = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("Offscreen Target"),
+                size: wgpu::Extent3d { width: 37, height: 5, depth_or_array_layers: 1 },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            });
+            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Clear Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color { r: 1.0, g: 0.0, b: 0.5, a: 1.0 }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            queue.submit(std::iter::once(encoder.finish()));
+
+            let image = capture_texture(&device, &queue, &texture).unwrap();
+            assert_eq!(image.dimensions(), (37, 5));
+            for pixel in image.pixels() {
+                let [r, g, b, a] = pixel.0;
+                assert_eq!((r, g, a), (255, 0, 255), "{:?}", format);
+                // Linear 0.5 is stored sRGB-encoded as ~188
+                assert!((187..=189).contains(&b), "{:?}: blue was {}", format, b);
+            }
+        }
+    }
+
+    #[test]
+    fn render_target_clears_and_reads_back() {
+        use metatopia_engine::graphics::{Color, RenderTarget};
+
+        let Some((device, queue)) = test_device() else {
+            eprintln!("No wgpu adapter available; skipping render target");
+            return;
+        };
+        let target = RenderTarget::new(&device, 20, 10, wgpu::TextureFormat::Rgba8Unorm).with_depth(&device);
+        assert_eq!(target.size(), (20, 10));
+        assert!(target.depth().is_some());
+        assert!(target.color().texture.usage().contains(wgpu::TextureUsages::TEXTURE_BINDING));
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        drop(target.begin_pass(&mut encoder, Some(Color::new(0.0, 1.0, 0.0, 1.0))));
+        // A pass without a clear color keeps what is already there
+        drop(target.begin_pass(&mut encoder, None));
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let image = target.capture(&device, &queue).unwrap();
+        assert_eq!(image.dimensions(), (20, 10));
+        assert!(image.pixels().all(|pixel| pixel.0 == [0, 255, 0, 255]));
+    }
+
+    #[test]
+    fn color_lerp_alpha_and_premultiplication() {
+        use metatopia_engine::graphics::Color;
+
+        let mid = Color::lerp(Color::BLACK, Color::new(1.0, 0.5, 0.0, 0.0), 0.5);
+        assert_eq!(mid, Color::new(0.5, 0.25, 0.0, 0.5));
+        assert_eq!(Color::RED.mix(Color::BLUE, 0.25), Color::new(0.75, 0.0, 0.25, 1.0));
+        // t is clamped to the endpoints
+        assert_eq!(Color::lerp(Color::RED, Color::BLUE, 2.0), Color::BLUE);
+        assert_eq!(Color::lerp(Color::RED, Color::BLUE, -1.0), Color::RED);
+
+        let faded = Color::YELLOW.with_alpha(0.25);
+        assert_eq!(faded, Color::new(1.0, 1.0, 0.0, 0.25));
+        assert_eq!(Color::WHITE.with_alpha(3.0).a, 1.0);
+
+        assert_eq!(Color::new(0.8, 0.4, 1.0, 0.5).premultiplied(), Color::new(0.4, 0.2, 0.5, 0.5));
+        assert_eq!(Color::CYAN.premultiplied(), Color::CYAN, "opaque colors are unchanged");
+
+        // Operators work in linear space; only alpha is kept in range
+        assert_eq!(Color::RED * 2.0, Color::new(2.0, 0.0, 0.0, 1.0));
+        assert_eq!(Color::RED + Color::GREEN, Color::new(1.0, 1.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn render_queue_draws_transparents_back_to_front() {
+        use cgmath::Point3;
+        use metatopia_engine::{EcsTransform, Renderable, World};
+        use metatopia_engine::graphics::RenderQueue;
+        use metatopia_engine::manifold::ChartId;
+
+        let renderable = |mesh: &str, transparent: bool| Renderable { transparent, ..Renderable::new(mesh, "default") };
+        let mut world = World::new();
+        for (mesh, z, transparent) in [
+            ("screen_near", -2.0, true),
+            ("wall_far", -20.0, false),
+            ("screen_far", -12.0, true),
+            ("crate_near", -1.0, false),
+            ("screen_mid", -6.0, true),
+        ] {
+            world.spawn((EcsTransform::new(ChartId(0), Point3::new(0.0, 0.0, z)), renderable(mesh, transparent)));
+        }
+        let hidden = world.spawn((EcsTransform::new(ChartId(0), Point3::new(0.0, 0.0, -3.0)), renderable("hidden", true)));
+        world.get_component_mut::<Renderable>(hidden).unwrap().visible = false;
+
+        let queue = RenderQueue::from_world(&world, Point3::new(0.0, 0.0, 0.0));
+        let order: Vec<&str> = queue.draw_order().map(|item| item.mesh_id.as_str()).collect();
+        assert_eq!(order, ["crate_near", "wall_far", "screen_far", "screen_mid", "screen_near"]);
+        assert_eq!(queue.transparent().len(), 3);
+        assert_eq!(queue.transparent()[0].distance2, 144.0);
+    }
+
+    #[test]
+    fn text_layout_emits_a_quad_per_visible_glyph() {
+        use metatopia_engine::graphics::{Color, layout_text, measure_text};
+
+        let quads = layout_text("Hi there\nok", (10.0, 20.0), 16.0, Color::YELLOW);
+        // Spaces and line breaks take up room but draw nothing
+        assert_eq!(quads.len(), 9);
+        assert_eq!(quads[0].min, [10.0, 20.0]);
+        assert_eq!(quads[0].max, [26.0, 36.0]);
+        assert_eq!(quads[2].min, [58.0, 20.0], "'t' comes after 'H', 'i' and a space");
+        assert_eq!(quads[7].min, [10.0, 36.0], "'o' starts the second line");
+        assert!(quads.iter().all(|quad| quad.color == Color::YELLOW.to_array()));
+
+        // Unsupported characters fall back to '?'
+        assert_eq!(layout_text("é", (0.0, 0.0), 8.0, Color::WHITE)[0].uv_min, layout_text("?", (0.0, 0.0), 8.0, Color::WHITE)[0].uv_min);
+        assert_eq!(measure_text("Hi there\nok", 16.0), (128.0, 32.0));
+    }
+
+    #[test]
+    fn text_renderer_draws_glyphs_over_the_target() {
+        use metatopia_engine::graphics::{Color, RenderTarget, TextRenderer};
+
+        let Some((device, queue)) = test_device() else {
+            eprintln!("No wgpu adapter available; skipping text rendering");
+            return;
+        };
+        let target = RenderTarget::new(&device, 8, 8, wgpu::TextureFormat::Rgba8Unorm);
+        let mut text = TextRenderer::new(&device, &queue);
+        text.queue("H", (0.0, 0.0), 8.0, Color::WHITE);
+        assert_eq!(text.queued().len(), 1);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        drop(target.begin_pass(&mut encoder, Some(Color::BLACK)));
+        text.render(&device, &mut encoder, target.view(), target.format(), target.size());
+        queue.submit(std::iter::once(encoder.finish()));
+        assert!(text.queued().is_empty(), "rendering empties the batch");
+
+        // The top row of 'H' is two 2-pixel uprights: ##..##..
+        let image = target.capture(&device, &queue).unwrap();
+        let top: Vec<bool> = (0..8).map(|x| image.get_pixel(x, 0).0[0] > 127).collect();
+        assert_eq!(top, [true, true, false, false, true, true, false, false]);
+    }
+
+    #[test]
+    fn debug_draw_calls_generate_line_list_vertices() {
+        use cgmath::{Point3, Vector3};
+        use metatopia_engine::GeodesicPath;
+        use metatopia_engine::graphics::{Color, DebugLines};
+        use metatopia_engine::manifold::GeometryType;
+        use metatopia_engine::math::{BoundingBox, Ray};
+
+        let mut lines = DebugLines::new();
+        lines.line(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0), Color::RED);
+        lines.ray(&Ray::new(Point3::new(0.0, 1.0, 0.0), Vector3::unit_z()), 2.0, Color::GREEN);
+        lines.aabb(&BoundingBox::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0)), Color::BLUE);
+        let mut path = GeodesicPath::new(GeometryType::Euclidean);
+        for i in 0..5 {
+            path.add_point(Point3::new(i as f32, 0.0, 0.0), Vector3::unit_x());
+        }
+        lines.path(&path, Color::WHITE);
+
+        // 1 line + 1 ray + 12 box edges + 4 path segments, two vertices each
+        let vertices = lines.vertices();
+        assert_eq!(vertices.len(), 2 * (1 + 1 + 12 + 4));
+        assert_eq!(vertices[3].position, [0.0, 1.0, 2.0], "the ray ends `length` along it");
+        assert_eq!(vertices[3].color, Color::GREEN.to_array());
+
+        // Every box edge runs along a single axis with unit half-extents
+        for edge in vertices[4..28].chunks(2) {
+            let changed = (0..3).filter(|&axis| edge[0].position[axis] != edge[1].position[axis]).count();
+            assert_eq!(changed, 1);
+        }
+
+        lines.clear();
+        assert!(lines.is_empty());
+    }
+
+    #[test]
+    fn debug_renderer_draws_lines_over_the_target() {
+        use cgmath::Point3;
+        use metatopia_engine::graphics::{Color, DebugRenderer, RenderTarget};
+
+        let Some((device, queue)) = test_device() else {
+            eprintln!("No wgpu adapter available; skipping debug lines");
+            return;
+        };
+        let target = RenderTarget::new(&device, 16, 16, wgpu::TextureFormat::Rgba8Unorm);
+        let mut debug = DebugRenderer::new(&device);
+        // Identity view-projection: a horizontal line through the middle of clip space
+        debug.lines_mut().line(Point3::new(-1.0, 0.0, 0.5), Point3::new(1.0, 0.0, 0.5), Color::RED);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        drop(target.begin_pass(&mut encoder, Some(Color::BLACK)));
+        debug.render(&device, &mut encoder, target.view(), None, target.format(), 1);
+        queue.submit(std::iter::once(encoder.finish()));
+        assert!(debug.lines().is_empty(), "rendering empties the queue");
+
+        let image = target.capture(&device, &queue).unwrap();
+        let red_rows: Vec<u32> = (0..16).filter(|&y| image.get_pixel(8, y).0[0] > 127).collect();
+        assert_eq!(red_rows.len(), 1, "one row is lit: {:?}", red_rows);
+        assert_eq!(image.get_pixel(8, 0).0, [0, 0, 0, 255]);
+    }
+}
+
+// ─── Texture Atlas ─────────────────────────────────────────────────────────
+
+mod atlas_tests {
+    use metatopia_engine::graphics::AtlasLayout;
+
+    #[test]
+    fn grid_atlas_uv_rect() {
+        let names = ["spray", "trap", "net", "boots", "ant", "spider", "cockroach"];
+        let layout = AtlasLayout::from_grid(256, 256, 4, 4, &names);
+
+        // Seventh cell: column 2, row 1
+        assert_eq!(layout.uv_rect("cockroach"), Some([0.5, 0.25, 0.75, 0.5]));
+        assert_eq!(layout.uv_rect("spray"), Some([0.0, 0.0, 0.25, 0.25]));
+        assert_eq!(layout.uv_rect("15"), Some([0.75, 0.75, 1.0, 1.0]), "unnamed cells use their index");
+        assert_eq!(layout.names().count(), 16);
+        assert!(layout.uv_rect("mosquito").is_none());
+    }
+
+    #[test]
+    fn json_atlas_and_quad_uvs() {
+        let layout = AtlasLayout::from_json(r#"{
+            "width": 128,
+            "height": 64,
+            "regions": { "pest": { "x": 32, "y": 16, "width": 32, "height": 16 } }
+        }"#).unwrap();
+        assert_eq!(layout.uv_rect("pest"), Some([0.25, 0.25, 0.5, 0.5]));
+
+        let (vertices, indices) = layout.quad("pest", 2.0).unwrap();
+        assert_eq!(indices, vec![0, 1, 2, 0, 2, 3]);
+        assert_eq!(vertices[0].tex_coords, [0.25, 0.5], "bottom-left samples the region's bottom");
+        assert_eq!(vertices[2].tex_coords, [0.5, 0.25]);
+        assert_eq!(vertices[2].position, [1.0, 1.0, 0.0]);
+
+        assert!(AtlasLayout::from_json("{ not json").is_err());
+    }
+}
+
+// ─── Audio ─────────────────────────────────────────────────────────────────
+
+mod audio_tests {
+    use metatopia_engine::audio::spatial_falloff;
+    use metatopia_engine::manifold::GeometryType;
+
+    #[test]
+    fn falloff_follows_geometry_curve() {
+        for geometry in [GeometryType::Euclidean, GeometryType::Spherical, GeometryType::Hyperbolic] {
+            assert_eq!(spatial_falloff(geometry, 0.0, 2.0), 1.0, "{:?}: full volume inside the reference distance", geometry);
+            assert_eq!(spatial_falloff(geometry, 2.0, 2.0), 1.0);
+        }
+
+        // Inverse square: doubling the distance quarters the volume
+        assert!((spatial_falloff(GeometryType::Euclidean, 4.0, 2.0) - 0.25).abs() < 1e-6);
+        assert!((spatial_falloff(GeometryType::Euclidean, 20.0, 2.0) - 0.01).abs() < 1e-6);
+        assert_eq!(spatial_falloff(GeometryType::Spherical, 4.0, 2.0), spatial_falloff(GeometryType::Euclidean, 4.0, 2.0));
+
+        // Exponential: each further reference distance scales by e^-2
+        let one_step = spatial_falloff(GeometryType::Hyperbolic, 4.0, 2.0);
+        assert!((one_step - (-2.0f32).exp()).abs() < 1e-6);
+        assert!((spatial_falloff(GeometryType::Hyperbolic, 6.0, 2.0) - one_step * one_step).abs() < 1e-6);
+        assert!(spatial_falloff(GeometryType::Hyperbolic, 20.0, 2.0) < spatial_falloff(GeometryType::Euclidean, 20.0, 2.0));
+    }
 }